@@ -207,6 +207,10 @@ pub struct Implementation {
     pub overriding: bool,
     pub generic: bool,
     pub access: Option<AccessModifier>,
+    /// the symbol name requested using `{extern_name := '...'}`, used instead of the POU's name
+    pub extern_name: Option<String>,
+    /// the LLVM function attributes requested using `{gnu_attr := '...'}`
+    pub function_attributes: Vec<String>,
 }
 
 #[derive(Debug, Copy, PartialEq, Eq, Clone, Hash)]
@@ -265,6 +269,15 @@ pub struct CompilationUnit {
     pub implementations: Vec<Implementation>,
     pub user_types: Vec<UserTypeDeclaration>,
     pub file_name: String,
+    /// the files included by `{include := 'file'}` directives
+    pub includes: Vec<Include>,
+}
+
+/// A `{include := 'file'}` directive, sharing the declarations of the named file with the including unit
+#[derive(Debug, Clone, PartialEq)]
+pub struct Include {
+    pub path: String,
+    pub location: SourceLocation,
 }
 
 impl CompilationUnit {
@@ -275,6 +288,7 @@ impl CompilationUnit {
             implementations: Vec::new(),
             user_types: Vec::new(),
             file_name: file_name.to_string(),
+            includes: Vec::new(),
         }
     }
 
@@ -294,6 +308,7 @@ impl CompilationUnit {
         self.units.extend(other.units);
         self.implementations.extend(other.implementations);
         self.user_types.extend(other.user_types);
+        self.includes.extend(other.includes);
     }
 }
 
@@ -453,12 +468,37 @@ impl Debug for UserTypeDeclaration {
     }
 }
 
+/// Distinguishes pointers which have to be dereferenced explicitly from references
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PointerKind {
+    /// a `POINTER TO` or `REF_TO`, its value is only accessed when dereferenced using `^`
+    Raw,
+    /// a `REFERENCE TO` or a by-ref parameter (e.g. `VAR_IN_OUT`), dereferenced implicitly
+    Reference,
+}
+
+impl PointerKind {
+    pub fn is_auto_deref(&self) -> bool {
+        matches!(self, PointerKind::Reference)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum DataType {
     StructType {
         name: Option<String>, //maybe None for inline structs
         variables: Vec<Variable>,
     },
+    /// a type whose members all share the same memory (offset zero)
+    UnionType {
+        name: Option<String>, //maybe None for inline unions
+        variables: Vec<Variable>,
+    },
+    /// a `{bitfield}` struct whose BOOL members are packed into single bits
+    BitfieldType {
+        name: Option<String>, //maybe None for inline bitfields
+        variables: Vec<Variable>,
+    },
     EnumType {
         name: Option<String>, //maybe empty for inline enums
         numeric_type: String,
@@ -478,6 +518,7 @@ pub enum DataType {
     PointerType {
         name: Option<String>,
         referenced_type: Box<DataTypeDeclaration>,
+        kind: PointerKind,
     },
     StringType {
         name: Option<String>,
@@ -499,6 +540,8 @@ impl DataType {
     pub fn set_name(&mut self, new_name: String) {
         match self {
             DataType::StructType { name, .. }
+            | DataType::UnionType { name, .. }
+            | DataType::BitfieldType { name, .. }
             | DataType::EnumType { name, .. }
             | DataType::SubRangeType { name, .. }
             | DataType::ArrayType { name, .. }
@@ -512,6 +555,8 @@ impl DataType {
     pub fn get_name(&self) -> Option<&str> {
         match &self {
             DataType::StructType { name, .. }
+            | DataType::UnionType { name, .. }
+            | DataType::BitfieldType { name, .. }
             | DataType::EnumType { name, .. }
             | DataType::ArrayType { name, .. }
             | DataType::PointerType { name, .. }
@@ -604,6 +649,8 @@ pub enum AstStatement {
     ParenExpression(Box<AstNode>),
     RangeStatement(RangeStatement),
     VlaRangeStatement,
+    // a reference to the current instance (`THIS`)
+    This,
     // Assignment
     Assignment(Assignment),
     // OutputAssignment
@@ -647,6 +694,7 @@ impl Debug for AstNode {
                 f.debug_struct("RangeStatement").field("start", start).field("end", end).finish()
             }
             AstStatement::VlaRangeStatement => f.debug_struct("VlaRangeStatement").finish(),
+            AstStatement::This => f.debug_struct("This").finish(),
             AstStatement::Assignment(Assignment { left, right }) => {
                 f.debug_struct("Assignment").field("left", left).field("right", right).finish()
             }
@@ -1025,6 +1073,27 @@ pub fn flatten_expression_list(list: &AstNode) -> Vec<&AstNode> {
     }
 }
 
+/// returns true if one of the given statements is a label, including labels nested in control statements.
+/// Such statements cannot be dropped, even if they are never reached by the regular control flow, since
+/// they can still be the target of a `JMP`
+pub fn contains_label(statements: &[AstNode]) -> bool {
+    statements.iter().any(|statement| match &statement.stmt {
+        AstStatement::LabelStatement(..) => true,
+        AstStatement::ControlStatement(AstControlStatement::If(IfStatement { blocks, else_block }))
+        | AstStatement::ControlStatement(AstControlStatement::Case(CaseStatement {
+            case_blocks: blocks,
+            else_block,
+            ..
+        })) => blocks.iter().any(|it| contains_label(&it.body)) || contains_label(else_block),
+        AstStatement::ControlStatement(AstControlStatement::ForLoop(ForLoopStatement { body, .. }))
+        | AstStatement::ControlStatement(AstControlStatement::WhileLoop(LoopStatement { body, .. }))
+        | AstStatement::ControlStatement(AstControlStatement::RepeatLoop(LoopStatement { body, .. })) => {
+            contains_label(body)
+        }
+        _ => false,
+    })
+}
+
 pub fn pre_process(unit: &mut CompilationUnit, id_provider: IdProvider) {
     pre_processor::pre_process(unit, id_provider)
 }
@@ -1059,7 +1128,13 @@ impl Operator {
 
 #[cfg(test)]
 mod tests {
-    use crate::ast::{ArgumentProperty, PouType, VariableBlockType};
+    use plc_source::source_location::SourceLocation;
+
+    use crate::{
+        ast::{contains_label, ArgumentProperty, AstFactory, PouType, VariableBlockType},
+        control_statements::ConditionalBlock,
+        literals::AstLiteral,
+    };
 
     #[test]
     fn display_pou() {
@@ -1081,6 +1156,27 @@ mod tests {
         assert_eq!(VariableBlockType::Global.to_string(), "Global");
         assert_eq!(VariableBlockType::InOut.to_string(), "InOut");
     }
+
+    #[test]
+    fn labels_nested_in_control_statements_are_found() {
+        let label = || AstFactory::create_label_statement("lbl".into(), SourceLocation::internal(), 0);
+        let condition = || AstFactory::create_literal(AstLiteral::Bool(false), SourceLocation::internal(), 0);
+        let if_statement = |body| {
+            AstFactory::create_if_statement(
+                vec![ConditionalBlock { condition: Box::new(condition()), body }],
+                vec![],
+                SourceLocation::internal(),
+                0,
+            )
+        };
+        let while_statement =
+            |body| AstFactory::create_while_statement(condition(), body, SourceLocation::internal(), 0);
+
+        assert!(contains_label(&[label()]));
+        assert!(contains_label(&[if_statement(vec![while_statement(vec![label()])])]));
+        assert!(!contains_label(&[if_statement(vec![while_statement(vec![])])]));
+        assert!(!contains_label(&[]));
+    }
 }
 
 pub struct AstFactory {}
@@ -1116,6 +1212,10 @@ impl AstFactory {
         AstNode { stmt: AstStatement::VlaRangeStatement, id, location }
     }
 
+    pub fn create_this(location: SourceLocation, id: AstId) -> AstNode {
+        AstNode { stmt: AstStatement::This, id, location }
+    }
+
     pub fn create_literal(kind: AstLiteral, location: SourceLocation, id: AstId) -> AstNode {
         AstNode { stmt: AstStatement::Literal(kind), id, location }
     }
@@ -51,7 +51,9 @@ pub fn pre_process(unit: &mut CompilationUnit, mut id_provider: IdProvider) {
     for dt in unit.user_types.iter_mut() {
         {
             match &mut dt.data_type {
-                DataType::StructType { name, variables, .. } => {
+                DataType::StructType { name, variables, .. }
+                | DataType::UnionType { name, variables }
+                | DataType::BitfieldType { name, variables } => {
                     let name: &str = name.as_ref().map(|it| it.as_str()).unwrap_or("undefined");
                     variables
                         .iter_mut()
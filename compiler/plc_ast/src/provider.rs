@@ -5,26 +5,57 @@ use std::sync::{
 
 use crate::ast::AstId;
 
+/// the number of ids reserved for a provider forked from a deterministic provider,
+/// a fork running out of its range panics instead of handing out the ids of the next fork
+const FORKED_ID_RANGE: usize = 1 << 22;
+
 #[derive(Clone)]
 pub struct IdProvider {
     current_id: Arc<AtomicUsize>,
+    /// the first id outside of the range reserved for this provider
+    end_id: usize,
+    deterministic: bool,
 }
 
 impl IdProvider {
+    /// creates a provider whose forks hand out ids of their own reserved range, so the ids
+    /// generated while processing units in parallel do not depend on the scheduling of the units
+    pub fn deterministic() -> Self {
+        IdProvider { deterministic: true, ..Default::default() }
+    }
+
     pub fn next_id(&mut self) -> AstId {
-        self.current_id.fetch_add(1, Ordering::Relaxed)
+        let id = self.current_id.fetch_add(1, Ordering::Relaxed);
+        assert!(id < self.end_id, "IdProvider ran out of ids (reserved range ends at {})", self.end_id);
+        id
+    }
+
+    /// returns a provider for processing a single unit, forks have to be created in the
+    /// same order (e.g. the order of the units) to get the same ids on every run.
+    /// Providers which are not deterministic return a clone sharing their counter
+    pub fn fork(&self) -> IdProvider {
+        if self.deterministic {
+            let start = self.current_id.fetch_add(FORKED_ID_RANGE, Ordering::Relaxed);
+            let end_id = start.checked_add(FORKED_ID_RANGE).filter(|end| *end <= self.end_id);
+            let Some(end_id) = end_id else {
+                panic!("IdProvider ran out of ids to reserve for a fork");
+            };
+            IdProvider { current_id: Arc::new(AtomicUsize::new(start)), end_id, deterministic: true }
+        } else {
+            self.clone()
+        }
     }
 }
 
 impl Default for IdProvider {
     fn default() -> Self {
-        IdProvider { current_id: Arc::new(AtomicUsize::new(1)) }
+        IdProvider { current_id: Arc::new(AtomicUsize::new(1)), end_id: usize::MAX, deterministic: false }
     }
 }
 
 #[cfg(test)]
 mod id_tests {
-    use super::IdProvider;
+    use super::{IdProvider, FORKED_ID_RANGE};
 
     #[test]
     fn id_provider_generates_unique_ids_over_clones() {
@@ -36,4 +67,26 @@ mod id_tests {
         assert_eq!(id1.next_id(), 3);
         assert_eq!(id2.next_id(), 4);
     }
+
+    #[test]
+    fn forks_of_a_deterministic_id_provider_do_not_depend_on_the_order_of_use() {
+        let mut ids = IdProvider::deterministic();
+        let mut fork1 = ids.fork();
+        let mut fork2 = ids.fork();
+
+        assert_eq!(fork2.next_id(), 1 + FORKED_ID_RANGE);
+        assert_eq!(fork1.next_id(), 1);
+        assert_eq!(fork2.next_id(), 2 + FORKED_ID_RANGE);
+        assert_eq!(fork1.next_id(), 2);
+        assert_eq!(ids.next_id(), 1 + 2 * FORKED_ID_RANGE);
+    }
+
+    #[test]
+    #[should_panic(expected = "IdProvider ran out of ids")]
+    fn a_fork_does_not_hand_out_ids_of_the_next_fork() {
+        let ids = IdProvider::deterministic();
+        let mut fork = ids.fork();
+        fork.current_id.store(1 + FORKED_ID_RANGE, std::sync::atomic::Ordering::Relaxed);
+        fork.next_id();
+    }
 }
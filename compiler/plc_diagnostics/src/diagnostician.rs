@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    path::{Component, Path, PathBuf},
+};
 
 use crate::{
     diagnostics::Diagnostic,
@@ -15,6 +18,19 @@ pub struct Diagnostician {
     reporter: Box<dyn DiagnosticReporter>,
     assessor: Box<dyn DiagnosticAssessor>,
     filename_fileid_mapping: HashMap<String, usize>,
+    file_paths: FilePaths,
+}
+
+/// how the paths of the registered files are printed in reported diagnostics
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum FilePaths {
+    /// the paths are printed as they were registered
+    #[default]
+    AsProvided,
+    /// the paths are printed as absolute paths
+    Absolute,
+    /// the paths are printed relative to the given root, paths outside of it are printed as absolute paths
+    RelativeTo(PathBuf),
 }
 
 impl Diagnostician {
@@ -22,11 +38,33 @@ impl Diagnostician {
     /// preview errors in the source
     /// returns the id to use to reference the given file
     pub fn register_file(&mut self, id: String, src: String) -> usize {
-        let handle = self.reporter.register(id.clone(), src);
+        let handle = self.reporter.register(self.get_display_path(&id), src);
         self.filename_fileid_mapping.insert(id, handle);
         handle
     }
 
+    /// sets how the paths of files registered from now on are printed
+    pub fn with_file_paths(mut self, file_paths: FilePaths) -> Self {
+        self.file_paths = file_paths;
+        self
+    }
+
+    /// returns the path under which the given file is reported, sources without a file
+    /// (e.g. `<internal>`) keep their name
+    fn get_display_path(&self, id: &str) -> String {
+        if id.starts_with('<') || self.file_paths == FilePaths::AsProvided {
+            return id.to_string();
+        }
+        let path = make_absolute(Path::new(id));
+        let path = match &self.file_paths {
+            FilePaths::RelativeTo(root) => {
+                path.strip_prefix(make_absolute(root)).map(Path::to_path_buf).unwrap_or(path)
+            }
+            _ => path,
+        };
+        path.to_string_lossy().to_string()
+    }
+
     fn get_file_handle(&self, file_name: Option<&str>) -> Option<usize> {
         file_name.and_then(|it| self.filename_fileid_mapping.get(it).cloned())
     }
@@ -37,13 +75,13 @@ impl Diagnostician {
             .iter()
             .flat_map(|it| match it {
                 Diagnostic::CombinedDiagnostic { inner_diagnostics, .. } => {
-                    let mut res = vec![it];
-                    res.extend(inner_diagnostics.iter().collect::<Vec<&Diagnostic>>());
+                    let mut res = vec![(it, false)];
+                    res.extend(inner_diagnostics.iter().map(|it| (it, true)));
                     res
                 }
-                _ => vec![it],
+                _ => vec![(it, false)],
             })
-            .map(|d| ResolvedDiagnostics {
+            .map(|(d, is_inner)| ResolvedDiagnostics {
                 message: d.get_message().to_string(),
                 severity: self.assess(d),
                 main_location: ResolvedLocation {
@@ -62,6 +100,7 @@ impl Diagnostician {
                         })
                         .collect()
                 }),
+                is_inner,
             })
             .collect::<Vec<_>>();
 
@@ -76,6 +115,7 @@ impl Diagnostician {
             assessor: Box::<DefaultDiagnosticAssessor>::default(),
             reporter: Box::<NullDiagnosticReporter>::default(),
             filename_fileid_mapping: HashMap::new(),
+            file_paths: FilePaths::default(),
         }
     }
 
@@ -85,6 +125,7 @@ impl Diagnostician {
             assessor: Box::<DefaultDiagnosticAssessor>::default(),
             reporter: Box::new(CodeSpanDiagnosticReporter::buffered()),
             filename_fileid_mapping: HashMap::new(),
+            file_paths: FilePaths::default(),
         }
     }
 
@@ -94,8 +135,30 @@ impl Diagnostician {
             reporter: Box::<ClangFormatDiagnosticReporter>::default(),
             assessor: Box::<DefaultDiagnosticAssessor>::default(),
             filename_fileid_mapping: HashMap::new(),
+            file_paths: FilePaths::default(),
+        }
+    }
+}
+
+/// resolves relative paths against the current working directory and normalizes `.` and `..` components.
+/// The path is normalized lexically, since registered files do not necessarily exist on disk
+fn make_absolute(path: &Path) -> PathBuf {
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().map(|it| it.join(path)).unwrap_or_else(|_| path.to_path_buf())
+    };
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
         }
     }
+    normalized
 }
 
 impl DiagnosticReporter for Diagnostician {
@@ -129,6 +192,7 @@ impl Default for Diagnostician {
             reporter: Box::<CodeSpanDiagnosticReporter>::default(),
             assessor: Box::<DefaultDiagnosticAssessor>::default(),
             filename_fileid_mapping: HashMap::new(),
+            file_paths: FilePaths::default(),
         }
     }
 }
@@ -136,6 +136,34 @@ impl Diagnostic {
         }
     }
 
+    pub fn incompatible_string_comparison(
+        left_type: &str,
+        right_type: &str,
+        location: SourceLocation,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "Cannot compare '{left_type}' with '{right_type}', both strings need the same encoding"
+            ),
+            range: vec![location],
+            err_no: ErrNo::type__incompatible_string_comparison,
+        }
+    }
+
+    pub fn incompatible_char_promotion(
+        char_type: &str,
+        string_type: &str,
+        location: SourceLocation,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "Cannot promote '{char_type}' to '{string_type}', characters are promoted to strings of the same encoding"
+            ),
+            range: vec![location],
+            err_no: ErrNo::type__incompatible_char_promotion,
+        }
+    }
+
     pub fn missing_token(expected_token: &str, range: SourceLocation) -> Diagnostic {
         Diagnostic::SyntaxError {
             message: format!("Missing expected Token {expected_token}"),
@@ -144,6 +172,14 @@ impl Diagnostic {
         }
     }
 
+    pub fn unterminated_var_block(range: SourceLocation) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: "Variable block is not terminated, missing END_VAR".to_string(),
+            range: vec![range],
+            err_no: ErrNo::syntax__unterminated_block,
+        }
+    }
+
     pub fn missing_action_container(range: SourceLocation) -> Diagnostic {
         Diagnostic::ImprovementSuggestion {
             message: "Missing Actions Container Name".to_string(),
@@ -159,6 +195,28 @@ impl Diagnostic {
         }
     }
 
+    pub fn no_such_member(member: &str, container: &str, location: SourceLocation) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!("`{container}` has no member named `{member}`"),
+            range: vec![location],
+            err_no: ErrNo::reference__unresolved,
+        }
+    }
+
+    pub fn explicit_dereference_required(
+        pointer: &str,
+        member: &str,
+        location: SourceLocation,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "`{pointer}` is a pointer and must be dereferenced explicitly, use `{pointer}^.{member}` instead"
+            ),
+            range: vec![location],
+            err_no: ErrNo::reference__unresolved,
+        }
+    }
+
     pub fn illegal_access(reference: &str, location: SourceLocation) -> Diagnostic {
         Diagnostic::SyntaxError {
             message: format!("Illegal access to private member {reference:}"),
@@ -231,6 +289,31 @@ impl Diagnostic {
         }
     }
 
+    pub fn incompatible_hardware_binding(
+        access_type: &str,
+        access_size: u64,
+        type_name: &str,
+        type_size: u64,
+        location: SourceLocation,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "{access_type}-Wise hardware binding ({access_size} bits) is incompatible with type {type_name} ({type_size} bits)"
+            ),
+            range: vec![location],
+            err_no: ErrNo::type__incompatible_hardware_binding,
+        }
+    }
+
+    pub fn hardware_binding_not_placed(region_name: &str, location: SourceLocation) -> Diagnostic {
+        Diagnostic::ImprovementSuggestion {
+            message: format!(
+                "Bit- and multi-segment addresses are not placed in `{region_name}`, the variable is allocated as an ordinary global"
+            ),
+            range: vec![location],
+        }
+    }
+
     pub fn incompatible_array_access_range(range: Range<i64>, location: SourceLocation) -> Diagnostic {
         Diagnostic::SyntaxError {
             message: format!("Array access must be in the range {}..{}", range.start, range.end),
@@ -239,6 +322,13 @@ impl Diagnostic {
         }
     }
 
+    pub fn string_access_out_of_range(length: i64, location: SourceLocation) -> Diagnostic {
+        Diagnostic::ImprovementSuggestion {
+            message: format!("String access must be in the range 1..{length}"),
+            range: vec![location],
+        }
+    }
+
     pub fn incompatible_array_access_variable(access_type: &str, location: SourceLocation) -> Diagnostic {
         Diagnostic::SyntaxError {
             message: format!(
@@ -295,6 +385,14 @@ impl Diagnostic {
         }
     }
 
+    pub fn invalid_bitfield_member(name: &str, type_name: &str, location: SourceLocation) -> Diagnostic {
+        Diagnostic::SemanticError {
+            message: format!("Bitfield member '{name}' must be of type BOOL but is '{type_name}'"),
+            range: vec![location],
+            err_no: ErrNo::type__invalid_type,
+        }
+    }
+
     pub fn empty_variable_block(location: SourceLocation) -> Diagnostic {
         Diagnostic::SyntaxError {
             message: "Variable block is empty".into(),
@@ -377,6 +475,13 @@ impl Diagnostic {
         }
     }
 
+    pub fn io_watch_error(file: &str, reason: &str) -> Diagnostic {
+        Diagnostic::GeneralError {
+            message: format!("Cannot watch file '{file}': {reason}"),
+            err_no: ErrNo::general__io_err,
+        }
+    }
+
     pub fn io_write_error(file: &str, reason: &str) -> Diagnostic {
         Diagnostic::GeneralError {
             message: format!("Cannot write file {file} {reason}'"),
@@ -388,6 +493,19 @@ impl Diagnostic {
         Diagnostic::GeneralError { message: reason.to_string(), err_no: ErrNo::general__param_err }
     }
 
+    /// reports a missing build description together with the directory it was searched in
+    pub fn build_config_not_found(file_name: &str, directory: &str, current_dir: &str) -> Diagnostic {
+        Diagnostic::param_error(&format!(
+            "Could not find '{file_name}' in '{directory}' (current directory: '{current_dir}')"
+        ))
+    }
+
+    pub fn unknown_config_format(config: &str) -> Diagnostic {
+        Diagnostic::param_error(&format!(
+            "Cannot identify the format of the hardware configuration '{config}', use --config-format or one of the extensions: json, toml"
+        ))
+    }
+
     pub fn llvm_error(file: &str, llvm_error: &str) -> Diagnostic {
         Diagnostic::GeneralError {
             message: format!("{file}: Internal llvm error: {:}", llvm_error),
@@ -425,6 +543,31 @@ impl Diagnostic {
         }
     }
 
+    pub fn no_matching_overload(
+        function: &str,
+        left_type: &str,
+        right_type: &str,
+        location: SourceLocation,
+    ) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "No matching overload of '{function}', arguments of type {left_type} and {right_type} have no common type."
+            ),
+            range: vec![location],
+            err_no: ErrNo::call__no_matching_overload,
+        }
+    }
+
+    pub fn type_nature_mismatch(actual: &str, location: SourceLocation) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!(
+                "Invalid type nature for generic argument. {actual} is no user-defined type (ANY_DERIVED)."
+            ),
+            range: vec![location],
+            err_no: ErrNo::type__invalid_nature,
+        }
+    }
+
     pub fn unknown_type_nature(nature: &str, location: SourceLocation) -> Diagnostic {
         Diagnostic::SyntaxError {
             message: format!("Unknown type nature {nature}."),
@@ -458,6 +601,13 @@ impl Diagnostic {
         Diagnostic::GeneralError { err_no: ErrNo::linker__generic_error, message: error.to_string() }
     }
 
+    pub fn invalid_entry_point(entry_point: &str, reason: &str) -> Diagnostic {
+        Diagnostic::GeneralError {
+            err_no: ErrNo::linker__invalid_entry_point,
+            message: format!("Cannot use '{entry_point}' as entry point, {reason}"),
+        }
+    }
+
     pub fn get_message(&self) -> &str {
         match self {
             Diagnostic::SyntaxError { message, .. }
@@ -517,6 +667,23 @@ impl Diagnostic {
         }
     }
 
+    pub fn unresolved_include(file: &str, range: SourceLocation) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!("Could not find the included file '{file}'"),
+            range: vec![range],
+            err_no: ErrNo::general__unresolved_include,
+        }
+    }
+
+    /// reports a file including itself, `cycle` lists the files from the including to the included file
+    pub fn cyclic_include(cycle: &[String], range: SourceLocation) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!("Cyclic include: {}", cycle.join(" -> ")),
+            range: vec![range],
+            err_no: ErrNo::general__cyclic_include,
+        }
+    }
+
     pub fn invalid_pragma_location(message: &str, range: SourceLocation) -> Diagnostic {
         Diagnostic::ImprovementSuggestion {
             message: format!("Invalid pragma location: {message}"),
@@ -524,6 +691,20 @@ impl Diagnostic {
         }
     }
 
+    pub fn unsupported_function_attribute(
+        attribute: &str,
+        allowed: &[&str],
+        range: SourceLocation,
+    ) -> Diagnostic {
+        Diagnostic::ImprovementSuggestion {
+            message: format!(
+                "Unsupported function attribute '{attribute}' will be ignored, allowed attributes are: {}",
+                allowed.join(", ")
+            ),
+            range: vec![range],
+        }
+    }
+
     pub fn non_constant_case_condition(case: &str, range: SourceLocation) -> Diagnostic {
         Diagnostic::SyntaxError {
             message: format!("{case}. Non constant variables are not supported in case conditions"),
@@ -540,6 +721,14 @@ impl Diagnostic {
         }
     }
 
+    pub fn control_flow_outside_loop(keyword: &str, location: SourceLocation) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!("{keyword} is only allowed inside a FOR, WHILE or REPEAT loop"),
+            range: vec![location],
+            err_no: ErrNo::syntax__control_flow_outside_loop,
+        }
+    }
+
     pub fn case_condition_used_outside_case_statement(range: SourceLocation) -> Diagnostic {
         Diagnostic::SyntaxError {
             message: "Case condition used outside of case statement! Did you mean to use ';'?".into(),
@@ -564,6 +753,14 @@ impl Diagnostic {
         }
     }
 
+    pub fn missing_input_parameter(parameter: &str, range: SourceLocation) -> Diagnostic {
+        Diagnostic::SyntaxError {
+            message: format!("Missing input parameter: {parameter} has no default value and must be passed"),
+            range: vec![range],
+            err_no: ErrNo::call__missing_input_parameter,
+        }
+    }
+
     pub fn invalid_parameter_type(range: SourceLocation) -> Diagnostic {
         Diagnostic::SyntaxError {
             message: "Cannot mix implicit and explicit call parameters!".into(),
@@ -609,6 +806,21 @@ impl Diagnostic {
         }
     }
 
+    pub fn inout_type_mismatch(
+        parameter_name: &str,
+        expected_type: &str,
+        actual_type: &str,
+        range: SourceLocation,
+    ) -> Diagnostic {
+        Diagnostic::SemanticError {
+            message: format!(
+                "Invalid argument for VAR_IN_OUT parameter {parameter_name}: expected '{expected_type}' but got '{actual_type}'"
+            ),
+            range: vec![range],
+            err_no: ErrNo::call__invalid_parameter_type,
+        }
+    }
+
     pub fn invalid_type_name(name: &str, range: Vec<SourceLocation>) -> Diagnostic {
         Diagnostic::SyntaxError {
             message: format!("{name} can not be used as a name because it is a built-in datatype"),
@@ -648,6 +860,14 @@ impl Diagnostic {
         }
     }
 
+    pub fn duplicate_symbol(name: &str, locations: Vec<SourceLocation>) -> Diagnostic {
+        Diagnostic::SemanticError {
+            message: format!("{name}: Duplicate symbol, it is defined in more than one module."),
+            range: locations,
+            err_no: ErrNo::duplicate_symbol,
+        }
+    }
+
     pub fn invalid_operation(message: &str, range: SourceLocation) -> Diagnostic {
         Diagnostic::SyntaxError {
             message: message.to_string(),
@@ -680,6 +900,20 @@ impl Diagnostic {
         }
     }
 
+    pub fn incompatible_array_assignment(
+        right_type: &str,
+        left_type: &str,
+        range: SourceLocation,
+    ) -> Diagnostic {
+        Diagnostic::SemanticError {
+            message: format!(
+                "Invalid assignment: cannot assign '{right_type}' to '{left_type}', the arrays differ in their dimensions or element type"
+            ),
+            range: vec![range],
+            err_no: ErrNo::arr__invalid_array_assignment,
+        }
+    }
+
     pub fn recursive_datastructure(path: &str, range: Vec<SourceLocation>) -> Diagnostic {
         Diagnostic::SemanticError {
             message: format!("Recursive data structure `{path}` has infinite size"),
@@ -688,6 +922,22 @@ impl Diagnostic {
         }
     }
 
+    pub fn illegal_recursion(path: &str, range: Vec<SourceLocation>) -> Diagnostic {
+        Diagnostic::SemanticError {
+            message: format!("Illegal recursion `{path}`, functions must not be called recursively"),
+            range,
+            err_no: ErrNo::pou__illegal_recursion,
+        }
+    }
+
+    pub fn this_outside_method(range: SourceLocation) -> Diagnostic {
+        Diagnostic::SemanticError {
+            message: "`THIS` is only allowed in function blocks, their actions and methods".to_string(),
+            range: vec![range],
+            err_no: ErrNo::pou__this_outside_method,
+        }
+    }
+
     pub fn vla_by_val_warning(range: SourceLocation) -> Diagnostic {
         Diagnostic::ImprovementSuggestion {
             message: "Variable Length Arrays are always by-ref, even when declared in a by-value block"
@@ -696,6 +946,110 @@ impl Diagnostic {
         }
     }
 
+    pub fn abs_on_unsigned(type_name: &str, range: SourceLocation) -> Diagnostic {
+        Diagnostic::ImprovementSuggestion {
+            message: format!("ABS has no effect on the unsigned type `{type_name}`"),
+            range: vec![range],
+        }
+    }
+
+    pub fn invalid_target_feature(feature: &str) -> Diagnostic {
+        Diagnostic::ImprovementSuggestion {
+            message: format!(
+                "Target feature '{feature}' is ignored, features must be enabled with '+' or disabled with '-'"
+            ),
+            range: vec![SourceLocation::undefined()],
+        }
+    }
+
+    pub fn unknown_target_feature(feature: &str, target: &str) -> Diagnostic {
+        Diagnostic::ImprovementSuggestion {
+            message: format!("Target feature '{feature}' is ignored, it is not a feature of '{target}'"),
+            range: vec![SourceLocation::undefined()],
+        }
+    }
+
+    pub fn unknown_target_cpu(cpu: &str, target: &str) -> Diagnostic {
+        Diagnostic::ImprovementSuggestion {
+            message: format!("Target cpu '{cpu}' is ignored, it is not a cpu of '{target}'"),
+            range: vec![SourceLocation::undefined()],
+        }
+    }
+
+    pub fn strip_with_debug_info() -> Diagnostic {
+        Diagnostic::ImprovementSuggestion {
+            message: "The generated debug information is removed again by --strip".to_string(),
+            range: vec![SourceLocation::undefined()],
+        }
+    }
+
+    pub fn unknown_targets(triples: &[&str]) -> Diagnostic {
+        let triples = triples.iter().map(|it| format!("'{it}'")).collect::<Vec<_>>().join(", ");
+        Diagnostic::GeneralError {
+            message: format!("Unknown target triples: {triples}"),
+            err_no: ErrNo::general__param_err,
+        }
+    }
+
+    pub fn shift_amount_out_of_range(
+        amount: i128,
+        type_name: &str,
+        width: u32,
+        range: SourceLocation,
+    ) -> Diagnostic {
+        Diagnostic::ImprovementSuggestion {
+            message: format!(
+                "Shift amount {amount} is not smaller than the width of `{type_name}` ({width} bits)"
+            ),
+            range: vec![range],
+        }
+    }
+
+    pub fn concat_truncated(type_name: &str, capacity: i64, range: SourceLocation) -> Diagnostic {
+        Diagnostic::ImprovementSuggestion {
+            message: format!(
+                "The result of CONCAT exceeds the length of `{type_name}` and will be truncated to {capacity} characters"
+            ),
+            range: vec![range],
+        }
+    }
+
+    pub fn string_literal_truncated(literal: &str, type_name: &str, range: SourceLocation) -> Diagnostic {
+        Diagnostic::ImprovementSuggestion {
+            message: format!(
+                "The literal {literal} exceeds the length of `{type_name}` and will be truncated"
+            ),
+            range: vec![range],
+        }
+    }
+
+    pub fn invalid_date_time_arithmetic(
+        operator: &str,
+        left_type: &str,
+        right_type: &str,
+        range: SourceLocation,
+    ) -> Diagnostic {
+        Diagnostic::ImprovementSuggestion {
+            message: format!(
+                "`{left_type} {operator} {right_type}` is no valid date and time operation, the values are treated as plain numbers"
+            ),
+            range: vec![range],
+        }
+    }
+
+    pub fn mixed_signedness_comparison(
+        left_type: &str,
+        right_type: &str,
+        range: SourceLocation,
+    ) -> Diagnostic {
+        Diagnostic::ImprovementSuggestion {
+            message: format!(
+                "Comparison between signed and unsigned types `{left_type}` and `{right_type}`, consider an explicit cast"
+            ),
+            range: vec![range],
+        }
+    }
+
     pub fn invalid_vla_container(message: String, range: SourceLocation) -> Diagnostic {
         Diagnostic::SemanticError { message, range: vec![range], err_no: ErrNo::vla__invalid_container }
     }
@@ -708,6 +1062,14 @@ impl Diagnostic {
         }
     }
 
+    pub fn invalid_array_slice(message: &str, range: SourceLocation) -> Diagnostic {
+        Diagnostic::SemanticError {
+            message: message.to_string(),
+            range: vec![range],
+            err_no: ErrNo::vla__invalid_array_slice,
+        }
+    }
+
     pub fn invalid_range_statement(entity: &AstNode, range: SourceLocation) -> Diagnostic {
         Diagnostic::SyntaxError {
             message: format!("Expected a range statement, got {entity:?} instead"),
@@ -725,10 +1087,49 @@ impl Diagnostic {
         }
     }
 
+    pub fn unsupported_configuration_element(element: &str, location: SourceLocation) -> Diagnostic {
+        Diagnostic::ImprovementSuggestion {
+            message: format!(
+                "{element} is not supported inside a CONFIGURATION and will be ignored, only VAR_GLOBAL blocks are supported"
+            ),
+            range: vec![location],
+        }
+    }
+
+    pub fn null_dereference(pointer: &str, location: SourceLocation) -> Diagnostic {
+        Diagnostic::ImprovementSuggestion {
+            message: format!("Dereferencing `{pointer}` which was assigned NULL"),
+            range: vec![location],
+        }
+    }
+
+    pub fn unreachable_code(reason: &str, location: SourceLocation) -> Diagnostic {
+        Diagnostic::ImprovementSuggestion {
+            message: format!("Unreachable code, {reason}"),
+            range: vec![location],
+        }
+    }
+
     pub fn overflow(message: String, location: SourceLocation) -> Diagnostic {
         Diagnostic::SemanticError { message, range: vec![location], err_no: ErrNo::var__overflow }
     }
 
+    pub fn reserved_keyword(name: &str, location: SourceLocation) -> Diagnostic {
+        Diagnostic::SemanticError {
+            message: format!("'{name}' is a reserved keyword and cannot be used as a name"),
+            range: vec![location],
+            err_no: ErrNo::syntax__reserved_keyword,
+        }
+    }
+
+    pub fn division_by_zero(location: SourceLocation) -> Diagnostic {
+        Diagnostic::SemanticError {
+            message: "Division by zero".into(),
+            range: vec![location],
+            err_no: ErrNo::var__division_by_zero,
+        }
+    }
+
     pub fn index_out_of_bounds(range: SourceLocation) -> Diagnostic {
         Diagnostic::SemanticError {
             message: "Index out of bounds.".into(),
@@ -804,6 +1205,15 @@ impl Diagnostic {
         }
     }
 
+    pub fn conflicting_library_linkage(library: &str, linkage: &str, other_linkage: &str) -> Diagnostic {
+        Diagnostic::invalid_build_description_file(
+            format!(
+                "Library '{library}' is declared with conflicting linkages '{linkage}' and '{other_linkage}'"
+            ),
+            None,
+        )
+    }
+
     pub fn invalid_build_description_file(message: String, location: Option<SourceLocation>) -> Diagnostic {
         let range = if let Some(range) = location { vec![range] } else { vec![SourceLocation::internal()] };
         Diagnostic::SemanticError { message, range, err_no: ErrNo::plc_json__invalid }
@@ -840,9 +1250,159 @@ impl SerdeError {
 
 #[cfg(test)]
 mod tests {
-    use codespan_reporting::files::{Location, SimpleFile};
+    use codespan_reporting::{
+        files::{Location, SimpleFile},
+        term::termcolor::ColorChoice,
+    };
+    use plc_source::{source_location::SourceLocationFactory, SourceCode};
+
+    use crate::{
+        diagnostician::{Diagnostician, FilePaths, Severity},
+        diagnostics::Diagnostic,
+        errno::ErrNo,
+        reporter::{clang::ClangFormatDiagnosticReporter, codespan::color_choice, DiagnosticReporter},
+    };
+
+    #[test]
+    fn colors_are_only_used_for_terminals_without_no_color() {
+        assert_eq!(color_choice(true, false), ColorChoice::Auto);
+        assert_eq!(color_choice(false, false), ColorChoice::Never);
+        assert_eq!(color_choice(true, true), ColorChoice::Never);
+        assert_eq!(color_choice(false, true), ColorChoice::Never);
+    }
+
+    #[test]
+    fn buffered_reports_contain_no_color_codes() {
+        let mut diagnostician = Diagnostician::buffered();
+        let source = SourceCode::new("PROGRAM prg END_PROGRAM", "test.st");
+        diagnostician.register_file("test.st".into(), source.source.clone());
+        let location = SourceLocationFactory::for_source(&source).create_range(8..11);
+
+        diagnostician.handle(&[
+            Diagnostic::syntax_error("an error", location.clone()),
+            Diagnostic::ImprovementSuggestion { message: "a warning".into(), range: vec![location] },
+        ]);
+
+        let report = diagnostician.buffer().unwrap();
+        assert!(report.contains("error: an error"));
+        assert!(report.contains("warning: a warning"));
+        assert!(!report.contains('\u{1b}'));
+    }
 
-    use crate::{diagnostician::Severity, reporter::clang::ClangFormatDiagnosticReporter};
+    #[test]
+    fn diagnostics_of_several_files_are_grouped_by_file() {
+        let mut diagnostician = Diagnostician::buffered();
+        let b = SourceCode::new("PROGRAM b END_PROGRAM", "src/b.st");
+        let a = SourceCode::new("PROGRAM a END_PROGRAM", "src/a.st");
+        diagnostician.register_file("src/b.st".into(), b.source.clone());
+        diagnostician.register_file("src/a.st".into(), a.source.clone());
+        let in_b = SourceLocationFactory::for_source(&b).create_range(8..9);
+        let in_a = SourceLocationFactory::for_source(&a).create_range(8..9);
+
+        diagnostician.handle(&[
+            Diagnostic::syntax_error("first error in b", in_b.clone()),
+            Diagnostic::syntax_error("error in a", in_a),
+            Diagnostic::syntax_error("second error in b", in_b),
+        ]);
+
+        // the diagnostics are reported under a header per file, sorted by path
+        let report = diagnostician.buffer().unwrap();
+        let position = |text: &str| report.find(text).unwrap_or_else(|| panic!("{text} in {report}"));
+        assert_eq!(report.matches("==> ").count(), 2, "{report}");
+        assert!(position("==> src/a.st <==") < position("error: error in a"), "{report}");
+        assert!(position("error: error in a") < position("==> src/b.st <=="), "{report}");
+        assert!(position("==> src/b.st <==") < position("error: first error in b"), "{report}");
+        assert!(position("error: first error in b") < position("error: second error in b"), "{report}");
+
+        // diagnostics of a single file are reported without a header
+        assert!(!report_in(Diagnostician::buffered(), "src/a.st").contains("==>"));
+    }
+
+    #[test]
+    fn inner_diagnostics_are_reported_with_their_combined_diagnostic() {
+        let mut diagnostician = Diagnostician::buffered();
+        let b = SourceCode::new("PROGRAM b END_PROGRAM", "src/b.st");
+        let a = SourceCode::new("PROGRAM a END_PROGRAM", "src/a.st");
+        diagnostician.register_file("src/b.st".into(), b.source.clone());
+        diagnostician.register_file("src/a.st".into(), a.source.clone());
+        let in_b = SourceLocationFactory::for_source(&b).create_range(8..9);
+        let in_a = SourceLocationFactory::for_source(&a).create_range(8..9);
+
+        diagnostician.handle(&[
+            Diagnostic::CombinedDiagnostic {
+                message: "combined".into(),
+                inner_diagnostics: vec![
+                    Diagnostic::syntax_error("inner error in b", in_b),
+                    Diagnostic::syntax_error("inner error in a", in_a.clone()),
+                ],
+                err_no: ErrNo::general__err,
+            },
+            Diagnostic::syntax_error("error in a", in_a),
+        ]);
+
+        // the group is reported under the header of its first file
+        let report = diagnostician.buffer().unwrap();
+        let position = |text: &str| report.find(text).unwrap_or_else(|| panic!("{text} in {report}"));
+        assert!(position("==> src/a.st <==") < position("error: error in a"), "{report}");
+        assert!(position("error: error in a") < position("==> src/b.st <=="), "{report}");
+        assert!(position("==> src/b.st <==") < position("error: combined"), "{report}");
+        assert!(position("error: combined") < position("error: inner error in b"), "{report}");
+        assert!(position("error: inner error in b") < position("error: inner error in a"), "{report}");
+    }
+
+    fn report_in(diagnostician: Diagnostician, file: &str) -> String {
+        let mut diagnostician = diagnostician;
+        let source = SourceCode::new("PROGRAM prg END_PROGRAM", file);
+        diagnostician.register_file(file.into(), source.source.clone());
+        let location = SourceLocationFactory::for_source(&source).create_range(8..11);
+        diagnostician.handle(&[Diagnostic::syntax_error("an error", location)]);
+        diagnostician.buffer().unwrap()
+    }
+
+    #[test]
+    fn file_paths_are_reported_as_provided_by_default() {
+        let report = report_in(Diagnostician::buffered(), "src/test.st");
+        assert!(report.contains("┌─ src/test.st:1:9"), "{report}");
+    }
+
+    #[test]
+    fn file_paths_are_reported_as_absolute_paths() {
+        let root = std::env::current_dir().unwrap();
+        let report = report_in(Diagnostician::buffered().with_file_paths(FilePaths::Absolute), "src/test.st");
+        let expected = root.join("src").join("test.st");
+        assert!(report.contains(&format!("┌─ {}:1:9", expected.to_string_lossy())), "{report}");
+    }
+
+    #[test]
+    fn file_paths_are_reported_relative_to_the_project_root() {
+        let root = std::env::current_dir().unwrap().join("project");
+        let diagnostician = Diagnostician::buffered().with_file_paths(FilePaths::RelativeTo(root.clone()));
+        let file = root.join("src").join("test.st");
+        let report = report_in(diagnostician, &file.to_string_lossy());
+        let expected = std::path::Path::new("src").join("test.st");
+        assert!(report.contains(&format!("┌─ {}:1:9", expected.to_string_lossy())), "{report}");
+
+        // files outside of the root are reported as absolute paths
+        let diagnostician = Diagnostician::buffered().with_file_paths(FilePaths::RelativeTo(root));
+        let report = report_in(diagnostician, "./lib.st");
+        let expected = std::env::current_dir().unwrap().join("lib.st");
+        assert!(report.contains(&format!("┌─ {}:1:9", expected.to_string_lossy())), "{report}");
+    }
+
+    #[test]
+    fn parent_directories_are_resolved_in_reported_file_paths() {
+        let root = std::env::current_dir().unwrap().join("project");
+        let diagnostician = Diagnostician::buffered().with_file_paths(FilePaths::RelativeTo(root.clone()));
+        let file = root.join("lib").join("..").join("src").join(".").join("test.st");
+        let report = report_in(diagnostician, &file.to_string_lossy());
+        let expected = std::path::Path::new("src").join("test.st");
+        assert!(report.contains(&format!("┌─ {}:1:9", expected.to_string_lossy())), "{report}");
+
+        let report =
+            report_in(Diagnostician::buffered().with_file_paths(FilePaths::Absolute), "src/../test.st");
+        let expected = std::env::current_dir().unwrap().join("test.st");
+        assert!(report.contains(&format!("┌─ {}:1:9", expected.to_string_lossy())), "{report}");
+    }
 
     #[test]
     fn test_build_diagnostic_msg() {
@@ -9,12 +9,17 @@ pub enum ErrNo {
     general__err,
     general__io_err,
     general__param_err,
+    general__unresolved_include,
+    general__cyclic_include,
     duplicate_symbol,
 
     //syntax
     syntax__generic_error,
     syntax__missing_token,
     syntax__unexpected_token,
+    syntax__reserved_keyword,
+    syntax__control_flow_outside_loop,
+    syntax__unterminated_block,
 
     //semantic
     // pou related
@@ -24,10 +29,14 @@ pub enum ErrNo {
     pou__empty_variable_block,
     pou__missing_action_container,
     pou__recursive_data_structure,
+    pou__illegal_recursion,
+    pou__this_outside_method,
 
     // call
     call__invalid_parameter_type,
     call__invalid_parameter_count,
+    call__missing_input_parameter,
+    call__no_matching_overload,
 
     //variable related
     var__unresolved_constant,
@@ -39,6 +48,7 @@ pub enum ErrNo {
     var__assigning_to_var_input_ref,
     var__overflow,
     var__invalid_enum_variant,
+    var__division_by_zero,
 
     //array related
     arr__invalid_array_assignment,
@@ -47,6 +57,7 @@ pub enum ErrNo {
     vla__invalid_container,
     vla__invalid_array_access,
     vla__dimension_idx_out_of_bounds,
+    vla__invalid_array_slice,
 
     //reference related
     reference__unresolved,
@@ -62,6 +73,7 @@ pub enum ErrNo {
     type__incompatible_directaccess,
     type__incompatible_directaccess_variable,
     type__incompatible_directaccess_range,
+    type__incompatible_hardware_binding,
     type__incompatible_arrayaccess_range,
     type__incompatible_arrayaccess_variable,
     type__incompatible_arrayaccess_type,
@@ -72,6 +84,8 @@ pub enum ErrNo {
     type__incompatible_size,
     type__invalid_operation,
     type__invalid_name,
+    type__incompatible_string_comparison,
+    type__incompatible_char_promotion,
 
     //codegen related
     codegen__general,
@@ -82,6 +96,7 @@ pub enum ErrNo {
     debug_general,
     //linker
     linker__generic_error,
+    linker__invalid_entry_point,
 
     //switch case
     case__duplicate_condition,
@@ -39,4 +39,6 @@ pub struct ResolvedDiagnostics {
     pub severity: Severity,
     pub main_location: ResolvedLocation,
     pub additional_locations: Option<Vec<ResolvedLocation>>,
+    /// true for the inner diagnostics of a combined diagnostic, they directly follow the combined diagnostic
+    pub is_inner: bool,
 }
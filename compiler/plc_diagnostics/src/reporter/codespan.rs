@@ -1,3 +1,8 @@
+use std::{
+    collections::HashSet,
+    io::{IsTerminal, Write},
+};
+
 use codespan_reporting::{
     diagnostic::{Diagnostic, Label},
     files::SimpleFiles,
@@ -77,10 +82,64 @@ impl CodeSpanDiagnosticReporter {
     fn emit(&mut self, diag: Diagnostic<usize>) -> Result<(), codespan_reporting::files::Error> {
         codespan_reporting::term::emit(&mut self.writer, &self.config, &self.files, &diag)
     }
+
+    /// returns the name of the registered file with the given handle
+    fn get_file_name(&self, file_handle: usize) -> Option<String> {
+        self.files.get(file_handle).ok().map(|it| it.name().to_string())
+    }
+
+    fn report_diagnostic(&mut self, d: &ResolvedDiagnostics) {
+        let diagnostic_factory = match d.severity {
+            Severity::Critical | Severity::Error => codespan_reporting::diagnostic::Diagnostic::error(),
+            Severity::Warning => codespan_reporting::diagnostic::Diagnostic::warning(),
+            Severity::Info => codespan_reporting::diagnostic::Diagnostic::note(),
+        };
+
+        let mut labels = vec![];
+
+        if !matches!(d.main_location.span, CodeSpan::None) {
+            labels.push(
+                Label::primary(
+                    d.main_location.file_handle,
+                    d.main_location.span.to_range().unwrap_or_else(|| 0..0),
+                )
+                .with_message(d.message.as_str()),
+            );
+        }
+
+        if let Some(additional_locations) = &d.additional_locations {
+            labels.extend(additional_locations.iter().filter_map(|it| {
+                if !matches!(it.span, CodeSpan::None) {
+                    Some(
+                        Label::secondary(it.file_handle, it.span.to_range().unwrap_or_else(|| 0..0))
+                            .with_message("see also"),
+                    )
+                } else {
+                    None
+                }
+            }));
+        }
+
+        let diag = diagnostic_factory.with_labels(labels).with_message(d.message.as_str());
+
+        let result = self.emit(diag);
+        if result.is_err() && d.main_location.is_internal() {
+            eprintln!("<internal>: {}", d.message);
+        }
+    }
+}
+
+/// Colors are only used when reporting to a terminal and the user did not opt out by setting `NO_COLOR`
+pub(crate) fn color_choice(is_terminal: bool, no_color: bool) -> ColorChoice {
+    if is_terminal && !no_color {
+        ColorChoice::Auto
+    } else {
+        ColorChoice::Never
+    }
 }
 
 impl Default for CodeSpanDiagnosticReporter {
-    /// creates the default CodeSpanDiagnosticReporter reporting to StdErr, with colors
+    /// creates the default CodeSpanDiagnosticReporter reporting to StdErr, with colors if StdErr is a terminal
     fn default() -> Self {
         Self::terminal(
             codespan_reporting::term::Config {
@@ -91,51 +150,54 @@ impl Default for CodeSpanDiagnosticReporter {
                 start_context_lines: 5,
                 end_context_lines: 3,
             },
-            StandardStream::stderr(ColorChoice::Always),
+            StandardStream::stderr(color_choice(
+                std::io::stderr().is_terminal(),
+                std::env::var_os("NO_COLOR").is_some_and(|it| !it.is_empty()),
+            )),
         )
     }
 }
 
 impl DiagnosticReporter for CodeSpanDiagnosticReporter {
+    /// Diagnostics of several files are grouped under a `==> <file> <==` header per file, sorted by the
+    /// files' paths. Diagnostics without a registered file are reported last
     fn report(&mut self, diagnostics: &[ResolvedDiagnostics]) {
-        for d in diagnostics {
-            let diagnostic_factory = match d.severity {
-                Severity::Critical | Severity::Error => codespan_reporting::diagnostic::Diagnostic::error(),
-                Severity::Warning => codespan_reporting::diagnostic::Diagnostic::warning(),
-                Severity::Info => codespan_reporting::diagnostic::Diagnostic::note(),
-            };
-
-            let mut labels = vec![];
-
-            if !matches!(d.main_location.span, CodeSpan::None) {
-                labels.push(
-                    Label::primary(
-                        d.main_location.file_handle,
-                        d.main_location.span.to_range().unwrap_or_else(|| 0..0),
-                    )
-                    .with_message(d.message.as_str()),
-                );
-            }
+        let files = diagnostics
+            .iter()
+            .map(|it| it.main_location.file_handle)
+            .filter(|it| self.files.get(*it).is_ok())
+            .collect::<HashSet<_>>();
+        if files.len() < 2 {
+            diagnostics.iter().for_each(|it| self.report_diagnostic(it));
+            return;
+        }
 
-            if let Some(additional_locations) = &d.additional_locations {
-                labels.extend(additional_locations.iter().filter_map(|it| {
-                    if !matches!(it.span, CodeSpan::None) {
-                        Some(
-                            Label::secondary(it.file_handle, it.span.to_range().unwrap_or_else(|| 0..0))
-                                .with_message("see also"),
-                        )
-                    } else {
-                        None
+        // the inner diagnostics of a combined diagnostic are kept right after it, the group is sorted
+        // by the first file it reports on
+        let mut groups: Vec<(Option<String>, Vec<&ResolvedDiagnostics>)> = vec![];
+        for d in diagnostics {
+            let file = self.get_file_name(d.main_location.file_handle);
+            match groups.last_mut() {
+                Some((group_file, group)) if d.is_inner => {
+                    group.push(d);
+                    if group_file.is_none() {
+                        *group_file = file;
                     }
-                }));
+                }
+                _ => groups.push((file, vec![d])),
             }
-
-            let diag = diagnostic_factory.with_labels(labels).with_message(d.message.as_str());
-
-            let result = self.emit(diag);
-            if result.is_err() && d.main_location.is_internal() {
-                eprintln!("<internal>: {}", d.message);
+        }
+        groups.sort_by(|(a, _), (b, _)| a.is_none().cmp(&b.is_none()).then_with(|| a.cmp(b)));
+
+        let mut current_file = None;
+        for (file, group) in groups {
+            if current_file.as_ref() != Some(&file) {
+                let header = file.as_deref().unwrap_or("<internal>");
+                // a failing header is no reason to drop the diagnostics
+                let _ = writeln!(self.writer, "==> {header} <==");
+                current_file = Some(file);
             }
+            group.into_iter().for_each(|it| self.report_diagnostic(it));
         }
     }
 
@@ -1,9 +1,15 @@
 // Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
 use clap::{ArgGroup, CommandFactory, ErrorKind, Parser, Subcommand};
 use encoding_rs::Encoding;
-use std::{ffi::OsStr, num::ParseIntError, path::PathBuf};
+use plc_diagnostics::diagnostics::Diagnostic;
+use std::{ffi::OsStr, fs, num::ParseIntError, path::PathBuf};
 
-use plc::{output::FormatOption, ConfigFormat, DebugLevel, ErrorFormat, Target, Threads};
+use plc::{
+    codegen::TargetTuning, output::FormatOption, ConfigFormat, DebugLevel, DiagnosticPaths, ErrorFormat,
+    Target, Threads,
+};
+
+use crate::pipelines::DEFAULT_READ_RETRIES;
 
 pub type ParameterError = clap::Error;
 
@@ -50,12 +56,45 @@ pub struct CompileParameters {
     )]
     pub output_bit_code: bool,
 
+    #[clap(
+        long = "asm",
+        short = 'S',
+        group = "format",
+        global = true,
+        help = "Emit target assembly as output"
+    )]
+    pub output_assembly: bool,
+
     #[clap(short = 'c', global = true, help = "Do not link after compiling object code")]
     pub compile_only: bool,
 
     #[clap(long, name = "target-triple", global = true, help = "A target-triple supported by LLVM")]
     pub target: Vec<Target>,
 
+    #[clap(
+        long,
+        name = "target-cpu",
+        global = true,
+        help = "The CPU to generate code for, e.g. cortex-m4 (default: generic)"
+    )]
+    pub target_cpu: Option<String>,
+
+    #[clap(
+        long,
+        name = "target-features",
+        global = true,
+        help = "A comma separated list of CPU features to enable (+) or disable (-), e.g. +vfp4,-neon"
+    )]
+    pub target_features: Option<String>,
+
+    #[clap(
+        long,
+        name = "inline-threshold",
+        global = true,
+        help = "The threshold up to which functions are inlined when optimizing (overrides the default of -O)"
+    )]
+    pub inline_threshold: Option<u32>,
+
     #[clap(
         long,
         name = "encoding",
@@ -80,23 +119,81 @@ pub struct CompileParameters {
     #[clap(name = "library", long, short = 'l', help = "Library name to link")]
     pub libraries: Vec<String>,
 
+    #[clap(
+        name = "whole-archive-library",
+        long,
+        help = "Static library to link including all of its objects, even unreferenced ones"
+    )]
+    pub whole_archive_libraries: Vec<String>,
+
     #[clap(long, name = "sysroot", global = true, help = "Path to system root, used for linking")]
     pub sysroot: Vec<String>,
 
     #[clap(name = "include", long, short = 'i', help = "Include source files for external functions")]
     pub includes: Vec<String>,
 
+    #[clap(
+        name = "include-dir",
+        long,
+        short = 'I',
+        global = true,
+        help = "Search path for the files of `{include := 'file'}` directives"
+    )]
+    pub include_directories: Vec<String>,
+
+    #[clap(
+        name = "define",
+        long,
+        short = 'D',
+        help = "Define a global constant NAME with the given literal VALUE (NAME=VALUE)",
+        parse(try_from_str = parse_define),
+        global = true
+    )]
+    pub defines: Vec<(String, String)>,
+
     #[clap(
         name = "hardware-conf",
         long,
         global = true,
         help = "Generate Hardware configuration files to the given location. 
-    Format is detected by extenstion.
-    Supported formats : json, toml",
-    parse(try_from_str = validate_config)
-    ) ]
+    Format is detected by extenstion unless --config-format is given.
+    Supported formats : json, toml"
+    )]
     pub hardware_config: Option<String>,
 
+    #[clap(
+        name = "config-format",
+        long,
+        help = "Format of the hardware configuration, detected from the extension of --hardware-conf if omitted",
+        arg_enum,
+        global = true
+    )]
+    pub hardware_config_format: Option<ConfigFormat>,
+
+    #[clap(
+        name = "emit-metadata",
+        long,
+        global = true,
+        help = "Write a JSON description of the exported POUs and types to the given location"
+    )]
+    pub emit_metadata: Option<String>,
+
+    #[clap(
+        name = "emit-header",
+        long,
+        global = true,
+        help = "Write a C header declaring the exported POUs and types to the given location"
+    )]
+    pub emit_header: Option<String>,
+
+    #[clap(
+        name = "emit-bundle",
+        long,
+        global = true,
+        help = "Write a JSON manifest grouping the generated objects of every unit by target to the given location"
+    )]
+    pub emit_bundle: Option<String>,
+
     #[clap(
         name = "optimization",
         long,
@@ -118,9 +215,59 @@ pub struct CompileParameters {
     )]
     pub error_format: ErrorFormat,
 
+    #[clap(
+        name = "diagnostic-paths",
+        long,
+        help = "Print the paths of source files in diagnostics as provided, absolute or relative to the project root",
+        arg_enum,
+        default_value = "as-provided",
+        global = true
+    )]
+    pub diagnostic_paths: DiagnosticPaths,
+
     #[clap(name = "linker", long, help = "Define a custom (cc compatible) linker command", global = true)]
     pub linker: Option<String>,
 
+    #[clap(
+        name = "emit-map",
+        long,
+        global = true,
+        help = "Write a map of the linked symbols and their sizes to the given location"
+    )]
+    pub emit_map: Option<String>,
+
+    #[clap(
+        name = "strip",
+        long,
+        global = true,
+        help = "Remove the symbol table and debug information from the linked output"
+    )]
+    pub strip: bool,
+
+    #[clap(
+        name = "profile",
+        long,
+        global = true,
+        help = "Count the calls of every POU in an exported `__profile_<POU>` counter, e.g. for coverage tooling"
+    )]
+    pub profile: bool,
+
+    #[clap(
+        name = "no-range-checks",
+        long,
+        global = true,
+        help = "Do not check assignments to sub-range variables with the declared `CheckRange*` functions"
+    )]
+    pub no_range_checks: bool,
+
+    #[clap(
+        name = "entry-point",
+        long,
+        global = true,
+        help = "Generate a C main function calling the given PROGRAM, e.g. to link an executable"
+    )]
+    pub entry_point: Option<String>,
+
     #[clap(
         name = "debug",
         long,
@@ -140,6 +287,23 @@ pub struct CompileParameters {
     )]
     pub generate_varinfo: bool,
 
+    #[clap(
+        name = "split-debug",
+        long,
+        global = true,
+        help = "Emit the debug information into a `.dwo` file next to each object (split DWARF), requires `llc`"
+    )]
+    pub split_debug: bool,
+
+    #[clap(
+        name = "read-retries",
+        long,
+        help = "How often reading a source file is retried before the build fails",
+        default_value_t = DEFAULT_READ_RETRIES,
+        global = true
+    )]
+    pub read_retries: u32,
+
     #[clap(
         name = "threads",
         long,
@@ -161,6 +325,49 @@ pub struct CompileParameters {
     #[clap(name = "check", long, help = "Check only, do not generate any output", global = true)]
     pub check_only: bool,
 
+    #[clap(name = "stats", long, help = "Print statistics about the index after indexing", global = true)]
+    pub print_stats: bool,
+
+    #[clap(
+        name = "print-ir-after-opt",
+        long,
+        help = "Print the IR of every unit as it was optimized for each target",
+        global = true
+    )]
+    pub print_ir_after_opt: bool,
+
+    #[clap(
+        name = "forbid-recursion",
+        long,
+        help = "Report recursive calls between functions as errors",
+        global = true
+    )]
+    pub forbid_recursion: bool,
+
+    #[clap(
+        name = "warn-mixed-signedness",
+        long,
+        help = "Warn about comparisons between signed and unsigned integers",
+        global = true
+    )]
+    pub warn_mixed_signedness: bool,
+
+    #[clap(
+        name = "keep-going",
+        long,
+        help = "Continue generating the remaining files if the code generation of a file fails",
+        global = true
+    )]
+    pub keep_going: bool,
+
+    #[clap(
+        name = "dry-run",
+        long,
+        help = "List the files that would be compiled and linked and the resulting output, then exit",
+        global = true
+    )]
+    pub dry_run: bool,
+
     #[clap(subcommand)]
     pub commands: Option<SubCommands>,
 }
@@ -205,16 +412,16 @@ impl SubCommands {
     }
 }
 
-fn parse_encoding(encoding: &str) -> Result<&'static Encoding, String> {
-    Encoding::for_label(encoding.as_bytes()).ok_or(format!("Unknown encoding {encoding}"))
+fn parse_define(define: &str) -> Result<(String, String), String> {
+    define
+        .split_once('=')
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .filter(|(name, value)| !name.is_empty() && !value.is_empty())
+        .ok_or(format!("Invalid define {define}, expected NAME=VALUE"))
 }
 
-fn validate_config(config_name: &str) -> Result<String, String> {
-    if get_config_format(config_name).is_some() {
-        Ok(config_name.to_string())
-    } else {
-        Err(format!(r#"Cannot identify format type for {config_name}, valid extensions : "json", "toml""#))
-    }
+fn parse_encoding(encoding: &str) -> Result<&'static Encoding, String> {
+    Encoding::for_label(encoding.as_bytes()).ok_or(format!("Unknown encoding {encoding}"))
 }
 
 fn get_parallel_threads(thread_count: &str) -> Result<Threads, ParseIntError> {
@@ -236,8 +443,13 @@ pub fn get_config_format(name: &str) -> Option<ConfigFormat> {
 }
 
 impl CompileParameters {
+    /// Parses the given command line, `@file` arguments are replaced with the arguments listed in `file`
     pub fn parse<T: AsRef<OsStr> + AsRef<str>>(args: &[T]) -> Result<CompileParameters, ParameterError> {
-        CompileParameters::try_parse_from(args).and_then(|result| {
+        let mut expanded = vec![];
+        for arg in args {
+            expand_response_file(AsRef::<str>::as_ref(arg), &mut vec![], &mut expanded)?;
+        }
+        CompileParameters::try_parse_from(expanded).and_then(|result| {
             if result.sysroot.len() > result.target.len() {
                 let mut cmd = CompileParameters::command();
                 Err(cmd.error(
@@ -266,6 +478,8 @@ impl CompileParameters {
             Some(FormatOption::Bitcode)
         } else if self.output_ir {
             Some(FormatOption::IR)
+        } else if self.output_assembly {
+            Some(FormatOption::Assembly)
         } else if self.output_pic_obj {
             Some(FormatOption::PIC)
         } else if self.output_shared_obj {
@@ -297,8 +511,19 @@ impl CompileParameters {
         self.output_format().unwrap_or_default()
     }
 
-    pub fn config_format(&self) -> Option<ConfigFormat> {
-        self.hardware_config.as_deref().and_then(get_config_format)
+    /// Returns the format of the hardware configuration, given with `--config-format` or detected from
+    /// the configuration's extension
+    pub fn config_format(&self) -> Result<Option<ConfigFormat>, Diagnostic> {
+        let Some(config) = self.hardware_config.as_deref() else { return Ok(None) };
+        self.hardware_config_format
+            .or_else(|| get_config_format(config))
+            .map(Some)
+            .ok_or_else(|| Diagnostic::unknown_config_format(config))
+    }
+
+    /// Returns the cpu and features machine code should be generated for
+    pub fn get_target_tuning(&self) -> TargetTuning {
+        TargetTuning { cpu: self.target_cpu.clone(), features: self.target_features.clone() }
     }
 
     /// Returns the location where the build artifacts should be stored / output
@@ -321,11 +546,46 @@ impl CompileParameters {
     }
 }
 
+/// Appends the given argument to `expanded`. An argument of the form `@file` is replaced by the
+/// arguments in `file`, split like a shell would split them. Response files may reference further
+/// response files, `visited` holds the files currently being expanded to detect cycles
+fn expand_response_file(
+    arg: &str,
+    visited: &mut Vec<PathBuf>,
+    expanded: &mut Vec<String>,
+) -> Result<(), ParameterError> {
+    let Some(file) = arg.strip_prefix('@') else {
+        expanded.push(arg.to_string());
+        return Ok(());
+    };
+    let io_error = |err: std::io::Error| {
+        CompileParameters::command()
+            .error(ErrorKind::Io, format!("Cannot read response file '{file}': {err}"))
+    };
+    let path = fs::canonicalize(file).map_err(io_error)?;
+    if visited.contains(&path) {
+        return Err(CompileParameters::command()
+            .error(ErrorKind::InvalidValue, format!("Response file '{file}' references itself")));
+    }
+    let content = fs::read_to_string(&path).map_err(io_error)?;
+    let args = shell_words::split(&content).map_err(|err| {
+        CompileParameters::command()
+            .error(ErrorKind::InvalidValue, format!("Invalid response file '{file}': {err}"))
+    })?;
+
+    visited.push(path);
+    for arg in args {
+        expand_response_file(&arg, visited, expanded)?;
+    }
+    visited.pop();
+    Ok(())
+}
+
 #[cfg(test)]
 mod cli_tests {
-    use super::{CompileParameters, SubCommands};
+    use super::{CompileParameters, SubCommands, DEFAULT_READ_RETRIES};
     use clap::{CommandFactory, ErrorKind};
-    use plc::{output::FormatOption, ConfigFormat, ErrorFormat, OptimizationLevel};
+    use plc::{output::FormatOption, ConfigFormat, DiagnosticPaths, ErrorFormat, OptimizationLevel};
     use pretty_assertions::assert_eq;
     use std::ffi::OsStr;
     use std::fmt::Debug;
@@ -477,6 +737,12 @@ mod cli_tests {
         let parameters = CompileParameters::parse(vec_of_strings!("examples/test/echo", "--bc")).unwrap();
         assert_eq!(parameters.output_format_or_default(), FormatOption::Bitcode);
 
+        let parameters = CompileParameters::parse(vec_of_strings!("examples/test/foxtrot", "--asm")).unwrap();
+        assert_eq!(parameters.output_format_or_default(), FormatOption::Assembly);
+
+        let parameters = CompileParameters::parse(vec_of_strings!("examples/test/foxtrot", "-S")).unwrap();
+        assert_eq!(parameters.output_format_or_default(), FormatOption::Assembly);
+
         let parameters = CompileParameters::parse(vec_of_strings!("examples/test/echo.st")).unwrap();
         assert_eq!(parameters.output_format_or_default(), FormatOption::Static);
     }
@@ -582,6 +848,20 @@ mod cli_tests {
         assert_eq!(parameters.libraries, vec!["test", "c", "xx"]);
     }
 
+    #[test]
+    fn whole_archive_libraries_added() {
+        let parameters = CompileParameters::parse(vec_of_strings!(
+            "input.st",
+            "-lc",
+            "--whole-archive-library",
+            "plugins",
+            "--whole-archive-library=drivers"
+        ))
+        .unwrap();
+        assert_eq!(parameters.libraries, vec!["c"]);
+        assert_eq!(parameters.whole_archive_libraries, vec!["plugins", "drivers"]);
+    }
+
     #[test]
     fn cli_supports_version() {
         match CompileParameters::parse(vec_of_strings!("input.st", "--version")) {
@@ -675,20 +955,266 @@ mod cli_tests {
         assert_eq!(parameters.includes, vec!["include1", "include2", "include3"]);
     }
 
+    #[test]
+    fn include_directories_added() {
+        let parameters =
+            CompileParameters::parse(vec_of_strings!("input.st", "-I", "shared", "--include-dir", "common"))
+                .unwrap();
+        assert_eq!(parameters.include_directories, vec!["shared", "common"]);
+    }
+
     #[test]
     fn config_option_set() {
         let parameters =
             CompileParameters::parse(vec_of_strings!("foo", "--hardware-conf=conf.json")).unwrap();
         assert_eq!(parameters.hardware_config, Some("conf.json".to_string()));
-        assert_eq!(parameters.config_format().unwrap(), ConfigFormat::JSON);
+        assert_eq!(parameters.config_format().unwrap(), Some(ConfigFormat::JSON));
         let parameters =
             CompileParameters::parse(vec_of_strings!("foo", "--hardware-conf=conf.toml")).unwrap();
         assert_eq!(parameters.hardware_config, Some("conf.toml".to_string()));
-        assert_eq!(parameters.config_format().unwrap(), ConfigFormat::TOML);
+        assert_eq!(parameters.config_format().unwrap(), Some(ConfigFormat::TOML));
+
+        let parameters = CompileParameters::parse(vec_of_strings!("foo")).unwrap();
+        assert_eq!(parameters.config_format().unwrap(), None);
+    }
+
+    #[test]
+    fn config_format_overrides_the_extension() {
+        let parameters = CompileParameters::parse(vec_of_strings!(
+            "foo",
+            "--hardware-conf=conf.cfg",
+            "--config-format=toml"
+        ))
+        .unwrap();
+        assert_eq!(parameters.config_format().unwrap(), Some(ConfigFormat::TOML));
+        let parameters = CompileParameters::parse(vec_of_strings!(
+            "foo",
+            "--hardware-conf=conf.toml",
+            "--config-format=json"
+        ))
+        .unwrap();
+        assert_eq!(parameters.config_format().unwrap(), Some(ConfigFormat::JSON));
+
+        // without a format, unknown extensions are reported
+        for config in ["foo", "conf.foo", "conf.xml"] {
+            let argument = format!("--hardware-conf={config}");
+            let parameters = CompileParameters::parse(vec_of_strings!("foo", argument.as_str())).unwrap();
+            let error = parameters.config_format().unwrap_err();
+            assert_eq!(
+                error.get_message(),
+                format!(
+                    "Cannot identify the format of the hardware configuration '{config}', use --config-format or one of the extensions: json, toml"
+                )
+            );
+        }
+        expect_argument_error(vec_of_strings!("foo", "--config-format=xml"), ErrorKind::InvalidValue);
+    }
+
+    #[test]
+    fn emit_metadata_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert_eq!(parameters.emit_metadata, None);
+        let parameters =
+            CompileParameters::parse(vec_of_strings!("input.st", "--emit-metadata=lib.json")).unwrap();
+        assert_eq!(parameters.emit_metadata, Some("lib.json".to_string()));
+    }
+
+    #[test]
+    fn emit_header_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert_eq!(parameters.emit_header, None);
+        let parameters =
+            CompileParameters::parse(vec_of_strings!("input.st", "--emit-header=lib.h")).unwrap();
+        assert_eq!(parameters.emit_header, Some("lib.h".to_string()));
+    }
+
+    #[test]
+    fn entry_point_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert_eq!(parameters.entry_point, None);
+        let parameters =
+            CompileParameters::parse(vec_of_strings!("input.st", "--entry-point", "startup")).unwrap();
+        assert_eq!(parameters.entry_point, Some("startup".to_string()));
+    }
+
+    #[test]
+    fn emit_map_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert_eq!(parameters.emit_map, None);
+        let parameters =
+            CompileParameters::parse(vec_of_strings!("input.st", "--emit-map", "build/out.map")).unwrap();
+        assert_eq!(parameters.emit_map, Some("build/out.map".to_string()));
+    }
+
+    #[test]
+    fn strip_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert!(!parameters.strip);
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st", "--strip")).unwrap();
+        assert!(parameters.strip);
+    }
+
+    #[test]
+    fn split_debug_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st", "-g")).unwrap();
+        assert!(!parameters.split_debug);
+        let parameters =
+            CompileParameters::parse(vec_of_strings!("input.st", "-g", "--split-debug")).unwrap();
+        assert!(parameters.split_debug);
+    }
+
+    #[test]
+    fn profile_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert!(!parameters.profile);
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st", "--profile")).unwrap();
+        assert!(parameters.profile);
+    }
+
+    #[test]
+    fn no_range_checks_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert!(!parameters.no_range_checks);
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st", "--no-range-checks")).unwrap();
+        assert!(parameters.no_range_checks);
+    }
+
+    #[test]
+    fn emit_bundle_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert_eq!(parameters.emit_bundle, None);
+        let parameters =
+            CompileParameters::parse(vec_of_strings!("input.st", "--emit-bundle=bundle.json")).unwrap();
+        assert_eq!(parameters.emit_bundle, Some("bundle.json".to_string()));
+    }
 
-        expect_argument_error(vec_of_strings!("foo", "--hardware-conf=foo"), ErrorKind::ValueValidation);
-        expect_argument_error(vec_of_strings!("foo", "--hardware-conf=conf.foo"), ErrorKind::ValueValidation);
-        expect_argument_error(vec_of_strings!("foo", "--hardware-conf=conf.xml"), ErrorKind::ValueValidation);
+    #[test]
+    fn arguments_are_read_from_response_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("nested.txt");
+        std::fs::write(&nested, "--ir\n-o 'out file.ll'").unwrap();
+        let args = dir.path().join("args.txt");
+        std::fs::write(&args, format!("\"my sources/a.st\" b.st\n@{}", nested.to_string_lossy())).unwrap();
+
+        let response_file = format!("@{}", args.to_string_lossy());
+        let parameters = CompileParameters::parse(vec_of_strings!(response_file.as_str())).unwrap();
+
+        assert_eq!(parameters.input, vec!["my sources/a.st".to_string(), "b.st".to_string()]);
+        assert!(parameters.output_ir);
+        assert_eq!(parameters.output, Some("out file.ll".to_string()));
+    }
+
+    #[test]
+    fn cyclic_response_files_result_in_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let args = dir.path().join("args.txt");
+        std::fs::write(&args, format!("input.st @{}", args.to_string_lossy())).unwrap();
+
+        let response_file = format!("@{}", args.to_string_lossy());
+        expect_argument_error(vec_of_strings!(response_file.as_str()), ErrorKind::InvalidValue);
+    }
+
+    #[test]
+    fn stats_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert!(!parameters.print_stats);
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st", "--stats")).unwrap();
+        assert!(parameters.print_stats);
+    }
+
+    #[test]
+    fn print_ir_after_opt_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert!(!parameters.print_ir_after_opt);
+        let parameters =
+            CompileParameters::parse(vec_of_strings!("input.st", "-O", "aggressive", "--print-ir-after-opt"))
+                .unwrap();
+        assert!(parameters.print_ir_after_opt);
+    }
+
+    #[test]
+    fn forbid_recursion_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert!(!parameters.forbid_recursion);
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st", "--forbid-recursion")).unwrap();
+        assert!(parameters.forbid_recursion);
+    }
+
+    #[test]
+    fn define_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!(
+            "input.st",
+            "--define",
+            "VERSION=3",
+            "-D",
+            "NAME = 'plc'"
+        ))
+        .unwrap();
+        assert_eq!(
+            parameters.defines,
+            vec![("VERSION".to_string(), "3".to_string()), ("NAME".to_string(), "'plc'".to_string())]
+        );
+        assert!(CompileParameters::parse(vec_of_strings!("input.st", "--define", "VERSION")).is_err());
+    }
+
+    #[test]
+    fn warn_mixed_signedness_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert!(!parameters.warn_mixed_signedness);
+        let parameters =
+            CompileParameters::parse(vec_of_strings!("input.st", "--warn-mixed-signedness")).unwrap();
+        assert!(parameters.warn_mixed_signedness);
+    }
+
+    #[test]
+    fn dry_run_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert!(!parameters.dry_run);
+        let parameters = CompileParameters::parse(vec_of_strings!("build", "--dry-run")).unwrap();
+        assert!(parameters.dry_run);
+    }
+
+    #[test]
+    fn target_tuning_options_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert_eq!(parameters.target_cpu, None);
+        assert_eq!(parameters.target_features, None);
+        let parameters = CompileParameters::parse(vec_of_strings!(
+            "input.st",
+            "--target-cpu",
+            "cortex-m4",
+            "--target-features",
+            "+vfp4,-neon"
+        ))
+        .unwrap();
+        assert_eq!(parameters.target_cpu.as_deref(), Some("cortex-m4"));
+        assert_eq!(parameters.target_features.as_deref(), Some("+vfp4,-neon"));
+    }
+
+    #[test]
+    fn inline_threshold_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert_eq!(parameters.inline_threshold, None);
+        let parameters =
+            CompileParameters::parse(vec_of_strings!("input.st", "--inline-threshold", "50")).unwrap();
+        assert_eq!(parameters.inline_threshold, Some(50));
+    }
+
+    #[test]
+    fn read_retries_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert_eq!(parameters.read_retries, DEFAULT_READ_RETRIES);
+        let parameters =
+            CompileParameters::parse(vec_of_strings!("input.st", "--read-retries", "5")).unwrap();
+        assert_eq!(parameters.read_retries, 5);
+    }
+
+    #[test]
+    fn keep_going_option_set() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert!(!parameters.keep_going);
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st", "--keep-going")).unwrap();
+        assert!(parameters.keep_going);
     }
 
     #[test]
@@ -707,6 +1233,22 @@ mod cli_tests {
         expect_argument_error(vec_of_strings!("input.st", "--error-format=nothing"), ErrorKind::InvalidValue);
     }
 
+    #[test]
+    fn diagnostic_paths_set() {
+        let params = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert_eq!(params.diagnostic_paths, DiagnosticPaths::AsProvided);
+        let params =
+            CompileParameters::parse(vec_of_strings!("input.st", "--diagnostic-paths=absolute")).unwrap();
+        assert_eq!(params.diagnostic_paths, DiagnosticPaths::Absolute);
+        let params =
+            CompileParameters::parse(vec_of_strings!("input.st", "--diagnostic-paths=relative")).unwrap();
+        assert_eq!(params.diagnostic_paths, DiagnosticPaths::Relative);
+        expect_argument_error(
+            vec_of_strings!("input.st", "--diagnostic-paths=short"),
+            ErrorKind::InvalidValue,
+        );
+    }
+
     #[test]
     fn target_sysroot_mismatch() {
         let error = CompileParameters::parse(vec_of_strings!(
@@ -19,10 +19,16 @@ use ast::provider::IdProvider;
 use cli::{CompileParameters, ParameterError};
 use pipelines::AnnotatedProject;
 use plc::{
-    codegen::CodegenContext, output::FormatOption, DebugLevel, ErrorFormat, OptimizationLevel, Threads,
+    codegen::{CodegenContext, TargetTuning},
+    output::FormatOption,
+    validation::ValidationOptions,
+    DebugLevel, DiagnosticPaths, ErrorFormat, OptimizationLevel, Target, Threads,
 };
 
-use plc_diagnostics::{diagnostician::Diagnostician, diagnostics::Diagnostic};
+use plc_diagnostics::{
+    diagnostician::{Diagnostician, FilePaths},
+    diagnostics::Diagnostic,
+};
 use project::project::{LibraryInformation, Project};
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 use source_code::SourceContainer;
@@ -51,6 +57,26 @@ pub struct CompileOptions {
     pub optimization: OptimizationLevel,
     pub error_format: ErrorFormat,
     pub debug_level: DebugLevel,
+    /// Emit the debug information into a `.dwo` file next to each object instead of the object itself
+    pub split_debug: bool,
+    /// Continue generating the remaining units if the codegen of a unit fails
+    pub keep_going: bool,
+    /// Merge all units into one module, producing a single object
+    pub single_module: bool,
+    /// The cpu and features to generate machine code for
+    pub tuning: TargetTuning,
+    /// Overrides the threshold up to which the optimizer inlines functions, trading code size for speed
+    pub inline_threshold: Option<u32>,
+    /// The PROGRAM called by the generated C `main` function
+    pub entry_point: Option<String>,
+    /// Count the calls of every POU in exported counters, e.g. for coverage tooling
+    pub profile: bool,
+    /// Call the declared `CheckRange*` functions on assignments to sub-range variables
+    pub range_checks: bool,
+    /// Print the IR of every generated module as it was optimized for its target
+    pub print_ir_after_opt: bool,
+    /// Global constants declared as `NAME=VALUE` in addition to the sources
+    pub defines: Vec<(String, String)>,
 }
 
 impl Default for CompileOptions {
@@ -63,6 +89,16 @@ impl Default for CompileOptions {
             optimization: OptimizationLevel::None,
             error_format: ErrorFormat::None,
             debug_level: DebugLevel::None,
+            split_debug: false,
+            keep_going: false,
+            single_module: false,
+            tuning: TargetTuning::default(),
+            inline_threshold: None,
+            entry_point: None,
+            profile: false,
+            range_checks: true,
+            print_ir_after_opt: false,
+            defines: vec![],
         }
     }
 }
@@ -70,9 +106,16 @@ impl Default for CompileOptions {
 #[derive(Clone, Default, Debug)]
 pub struct LinkOptions {
     pub libraries: Vec<String>,
+    /// static libraries of which all objects are linked, e.g. because they register themselves
+    /// through constructors
+    pub whole_archive_libraries: Vec<String>,
     pub library_pathes: Vec<PathBuf>,
     pub format: FormatOption,
     pub linker: Option<String>,
+    /// location of the symbol/size map written by the linker
+    pub emit_map: Option<PathBuf>,
+    /// remove the symbol table and debug information from the linked output
+    pub strip: bool,
 }
 
 #[derive(Debug)]
@@ -120,8 +163,16 @@ impl Display for CompileError {
 
 pub fn compile<T: AsRef<str> + AsRef<OsStr> + Debug>(args: &[T]) -> Result<(), CompileError> {
     //Parse the arguments
-    let compile_parameters = CompileParameters::parse(args)?;
+    let mut compile_parameters = CompileParameters::parse(args)?;
     let project = get_project(&compile_parameters)?;
+    // an unknown format of the hardware configuration is reported before compiling
+    compile_parameters.config_format()?;
+    use_project_targets(&mut compile_parameters, &project);
+    if compile_parameters.dry_run {
+        let output_name = project.get_output_name();
+        print!("{}", describe_build(&compile_parameters, &project, &output_name)?);
+        return Ok(());
+    }
     let output_format = compile_parameters.output_format().unwrap_or_else(|| project.get_output_format());
     let location = project.get_location().map(|it| it.to_path_buf());
     if let Some(location) = &location {
@@ -138,12 +189,19 @@ pub fn compile<T: AsRef<str> + AsRef<OsStr> + Debug>(args: &[T]) -> Result<(), C
         log::debug!("LIB_LOCATION={}", location.to_string_lossy());
         env::set_var("LIB_LOCATION", location);
     }
-    let id_provider = IdProvider::default();
+    let id_provider = IdProvider::deterministic();
     let mut diagnostician = match compile_parameters.error_format {
         ErrorFormat::Rich => Diagnostician::default(),
         ErrorFormat::Clang => Diagnostician::clang_format_diagnostician(),
         ErrorFormat::None => Diagnostician::null_diagnostician(),
-    };
+    }
+    .with_file_paths(match compile_parameters.diagnostic_paths {
+        DiagnosticPaths::AsProvided => FilePaths::AsProvided,
+        DiagnosticPaths::Absolute => FilePaths::Absolute,
+        DiagnosticPaths::Relative => {
+            FilePaths::RelativeTo(location.clone().unwrap_or_else(|| PathBuf::from(".")))
+        }
+    });
 
     //Set the global thread count
     let thread_pool = rayon::ThreadPoolBuilder::new();
@@ -160,20 +218,56 @@ pub fn compile<T: AsRef<str> + AsRef<OsStr> + Debug>(args: &[T]) -> Result<(), C
     }
 
     // 1 : Parse
-    let annotated_project = pipelines::ParsedProject::parse(
+    let indexed_project = pipelines::ParsedProject::parse_with_read_retries(
         &project,
         compile_parameters.encoding,
         id_provider.clone(),
         &mut diagnostician,
+        compile_parameters.read_retries,
     )?
+    .with_defines(&compile_parameters.defines, id_provider.clone())?
     // 2 : Index
     .index(id_provider.clone())?
+    .with_target_layout(&compile_parameters.target)
+    .with_string_alignment(project.get_string_alignment());
+    if compile_parameters.print_stats {
+        println!("{}", indexed_project.get_index().statistics());
+    }
     // 3 : Resolve
-    .annotate(id_provider, &diagnostician)?;
+    let annotated_project = indexed_project.annotate(id_provider, &diagnostician)?;
     // 4 : Validate
-    annotated_project.validate(&mut diagnostician)?;
+    let validation_options = ValidationOptions {
+        forbid_recursive_calls: compile_parameters.forbid_recursion,
+        warn_mixed_signedness: compile_parameters.warn_mixed_signedness,
+    };
+    annotated_project.validate_with_options(&mut diagnostician, validation_options)?;
+    if let Some(entry_point) = &compile_parameters.entry_point {
+        annotated_project.validate_entry_point(entry_point)?;
+    }
+    // the layouts described by the metadata and header are the ones of the first target
+    let layout_target = compile_parameters.target.first().unwrap_or(&Target::System);
+    if let Some(location) = &compile_parameters.emit_metadata {
+        annotated_project.generate_metadata(location, layout_target)?;
+    }
+    if let Some(location) = &compile_parameters.emit_header {
+        annotated_project.generate_header(location, layout_target)?;
+    }
     // 5 : Codegen
     if !compile_parameters.is_check() {
+        // the libraries have to be available for the same targets, their architectures are validated too
+        let library_targets = project.get_libraries().iter().flat_map(LibraryInformation::get_architectures);
+        let targets = compile_parameters.target.iter().chain(library_targets).cloned().collect::<Vec<_>>();
+        plc::codegen::validate_targets(&targets)?;
+        let tuning = compile_parameters.get_target_tuning();
+        if compile_parameters.target.is_empty() {
+            diagnostician.handle(&tuning.validate(&Target::System));
+        }
+        for target in &compile_parameters.target {
+            diagnostician.handle(&tuning.validate(target));
+        }
+        if compile_parameters.strip && compile_parameters.debug_level() == DebugLevel::Full {
+            diagnostician.handle(&[Diagnostic::strip_with_debug_info()]);
+        }
         let res = generate(
             location,
             compile_parameters,
@@ -183,7 +277,11 @@ pub fn compile<T: AsRef<str> + AsRef<OsStr> + Debug>(args: &[T]) -> Result<(), C
             build_location,
             lib_location,
         )
-        .map_err(|err| Diagnostic::codegen_error(err.get_message(), err.get_location()));
+        .map_err(|err| match err {
+            // keep the diagnostics of all failed units
+            Diagnostic::CombinedDiagnostic { .. } => err,
+            _ => Diagnostic::codegen_error(err.get_message(), err.get_location()),
+        });
         if let Err(res) = res {
             diagnostician.handle(&[res]);
             return Err(Diagnostic::GeneralError {
@@ -204,7 +302,7 @@ pub fn parse_and_annotate<T: SourceContainer>(
 ) -> Result<AnnotatedProject, Diagnostic> {
     // Parse the source to ast
     let project = Project::new(name.to_string()).with_sources(src);
-    let id_provider = IdProvider::default();
+    let id_provider = IdProvider::deterministic();
     let mut diagnostician = Diagnostician::default();
     pipelines::ParsedProject::parse(&project, None, id_provider.clone(), &mut diagnostician)?
         // Create an index, add builtins
@@ -265,13 +363,21 @@ fn generate(
         optimization: compile_parameters.optimization,
         error_format: compile_parameters.error_format,
         debug_level: compile_parameters.debug_level(),
+        split_debug: compile_parameters.split_debug,
+        keep_going: compile_parameters.keep_going,
+        single_module: compile_parameters.single_module,
+        tuning: compile_parameters.get_target_tuning(),
+        inline_threshold: compile_parameters.inline_threshold,
+        entry_point: compile_parameters.entry_point.clone(),
+        profile: compile_parameters.profile,
+        range_checks: !compile_parameters.no_range_checks,
+        print_ir_after_opt: compile_parameters.print_ir_after_opt,
+        defines: compile_parameters.defines.clone(),
     };
-    let res = if compile_parameters.single_module {
-        log::info!("Using single module mode");
-        annotated_project.codegen_single_module(compile_options, &compile_parameters.target)?
-    } else {
-        annotated_project.codegen(compile_options, &compile_parameters.target)?
-    };
+    let res = annotated_project.codegen(compile_options, &compile_parameters.target)?;
+    if let Some(location) = &compile_parameters.emit_bundle {
+        pipelines::write_bundle_manifest(&res, location)?;
+    }
     let libraries =
         project.get_libraries().iter().map(LibraryInformation::get_link_name).map(str::to_string).collect();
     let library_pathes = project
@@ -282,9 +388,12 @@ fn generate(
         .collect();
     let linker_options = LinkOptions {
         libraries,
+        whole_archive_libraries: compile_parameters.whole_archive_libraries.clone(),
         library_pathes,
         format: output_format,
         linker: compile_parameters.linker.to_owned(),
+        emit_map: compile_parameters.emit_map.as_ref().map(PathBuf::from),
+        strip: compile_parameters.strip,
     };
     let output_name = project.get_output_name();
     res.into_par_iter()
@@ -299,7 +408,7 @@ fn generate(
         })
         .collect::<Result<Vec<_>, _>>()?;
     if let Some((location, format)) =
-        compile_parameters.hardware_config.as_ref().zip(compile_parameters.config_format())
+        compile_parameters.hardware_config.as_ref().zip(compile_parameters.config_format()?)
     {
         annotated_project.generate_hardware_information(format, location)?;
     }
@@ -319,6 +428,99 @@ fn generate(
     Ok(())
 }
 
+/// Describes the files that would be compiled and linked for the given project and where the
+/// resulting output would be stored, without parsing or generating any code.
+/// All listed paths are absolute.
+pub fn dry_run<T: AsRef<str> + AsRef<OsStr> + Debug>(args: &[T]) -> Result<String, CompileError> {
+    let mut compile_parameters = CompileParameters::parse(args)?;
+    let project = get_project(&compile_parameters)?;
+    use_project_targets(&mut compile_parameters, &project);
+    let output_name = project.get_output_name();
+    Ok(describe_build(&compile_parameters, &project, &output_name)?)
+}
+
+/// Describes the build of the given project linking to `output_name` (the output name
+/// resolved from the command line and the project configuration)
+fn describe_build(
+    compile_parameters: &CompileParameters,
+    project: &Project<PathBuf>,
+    output_name: &str,
+) -> Result<String, Diagnostic> {
+    let current_dir = env::current_dir()?;
+    let to_absolute = |path: &Path| current_dir.join(path).to_string_lossy().to_string();
+
+    let libraries = project.get_libraries();
+    let sources = project.get_sources().iter().map(|it| to_absolute(it)).collect::<Vec<_>>();
+    let includes = project
+        .get_includes()
+        .iter()
+        .chain(libraries.iter().flat_map(LibraryInformation::get_includes))
+        .map(|it| to_absolute(it))
+        .collect::<Vec<_>>();
+    let objects = project
+        .get_objects()
+        .iter()
+        .cloned()
+        .chain(libraries.iter().flat_map(|it| it.get_compiled_lib().get_objects().to_vec()))
+        .map(|it| to_absolute(it.get_path()))
+        .collect::<Vec<_>>();
+    let libraries = libraries
+        .iter()
+        .map(|it| match it.get_path() {
+            Some(path) => format!("{} ({})", it.get_link_name(), to_absolute(path)),
+            None => it.get_link_name().to_string(),
+        })
+        .collect::<Vec<_>>();
+
+    let targets = if compile_parameters.target.is_empty() {
+        vec![Target::System]
+    } else {
+        compile_parameters.target.clone()
+    };
+    let build_location = compile_parameters.get_build_location();
+    let outputs = targets
+        .iter()
+        .map(|target| {
+            let output = build_location
+                .as_deref()
+                .map(|it| target.append_to(it).join(output_name))
+                .unwrap_or_else(|| PathBuf::from(output_name));
+            to_absolute(&output)
+        })
+        .collect::<Vec<_>>();
+    let targets = targets
+        .iter()
+        .map(|it| {
+            it.try_get_name()
+                .map(str::to_string)
+                .unwrap_or_else(|| it.get_target_triple().as_str().to_string_lossy().to_string())
+        })
+        .collect::<Vec<_>>();
+
+    let mut description = String::new();
+    for (title, entries) in [
+        ("Sources", sources),
+        ("Includes", includes),
+        ("Objects", objects),
+        ("Libraries", libraries),
+        ("Targets", targets),
+        ("Output", outputs),
+    ] {
+        description.push_str(&format!("{title}:\n"));
+        for entry in entries {
+            description.push_str(&format!("    {entry}\n"));
+        }
+    }
+    Ok(description)
+}
+
+/// Falls back to the targets of the project configuration if no target was given on the command line
+fn use_project_targets(compile_parameters: &mut CompileParameters, project: &Project<PathBuf>) {
+    if compile_parameters.target.is_empty() {
+        compile_parameters.target = project.get_targets().to_vec();
+    }
+}
+
 fn get_project(compile_parameters: &CompileParameters) -> Result<Project<PathBuf>, Diagnostic> {
     let current_dir = env::current_dir()?;
     //Create a project from either the subcommand or single params
@@ -335,8 +537,16 @@ fn get_project(compile_parameters: &CompileParameters) -> Result<Project<PathBuf
                     it
                 }
             })
-            .or_else(|| get_config(&current_dir))
-            .ok_or_else(|| Diagnostic::param_error("Could not find 'plc.json'"))?;
+            .unwrap_or_else(|| current_dir.join("plc.json"));
+        if !config.is_file() {
+            let file_name = config.file_name().map(|it| it.to_string_lossy()).unwrap_or_default();
+            let directory = config.parent().unwrap_or(&current_dir);
+            return Err(Diagnostic::build_config_not_found(
+                &file_name,
+                &directory.to_string_lossy(),
+                &current_dir.to_string_lossy(),
+            ));
+        }
         Project::from_config(&config)
     } else {
         //Build with parameters
@@ -364,8 +574,9 @@ fn get_project(compile_parameters: &CompileParameters) -> Result<Project<PathBuf
             }
         })
         .map(|proj| proj.with_output_name(compile_parameters.output.clone()))
-}
-
-fn get_config(root: &Path) -> Option<PathBuf> {
-    Some(root.join("plc.json"))
+        .map(|proj| {
+            proj.with_include_directories(
+                compile_parameters.include_directories.iter().map(PathBuf::from).collect(),
+            )
+        })
 }
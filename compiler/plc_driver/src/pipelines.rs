@@ -1,24 +1,37 @@
 use std::{
+    collections::HashSet,
     env,
     fs::{self, File},
-    io::Write,
+    io::{self, ErrorKind, Write},
     path::{Path, PathBuf},
+    thread,
+    time::Duration,
 };
 
 use crate::{CompileOptions, LinkOptions};
 use ast::{
-    ast::{pre_process, CompilationUnit, LinkageType},
+    ast::{
+        pre_process, AccessModifier, AstStatement, CompilationUnit, DataTypeDeclaration, Include,
+        LinkageType, Variable, VariableBlock, VariableBlockType,
+    },
+    literals::{AstLiteral, StringValue},
     provider::IdProvider,
 };
 use encoding_rs::Encoding;
-use indexmap::IndexSet;
+use indexmap::{IndexMap, IndexSet};
 use plc::{
-    codegen::{CodegenContext, GeneratedModule},
-    index::Index,
+    codegen::{layout::TypeLayouts, CodegenContext, GeneratedModule},
+    datalayout::{Bytes, DataLayout},
+    index::{Index, PouIndexEntry},
+    lexer::{lex_with_ids, Token},
     output::FormatOption,
-    parser::parse_file,
+    parser::{expressions_parser::parse_expression, parse_file},
     resolver::{AnnotationMapImpl, AstAnnotations, Dependency, StringLiterals, TypeAnnotator},
-    validation::Validator,
+    typesystem::{
+        BOOL_TYPE, DATE_AND_TIME_TYPE, DATE_TYPE, DINT_TYPE, LINT_TYPE, LREAL_TYPE, STRING_TYPE,
+        TIME_OF_DAY_TYPE, TIME_TYPE, WSTRING_TYPE,
+    },
+    validation::{ValidationOptions, Validator},
     ConfigFormat, Target,
 };
 use plc_diagnostics::{
@@ -31,7 +44,110 @@ use project::{
     project::{LibraryInformation, Project},
 };
 use rayon::prelude::*;
-use source_code::{source_location::SourceLocation, SourceContainer};
+use serde::Serialize;
+use source_code::{
+    source_location::{SourceLocation, SourceLocationFactory},
+    SourceCode, SourceContainer,
+};
+
+/// How often reading a source is retried by default before the build fails, some network or FUSE
+/// filesystems occasionally report transient read errors
+pub const DEFAULT_READ_RETRIES: u32 = 2;
+
+/// The pause between two attempts to read a source
+const READ_RETRY_DELAY: Duration = Duration::from_millis(20);
+
+/// Loads the given source, retrying up to `read_retries` times if reading it fails with a transient error
+fn load_source<T: SourceContainer>(
+    container: &T,
+    encoding: Option<&'static Encoding>,
+    read_retries: u32,
+) -> Result<SourceCode, Diagnostic> {
+    let mut attempt = 0;
+    loop {
+        match container.load_source(encoding) {
+            Ok(source) => return Ok(source),
+            Err(err) if attempt < read_retries && is_transient(&err) => {
+                attempt += 1;
+                log::warn!("Reading {:?} failed ({err}), retrying", container.get_location());
+                thread::sleep(READ_RETRY_DELAY);
+            }
+            Err(err) => {
+                return Err(Diagnostic::io_read_error(
+                    &container.get_location().expect("Location should not be empty").to_string_lossy(),
+                    &err.to_string(),
+                ))
+            }
+        }
+    }
+}
+
+/// whether reading a source may succeed when it is retried, a missing or unreadable source
+/// or one with an invalid content fails the same way again
+fn is_transient(error: &io::Error) -> bool {
+    !matches!(
+        error.kind(),
+        ErrorKind::NotFound | ErrorKind::PermissionDenied | ErrorKind::InvalidInput | ErrorKind::InvalidData
+    )
+}
+
+/// Parses the files of `{include := 'file'}` directives. A file is searched next to the including file
+/// first and then in the include directories, every file is parsed once even if it is included several times
+struct IncludeResolver<'a> {
+    include_directories: &'a [PathBuf],
+    encoding: Option<&'static Encoding>,
+    read_retries: u32,
+    id_provider: IdProvider,
+    /// the canonical paths of the files parsed so far
+    parsed: HashSet<PathBuf>,
+    /// the units of the included files
+    units: Vec<CompilationUnit>,
+}
+
+impl IncludeResolver<'_> {
+    fn resolve(&self, including_file: &Path, include: &Include) -> Result<PathBuf, Diagnostic> {
+        let directory = including_file.parent().unwrap_or(Path::new(""));
+        std::iter::once(directory)
+            .chain(self.include_directories.iter().map(PathBuf::as_path))
+            .map(|it| it.join(&include.path))
+            .find(|it| it.is_file())
+            .and_then(|it| fs::canonicalize(it).ok())
+            .ok_or_else(|| Diagnostic::unresolved_include(&include.path, include.location.clone()))
+    }
+
+    /// parses the given includes and the files they include in turn, `chain` holds the files
+    /// including the current one to detect cycles
+    fn include_all(
+        &mut self,
+        including_file: &Path,
+        includes: &[Include],
+        chain: &mut Vec<PathBuf>,
+        diagnostician: &mut Diagnostician,
+    ) -> Result<(), Diagnostic> {
+        for include in includes {
+            let file = self.resolve(including_file, include)?;
+            if chain.contains(&file) {
+                let cycle = chain
+                    .iter()
+                    .chain(std::iter::once(&file))
+                    .map(|it| it.to_string_lossy().to_string())
+                    .collect::<Vec<_>>();
+                return Err(Diagnostic::cyclic_include(&cycle, include.location.clone()));
+            }
+            if !self.parsed.insert(file.clone()) {
+                continue;
+            }
+            let source = load_source(&file, self.encoding, self.read_retries)?;
+            let unit = parse_file(source, LinkageType::Internal, self.id_provider.clone(), diagnostician);
+            let nested = unit.includes.clone();
+            self.units.push(unit);
+            chain.push(file.clone());
+            self.include_all(&file, &nested, chain, diagnostician)?;
+            chain.pop();
+        }
+        Ok(())
+    }
+}
 
 ///Represents a parsed project
 ///For this struct to be built, the project would have been parsed correctly and an AST would have
@@ -46,6 +162,18 @@ impl ParsedProject {
         encoding: Option<&'static Encoding>,
         id_provider: IdProvider,
         diagnostician: &mut Diagnostician,
+    ) -> Result<Self, Diagnostic> {
+        Self::parse_with_read_retries(project, encoding, id_provider, diagnostician, DEFAULT_READ_RETRIES)
+    }
+
+    /// Parses a giving project like [`ParsedProject::parse`], retrying to read a source up to
+    /// `read_retries` times before its read error is reported
+    pub fn parse_with_read_retries<T: SourceContainer>(
+        project: &Project<T>,
+        encoding: Option<&'static Encoding>,
+        id_provider: IdProvider,
+        diagnostician: &mut Diagnostician,
+        read_retries: u32,
     ) -> Result<Self, Diagnostic> {
         //TODO in parallel
         //Parse the source files
@@ -55,12 +183,7 @@ impl ParsedProject {
             .get_sources()
             .iter()
             .map(|it| {
-                let loaded_source = it.load_source(encoding).map_err(|err| {
-                    Diagnostic::io_read_error(
-                        &it.get_location().expect("Location should not be empty").to_string_lossy(),
-                        &err,
-                    )
-                })?;
+                let loaded_source = load_source(it, encoding, read_retries)?;
 
                 let parse_func = match loaded_source.get_type() {
                     source_code::SourceType::Text => parse_file,
@@ -71,17 +194,27 @@ impl ParsedProject {
             })
             .collect::<Result<Vec<_>, Diagnostic>>()?;
         units.extend(sources);
+        //Parse the files included by `{include := 'file'}` directives of the sources
+        let mut resolver = IncludeResolver {
+            include_directories: project.get_include_directories(),
+            encoding,
+            read_retries,
+            id_provider: id_provider.clone(),
+            parsed: units.iter().filter_map(|it| fs::canonicalize(&it.file_name).ok()).collect(),
+            units: vec![],
+        };
+        for unit in &units {
+            let file = PathBuf::from(&unit.file_name);
+            let mut chain = fs::canonicalize(&file).into_iter().collect();
+            resolver.include_all(&file, &unit.includes, &mut chain, diagnostician)?;
+        }
+        units.extend(resolver.units);
         //Parse the includes
         let includes = project
             .get_includes()
             .iter()
             .map(|it| {
-                let loaded_source = it.load_source(encoding).map_err(|err| {
-                    Diagnostic::io_read_error(
-                        &it.get_location().expect("Location should not be empty").to_string_lossy(),
-                        &err,
-                    )
-                })?;
+                let loaded_source = load_source(it, encoding, read_retries)?;
                 Ok(parse_file(loaded_source, LinkageType::External, id_provider.clone(), diagnostician))
             })
             .collect::<Result<Vec<_>, Diagnostic>>()?;
@@ -92,12 +225,7 @@ impl ParsedProject {
             .iter()
             .flat_map(LibraryInformation::get_includes)
             .map(|it| {
-                let loaded_source = it.load_source(encoding).map_err(|err| {
-                    Diagnostic::io_read_error(
-                        &it.get_location().expect("Location should not be empty").to_string_lossy(),
-                        &err,
-                    )
-                })?;
+                let loaded_source = load_source(it, encoding, read_retries)?;
                 Ok(parse_file(loaded_source, LinkageType::External, id_provider.clone(), diagnostician))
             })
             .collect::<Result<Vec<_>, Diagnostic>>()?;
@@ -106,14 +234,92 @@ impl ParsedProject {
         Ok(ParsedProject(units))
     }
 
+    /// Registers each `(name, value)` pair as a `VAR_GLOBAL CONSTANT` of the project.
+    /// The value has to be a literal, the constant's type is derived from the literal
+    /// (e.g. `3` becomes a `DINT`, `'abc'` a `STRING`).
+    /// The constants are declared in the first compilation unit of the project, a define clashing
+    /// with a global variable is reported at the global's declaration.
+    /// Local variables may shadow a define like any other global.
+    pub fn with_defines(
+        mut self,
+        defines: &[(String, String)],
+        id_provider: IdProvider,
+    ) -> Result<Self, Diagnostic> {
+        let Some(unit) = self.0.first() else {
+            return Ok(self);
+        };
+        let file_name = unit.file_name.clone();
+        let location =
+            SourceLocationFactory::for_source(&SourceCode::new("", &file_name)).create_file_only_location();
+
+        let mut variables: Vec<Variable> = vec![];
+        for (name, value) in defines {
+            let conflicts = self
+                .0
+                .iter()
+                .flat_map(|it| it.global_vars.iter())
+                .flat_map(|it| it.variables.iter())
+                .chain(variables.iter())
+                .filter(|it| it.name.eq_ignore_ascii_case(name))
+                .map(|it| it.location.clone())
+                .collect::<Vec<_>>();
+            if let Some((declaration, others)) = conflicts.split_first() {
+                return Err(Diagnostic::global_name_conflict(name, declaration.clone(), others.to_vec()));
+            }
+
+            let location_factory = SourceLocationFactory::for_source(&SourceCode::new(value, &file_name));
+            let mut lexer = lex_with_ids(value, id_provider.clone(), location_factory);
+            let initializer = parse_expression(&mut lexer);
+            let type_name = match initializer.get_stmt() {
+                AstStatement::Literal(literal)
+                    if lexer.token == Token::End && lexer.diagnostics.is_empty() =>
+                {
+                    get_define_type(literal)
+                }
+                _ => None,
+            }
+            .ok_or_else(|| {
+                Diagnostic::param_error(&format!(
+                    "Invalid value for define {name}, expected a literal: {value}"
+                ))
+            })?;
+
+            variables.push(Variable {
+                name: name.clone(),
+                data_type_declaration: DataTypeDeclaration::DataTypeReference {
+                    referenced_type: type_name.to_string(),
+                    location: location.clone(),
+                },
+                initializer: Some(initializer),
+                address: None,
+                location: location.clone(),
+            });
+        }
+
+        if !variables.is_empty() {
+            self.0[0].global_vars.push(VariableBlock {
+                access: AccessModifier::Protected,
+                constant: true,
+                retain: false,
+                variables,
+                variable_block_type: VariableBlockType::Global,
+                linkage: LinkageType::Internal,
+                location,
+            });
+        }
+        Ok(self)
+    }
+
     /// Creates an index out of a pased project. The index could then be used to query datatypes
     pub fn index(self, id_provider: IdProvider) -> Result<IndexedProject, Diagnostic> {
+        let id_providers = self.0.iter().map(|_| id_provider.fork()).collect::<Vec<_>>();
         let indexed_units = self
             .0
             .into_par_iter()
-            .map(|mut unit| {
+            .zip(id_providers)
+            .map(|(mut unit, id_provider)| {
                 //Preprocess
-                pre_process(&mut unit, id_provider.clone());
+                pre_process(&mut unit, id_provider);
                 //import to index
                 let index = plc::index::visitor::visit(&unit);
 
@@ -133,13 +339,31 @@ impl ParsedProject {
             global_index.register_type(data_type);
         }
         // import builtin functions
-        let builtins = plc::builtins::parse_built_ins(id_provider);
+        let builtins = plc::builtins::parse_unshadowed_built_ins(id_provider, &global_index);
         global_index.import(plc::index::visitor::visit(&builtins));
 
         Ok(IndexedProject { units, index: global_index })
     }
 }
 
+/// returns the name of the type a define with the given literal value is declared with
+fn get_define_type(literal: &AstLiteral) -> Option<&'static str> {
+    let type_name = match literal {
+        AstLiteral::Integer(value) if i32::try_from(*value).is_ok() => DINT_TYPE,
+        AstLiteral::Integer(value) if i64::try_from(*value).is_ok() => LINT_TYPE,
+        AstLiteral::Real(_) => LREAL_TYPE,
+        AstLiteral::Bool(_) => BOOL_TYPE,
+        AstLiteral::String(StringValue { is_wide: false, .. }) => STRING_TYPE,
+        AstLiteral::String(StringValue { is_wide: true, .. }) => WSTRING_TYPE,
+        AstLiteral::Time(_) => TIME_TYPE,
+        AstLiteral::Date(_) => DATE_TYPE,
+        AstLiteral::DateAndTime(_) => DATE_AND_TIME_TYPE,
+        AstLiteral::TimeOfDay(_) => TIME_OF_DAY_TYPE,
+        _ => return None,
+    };
+    Some(type_name)
+}
+
 ///A project that has also been indexed
 /// Units inside an index project could be resolved and annotated
 pub struct IndexedProject {
@@ -148,6 +372,29 @@ pub struct IndexedProject {
 }
 
 impl IndexedProject {
+    pub fn get_index(&self) -> &Index {
+        &self.index
+    }
+
+    /// Uses the type layout of the given target to calculate the sizes and alignments of types.
+    /// The default layout is kept if several targets are compiled at once
+    pub fn with_target_layout(mut self, targets: &[Target]) -> Self {
+        if let [target] = targets {
+            self.index.set_type_layout(DataLayout::for_target(target));
+        }
+        self
+    }
+
+    /// Aligns `STRING` and `WSTRING` variables to the given number of bytes instead of the
+    /// alignment of the target's layout
+    pub fn with_string_alignment(mut self, alignment: Option<u32>) -> Self {
+        if let Some(alignment) = alignment {
+            let layout = DataLayout { string: Some(Bytes::new(alignment)), ..*self.index.get_type_layout() };
+            self.index.set_type_layout(layout);
+        }
+        self
+    }
+
     /// Creates annotations on the project in order to facilitate codegen and validation
     pub fn annotate(
         self,
@@ -161,12 +408,14 @@ impl IndexedProject {
         let mut annotated_units = Vec::new();
         let mut all_annotations = AnnotationMapImpl::default();
 
+        let id_providers = self.units.iter().map(|_| id_provider.fork()).collect::<Vec<_>>();
         let result = self
             .units
             .into_par_iter()
-            .map(|unit| {
+            .zip(id_providers)
+            .map(|(unit, id_provider)| {
                 let (annotation, dependencies, literals) =
-                    TypeAnnotator::visit_unit(&full_index, &unit, id_provider.clone());
+                    TypeAnnotator::visit_unit(&full_index, &unit, id_provider);
                 (unit, annotation, dependencies, literals)
             })
             .collect::<Vec<_>>();
@@ -192,22 +441,37 @@ pub struct AnnotatedProject {
 }
 
 impl AnnotatedProject {
-    /// Validates the project, reports any new diagnostics on the fly
+    /// Validates the project, reports the diagnostics of all units together
     pub fn validate(&self, diagnostician: &mut Diagnostician) -> Result<(), Diagnostic> {
+        self.validate_with_options(diagnostician, ValidationOptions::default())
+    }
+
+    /// Validates the project including the optional validations enabled in `options`,
+    /// reports the diagnostics of all units together
+    pub fn validate_with_options(
+        &self,
+        diagnostician: &mut Diagnostician,
+        options: ValidationOptions,
+    ) -> Result<(), Diagnostic> {
         // perform global validation
-        let mut validator = Validator::new();
+        let mut validator = Validator::with_options(options);
         validator.perform_global_validation(&self.index);
-        let diagnostics = validator.diagnostics();
-        let mut severity = diagnostician.handle(&diagnostics);
+        let mut diagnostics = validator.diagnostics();
 
         //Perform per unit validation
         self.units.iter().for_each(|(unit, _, _)| {
             // validate unit
             validator.visit_unit(&self.annotations, &self.index, unit);
-            // log errors
-            let diagnostics = validator.diagnostics();
-            severity = severity.max(diagnostician.handle(&diagnostics));
+            diagnostics.extend(validator.diagnostics());
         });
+
+        // perform validations requiring all units to be visited
+        validator.perform_call_graph_validation();
+        diagnostics.extend(validator.diagnostics());
+
+        // the diagnostics are reported at once, so they can be grouped by file
+        let severity = diagnostician.handle(&diagnostics);
+
         if severity == Severity::Critical {
             Err(Diagnostic::GeneralError {
                 message: "Compilation aborted due to critical errors".into(),
@@ -218,6 +482,31 @@ impl AnnotatedProject {
         }
     }
 
+    /// Checks that the given entry point names a `PROGRAM` the generated C `main` function can call
+    pub fn validate_entry_point(&self, entry_point: &str) -> Result<(), Diagnostic> {
+        self.find_entry_point(entry_point).map(|_| ())
+    }
+
+    /// Returns the symbols of the entry point program and its instance
+    fn find_entry_point(&self, entry_point: &str) -> Result<(&str, &str), Diagnostic> {
+        let Some(PouIndexEntry::Program { instance_variable, .. }) = self.index.find_pou(entry_point) else {
+            return Err(Diagnostic::invalid_entry_point(entry_point, "there is no PROGRAM with that name"));
+        };
+        if let Some(pou) = self.index.get_implementations().values().find(|it| it.get_symbol_name() == "main")
+        {
+            return Err(Diagnostic::invalid_entry_point(
+                entry_point,
+                &format!("'{}' conflicts with the generated main function", pou.get_call_name()),
+            ));
+        }
+        let symbol_name = self
+            .index
+            .find_implementation_by_name(entry_point)
+            .map(|it| it.get_symbol_name())
+            .unwrap_or(entry_point);
+        Ok((symbol_name, instance_variable.get_name()))
+    }
+
     pub fn codegen_to_string(&self, compile_options: &CompileOptions) -> Result<Vec<String>, Diagnostic> {
         self.units
             .iter()
@@ -251,6 +540,26 @@ impl AnnotatedProject {
         module.map(Some)
     }
 
+    /// Generates all units into a single in-memory module and calls the POU `name` using a JIT engine.
+    /// `params` is passed as the POU's only argument, e.g. the instance of a `PROGRAM` or a `VAR_IN_OUT`
+    pub fn jit_function<T, U>(&self, name: &str, params: &mut T) -> Result<U, Diagnostic> {
+        let symbol_name = self
+            .index
+            .find_implementation_by_name(name)
+            .map(|it| it.get_symbol_name())
+            .ok_or_else(|| Diagnostic::unresolved_reference(name, SourceLocation::undefined()))?;
+        let context = CodegenContext::create();
+        let compile_options = CompileOptions {
+            optimization: plc::OptimizationLevel::None,
+            debug_level: plc::DebugLevel::None,
+            ..Default::default()
+        };
+        let module = self
+            .generate_single_module(&context, &compile_options)?
+            .ok_or_else(|| Diagnostic::unresolved_reference(name, SourceLocation::undefined()))?;
+        Ok(module.run::<T, U>(symbol_name, params))
+    }
+
     fn generate_module<'ctx>(
         &self,
         context: &'ctx CodegenContext,
@@ -265,7 +574,9 @@ impl AnnotatedProject {
             &unit.file_name,
             compile_options.optimization,
             compile_options.debug_level,
-        );
+            compile_options.split_debug,
+        )
+        .with_range_checks(compile_options.range_checks);
         //Create a types codegen, this contains all the type declarations
         //Associate the index type with LLVM types
         let llvm_index = code_generator.generate_llvm_index(
@@ -275,7 +586,10 @@ impl AnnotatedProject {
             dependencies,
             &self.index,
         )?;
-        code_generator.generate(context, unit, &self.annotations, &self.index, &llvm_index)
+        code_generator
+            .generate(context, unit, &self.annotations, &self.index, &llvm_index)
+            .map(|it| it.with_inline_threshold(compile_options.inline_threshold))
+            .map(|it| if compile_options.profile { it.with_profile_counters() } else { it })
     }
 
     pub fn codegen_single_module<'ctx>(
@@ -291,6 +605,13 @@ impl AnnotatedProject {
         let context = CodegenContext::create(); //Create a build location for the generated object files
         let targets = if targets.is_empty() { &[Target::System] } else { targets };
         let module = self.generate_single_module(&context, &compile_options)?.unwrap();
+        let module = match compile_options.entry_point.as_deref() {
+            Some(entry_point) => {
+                let (program, instance) = self.find_entry_point(entry_point)?;
+                module.with_entry_point(program, instance)?
+            }
+            None => module,
+        };
         let mut result = vec![];
         for target in targets {
             let obj: Object = module
@@ -300,10 +621,18 @@ impl AnnotatedProject {
                     compile_options.output_format,
                     target,
                     compile_options.optimization,
+                    &compile_options.tuning,
                 )
                 .map(Into::into)?;
+            if compile_options.print_ir_after_opt {
+                println!("{}", module.persist_to_string());
+            }
 
-            result.push(GeneratedProject { target: target.clone(), objects: vec![obj] });
+            result.push(GeneratedProject {
+                target: target.clone(),
+                location: target.append_to(&compile_directory),
+                objects: vec![obj],
+            });
         }
 
         Ok(result)
@@ -314,12 +643,18 @@ impl AnnotatedProject {
         compile_options: CompileOptions,
         targets: &'ctx [Target],
     ) -> Result<Vec<GeneratedProject>, Diagnostic> {
+        if compile_options.single_module {
+            log::info!("Using single module mode");
+            return self.codegen_single_module(compile_options, targets);
+        }
         let compile_directory = compile_options.build_location.clone().unwrap_or_else(|| {
             let tempdir = tempfile::tempdir().unwrap();
             tempdir.into_path()
         });
         ensure_compile_dirs(targets, &compile_directory)?;
         let targets = if targets.is_empty() { &[Target::System] } else { targets };
+        let entry_point =
+            compile_options.entry_point.as_deref().map(|it| self.find_entry_point(it)).transpose()?;
         let res = targets
             .par_iter()
             .map(|target| {
@@ -340,30 +675,74 @@ impl AnnotatedProject {
                             unit_location.as_path()
                         };
 
-                        let output_name = match compile_options.output_format {
-                            FormatOption::IR => output_name.with_extension("ll"),
-                            FormatOption::Bitcode => output_name.with_extension("bc"),
-                            _ => output_name.with_extension("o"),
-                        };
+                        // replace the source's extension (e.g. `foo.st` becomes `foo.o`)
+                        let output_name =
+                            output_name.with_extension(compile_options.output_format.get_extension());
 
                         let context = CodegenContext::create(); //Create a build location for the generated object files
                         let module =
                             self.generate_module(&context, &compile_options, unit, dependencies, literals)?;
-                        module
+                        let object = module
                             .persist(
                                 Some(&compile_directory),
                                 &output_name.to_string_lossy(),
                                 compile_options.output_format,
                                 target,
                                 compile_options.optimization,
+                                &compile_options.tuning,
                             )
                             .map(Into::into)
                             // Not needed here but might be a good idea for consistency
-                            .map(|it: Object| it.with_target(target))
+                            .map(|it: Object| it.with_target(target));
+                        // persisting optimized the module for the target, print what was emitted
+                        if compile_options.print_ir_after_opt && object.is_ok() {
+                            println!("{}", module.persist_to_string());
+                        }
+                        object
                     })
-                    .collect::<Result<Vec<_>, Diagnostic>>()?;
+                    .collect::<Vec<_>>();
+
+                let mut objects = if compile_options.keep_going {
+                    // report all failed units, the objects of the other units were persisted nevertheless
+                    let (objects, diagnostics): (Vec<_>, Vec<_>) =
+                        objects.into_iter().partition(Result::is_ok);
+                    if !diagnostics.is_empty() {
+                        return Err(Diagnostic::CombinedDiagnostic {
+                            message: format!(
+                                "{} of {} units could not be generated",
+                                diagnostics.len(),
+                                self.units.len()
+                            ),
+                            inner_diagnostics: diagnostics.into_iter().filter_map(Result::err).collect(),
+                            err_no: ErrNo::codegen__general,
+                        });
+                    }
+                    objects.into_iter().filter_map(Result::ok).collect()
+                } else {
+                    objects.into_iter().collect::<Result<Vec<_>, Diagnostic>>()?
+                };
+
+                if let Some((program, instance)) = entry_point {
+                    // the main function is generated into its own object, next to the units
+                    let context = CodegenContext::create();
+                    let output_name = Path::new("__entry_point")
+                        .with_extension(compile_options.output_format.get_extension());
+                    let object = GeneratedModule::entry_point(&context, program, instance)?.persist(
+                        Some(&compile_directory),
+                        &output_name.to_string_lossy(),
+                        compile_options.output_format,
+                        target,
+                        compile_options.optimization,
+                        &compile_options.tuning,
+                    )?;
+                    objects.push(Object::from(object).with_target(target));
+                }
 
-                Ok(GeneratedProject { target: target.clone(), objects })
+                Ok(GeneratedProject {
+                    target: target.clone(),
+                    location: target.append_to(&compile_directory),
+                    objects,
+                })
             })
             .collect::<Result<Vec<_>, Diagnostic>>()?;
 
@@ -382,6 +761,31 @@ impl AnnotatedProject {
         })?;
         Ok(())
     }
+
+    /// Writes the interface description of the exported POUs and types to the given location,
+    /// describing their layout on the given target
+    pub fn generate_metadata(&self, location: &str, target: &Target) -> Result<(), Diagnostic> {
+        let context = CodegenContext::create();
+        let layouts = TypeLayouts::new(&context, &self.index, &self.annotations, target)?;
+        let metadata = plc::metadata::collect_metadata(&self.index, &layouts);
+        let generated_metadata = plc::metadata::generate_metadata(&metadata)?;
+        File::create(location).and_then(|mut it| it.write_all(generated_metadata.as_bytes())).map_err(
+            |it| Diagnostic::GeneralError { err_no: ErrNo::general__io_err, message: it.to_string() },
+        )?;
+        Ok(())
+    }
+
+    /// Writes a C header declaring the exported POUs and types to the given location, asserting
+    /// their layout on the given target
+    pub fn generate_header(&self, location: &str, target: &Target) -> Result<(), Diagnostic> {
+        let context = CodegenContext::create();
+        let layouts = TypeLayouts::new(&context, &self.index, &self.annotations, target)?;
+        let header = plc::header::generate_header(&self.index, &layouts);
+        File::create(location).and_then(|mut it| it.write_all(header.as_bytes())).map_err(|it| {
+            Diagnostic::GeneralError { err_no: ErrNo::general__io_err, message: it.to_string() }
+        })?;
+        Ok(())
+    }
 }
 
 /// Ensures the directores for the various targets have been created
@@ -400,9 +804,60 @@ fn ensure_compile_dirs(targets: &[Target], compile_directory: &Path) -> Result<(
 #[derive(Debug)]
 pub struct GeneratedProject {
     target: Target,
+    /// the directory the objects of the target were generated into
+    location: PathBuf,
     objects: Vec<Object>,
 }
 
+/// The objects generated for one unit, one for every target
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct BundledUnit {
+    /// the path of the unit's objects relative to the directory of their target, e.g. `src/main.o`
+    pub name: String,
+    pub objects: Vec<BundledObject>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct BundledObject {
+    /// the normalized target triple, e.g. `x86_64-pc-linux-gnu`
+    pub target: String,
+    pub path: PathBuf,
+}
+
+#[derive(Serialize)]
+struct BundleManifest<'a> {
+    units: &'a [BundledUnit],
+}
+
+/// Groups the objects generated for several targets by the unit they were generated from, e.g. to
+/// distribute a library for several architectures
+pub fn bundle_by_unit(projects: &[GeneratedProject]) -> Vec<BundledUnit> {
+    let mut units: IndexMap<String, Vec<BundledObject>> = IndexMap::new();
+    for project in projects {
+        let target = project.target.get_target_triple().as_str().to_string_lossy().to_string();
+        for object in &project.objects {
+            let path = object.get_path();
+            let name = path.strip_prefix(&project.location).unwrap_or(path);
+            units
+                .entry(name.to_string_lossy().to_string())
+                .or_default()
+                .push(BundledObject { target: target.clone(), path: path.to_path_buf() });
+        }
+    }
+    units.into_iter().map(|(name, objects)| BundledUnit { name, objects }).collect()
+}
+
+/// Writes a JSON manifest listing the objects of every unit for each target to the given location
+pub fn write_bundle_manifest(projects: &[GeneratedProject], location: &str) -> Result<(), Diagnostic> {
+    let units = bundle_by_unit(projects);
+    let manifest = serde_json::to_string_pretty(&BundleManifest { units: &units })
+        .map_err(|e| Diagnostic::GeneralError { message: e.to_string(), err_no: ErrNo::general__io_err })?;
+    File::create(location)
+        .and_then(|mut it| it.write_all(manifest.as_bytes()))
+        .map_err(|it| Diagnostic::GeneralError { err_no: ErrNo::general__io_err, message: it.to_string() })?;
+    Ok(())
+}
+
 impl GeneratedProject {
     pub fn link(
         &self,
@@ -450,6 +905,19 @@ impl GeneratedProject {
                     })??;
                 codegen.persist_to_ir(output_location)
             }
+            FormatOption::Assembly => {
+                //Assembly files cannot be combined, just copy over the single generated file
+                let [obj] = &self.objects[..] else {
+                    return Err(Diagnostic::codegen_error(
+                        "Cannot combine multiple assembly files, use --single-module",
+                        SourceLocation::undefined(),
+                    ));
+                };
+                if obj.get_path() != output_location {
+                    std::fs::copy(obj.get_path(), &output_location)?;
+                }
+                Ok(output_location)
+            }
             FormatOption::Object if self.objects.len() == 1 && objects.is_empty() => {
                 //Just copy over the object file, no need for a linker
                 if let [obj] = &self.objects[..] {
@@ -479,9 +947,27 @@ impl GeneratedProject {
                 for lib in &link_options.libraries {
                     linker.add_lib(lib);
                 }
+                for lib in &link_options.whole_archive_libraries {
+                    linker.add_whole_archive_lib(lib);
+                }
                 if let Some(sysroot) = self.target.get_sysroot() {
                     linker.add_sysroot(sysroot);
                 }
+                if let Some(map) = &link_options.emit_map {
+                    let directory =
+                        map.parent().filter(|it| !it.as_os_str().is_empty()).unwrap_or(Path::new("."));
+                    if !directory.is_dir() {
+                        return Err(Diagnostic::link_error(&format!(
+                            "Cannot write the linker map to '{}', the directory '{}' does not exist",
+                            map.display(),
+                            directory.display()
+                        )));
+                    }
+                    linker.add_map_file(&map.to_string_lossy());
+                }
+                if link_options.strip {
+                    linker.strip_symbols();
+                }
                 //Include the current directory in lib search
                 linker.add_lib_path(".");
                 if let Some(loc) = build_location {
@@ -504,7 +990,8 @@ impl GeneratedProject {
             }
         }?;
 
-        let output: Object = Object::from(output_location).with_target(&self.target);
+        let output: Object =
+            Object::from(output_location).with_target(&self.target).with_format(link_options.format.into());
         Ok(output)
     }
 }
@@ -24,20 +24,31 @@ impl Default for MainType {
 /// An implementation is also provided for `Vec<SourceContainer>`
 ///
 pub fn compile<T: Compilable>(context: &CodegenContext, source: T) -> GeneratedModule<'_> {
-    let source = source.containers();
-    let project = Project::new("TestProject".to_string()).with_sources(source);
-    let mut diagnostician = Diagnostician::null_diagnostician();
-    let id_provider = IdProvider::default();
-    let parsed_project =
-        ParsedProject::parse(&project, None, id_provider.clone(), &mut diagnostician).unwrap();
-    let indexed_project = parsed_project.index(id_provider.clone()).unwrap();
-    let annotated_project = indexed_project.annotate(id_provider, &diagnostician).unwrap();
     let compile_options = CompileOptions {
         optimization: plc::OptimizationLevel::None,
         debug_level: plc::DebugLevel::None,
         ..Default::default()
     };
+    compile_with_options(context, source, compile_options)
+}
 
+///
+/// Compiles the given sources with the given options
+///
+pub fn compile_with_options<T: Compilable>(
+    context: &CodegenContext,
+    source: T,
+    compile_options: CompileOptions,
+) -> GeneratedModule<'_> {
+    let source = source.containers();
+    let project = Project::new("TestProject".to_string()).with_sources(source);
+    let mut diagnostician = Diagnostician::null_diagnostician();
+    let id_provider = IdProvider::deterministic();
+    let parsed_project = ParsedProject::parse(&project, None, id_provider.clone(), &mut diagnostician)
+        .and_then(|it| it.with_defines(&compile_options.defines, id_provider.clone()))
+        .unwrap();
+    let indexed_project = parsed_project.index(id_provider.clone()).unwrap();
+    let annotated_project = indexed_project.annotate(id_provider, &diagnostician).unwrap();
     annotated_project.generate_single_module(context, &compile_options).unwrap().unwrap()
 }
 
@@ -6,10 +6,17 @@ use plc_diagnostics::{diagnostician::Diagnostician, diagnostics::Diagnostic};
 use project::project::Project;
 use source_code::SourceContainer;
 
-use crate::{pipelines, CompileOptions};
+use crate::{
+    pipelines::{self, AnnotatedProject},
+    CompileOptions,
+};
 
+mod defines;
 mod external_files;
+mod jit;
 mod multi_files;
+mod source_loading;
+mod string_alignment;
 
 pub fn compile_with_root<S, T>(
     sources: T,
@@ -35,22 +42,36 @@ where
     T: IntoIterator<Item = S>,
 {
     let path: Option<PathBuf> = root.map(|it| it.into());
-    let mut diagnostician = Diagnostician::null_diagnostician();
     //Create a project
     let project = Project::new("TestProject".into()).with_sources(sources).with_source_includes(includes);
-    //Parse
-    let id_provider = IdProvider::default();
     let compile_options = CompileOptions {
         root: path,
         debug_level,
         optimization: plc::OptimizationLevel::None,
         ..Default::default()
     };
-    pipelines::ParsedProject::parse(&project, None, id_provider.clone(), &mut diagnostician)?
+    annotate_with_options(&project, &compile_options)?
+        //Codegen
+        .codegen_to_string(&compile_options)
+}
+
+/// Parses, indexes and annotates the project like the driver does, declaring the defines of the
+/// given options and aligning strings as configured in the project
+pub fn annotate_with_options<S>(
+    project: &Project<S>,
+    compile_options: &CompileOptions,
+) -> Result<AnnotatedProject, Diagnostic>
+where
+    S: SourceContainer + Debug,
+{
+    let mut diagnostician = Diagnostician::null_diagnostician();
+    //Parse
+    let id_provider = IdProvider::deterministic();
+    pipelines::ParsedProject::parse(project, None, id_provider.clone(), &mut diagnostician)?
+        .with_defines(&compile_options.defines, id_provider.clone())?
         //Index
         .index(id_provider.clone())?
+        .with_string_alignment(project.get_string_alignment())
         //Resolve
-        .annotate(id_provider, &diagnostician)?
-        //Codegen
-        .codegen_to_string(&compile_options)
+        .annotate(id_provider, &diagnostician)
 }
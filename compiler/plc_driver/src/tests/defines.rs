@@ -0,0 +1,109 @@
+use plc::codegen::CodegenContext;
+use plc_diagnostics::diagnostician::Diagnostician;
+use project::project::Project;
+use source_code::SourceCode;
+
+use crate::{runner, tests::annotate_with_options, CompileOptions};
+
+#[test]
+fn defines_can_be_referenced_from_st_code() {
+    //Given a project compiled with VERSION=3 and FACTOR=2.5
+    let src = "
+        FUNCTION get_version : DINT
+        VAR_IN_OUT
+            scaled : LREAL;
+        END_VAR
+            scaled := VERSION * FACTOR;
+            get_version := VERSION;
+        END_FUNCTION
+        ";
+    let project = Project::new("TestProject".into()).with_sources(vec![SourceCode::from(src)]);
+    let compile_options = CompileOptions {
+        defines: vec![("VERSION".into(), "3".into()), ("FACTOR".into(), "2.5".into())],
+        ..Default::default()
+    };
+    let project = annotate_with_options(&project, &compile_options).unwrap();
+    let mut diagnostician = Diagnostician::null_diagnostician();
+    project.validate(&mut diagnostician).unwrap();
+
+    //When the function is called
+    let mut scaled = 0.0;
+    let version: i32 = project.jit_function("get_version", &mut scaled).unwrap();
+
+    //Then the defined constants were used
+    assert_eq!(version, 3);
+    assert_eq!(scaled, 7.5);
+}
+
+#[test]
+fn defines_of_the_compile_options_are_declared() {
+    let context = CodegenContext::create();
+    let compile_options =
+        CompileOptions { defines: vec![("VERSION".into(), "3".into())], ..Default::default() };
+    let module = runner::compile_with_options(
+        &context,
+        "
+        FUNCTION main : DINT
+            main := VERSION;
+        END_FUNCTION
+        ",
+        compile_options,
+    );
+
+    let version: i32 = module.run_no_param("main");
+    assert_eq!(version, 3);
+}
+
+#[test]
+fn redefining_an_existing_global_is_an_error() {
+    let src = "
+        VAR_GLOBAL CONSTANT
+            VERSION : DINT := 1;
+        END_VAR
+        ";
+    let project = Project::new("TestProject".into()).with_sources(vec![SourceCode::from(src)]);
+    let compile_options =
+        CompileOptions { defines: vec![("version".into(), "3".into())], ..Default::default() };
+
+    let result = annotate_with_options(&project, &compile_options);
+
+    assert_eq!(result.err().unwrap().get_message(), "version: Duplicate symbol.");
+}
+
+#[test]
+fn local_variables_can_shadow_a_define() {
+    let src = "
+        FUNCTION get_version : DINT
+        VAR_IN_OUT
+            unused : DINT;
+        END_VAR
+        VAR
+            Version : DINT := 7;
+        END_VAR
+            get_version := Version;
+        END_FUNCTION
+        ";
+    let project = Project::new("TestProject".into()).with_sources(vec![SourceCode::from(src)]);
+    let compile_options =
+        CompileOptions { defines: vec![("VERSION".into(), "3".into())], ..Default::default() };
+    let project = annotate_with_options(&project, &compile_options).unwrap();
+    let mut diagnostician = Diagnostician::null_diagnostician();
+    project.validate(&mut diagnostician).unwrap();
+
+    let version: i32 = project.jit_function("get_version", &mut 0).unwrap();
+    assert_eq!(version, 7);
+}
+
+#[test]
+fn defines_must_be_literals() {
+    let project = Project::new("TestProject".into()).with_sources(vec![SourceCode::from("")]);
+    let compile_options =
+        CompileOptions { defines: vec![("VERSION".into(), "3 + x".into())], ..Default::default() };
+
+    let result = annotate_with_options(&project, &compile_options);
+
+    assert_eq!(
+        result.err().unwrap().get_message(),
+        "Invalid value for define VERSION, expected a literal: 3 + x"
+    );
+}
@@ -0,0 +1,59 @@
+use project::project::Project;
+use source_code::SourceCode;
+
+use crate::{tests::annotate_with_options, CompileOptions};
+
+#[test]
+fn jit_function_calls_a_user_function_by_name() {
+    //Given a function with an in-out parameter and a program
+    let src = "
+        FUNCTION add_five : DINT
+        VAR_IN_OUT
+            x : DINT;
+        END_VAR
+            x := x + 5;
+            add_five := x * 2;
+        END_FUNCTION
+
+        PROGRAM prg
+        VAR_INPUT
+            a, b : DINT;
+        END_VAR
+            a := a + b;
+        END_PROGRAM
+        ";
+    let project = Project::new("TestProject".into()).with_sources(vec![SourceCode::from(src)]);
+    let project = annotate_with_options(&project, &CompileOptions::default()).unwrap();
+
+    //When they are called by name
+    let mut x = 10;
+    let result: i32 = project.jit_function("add_five", &mut x).unwrap();
+    let mut instance = [3, 4];
+    let _: i32 = project.jit_function("prg", &mut instance).unwrap();
+
+    //Then the parameters were passed and the results returned
+    assert_eq!(result, 30);
+    assert_eq!(x, 15);
+    assert_eq!(instance, [7, 4]);
+}
+
+#[test]
+fn jit_function_reports_unknown_functions() {
+    let project = Project::new("TestProject".into())
+        .with_sources(vec![SourceCode::from("FUNCTION foo : DINT END_FUNCTION")]);
+    let project = annotate_with_options(&project, &CompileOptions::default()).unwrap();
+
+    let result = project.jit_function::<i32, i32>("bar", &mut 0);
+
+    assert_eq!(result.unwrap_err().get_message(), "Could not resolve reference to bar");
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "The parameter passed to 'prg' has 4 bytes but 8 bytes are expected")]
+fn running_a_program_with_a_too_small_parameter_panics() {
+    let context = plc::codegen::CodegenContext::create();
+    let module = crate::runner::compile(&context, "PROGRAM prg VAR a, b : DINT; END_VAR END_PROGRAM");
+    let mut params = 0_i32;
+    let _: i32 = module.run("prg", &mut params);
+}
@@ -1,7 +1,16 @@
-use plc::DebugLevel;
+use std::fs;
+
+use ast::ast::{flatten_expression_list, DataType};
+use plc::{codegen::CodegenContext, output::FormatOption, DebugLevel};
+use plc_diagnostics::{diagnostics::Diagnostic, errno::ErrNo};
+use project::{object::ObjectFormat, project::Project};
 use source_code::SourceCode;
 
-use crate::tests::compile_with_root;
+use crate::{
+    parse_and_annotate,
+    tests::{annotate_with_options, compile_with_root},
+    CompileOptions, LinkOptions,
+};
 
 #[test]
 fn multiple_source_files_generated() {
@@ -112,3 +121,278 @@ fn multiple_files_in_different_locations_with_debug_info() {
     //The functions are defined correctly
     insta::assert_snapshot!(results.join("\n"));
 }
+
+#[test]
+fn generated_objects_replace_the_source_extension() {
+    //Given a source file with an .st extension
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    let foo = root.join("foo.st");
+    fs::write(&foo, "FUNCTION foo : INT END_FUNCTION").unwrap();
+
+    let project = Project::new("TestProject".into()).with_sources(vec![foo]);
+    let annotated_project = annotate_with_options(&project, &CompileOptions::default()).unwrap();
+    let compile_options = CompileOptions {
+        root: Some(root.clone()),
+        build_location: Some(root.join("build")),
+        output_format: FormatOption::Object,
+        ..Default::default()
+    };
+
+    //When it is generated to an object
+    annotated_project.codegen(compile_options, &[]).unwrap();
+
+    //Then the object is named after the source without its extension
+    assert!(root.join("build").join("foo.o").exists());
+    assert!(!root.join("build").join("foo.st.o").exists());
+}
+
+#[test]
+#[ignore = "splits the debug information with llc, which is not installed everywhere"]
+fn split_debug_moves_the_debug_information_into_a_dwo_file() {
+    //Given a source file
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    let foo = root.join("foo.st");
+    fs::write(&foo, "FUNCTION foo : INT VAR x : INT; END_VAR x := 1; END_FUNCTION").unwrap();
+
+    let project = Project::new("TestProject".into()).with_sources(vec![foo]);
+    let annotated_project = annotate_with_options(&project, &CompileOptions::default()).unwrap();
+    let compile_options = CompileOptions {
+        root: Some(root.clone()),
+        build_location: Some(root.join("build")),
+        output_format: FormatOption::Object,
+        debug_level: DebugLevel::Full,
+        split_debug: true,
+        ..Default::default()
+    };
+
+    //When it is generated to an object with split debug information
+    annotated_project.codegen(compile_options, &[]).unwrap();
+
+    //Then the debug information is written into a companion .dwo file
+    let dwo = fs::read(root.join("build").join("foo.dwo")).unwrap();
+    let contains =
+        |content: &[u8], section: &str| content.windows(section.len()).any(|it| it == section.as_bytes());
+    assert!(contains(&dwo, ".debug_info.dwo"));
+
+    //And the object only references it
+    let object = fs::read(root.join("build").join("foo.o")).unwrap();
+    assert!(contains(&object, "foo.dwo"));
+    assert!(!contains(&object, ".debug_info.dwo"));
+}
+
+#[test]
+fn single_module_generates_one_object_for_all_units() {
+    //Given two source files
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    let foo = root.join("foo.st");
+    fs::write(&foo, "FUNCTION first_unit_function : INT END_FUNCTION").unwrap();
+    let bar = root.join("bar.st");
+    fs::write(&bar, "FUNCTION second_unit_function : INT END_FUNCTION").unwrap();
+
+    let project = Project::new("TestProject".into()).with_sources(vec![foo, bar]);
+    let annotated_project = annotate_with_options(&project, &CompileOptions::default()).unwrap();
+    let compile_options = CompileOptions {
+        root: Some(root.clone()),
+        build_location: Some(root.join("build")),
+        output: "TestProject.o".into(),
+        output_format: FormatOption::Object,
+        single_module: true,
+        ..Default::default()
+    };
+
+    //When they are generated into a single module
+    annotated_project.codegen(compile_options, &[]).unwrap();
+
+    //Then exactly one object is generated
+    let files = fs::read_dir(root.join("build")).unwrap().collect::<Vec<_>>();
+    assert_eq!(files.len(), 1);
+
+    //And it contains the symbols of both units
+    let content = fs::read(root.join("build").join("TestProject.o")).unwrap();
+    let contains = |symbol: &str| content.windows(symbol.len()).any(|it| it == symbol.as_bytes());
+    assert!(contains("first_unit_function"));
+    assert!(contains("second_unit_function"));
+}
+
+#[test]
+fn keep_going_generates_the_units_that_do_not_fail_codegen() {
+    //Given a source file that can be generated and one that fails codegen
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    let good = root.join("good.st");
+    fs::write(&good, "FUNCTION good : INT END_FUNCTION").unwrap();
+    let bad = root.join("bad.st");
+    fs::write(&bad, "PROGRAM bad EXIT; END_PROGRAM").unwrap();
+
+    let project = Project::new("TestProject".into()).with_sources(vec![good, bad]);
+    let annotated_project = annotate_with_options(&project, &CompileOptions::default()).unwrap();
+    let compile_options = CompileOptions {
+        root: Some(root.clone()),
+        build_location: Some(root.join("build")),
+        output_format: FormatOption::IR,
+        keep_going: true,
+        ..Default::default()
+    };
+
+    //When they are generated
+    let result = annotated_project.codegen(compile_options, &[]);
+
+    //Then the failing file is reported
+    let Err(Diagnostic::CombinedDiagnostic { message, inner_diagnostics, .. }) = result else {
+        panic!("expected a combined codegen error but got {result:?}")
+    };
+    assert_eq!(message, "1 of 2 units could not be generated");
+    assert_eq!(inner_diagnostics.len(), 1);
+    //And the other file was generated nevertheless
+    assert!(root.join("build").join("good.ll").exists());
+    assert!(!root.join("build").join("bad.ll").exists());
+}
+
+#[test]
+fn symbol_map_associates_generated_symbols_with_their_source_files() {
+    //Given two source files with a function each
+    let foo = SourceCode::new("FUNCTION foo : INT END_FUNCTION", "foo.st");
+    let bar = SourceCode::new("FUNCTION bar : INT\nVAR_INPUT x : INT; END_VAR\nEND_FUNCTION", "bar.st");
+
+    let project = Project::new("TestProject".into()).with_sources(vec![foo, bar]);
+    let annotated_project = annotate_with_options(&project, &CompileOptions::default()).unwrap();
+
+    //When they are generated into one module
+    let context = CodegenContext::create();
+    let module =
+        annotated_project.generate_single_module(&context, &CompileOptions::default()).unwrap().unwrap();
+
+    //Then both functions are mapped to the file they were declared in
+    let symbol_map = module.symbol_map();
+    let foo = &symbol_map["foo"];
+    assert_eq!(foo.name, "foo");
+    assert_eq!(foo.location.get_file_name(), Some("foo.st"));
+    assert_eq!(foo.location.get_line(), 0);
+    let bar = &symbol_map["bar"];
+    assert_eq!(bar.name, "bar");
+    assert_eq!(bar.location.get_file_name(), Some("bar.st"));
+    assert_eq!(bar.location.get_line(), 0);
+}
+
+#[test]
+fn merging_modules_that_define_the_same_function_is_an_error() {
+    //Given two source files that both define main
+    let a = SourceCode::new("FUNCTION main : INT END_FUNCTION", "a.st");
+    let b = SourceCode::new("FUNCTION main : INT END_FUNCTION", "b.st");
+
+    let project = Project::new("TestProject".into()).with_sources(vec![a, b]);
+    let annotated_project = annotate_with_options(&project, &CompileOptions::default()).unwrap();
+
+    //When they are generated into one module
+    let context = CodegenContext::create();
+    let Err(diagnostic) = annotated_project.generate_single_module(&context, &CompileOptions::default())
+    else {
+        panic!("expected the modules to conflict");
+    };
+
+    //Then the duplicate definition is reported before linking
+    assert_eq!(diagnostic.get_type(), &ErrNo::duplicate_symbol);
+    assert_eq!(diagnostic.get_message(), "main: Duplicate symbol, it is defined in more than one module.");
+}
+
+#[test]
+#[cfg_attr(target_os = "windows", ignore = "relocatable objects are not supported by the windows linkers")]
+fn relocatable_linking_combines_the_objects_into_one_object() {
+    //Given two source files, one of them calling a function that is not defined in the project
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    let foo = root.join("foo.st");
+    fs::write(&foo, "FUNCTION foo : DINT foo := bar(); END_FUNCTION").unwrap();
+    let bar = root.join("bar.st");
+    fs::write(&bar, "FUNCTION bar : DINT bar := external(); END_FUNCTION").unwrap();
+    let external = root.join("external.st");
+    fs::write(&external, "{external} FUNCTION external : DINT END_FUNCTION").unwrap();
+
+    let project =
+        Project::new("TestProject".into()).with_sources(vec![foo, bar]).with_source_includes(vec![external]);
+    let annotated_project = annotate_with_options(&project, &CompileOptions::default()).unwrap();
+    let compile_options = CompileOptions {
+        root: Some(root.clone()),
+        build_location: Some(root.join("build")),
+        output_format: FormatOption::Relocatable,
+        ..Default::default()
+    };
+    let generated = annotated_project.codegen(compile_options, &[]).unwrap();
+
+    //When the objects are linked into a relocatable object
+    let link_options = LinkOptions { format: FormatOption::Relocatable, ..Default::default() };
+    let object = generated[0].link(&[], Some(&root.join("build")), None, "combined.o", link_options).unwrap();
+
+    //Then a single object is created, the unresolved function is left to the final link
+    assert_eq!(object.get_format(), ObjectFormat::Object);
+    assert_eq!(object.get_path(), root.join("build").join("combined.o"));
+    let content = fs::read(object.get_path()).unwrap();
+    let contains = |symbol: &str| content.windows(symbol.len()).any(|it| it == symbol.as_bytes());
+    assert!(contains("foo"));
+    assert!(contains("bar"));
+    assert!(contains("external"));
+}
+
+#[test]
+fn ids_generated_for_multiple_files_are_the_same_on_every_run() {
+    let sources = (0..8)
+        .map(|i| {
+            SourceCode::new(
+                format!("TYPE Color{i} : (red{i}, green{i}, blue{i}); END_TYPE"),
+                format!("file{i}.st"),
+            )
+        })
+        .collect::<Vec<_>>();
+    let generated_ids = || {
+        let project = parse_and_annotate("TestProject", sources.clone()).unwrap();
+        project
+            .units
+            .iter()
+            .flat_map(|(unit, ..)| &unit.user_types)
+            .filter_map(|it| match &it.data_type {
+                DataType::EnumType { elements, .. } => Some(elements),
+                _ => None,
+            })
+            .flat_map(flatten_expression_list)
+            .map(|it| it.get_id())
+            .collect::<Vec<_>>()
+    };
+
+    // the enum elements' initializers are generated while the units are pre-processed in parallel
+    let ids = generated_ids();
+    assert_eq!(ids.len(), 24);
+    for _ in 0..5 {
+        assert_eq!(generated_ids(), ids);
+    }
+}
+
+#[test]
+fn symbols_generated_for_multiple_files_are_named_the_same_on_every_run() {
+    let sources = (0..8)
+        .map(|i| {
+            SourceCode::new(
+                format!(
+                    "
+                FUNCTION foo{i} : STRING[{i}]
+                VAR
+                    arr : ARRAY[0..{i}] OF INT;
+                    ptr : REF_TO DINT;
+                END_VAR
+                    foo{i} := 'a string literal';
+                END_FUNCTION
+                "
+                ),
+                format!("file{i}.st"),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // the internal types and literals are named while the units are processed in parallel
+    let ir = compile_with_root(sources.clone(), vec![], "root", DebugLevel::None).unwrap();
+    for _ in 0..5 {
+        assert_eq!(compile_with_root(sources.clone(), vec![], "root", DebugLevel::None).unwrap(), ir);
+    }
+}
@@ -0,0 +1,152 @@
+use std::{
+    cell::Cell,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+use ast::provider::IdProvider;
+use encoding_rs::Encoding;
+use plc_diagnostics::{diagnostician::Diagnostician, diagnostics::Diagnostic, reporter::DiagnosticReporter};
+use project::project::Project;
+use source_code::{SourceCode, SourceContainer};
+
+use crate::{pipelines::ParsedProject, tests::annotate_with_options, CompileOptions};
+
+/// A source whose first reads fail, like a file on a flaky network filesystem
+#[derive(Debug)]
+struct FlakySource {
+    source: SourceCode,
+    failures: Cell<u32>,
+    error: ErrorKind,
+}
+
+impl FlakySource {
+    fn new(src: &str, failures: u32) -> Self {
+        FlakySource {
+            source: SourceCode::new(src, "flaky.st"),
+            failures: Cell::new(failures),
+            error: ErrorKind::WouldBlock,
+        }
+    }
+
+    fn with_error(self, error: ErrorKind) -> Self {
+        FlakySource { error, ..self }
+    }
+}
+
+impl SourceContainer for FlakySource {
+    fn load_source(&self, _: Option<&'static Encoding>) -> Result<SourceCode, io::Error> {
+        if self.failures.get() > 0 {
+            self.failures.set(self.failures.get() - 1);
+            return Err(io::Error::new(self.error, "Resource temporarily unavailable"));
+        }
+        Ok(self.source.clone())
+    }
+
+    fn get_location(&self) -> Option<&Path> {
+        self.source.path.as_deref()
+    }
+}
+
+#[test]
+fn sources_failing_to_be_read_once_are_retried() {
+    let project =
+        Project::new("TestProject".into()).with_sources(vec![FlakySource::new("PROGRAM prg END_PROGRAM", 1)]);
+    let mut diagnostician = Diagnostician::null_diagnostician();
+
+    let result = ParsedProject::parse(&project, None, IdProvider::default(), &mut diagnostician);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn read_errors_are_reported_once_the_retries_are_exhausted() {
+    let project =
+        Project::new("TestProject".into()).with_sources(vec![FlakySource::new("PROGRAM prg END_PROGRAM", 3)]);
+    let mut diagnostician = Diagnostician::null_diagnostician();
+
+    let result =
+        ParsedProject::parse_with_read_retries(&project, None, IdProvider::default(), &mut diagnostician, 2);
+
+    assert_eq!(result.err(), Some(Diagnostic::io_read_error("flaky.st", "Resource temporarily unavailable")));
+}
+
+#[test]
+fn missing_sources_are_not_retried() {
+    // a source that would be found when reading it a second time
+    let source = FlakySource::new("PROGRAM prg END_PROGRAM", 1).with_error(ErrorKind::NotFound);
+    let project = Project::new("TestProject".into()).with_sources(vec![source]);
+    let mut diagnostician = Diagnostician::null_diagnostician();
+
+    let result =
+        ParsedProject::parse_with_read_retries(&project, None, IdProvider::default(), &mut diagnostician, 2);
+
+    assert_eq!(result.err(), Some(Diagnostic::io_read_error("flaky.st", "Resource temporarily unavailable")));
+}
+
+#[test]
+fn globals_of_included_files_are_resolved() {
+    let dir = tempfile::tempdir().unwrap();
+    let shared = dir.path().join("shared");
+    std::fs::create_dir(&shared).unwrap();
+    std::fs::write(shared.join("globals.st"), "VAR_GLOBAL counter : DINT; END_VAR").unwrap();
+    std::fs::write(shared.join("types.st"), "{include := 'globals.st'} TYPE Level : INT; END_TYPE").unwrap();
+    let main = dir.path().join("main.st");
+    std::fs::write(
+        &main,
+        "
+        {include := 'shared/types.st'}
+        {include := 'globals.st'}
+        PROGRAM main
+        VAR
+            level : Level;
+        END_VAR
+            counter := counter + level;
+        END_PROGRAM
+        ",
+    )
+    .unwrap();
+    let project =
+        Project::new("TestProject".into()).with_sources(vec![main]).with_include_directories(vec![shared]);
+
+    let project = annotate_with_options(&project, &CompileOptions::default()).unwrap();
+    let mut diagnostician = Diagnostician::buffered();
+    // globals.st is included twice but only parsed once, a second `counter` would be a duplicate
+    project.validate(&mut diagnostician).unwrap();
+    assert_eq!(diagnostician.buffer(), Some(String::new()));
+    assert!(project.get_index().find_global_variable("counter").is_some());
+}
+
+#[test]
+fn cyclic_includes_are_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = dir.path().join("first.st");
+    let second = dir.path().join("second.st");
+    std::fs::write(&first, "{include := 'second.st'} PROGRAM main END_PROGRAM").unwrap();
+    std::fs::write(&second, "{include := 'first.st'}").unwrap();
+    let project = Project::new("TestProject".into()).with_sources(vec![first.clone()]);
+    let mut diagnostician = Diagnostician::null_diagnostician();
+
+    let result = ParsedProject::parse(&project, None, IdProvider::default(), &mut diagnostician);
+
+    let Err(Diagnostic::SyntaxError { message, .. }) = result else { panic!("Expected a cyclic include") };
+    let (first, second) = (first.canonicalize().unwrap(), second.canonicalize().unwrap());
+    assert_eq!(
+        message,
+        format!("Cyclic include: {} -> {} -> {}", first.display(), second.display(), first.display())
+    );
+}
+
+#[test]
+fn unresolved_includes_are_reported() {
+    let project = Project::new("TestProject".into())
+        .with_sources(vec![SourceCode::new("{include := 'missing.st'} PROGRAM main END_PROGRAM", "main.st")]);
+    let mut diagnostician = Diagnostician::null_diagnostician();
+
+    let result = ParsedProject::parse(&project, None, IdProvider::default(), &mut diagnostician);
+
+    let Err(Diagnostic::SyntaxError { message, .. }) = result else {
+        panic!("Expected an unresolved include")
+    };
+    assert_eq!(message, "Could not find the included file 'missing.st'");
+}
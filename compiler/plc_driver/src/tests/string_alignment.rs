@@ -0,0 +1,73 @@
+use project::project::Project;
+use source_code::SourceCode;
+
+use crate::{tests::annotate_with_options, CompileOptions};
+
+fn codegen_with_string_alignment(src: &str, alignment: Option<u32>) -> String {
+    let project = Project::new("TestProject".into())
+        .with_sources(vec![SourceCode::from(src)])
+        .with_string_alignment(alignment);
+    let compile_options = CompileOptions::default();
+    annotate_with_options(&project, &compile_options)
+        .and_then(|it| it.codegen_to_string(&compile_options))
+        .unwrap()
+        .join("\n")
+}
+
+#[test]
+fn string_variables_are_aligned_as_configured() {
+    let src = "
+        VAR_GLOBAL
+            s : STRING;
+            ws : WSTRING[10];
+            i : INT;
+        END_VAR
+    ";
+
+    //Without an override, strings keep LLVM's natural alignment
+    let default = codegen_with_string_alignment(src, None);
+    assert!(default.contains("@s = global [81 x i8] zeroinitializer\n"), "{default}");
+    assert!(default.contains("@ws = global [11 x i16] zeroinitializer\n"), "{default}");
+
+    //With an override, all string variables but nothing else are aligned to it
+    let aligned = codegen_with_string_alignment(src, Some(4));
+    assert!(aligned.contains("@s = global [81 x i8] zeroinitializer, align 4\n"), "{aligned}");
+    assert!(aligned.contains("@ws = global [11 x i16] zeroinitializer, align 4\n"), "{aligned}");
+    assert!(aligned.contains("@i = global i16 0\n"), "{aligned}");
+}
+
+#[test]
+fn string_members_and_locals_are_aligned_as_configured() {
+    let src = "
+        TYPE MyStruct : STRUCT
+            b : BYTE;
+            s : STRING;
+        END_STRUCT
+        END_TYPE
+
+        FUNCTION foo : INT
+        VAR
+            local : STRING;
+        END_VAR
+        END_FUNCTION
+
+        PROGRAM prg
+        VAR
+            st : MyStruct;
+        END_VAR
+            st.s := 'abc';
+        END_PROGRAM
+    ";
+
+    //Without an override, strings are placed by LLVM's natural alignment
+    let default = codegen_with_string_alignment(src, None);
+    assert!(default.contains("%MyStruct = type { i8, [81 x i8] }\n"), "{default}");
+    assert!(default.contains("%local = alloca [81 x i8], align 1\n"), "{default}");
+    assert!(default.contains("getelementptr inbounds %MyStruct, %MyStruct* %st, i32 0, i32 1"), "{default}");
+
+    //With an override, string members are preceded by a zero-sized field aligning them
+    let aligned = codegen_with_string_alignment(src, Some(4));
+    assert!(aligned.contains("%MyStruct = type { i8, [0 x <4 x i8>], [81 x i8] }\n"), "{aligned}");
+    assert!(aligned.contains("%local = alloca [81 x i8], align 4\n"), "{aligned}");
+    assert!(aligned.contains("getelementptr inbounds %MyStruct, %MyStruct* %st, i32 0, i32 2"), "{aligned}");
+}
@@ -42,6 +42,10 @@ pub enum LinkageInfo {
 pub struct ProjectConfig {
     pub name: String,
     pub files: Vec<PathBuf>,
+    /// Glob patterns of files which are not compiled, even if they are matched by `files`
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
     #[serde(default)]
     pub compile_type: FormatOption,
     #[serde(default)]
@@ -51,6 +55,10 @@ pub struct ProjectConfig {
     pub libraries: Vec<LibraryConfig>,
     #[serde(default)]
     pub package_commands: Vec<String>,
+    /// Targets to build for if none are given on the command line
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<String>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
@@ -58,6 +66,10 @@ pub struct ProjectConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(alias = "format-version")]
     pub format_version: Option<String>,
+    /// Alignment in bytes of `STRING` and `WSTRING` variables, overrides the target's default
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub string_alignment: Option<u32>,
 }
 
 impl ProjectConfig {
@@ -71,6 +83,8 @@ impl ProjectConfig {
             err.into_diagnostic(&source)
         })?;
         config.validate()?;
+        config.validate_string_alignment()?;
+        config.validate_library_linkage()?;
 
         Ok(config)
     }
@@ -135,6 +149,35 @@ impl ProjectConfig {
             Diagnostic::invalid_build_description_file(message, None)
         })
     }
+
+    fn validate_string_alignment(&self) -> Result<(), Diagnostic> {
+        match self.string_alignment {
+            Some(alignment) if !alignment.is_power_of_two() => {
+                Err(Diagnostic::invalid_build_description_file(
+                    format!("string_alignment must be a power of two, got {alignment}"),
+                    None,
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Libraries of the same name have to be linked the same way, e.g. a library cannot be both `Static` and `System`
+    fn validate_library_linkage(&self) -> Result<(), Diagnostic> {
+        for (index, library) in self.libraries.iter().enumerate() {
+            if let Some(conflict) = self.libraries[..index]
+                .iter()
+                .find(|it| it.name == library.name && it.package != library.package)
+            {
+                return Err(Diagnostic::conflicting_library_linkage(
+                    &library.name,
+                    &format!("{:?}", conflict.package),
+                    &format!("{:?}", library.package),
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 //TODO: I don't think this belongs here
@@ -159,6 +202,7 @@ mod tests {
     use crate::build_config::default_targets;
     use insta::assert_snapshot;
     use plc::output::FormatOption;
+    use plc_diagnostics::diagnostics::Diagnostic;
 
     use super::LibraryConfig;
     use super::{LinkageInfo, ProjectConfig};
@@ -297,6 +341,7 @@ mod tests {
         let test_project = ProjectConfig {
             name: "MyProject".to_string(),
             files: vec![PathBuf::from("simple_program.st")],
+            exclude: vec![],
             compile_type: FormatOption::Shared,
             output: Some(String::from("proj.so")),
             libraries: vec![
@@ -330,8 +375,10 @@ mod tests {
                 },
             ],
             package_commands: vec![],
+            targets: vec![],
             version: None,
             format_version: None,
+            string_alignment: None,
         };
         let proj = ProjectConfig::try_parse(SIMPLE_PROGRAM.into()).unwrap();
 
@@ -423,6 +470,58 @@ mod tests {
         assert_snapshot!(diag.to_string())
     }
 
+    #[test]
+    fn string_alignment_must_be_a_power_of_two() {
+        let config = |alignment: u32| {
+            format!(
+                r#"{{
+                    "name": "MyProject",
+                    "files": ["simple_program.st"],
+                    "compile_type": "Shared",
+                    "string_alignment": {alignment}
+                }}"#
+            )
+        };
+
+        let proj = ProjectConfig::try_parse(config(4).as_str().into()).unwrap();
+        assert_eq!(proj.string_alignment, Some(4));
+
+        let Err(diag) = ProjectConfig::try_parse(config(3).as_str().into()) else {
+            panic!("expected errors")
+        };
+        assert_snapshot!(diag.to_string())
+    }
+
+    #[test]
+    fn libraries_of_the_same_name_with_different_linkage_are_reported() {
+        let config = r#"
+        {
+            "name": "MyProject",
+            "files": ["simple_program.st"],
+            "compile_type": "Shared",
+            "libraries": [
+                {
+                    "name": "mylib",
+                    "path": "libs/",
+                    "package": "Static",
+                    "include_path": ["mylib.st"]
+                },
+                {
+                    "name": "mylib",
+                    "path": "other_libs/",
+                    "package": "System",
+                    "include_path": ["mylib.st"]
+                }
+            ]
+        }
+        "#;
+
+        let Err(diag) = ProjectConfig::try_parse(config.into()) else {
+            panic!("expected errors")
+        };
+        assert_eq!(diag, Diagnostic::conflicting_library_linkage("mylib", "Static", "System"));
+    }
+
     #[test]
     fn json_with_optional_properties_is_valid() {
         match ProjectConfig::try_parse(OPTIONAL_PROPERTIES.into()) {
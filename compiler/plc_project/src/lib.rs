@@ -5,3 +5,5 @@
 mod build_config;
 pub mod object;
 pub mod project;
+#[cfg(feature = "watch")]
+pub mod watch;
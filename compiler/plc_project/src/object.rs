@@ -1,16 +1,16 @@
 use std::path::{Path, PathBuf};
 
-use plc::Target;
+use plc::{output::FormatOption, Target};
 
 #[derive(Debug, Clone)]
 pub struct Object {
     path: PathBuf,
     target: Target,
-    // TODO: format: ObjectFormat,
+    format: ObjectFormat,
 }
 
 /// Representation of a binary file
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ObjectFormat {
     /// Archive file containing several object files, used for static linking
     Archive,
@@ -22,6 +22,8 @@ pub enum ObjectFormat {
     Bitcode,
     /// An LLVM IR generated file (".ll")
     IR,
+    /// A target assembly file (".s")
+    Assembly,
     /// Default non specific representation, this is typically the ".o" file
     Object,
     /// Unknown type
@@ -30,16 +32,31 @@ pub enum ObjectFormat {
 
 impl From<PathBuf> for Object {
     fn from(path: PathBuf) -> Self {
-        // let format = match path.extension().and_then(|it| it.to_str()) {
-        //     Some("o") => ObjectFormat::Object,
-        //     Some("bc") => ObjectFormat::Bitcode,
-        //     Some("ir") => ObjectFormat::IR,
-        //     Some("so") => ObjectFormat::Shared,
-        //     Some("a") => ObjectFormat::Archive,
-        //     Some(_) => ObjectFormat::Unknown,
-        //     None => ObjectFormat::Executable,
-        // };
-        Object { path, target: Target::System }
+        let format = match path.extension().and_then(|it| it.to_str()) {
+            Some("o") => ObjectFormat::Object,
+            Some("bc") => ObjectFormat::Bitcode,
+            Some("ll") => ObjectFormat::IR,
+            Some("s") => ObjectFormat::Assembly,
+            Some("so") => ObjectFormat::Shared,
+            Some("a") => ObjectFormat::Archive,
+            Some(_) => ObjectFormat::Unknown,
+            None => ObjectFormat::Executable,
+        };
+        Object { path, target: Target::System, format }
+    }
+}
+
+impl From<FormatOption> for ObjectFormat {
+    fn from(format: FormatOption) -> Self {
+        match format {
+            FormatOption::Static => ObjectFormat::Executable,
+            FormatOption::Shared | FormatOption::PIC | FormatOption::NoPIC => ObjectFormat::Shared,
+            // a relocatable object can be combined with other objects like any object file
+            FormatOption::Object | FormatOption::Relocatable => ObjectFormat::Object,
+            FormatOption::Bitcode => ObjectFormat::Bitcode,
+            FormatOption::IR => ObjectFormat::IR,
+            FormatOption::Assembly => ObjectFormat::Assembly,
+        }
     }
 }
 
@@ -52,7 +69,17 @@ impl Object {
         &self.path
     }
 
+    /// sets the format of the object, e.g. if it cannot be derived from the file's extension
+    pub fn with_format(mut self, format: ObjectFormat) -> Self {
+        self.format = format;
+        self
+    }
+
     pub fn get_target(&self) -> &Target {
         &self.target
     }
+
+    pub fn get_format(&self) -> ObjectFormat {
+        self.format
+    }
 }
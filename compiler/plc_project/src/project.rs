@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     env,
     path::{Path, PathBuf},
 };
@@ -11,7 +12,7 @@ use crate::{
     object::Object,
 };
 
-use plc::output::FormatOption;
+use plc::{output::FormatOption, Target};
 use source_code::{SourceContainer, SourceType};
 
 #[derive(Debug)]
@@ -45,7 +46,8 @@ pub struct CompiledLibrary<T: SourceContainer> {
     headers: Vec<T>,
     /// Objects files for the compiled library
     objects: Vec<Object>,
-    // architectures: Vec<Target>,
+    /// Targets the library is available for
+    architectures: Vec<Target>,
 }
 
 /// The information required by a project to successfully include a library
@@ -81,6 +83,12 @@ pub struct Project<T: SourceContainer> {
     format: FormatOption,
     /// Output Name
     output: Option<String>,
+    /// Targets to build for when none are given on the command line
+    targets: Vec<Target>,
+    /// Alignment in bytes of string variables, if it differs from the target's default
+    string_alignment: Option<u32>,
+    /// Directories searched for the files of `{include := 'file'}` directives
+    include_directories: Vec<PathBuf>,
 }
 
 impl<T: SourceContainer> LibraryInformation<T> {
@@ -100,6 +108,14 @@ impl<T: SourceContainer> LibraryInformation<T> {
         self.location.as_deref()
     }
 
+    /// Returns the targets a compiled library is available for
+    pub fn get_architectures(&self) -> &[Target] {
+        match &self.library {
+            Library::Compiled(lib) => &lib.architectures,
+            Library::Source(_) => &[],
+        }
+    }
+
     pub fn should_copy(&self) -> bool {
         matches!(self.linkage, Linkage::Shared(Package::Local))
     }
@@ -146,7 +162,8 @@ impl Project<PathBuf> {
                 }
                 let compiled_library = CompiledLibrary {
                     objects,
-                    headers: resolve_file_paths(Some(&lib_path), conf.include_path)?,
+                    headers: resolve_file_paths(Some(&lib_path), conf.include_path, &[])?,
+                    architectures: conf.architectures,
                 };
                 Ok(LibraryInformation {
                     name: conf.name,
@@ -159,7 +176,7 @@ impl Project<PathBuf> {
 
         let current_dir = env::current_dir()?;
         let location = config.parent().map(Path::to_path_buf).or(Some(current_dir));
-        let sources = resolve_file_paths(location.as_deref(), project_config.files)?;
+        let sources = resolve_file_paths(location.as_deref(), project_config.files, &project_config.exclude)?;
         Ok(Project {
             name: project_config.name,
             location,
@@ -169,12 +186,15 @@ impl Project<PathBuf> {
             output: project_config.output,
             includes: vec![],
             objects: vec![],
+            targets: project_config.targets.into_iter().map(Target::from).collect(),
+            string_alignment: project_config.string_alignment,
+            include_directories: vec![],
         })
     }
 
     pub fn with_file_pathes(self, files: Vec<PathBuf>) -> Self {
         let mut proj = self;
-        let files = resolve_file_paths(proj.get_location(), files).unwrap();
+        let files = resolve_file_paths(proj.get_location(), files, &[]).unwrap();
         for file in files {
             if matches!(file.get_type(), SourceType::Unknown) {
                 let obj = file.into();
@@ -188,7 +208,7 @@ impl Project<PathBuf> {
 
     pub fn with_include_pathes(self, files: Vec<PathBuf>) -> Self {
         let mut proj = self;
-        proj.includes = resolve_file_paths(proj.get_location(), files).unwrap();
+        proj.includes = resolve_file_paths(proj.get_location(), files, &[]).unwrap();
         proj
     }
 
@@ -216,6 +236,9 @@ impl<S: SourceContainer> Project<S> {
             libraries: vec![],
             format: FormatOption::default(),
             output: None,
+            targets: vec![],
+            string_alignment: None,
+            include_directories: vec![],
         }
     }
 
@@ -229,6 +252,18 @@ impl<S: SourceContainer> Project<S> {
         self
     }
 
+    /// Adds directories in which the files of `{include := 'file'}` directives are searched
+    pub fn with_include_directories(mut self, directories: Vec<PathBuf>) -> Self {
+        self.include_directories.extend(directories);
+        self
+    }
+
+    /// Aligns `STRING` and `WSTRING` variables to the given number of bytes
+    pub fn with_string_alignment(mut self, alignment: Option<u32>) -> Self {
+        self.string_alignment = alignment;
+        self
+    }
+
     pub fn with_libraries(self, libraries: Vec<String>) -> Self {
         let mut proj = self;
         for library in libraries {
@@ -236,7 +271,11 @@ impl<S: SourceContainer> Project<S> {
                 name: library.to_string(),
                 location: None,
                 linkage: Linkage::Shared(Package::System),
-                library: Library::Compiled(CompiledLibrary { headers: vec![], objects: vec![] }),
+                library: Library::Compiled(CompiledLibrary {
+                    headers: vec![],
+                    objects: vec![],
+                    architectures: vec![],
+                }),
             });
         }
         proj
@@ -269,11 +308,9 @@ impl<S: SourceContainer> Project<S> {
         self.output.as_ref().map(|it| it.to_string()).unwrap_or_else(|| {
             let input = self.get_name();
             match self.format {
-                FormatOption::Object | FormatOption::Relocatable => format!("{input}.o"),
                 FormatOption::Static => format!("{input}.out"),
                 FormatOption::Shared | FormatOption::PIC | FormatOption::NoPIC => format!("{input}.so"),
-                FormatOption::Bitcode => format!("{input}.bc"),
-                FormatOption::IR => format!("{input}.ll"),
+                format => format!("{input}.{}", format.get_extension()),
             }
         })
     }
@@ -281,24 +318,57 @@ impl<S: SourceContainer> Project<S> {
     pub fn get_output_format(&self) -> FormatOption {
         self.format
     }
+
+    pub fn get_targets(&self) -> &[Target] {
+        &self.targets
+    }
+
+    pub fn get_string_alignment(&self) -> Option<u32> {
+        self.string_alignment
+    }
+
+    pub fn get_include_directories(&self) -> &[PathBuf] {
+        &self.include_directories
+    }
 }
 
-fn resolve_file_paths(location: Option<&Path>, inputs: Vec<PathBuf>) -> Result<Vec<PathBuf>, Diagnostic> {
+/// resolves the given (glob) paths relative to the location, files matched by one of the `exclude` globs
+/// are skipped. Files matched more than once (e.g. by overlapping globs) are only returned once
+fn resolve_file_paths(
+    location: Option<&Path>,
+    inputs: Vec<PathBuf>,
+    exclude: &[String],
+) -> Result<Vec<PathBuf>, Diagnostic> {
+    // the excluded files are compared by their canonical paths, they count as already resolved
+    let mut resolved = HashSet::new();
+    for pattern in exclude {
+        resolved.extend(glob_paths(location, Path::new(pattern))?.into_iter().map(|it| canonicalize(&it)));
+    }
     let mut sources = Vec::new();
     for input in &inputs {
-        let input = location.map(|it| it.join(input)).unwrap_or(input.to_path_buf());
-        let path = &input.to_string_lossy();
-        let paths = glob(path)
-            .map_err(|e| Diagnostic::param_error(&format!("Failed to read glob pattern: {path}, ({e})")))?;
-
-        for p in paths {
-            let path = p.map_err(|err| Diagnostic::param_error(&format!("Illegal path: {err}")))?;
-            sources.push(path);
+        for path in glob_paths(location, input)? {
+            if resolved.insert(canonicalize(&path)) {
+                sources.push(path);
+            }
         }
     }
     Ok(sources)
 }
 
+/// returns the paths matching the given glob, relative to the location
+fn glob_paths(location: Option<&Path>, input: &Path) -> Result<Vec<PathBuf>, Diagnostic> {
+    let input = location.map(|it| it.join(input)).unwrap_or(input.to_path_buf());
+    let path = &input.to_string_lossy();
+    glob(path)
+        .map_err(|e| Diagnostic::param_error(&format!("Failed to read glob pattern: {path}, ({e})")))?
+        .map(|p| p.map_err(|err| Diagnostic::param_error(&format!("Illegal path: {err}"))))
+        .collect()
+}
+
+fn canonicalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
 impl From<LinkageInfo> for Linkage {
     fn from(value: LinkageInfo) -> Self {
         match value {
@@ -308,3 +378,58 @@ impl From<LinkageInfo> for Linkage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::Project;
+
+    #[test]
+    fn files_matched_more_than_once_are_only_compiled_once() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("main.st"), "PROGRAM main END_PROGRAM").unwrap();
+        fs::write(dir.path().join("other.st"), "PROGRAM other END_PROGRAM").unwrap();
+
+        //the file is listed directly, through a glob and through a relative path
+        let project = Project::<PathBuf>::new("TestProject".into()).with_file_pathes(vec![
+            dir.path().join("main.st"),
+            dir.path().join("*.st"),
+            dir.path().join("..").join(dir.path().file_name().unwrap()).join("main.st"),
+        ]);
+
+        let sources = project.get_sources();
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0], dir.path().join("main.st"));
+        assert_eq!(sources[1], dir.path().join("other.st"));
+    }
+
+    #[test]
+    fn excluded_files_are_not_compiled() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/nested")).unwrap();
+        fs::write(dir.path().join("src/main.st"), "PROGRAM main END_PROGRAM").unwrap();
+        fs::write(dir.path().join("src/main_test.st"), "PROGRAM main_test END_PROGRAM").unwrap();
+        fs::write(dir.path().join("src/nested/other.st"), "PROGRAM other END_PROGRAM").unwrap();
+        fs::write(dir.path().join("src/nested/other_test.st"), "PROGRAM other_test END_PROGRAM").unwrap();
+        fs::write(
+            dir.path().join("plc.json"),
+            r#"{
+                "name": "TestProject",
+                "files": ["src/**/*.st"],
+                "exclude": ["src/**/*_test.st"],
+                "compile_type": "Shared"
+            }"#,
+        )
+        .unwrap();
+
+        //the whole directory is included, but the tests are excluded
+        let project = Project::from_config(&dir.path().join("plc.json")).unwrap();
+
+        let mut sources =
+            project.get_sources().iter().map(|it| it.canonicalize().unwrap()).collect::<Vec<_>>();
+        sources.sort();
+        let src = dir.path().join("src").canonicalize().unwrap();
+        assert_eq!(sources, vec![src.join("main.st"), src.join("nested/other.st")]);
+    }
+}
@@ -0,0 +1,157 @@
+//! Watches the sources of a project to recompile it whenever a source changes, e.g. for a dev-server
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::mpsc::{channel, Receiver},
+    time::Duration,
+};
+
+use notify_debouncer_mini::{
+    new_debouncer,
+    notify::{RecommendedWatcher, RecursiveMode},
+    DebounceEventResult, DebouncedEventKind, Debouncer,
+};
+use plc_diagnostics::diagnostics::Diagnostic;
+
+use crate::project::Project;
+
+/// An iterator yielding the changed sources of a project.
+/// Successive writes within the debounce timeout are reported as a single change.
+/// Iterating blocks until the next change.
+pub struct ProjectWatcher {
+    /// the watched files stop being observed once the debouncer is dropped
+    _debouncer: Debouncer<RecommendedWatcher>,
+    events: Receiver<DebounceEventResult>,
+    sources: HashSet<PathBuf>,
+}
+
+impl Project<PathBuf> {
+    /// Watches the sources and includes of the project for changes,
+    /// writes happening within `debounce` of each other are reported as one change
+    pub fn watch(&self, debounce: Duration) -> Result<ProjectWatcher, Diagnostic> {
+        ProjectWatcher::new(self.get_sources().iter().chain(self.get_includes()), debounce)
+    }
+}
+
+impl ProjectWatcher {
+    fn new<'a>(
+        sources: impl Iterator<Item = &'a PathBuf>,
+        debounce: Duration,
+    ) -> Result<ProjectWatcher, Diagnostic> {
+        let (sender, events) = channel();
+        let mut debouncer = new_debouncer(debounce, sender)
+            .map_err(|err| Diagnostic::io_watch_error("", &err.to_string()))?;
+
+        let sources = sources.map(|it| canonicalize(it)).collect::<HashSet<_>>();
+        // editors often replace a file instead of writing it, so the parent directories are watched
+        let directories = sources.iter().filter_map(|it| it.parent()).collect::<HashSet<_>>();
+        for directory in directories {
+            debouncer
+                .watcher()
+                .watch(directory, RecursiveMode::NonRecursive)
+                .map_err(|err| Diagnostic::io_watch_error(&directory.to_string_lossy(), &err.to_string()))?;
+        }
+
+        Ok(ProjectWatcher { _debouncer: debouncer, events, sources })
+    }
+}
+
+impl Iterator for ProjectWatcher {
+    type Item = Vec<PathBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_change(&self.events, &self.sources)
+    }
+}
+
+/// blocks until the next debounced event touching one of the given sources and returns the changed sources,
+/// returns `None` once the events' sender is dropped
+fn next_change(events: &Receiver<DebounceEventResult>, sources: &HashSet<PathBuf>) -> Option<Vec<PathBuf>> {
+    loop {
+        let events = match events.recv().ok()? {
+            Ok(events) => events,
+            Err(err) => {
+                log::warn!("Failed to watch the project: {err}");
+                continue;
+            }
+        };
+
+        let mut changed = events
+            .into_iter()
+            // continuous events are reported while writes are still ongoing
+            .filter(|it| it.kind == DebouncedEventKind::Any)
+            .map(|it| canonicalize(&it.path))
+            .filter(|it| sources.contains(it))
+            .collect::<Vec<_>>();
+        changed.sort();
+        changed.dedup();
+        if !changed.is_empty() {
+            return Some(changed);
+        }
+    }
+}
+
+/// deleted files cannot be canonicalized, they are compared as they are
+fn canonicalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, fs, path::PathBuf, sync::mpsc::channel, thread, time::Duration};
+
+    use notify_debouncer_mini::{DebouncedEvent, DebouncedEventKind};
+
+    use crate::project::Project;
+
+    use super::next_change;
+
+    #[test]
+    fn successive_writes_to_a_source_are_reported_once() {
+        let main = PathBuf::from("/project/main.st");
+        let sources = HashSet::from([main.clone()]);
+        let (sender, events) = channel();
+
+        // a debounced batch of writes to the source and to a file that is not part of the project
+        sender
+            .send(Ok(vec![
+                DebouncedEvent::new(main.clone(), DebouncedEventKind::AnyContinuous),
+                DebouncedEvent::new(main.clone(), DebouncedEventKind::Any),
+                DebouncedEvent::new(PathBuf::from("/project/notes.txt"), DebouncedEventKind::Any),
+                DebouncedEvent::new(main.clone(), DebouncedEventKind::Any),
+            ]))
+            .unwrap();
+        // and a batch only touching other files
+        sender
+            .send(Ok(vec![DebouncedEvent::new(PathBuf::from("/project/notes.txt"), DebouncedEventKind::Any)]))
+            .unwrap();
+        drop(sender);
+
+        assert_eq!(next_change(&events, &sources), Some(vec![main]));
+        assert_eq!(next_change(&events, &sources), None);
+    }
+
+    #[test]
+    fn writing_a_source_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("main.st");
+        fs::write(&main, "PROGRAM main END_PROGRAM").unwrap();
+        let project = Project::<PathBuf>::new("TestProject".into()).with_file_pathes(vec![main.clone()]);
+
+        let watcher = project.watch(Duration::from_millis(10)).unwrap();
+        let (sender, changes) = channel();
+        thread::spawn(move || {
+            for changed in watcher {
+                if sender.send(changed).is_err() {
+                    break;
+                }
+            }
+        });
+
+        fs::write(&main, "PROGRAM main (* changed *) END_PROGRAM").unwrap();
+
+        let changed = changes.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(changed, vec![main.canonicalize().unwrap()]);
+    }
+}
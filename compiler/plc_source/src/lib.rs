@@ -1,6 +1,6 @@
 use std::{
     fs::File,
-    io::Read,
+    io::{self, ErrorKind, Read},
     path::{Path, PathBuf},
 };
 
@@ -25,7 +25,7 @@ pub enum SourceType {
 /// Furthermore it offers a location-String used when reporting diagnostics.
 pub trait SourceContainer {
     /// loads and returns the SourceEntry that contains the SourceCode and the path it was loaded from
-    fn load_source(&self, encoding: Option<&'static Encoding>) -> Result<SourceCode, String>;
+    fn load_source(&self, encoding: Option<&'static Encoding>) -> Result<SourceCode, io::Error>;
     /// returns the location of this source-container. Used when reporting diagnostics.
     fn get_location(&self) -> Option<&Path>;
 
@@ -66,7 +66,7 @@ pub struct SourceCode {
 
 /// tests can provide a SourceCode directly
 impl SourceContainer for SourceCode {
-    fn load_source(&self, _: Option<&'static Encoding>) -> Result<SourceCode, String> {
+    fn load_source(&self, _: Option<&'static Encoding>) -> Result<SourceCode, io::Error> {
         Ok(self.clone())
     }
 
@@ -89,15 +89,18 @@ impl SourceCodeFactory for &str {
 }
 
 impl<T: AsRef<Path>> SourceContainer for T {
-    fn load_source(&self, encoding: Option<&'static Encoding>) -> Result<SourceCode, String> {
+    fn load_source(&self, encoding: Option<&'static Encoding>) -> Result<SourceCode, io::Error> {
         let source_type = self.get_type();
         if matches!(source_type, SourceType::Text | SourceType::Xml) {
-            let mut file = File::open(self).map_err(|err| err.to_string())?;
+            let mut file = File::open(self)?;
             let source = create_source_code(&mut file, encoding)?;
 
             Ok(SourceCode { source, path: Some(self.as_ref().to_owned()) })
         } else {
-            Err(format!("{} is not a source file", &self.as_ref().to_string_lossy()))
+            Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("{} is not a source file", &self.as_ref().to_string_lossy()),
+            ))
         }
     }
 
@@ -109,10 +112,10 @@ impl<T: AsRef<Path>> SourceContainer for T {
 pub fn create_source_code<T: Read>(
     reader: &mut T,
     encoding: Option<&'static Encoding>,
-) -> Result<String, String> {
+) -> Result<String, io::Error> {
     let mut buffer = String::new();
     let mut decoder = DecodeReaderBytesBuilder::new().encoding(encoding).build(reader);
-    decoder.read_to_string(&mut buffer).map_err(|err| format!("{err}"))?;
+    decoder.read_to_string(&mut buffer)?;
     Ok(buffer)
 }
 
@@ -32,6 +32,8 @@ impl<'xml> Pou<'xml> {
             overriding: false,
             generic: false,
             access: None,
+            extern_name: None,
+            function_attributes: vec![],
         }
     }
 }
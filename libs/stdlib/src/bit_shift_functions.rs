@@ -1,4 +1,7 @@
-/// Defines shift operations
+//! Defines shift operations
+//!
+//! The compiler generates `SHL`, `SHR`, `ROL` and `ROR` inline, these symbols are only kept for
+//! objects compiled against earlier versions of the standard library
 
 #[allow(non_snake_case)]
 #[no_mangle]
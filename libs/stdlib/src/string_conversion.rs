@@ -2,7 +2,8 @@ use crate::string_functions::{CharsDecoder, CharsEncoder, EncodedCharsIter};
 
 /// .
 /// Converts WSTRING to STRING
-/// Limited by a return type of 80 charachters
+/// The conversion is lossy: unpaired surrogates are replaced by U+FFFD, the caller
+/// truncates the result to the length of its STRING, which may split a multi-byte character
 ///
 /// # Safety
 ///
@@ -19,7 +20,8 @@ pub unsafe extern "C" fn WSTRING_TO_STRING_EXT(src: *const u16, dest: *mut u8) -
 
 /// .
 /// Converts STRING to WSTRING
-/// Limited by a return type of 80 charachters
+/// Every character of the STRING is representable in UTF-16, characters outside of the
+/// basic multilingual plane take two code units of the resulting WSTRING
 ///
 /// # Safety
 ///
@@ -641,6 +641,8 @@ pub unsafe extern "C" fn REPLACE_EXT__WSTRING(
     0
 }
 
+/// The compiler generates `CONCAT` inline, this symbol is only kept for objects
+/// compiled against earlier versions of the standard library.
 /// Concatenates all given strings in the order in which they are given.
 /// Strings are passed as pointer of pointer to u8, where each pointer represents
 /// the starting address of each string. The amount of strings must be passed as
@@ -685,6 +687,8 @@ pub unsafe extern "C" fn CONCAT_EXT__STRING(dest: *mut u8, argc: i32, argv: *con
     0
 }
 
+/// The compiler generates `CONCAT` inline, this symbol is only kept for objects
+/// compiled against earlier versions of the standard library.
 /// Concatenates all given strings in the order in which they are given.
 /// Strings are passed as pointer of pointer to u8, where each pointer represents
 /// the starting address of each string. The amount of strings must be passed as
@@ -86,22 +86,6 @@ pub fn compile_with_native<T: Compilable>(context: &CodegenContext, source: T) -
         ("STRING_TO_WSTRING_EXT", iec61131std::string_conversion::STRING_TO_WSTRING_EXT as usize),
         ("WCHAR_TO_CHAR", iec61131std::string_conversion::WCHAR_TO_CHAR as usize),
         ("CHAR_TO_WCHAR", iec61131std::string_conversion::CHAR_TO_WCHAR as usize),
-        ("SHL__BYTE", iec61131std::bit_shift_functions::SHL__BYTE as usize),
-        ("SHL__WORD", iec61131std::bit_shift_functions::SHL__WORD as usize),
-        ("SHL__DWORD", iec61131std::bit_shift_functions::SHL__DWORD as usize),
-        ("SHL__LWORD", iec61131std::bit_shift_functions::SHL__LWORD as usize),
-        ("SHR__BYTE", iec61131std::bit_shift_functions::SHR__BYTE as usize),
-        ("SHR__WORD", iec61131std::bit_shift_functions::SHR__WORD as usize),
-        ("SHR__DWORD", iec61131std::bit_shift_functions::SHR__DWORD as usize),
-        ("SHR__LWORD", iec61131std::bit_shift_functions::SHR__LWORD as usize),
-        ("ROL__BYTE", iec61131std::bit_shift_functions::ROL__BYTE as usize),
-        ("ROL__WORD", iec61131std::bit_shift_functions::ROL__WORD as usize),
-        ("ROL__DWORD", iec61131std::bit_shift_functions::ROL__DWORD as usize),
-        ("ROL__LWORD", iec61131std::bit_shift_functions::ROL__LWORD as usize),
-        ("ROR__BYTE", iec61131std::bit_shift_functions::ROR__BYTE as usize),
-        ("ROR__WORD", iec61131std::bit_shift_functions::ROR__WORD as usize),
-        ("ROR__DWORD", iec61131std::bit_shift_functions::ROR__DWORD as usize),
-        ("ROR__LWORD", iec61131std::bit_shift_functions::ROR__LWORD as usize),
         ("DATE_AND_TIME_TO_DATE", iec61131std::date_time_conversion::DATE_AND_TIME_TO_DATE as usize),
         (
             "DATE_AND_TIME_TO_TIME_OF_DAY",
@@ -220,6 +220,33 @@ fn string_to_wstring_long_conversion() {
     assert_eq!(&maintype.res, &exp);
 }
 
+#[test]
+fn string_to_wstring_and_back_conversion() {
+    #[repr(C)]
+    struct MainType {
+        wide: [u16; 81],
+        res: [u8; 81],
+    }
+
+    let src = r#"
+	PROGRAM main
+	VAR
+		wide : WSTRING;
+		res : STRING;
+	END_VAR
+		wide := STRING_TO_WSTRING('Hèßlo😀');
+		res := WSTRING_TO_STRING(wide);
+    END_PROGRAM
+        "#;
+    let sources = add_std!(src, "string_conversion.st", "string_functions.st");
+    let mut maintype = MainType { wide: [0; 81], res: [0; 81] };
+    let _res: i32 = compile_and_run(sources, &mut maintype);
+    let end = maintype.wide.iter().position(|it| *it == 0).unwrap();
+    assert_eq!(String::from_utf16(&maintype.wide[..end]).unwrap(), "Hèßlo😀");
+    let end = maintype.res.iter().position(|it| *it == 0).unwrap();
+    assert_eq!(std::str::from_utf8(&maintype.res[..end]).unwrap(), "Hèßlo😀");
+}
+
 #[test]
 fn string_to_char_conversion() {
     #[derive(Default)]
@@ -1755,3 +1755,19 @@ fn test_string_not_equal_operator_works_for_long_literals() {
     let res: bool = compile_and_run_no_params(source);
     assert!(res);
 }
+
+#[test]
+fn test_string_relational_operators_compare_lexicographically() {
+    let src = r#"
+    FUNCTION main : BOOL
+    VAR_TEMP
+        s : STRING := 'abd';
+    END_VAR
+        main := 'abc' < 'abd' AND NOT ('abd' < 'abc') AND s > 'abc' AND 'abc' <= s AND NOT (s < s);
+    END_FUNCTION
+    "#;
+
+    let source = add_std!(src, "string_functions.st");
+    let res: bool = compile_and_run_no_params(source);
+    assert!(res);
+}
@@ -132,6 +132,7 @@ function run_check_style() {
 	CARGO_OPTIONS=$(set_cargo_options)
 	log "Running cargo clippy"
 	cargo clippy $CARGO_OPTIONS --workspace -- -Dwarnings
+	cargo clippy $CARGO_OPTIONS -p plc_project --features watch -- -Dwarnings
 	log "Running cargo fmt check"
 	cargo fmt -- --check
 }
@@ -177,6 +178,9 @@ function run_test() {
 	else
 		cargo test $CARGO_OPTIONS --workspace
 	fi
+	# Features which are disabled by default are not covered by the workspace tests
+	log "cargo test $CARGO_OPTIONS -p plc_project --features watch"
+	cargo test $CARGO_OPTIONS -p plc_project --features watch
 }
 
 function generate_sources() {
@@ -2,8 +2,11 @@ use std::collections::HashMap;
 
 use inkwell::{
     basic_block::BasicBlock,
-    types::BasicType,
-    values::{BasicValue, IntValue},
+    intrinsics::Intrinsic,
+    module::Module,
+    types::{BasicType, BasicTypeEnum},
+    values::{BasicValue, BasicValueEnum, FloatValue, FunctionValue, IntValue},
+    AddressSpace, IntPredicate,
 };
 use lazy_static::lazy_static;
 use plc_ast::{
@@ -24,9 +27,13 @@ use crate::{
     resolver::{
         self,
         generics::{no_generic_name_resolver, GenericType},
-        AnnotationMap, StatementAnnotation, TypeAnnotator, VisitorContext,
+        get_string_type_name_for_length, register_string_type, AnnotationMap, StatementAnnotation,
+        TypeAnnotator, VisitorContext,
+    },
+    typesystem::{
+        self, get_literal_actual_signed_type_name, DataTypeInformation, StringEncoding, DEFAULT_STRING_LEN,
+        STRING_TYPE,
     },
-    typesystem::{self, get_literal_actual_signed_type_name},
     validation::{Validator, Validators},
 };
 
@@ -42,9 +49,18 @@ lazy_static! {
                 END_VAR
                 END_FUNCTION
             ",
-                annotation: None,
+                annotation: Some(|annotator, _, operator, _, _| {
+                    // the address is as wide as a pointer of the target, not necessarily a LWORD
+                    let return_type = typesystem::get_pointer_sized_type_name(annotator.index);
+                    annotator.annotate(
+                        operator, resolver::StatementAnnotation::Function {
+                            return_type: return_type.to_string(), qualified_name: "ADR".to_string(), call_name: None
+                        }
+                    );
+                }),
                 validation: None,
                 generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[],
                 code: |generator, params, location| {
                     if let [reference] = params {
                         generator
@@ -68,7 +84,7 @@ lazy_static! {
                 END_VAR
                 END_FUNCTION
                 ",
-                annotation: Some(|annotator, operator, parameters, _| {
+                annotation: Some(|annotator, _, operator, parameters, _| {
                     // invalid amount of parameters is checked during validation
                     let Some(params) = parameters else { return; };
                     // Get the input and annotate it with a pointer type
@@ -104,6 +120,7 @@ lazy_static! {
                     }
                 }),
                 generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[],
                 code: |generator, params, location| {
                     if let [reference] = params {
                         generator
@@ -131,6 +148,7 @@ lazy_static! {
                 annotation : None,
                 validation: None,
                 generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[],
                 code: |generator, params, location| {
                     let llvm = generator.llvm;
                     let context = llvm.context;
@@ -188,6 +206,7 @@ lazy_static! {
                 annotation: None,
                 validation: None,
                 generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[],
                 code: |generator, params, location| {
                     if let &[g,in0,in1] = params {
                         // evaluate the parameters
@@ -231,6 +250,7 @@ lazy_static! {
                 annotation: None,
                 validation: None,
                 generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[],
                 code : |generator, params, location| {
                     if params.len() == 1 {
                         generator.generate_expression(params[0]).map(ExpressionValue::RValue)
@@ -251,6 +271,7 @@ lazy_static! {
                 annotation: None,
                 validation: None,
                 generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[],
                 code : |generator, params, location| {
                     if let [reference] = params {
                         // get name of datatype behind reference
@@ -277,6 +298,58 @@ lazy_static! {
                 }
             }
         ),
+        (
+            "TYPE_NAME",
+            BuiltIn {
+                decl: "FUNCTION TYPE_NAME<U: ANY> : STRING
+                VAR_INPUT
+                    in : U;
+                END_VAR
+                END_FUNCTION",
+                annotation: None,
+                // generic arguments are already reported as unresolved generics
+                validation: None,
+                generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[],
+                code: |generator, params, location| {
+                    generate_type_name(generator, params, location)
+                }
+            }
+        ),
+        (
+            "__POU_NAME",
+            BuiltIn {
+                decl: "FUNCTION __POU_NAME : STRING
+                END_FUNCTION",
+                annotation: Some(|annotator, statement, _, _, ctx| {
+                    let pou_name = ctx.get_pou().unwrap_or_default().to_string();
+                    annotator.replace_with_literal(&ctx, statement, AstLiteral::new_string(pou_name, false))
+                }),
+                validation: None,
+                generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[],
+                code: |_, _, location| {
+                    Err(Diagnostic::codegen_error("__POU_NAME should have been replaced by the resolver", location))
+                }
+            }
+        ),
+        (
+            "__LINE",
+            BuiltIn {
+                decl: "FUNCTION __LINE : DINT
+                END_FUNCTION",
+                annotation: Some(|annotator, statement, _, _, ctx| {
+                    let line = statement.get_location().get_line_plus_one();
+                    annotator.replace_with_literal(&ctx, statement, AstLiteral::new_integer(line as i128))
+                }),
+                validation: None,
+                generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[],
+                code: |_, _, location| {
+                    Err(Diagnostic::codegen_error("__LINE should have been replaced by the resolver", location))
+                }
+            }
+        ),
         (
             "LOWER_BOUND",
             BuiltIn {
@@ -288,13 +361,14 @@ lazy_static! {
                     dim : T;
                 END_VAR
                 END_FUNCTION",
-                annotation: Some(|annotator, _, parameters, _| {
+                annotation: Some(|annotator, _, _, parameters, _| {
                     annotate_variable_length_array_bound_function(annotator, parameters)
                 }),
                 validation: Some(|validator, operator, parameters, annotations, index| {
                     validate_variable_length_array_bound_function(validator, operator, parameters, annotations, index)
                 }),
                 generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[],
                 code : |generator, params, location| {
                     generate_variable_length_array_bound_function(generator, params, true, location)
                 }
@@ -311,21 +385,519 @@ lazy_static! {
                     dim : T;
                 END_VAR
                 END_FUNCTION",
-                annotation: Some(|annotator, _, parameters, _| {
+                annotation: Some(|annotator, _, _, parameters, _| {
                     annotate_variable_length_array_bound_function(annotator, parameters)
                 }),
                 validation: Some(|validator, operator, parameters, annotations, index| {
                     validate_variable_length_array_bound_function(validator, operator, parameters, annotations, index)
                 }),
                 generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[],
                 code : |generator, params, location| {
                     generate_variable_length_array_bound_function(generator, params, false, location)
                 }
             }
         ),
+        (
+            "ABS",
+            BuiltIn {
+                decl: "FUNCTION ABS<U: ANY_NUM> : U
+                VAR_INPUT
+                    in : U;
+                END_VAR
+                END_FUNCTION",
+                annotation: None,
+                validation: Some(|validator, _, parameters, annotations, index| {
+                    let Some(params) = parameters else { return; };
+                    let params = flatten_expression_list(params);
+                    let Some(param) = params.first() else { return; };
+                    let type_name = annotations.get_type_or_void(param, index).get_name();
+                    if index.get_intrinsic_type_by_name(type_name).get_type_information().is_unsigned_int() {
+                        validator.push_diagnostic(Diagnostic::abs_on_unsigned(type_name, param.get_location()));
+                    }
+                }),
+                generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[("llvm.fabs", Overload::Float)],
+                code: |generator, params, location| {
+                    if let [param] = params {
+                        let value = generator.generate_expression(param)?;
+                        let type_name = generator.get_type_hint_for(param)?.get_name();
+                        let builder = &generator.llvm.builder;
+                        if value.is_float_value() {
+                            generate_float_intrinsic_call(generator, "llvm.fabs", value.into_float_value(), location)
+                                .map(|it| ExpressionValue::RValue(it.as_basic_value_enum()))
+                        } else if generator.index.get_intrinsic_type_by_name(type_name).get_type_information().is_signed_int() {
+                            let value = value.into_int_value();
+                            let is_negative = builder.build_int_compare(IntPredicate::SLT, value, value.get_type().const_zero(), "");
+                            let negated = builder.build_int_neg(value, "");
+                            Ok(ExpressionValue::RValue(builder.build_select(is_negative, negated, value, "")))
+                        } else {
+                            // unsigned values are their own magnitude
+                            Ok(ExpressionValue::RValue(value))
+                        }
+                    } else {
+                        Err(Diagnostic::codegen_error("ABS expects exactly one parameter", location))
+                    }
+                }
+            }
+        ),
+        (
+            "ROUND",
+            BuiltIn {
+                decl: "FUNCTION ROUND<U: ANY_REAL> : U
+                VAR_INPUT
+                    in : U;
+                END_VAR
+                END_FUNCTION",
+                annotation: None,
+                validation: None,
+                generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[("llvm.round", Overload::Float)],
+                code: |generator, params, location| {
+                    if let [param] = params {
+                        let value = generator.generate_expression(param)?.into_float_value();
+                        generate_float_intrinsic_call(generator, "llvm.round", value, location)
+                            .map(|it| ExpressionValue::RValue(it.as_basic_value_enum()))
+                    } else {
+                        Err(Diagnostic::codegen_error("ROUND expects exactly one parameter", location))
+                    }
+                }
+            }
+        ),
+        (
+            "TRUNC",
+            BuiltIn {
+                decl: "FUNCTION TRUNC<U: ANY_REAL> : LINT
+                VAR_INPUT
+                    in : U;
+                END_VAR
+                END_FUNCTION",
+                annotation: None,
+                validation: None,
+                generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[("llvm.trunc", Overload::Float)],
+                code: |generator, params, location| {
+                    if let [param] = params {
+                        let value = generator.generate_expression(param)?.into_float_value();
+                        let truncated = generate_float_intrinsic_call(generator, "llvm.trunc", value, location)?;
+                        let result = generator.llvm.builder.build_float_to_signed_int(truncated, generator.llvm.context.i64_type(), "");
+                        Ok(ExpressionValue::RValue(result.as_basic_value_enum()))
+                    } else {
+                        Err(Diagnostic::codegen_error("TRUNC expects exactly one parameter", location))
+                    }
+                }
+            }
+        ),
+        (
+            "SHL",
+            BuiltIn {
+                decl: "FUNCTION SHL<U: ANY_BIT> : U
+                VAR_INPUT
+                    IN : U;
+                    n : UDINT;
+                END_VAR
+                END_FUNCTION",
+                annotation: None,
+                validation: Some(|validator, _, parameters, annotations, index| {
+                    validate_shift_amount(validator, parameters, annotations, index)
+                }),
+                generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[],
+                code: |generator, params, location| {
+                    generate_shift(generator, params, true, location)
+                }
+            }
+        ),
+        (
+            "SHR",
+            BuiltIn {
+                decl: "FUNCTION SHR<U: ANY_BIT> : U
+                VAR_INPUT
+                    IN : U;
+                    n : UDINT;
+                END_VAR
+                END_FUNCTION",
+                annotation: None,
+                validation: Some(|validator, _, parameters, annotations, index| {
+                    validate_shift_amount(validator, parameters, annotations, index)
+                }),
+                generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[],
+                code: |generator, params, location| {
+                    generate_shift(generator, params, false, location)
+                }
+            }
+        ),
+        (
+            "ROL",
+            BuiltIn {
+                decl: "FUNCTION ROL<U: ANY_BIT> : U
+                VAR_INPUT
+                    IN : U;
+                    n : UDINT;
+                END_VAR
+                END_FUNCTION",
+                annotation: None,
+                validation: Some(|validator, _, parameters, annotations, index| {
+                    validate_shift_amount(validator, parameters, annotations, index)
+                }),
+                generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[("llvm.fshl", Overload::Bit)],
+                code: |generator, params, location| {
+                    generate_rotation(generator, params, "llvm.fshl", location)
+                }
+            }
+        ),
+        (
+            "ROR",
+            BuiltIn {
+                decl: "FUNCTION ROR<U: ANY_BIT> : U
+                VAR_INPUT
+                    IN : U;
+                    n : UDINT;
+                END_VAR
+                END_FUNCTION",
+                annotation: None,
+                validation: Some(|validator, _, parameters, annotations, index| {
+                    validate_shift_amount(validator, parameters, annotations, index)
+                }),
+                generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[("llvm.fshr", Overload::Bit)],
+                code: |generator, params, location| {
+                    generate_rotation(generator, params, "llvm.fshr", location)
+                }
+            }
+        ),
+        (
+            "CONCAT",
+            BuiltIn {
+                decl: "FUNCTION CONCAT<U: ANY_STRING> : U
+                VAR_INPUT {ref}
+                    args : {sized} U...;
+                END_VAR
+                END_FUNCTION",
+                annotation: Some(|annotator, _, operator, parameters, _| {
+                    annotate_concat(annotator, operator, parameters)
+                }),
+                validation: Some(|validator, _, parameters, annotations, index| {
+                    validate_concat(validator, parameters, annotations, index)
+                }),
+                generic_name_resolver: no_generic_name_resolver,
+                intrinsics: &[],
+                code: |generator, params, location| {
+                    generate_concat(generator, params, location)
+                }
+            }
+        ),
     ]);
 }
 
+/// `CONCAT` results in a string of its parameters' encoding which is long enough to hold all of them,
+/// characters are promoted to strings of length 1 and keep their type, all other parameters are
+/// expected to be strings of that encoding
+fn annotate_concat(annotator: &mut TypeAnnotator, operator: &AstNode, parameters: Option<&AstNode>) {
+    let params = parameters.map(flatten_expression_list).unwrap_or_default();
+    let encoding = get_concat_encoding(&params, &annotator.annotation_map, annotator.index);
+    let length = get_concat_length(&params, &annotator.annotation_map, annotator.index);
+    let result_type = register_string_type(
+        &mut annotator.annotation_map.new_index,
+        encoding == StringEncoding::Utf16,
+        length,
+    );
+    annotator.annotate(
+        operator,
+        StatementAnnotation::Function {
+            return_type: result_type,
+            qualified_name: "CONCAT".to_string(),
+            call_name: None,
+        },
+    );
+    for param in params {
+        if !is_character(param, &annotator.annotation_map, annotator.index) {
+            annotator
+                .annotation_map
+                .annotate_type_hint(param, StatementAnnotation::value(encoding.get_string_type_name()));
+            annotator.annotation_map.add_generic_nature(param, TypeNature::String);
+        }
+    }
+}
+
+/// the length of the string resulting from `CONCAT`, the sum of its parameters' lengths
+fn get_concat_length(params: &[&AstNode], annotations: &dyn AnnotationMap, index: &Index) -> usize {
+    params
+        .iter()
+        .map(|it| {
+            let data_type = annotations.get_type_or_void(it, index);
+            match index.find_intrinsic_type(data_type.get_type_information()) {
+                // the last character of a string is reserved for the terminator
+                DataTypeInformation::String { size, .. } => size
+                    .as_int_value(index)
+                    .map(|it| (it - 1).max(0) as usize)
+                    .unwrap_or(DEFAULT_STRING_LEN as usize),
+                it if it.is_character() => 1,
+                _ => 0,
+            }
+        })
+        .sum()
+}
+
+/// the encoding of the string resulting from `CONCAT` is the one of its first string parameter,
+/// or the one of its first character if there are only characters
+fn get_concat_encoding(
+    params: &[&AstNode],
+    annotations: &dyn AnnotationMap,
+    index: &Index,
+) -> StringEncoding {
+    let encodings = params
+        .iter()
+        .filter_map(|it| annotations.get_type(it, index))
+        .map(|it| index.find_intrinsic_type(it.get_type_information()))
+        .collect::<Vec<_>>();
+    encodings
+        .iter()
+        .find_map(|it| match it {
+            DataTypeInformation::String { encoding, .. } => Some(*encoding),
+            _ => None,
+        })
+        .or_else(|| encodings.iter().find_map(|it| it.get_promoted_string_encoding()))
+        .unwrap_or(StringEncoding::Utf8)
+}
+
+fn is_character(param: &AstNode, annotations: &dyn AnnotationMap, index: &Index) -> bool {
+    index
+        .find_intrinsic_type(annotations.get_type_or_void(param, index).get_type_information())
+        .is_character()
+}
+
+/// reports characters passed to `CONCAT` which cannot be promoted to the resulting string (e.g. a `WCHAR`
+/// concatenated with a `STRING`)
+fn validate_concat(
+    validator: &mut Validator,
+    parameters: Option<&AstNode>,
+    annotations: &dyn AnnotationMap,
+    index: &Index,
+) {
+    let Some(parameters) = parameters else { return };
+    let params = flatten_expression_list(parameters);
+    let encoding = get_concat_encoding(&params, annotations, index);
+    let Some(result_type) = index.find_effective_type_by_name(encoding.get_string_type_name()) else {
+        return;
+    };
+    for param in params.iter().filter(|it| is_character(it, annotations, index)) {
+        let character_type = annotations.get_type_or_void(param, index);
+        if index.find_intrinsic_type(character_type.get_type_information()).get_promoted_string_encoding()
+            != Some(encoding)
+        {
+            validator.push_diagnostic(Diagnostic::incompatible_char_promotion(
+                character_type.get_name(),
+                result_type.get_name(),
+                param.get_location(),
+            ));
+        }
+    }
+}
+
+/// generates a loop per parameter copying its characters into the result until the parameter's
+/// terminator is reached, the result is large enough to hold all parameters
+fn generate_concat<'ink>(
+    generator: &ExpressionCodeGenerator<'ink, '_>,
+    params: &[&AstNode],
+    location: SourceLocation,
+) -> Result<ExpressionValue<'ink>, Diagnostic> {
+    let Some(first) = params.first() else {
+        return Err(Diagnostic::codegen_error("CONCAT expects at least one parameter", location));
+    };
+    let llvm = generator.llvm;
+    let context = llvm.context;
+    let builder = &llvm.builder;
+    let function = generator.get_function_context(first)?.function;
+
+    let encoding = get_concat_encoding(params, generator.annotations, generator.index);
+    let length = get_concat_length(params, generator.annotations, generator.index);
+    let result_type = generator
+        .llvm_index
+        .get_associated_type(&get_string_type_name_for_length(encoding == StringEncoding::Utf16, length))?;
+    let BasicTypeEnum::ArrayType(array_type) = result_type else {
+        return Err(Diagnostic::codegen_error("CONCAT expects STRING or WSTRING parameters", location));
+    };
+    // STRINGs are stored as i8, WSTRINGs as i16 characters
+    let char_type = array_type.get_element_type().into_int_type();
+    let char_ptr_type = char_type.ptr_type(AddressSpace::default());
+    let i32_type = context.i32_type();
+    // the last character is reserved for the terminator
+    let capacity = i32_type.const_int(array_type.len().saturating_sub(1) as u64, false);
+
+    let result = llvm.create_local_variable("", &result_type);
+    let result_chars = builder.build_pointer_cast(result, char_ptr_type, "");
+    let position = llvm.create_local_variable("", &i32_type.as_basic_type_enum());
+    builder.build_store(position, i32_type.const_zero());
+    let source_index = llvm.create_local_variable("", &i32_type.as_basic_type_enum());
+
+    for param in params {
+        let source = if is_character(param, generator.annotations, generator.index) {
+            // characters are copied from a string of length 1
+            let character_type = generator.llvm_index.get_associated_type(
+                generator.annotations.get_type_or_void(param, generator.index).get_name(),
+            )?;
+            let promoted_type = character_type.into_int_type().array_type(2).as_basic_type_enum();
+            let promoted = llvm.create_local_variable("", &promoted_type);
+            generator.generate_promoted_character(promoted, param)?
+        } else {
+            match generator.generate_expression_value(param)? {
+                ExpressionValue::LValue(ptr) => ptr,
+                ExpressionValue::RValue(value) if value.is_pointer_value() => value.into_pointer_value(),
+                ExpressionValue::RValue(value) => {
+                    let temp = llvm.create_local_variable("", &value.get_type());
+                    builder.build_store(temp, value);
+                    temp
+                }
+            }
+        };
+        let source = builder.build_pointer_cast(source, char_ptr_type, "");
+        builder.build_store(source_index, i32_type.const_zero());
+
+        let condition_block = context.append_basic_block(function, "concat_condition");
+        let body_block = context.append_basic_block(function, "concat_body");
+        let continue_block = context.append_basic_block(function, "concat_continue");
+        builder.build_unconditional_branch(condition_block);
+
+        // copy while the source is not terminated and the result is not full
+        builder.position_at_end(condition_block);
+        let index = builder.build_load(source_index, "").into_int_value();
+        let current_position = builder.build_load(position, "").into_int_value();
+        let char = builder.build_load(unsafe { builder.build_in_bounds_gep(source, &[index], "") }, "");
+        let is_terminated =
+            builder.build_int_compare(IntPredicate::EQ, char.into_int_value(), char_type.const_zero(), "");
+        let is_full = builder.build_int_compare(IntPredicate::UGE, current_position, capacity, "");
+        let is_done = builder.build_or(is_terminated, is_full, "");
+        builder.build_conditional_branch(is_done, continue_block, body_block);
+
+        builder.position_at_end(body_block);
+        builder
+            .build_store(unsafe { builder.build_in_bounds_gep(result_chars, &[current_position], "") }, char);
+        builder.build_store(source_index, builder.build_int_add(index, i32_type.const_int(1, false), ""));
+        builder
+            .build_store(position, builder.build_int_add(current_position, i32_type.const_int(1, false), ""));
+        builder.build_unconditional_branch(condition_block);
+
+        builder.position_at_end(continue_block);
+    }
+
+    let end = builder.build_load(position, "").into_int_value();
+    builder.build_store(
+        unsafe { builder.build_in_bounds_gep(result_chars, &[end], "") },
+        char_type.const_zero(),
+    );
+    Ok(ExpressionValue::LValue(result))
+}
+
+/// generates a constant `STRING` holding the display name of the parameter's type, the parameter
+/// itself is not evaluated
+fn generate_type_name<'ink>(
+    generator: &ExpressionCodeGenerator<'ink, '_>,
+    params: &[&AstNode],
+    location: SourceLocation,
+) -> Result<ExpressionValue<'ink>, Diagnostic> {
+    let [param] = params else {
+        return Err(Diagnostic::codegen_error("Expected exactly one parameter for TYPE_NAME", location));
+    };
+    let type_name = generator
+        .annotations
+        .get_type(param, generator.index)
+        .map(|it| it.get_display_name(generator.index))
+        .ok_or_else(|| {
+            Diagnostic::codegen_error("Could not resolve the type of the parameter", location.clone())
+        })?;
+    let result_type = generator.llvm_index.get_associated_type(STRING_TYPE)?;
+    let BasicTypeEnum::ArrayType(array_type) = result_type else {
+        return Err(Diagnostic::codegen_error("Could not find associated data type: STRING", location));
+    };
+    let llvm = generator.llvm;
+    let result = llvm.create_local_variable("", &result_type);
+    llvm.builder.build_store(result, llvm.create_const_utf8_string(&type_name, array_type.len() as usize)?);
+    Ok(ExpressionValue::LValue(result))
+}
+
+/// warns about literal shift amounts that are not smaller than the width of the shifted operand
+fn validate_shift_amount(
+    validator: &mut Validator,
+    parameters: Option<&AstNode>,
+    annotations: &dyn AnnotationMap,
+    index: &Index,
+) {
+    let Some(parameters) = parameters else { return };
+    let params = flatten_expression_list(parameters);
+    let [value, amount] = params.as_slice() else { return };
+    let AstStatement::Literal(AstLiteral::Integer(amount_value)) = amount.get_stmt() else { return };
+    let value_type = annotations.get_type_or_void(value, index);
+    let width = value_type.get_type_information().get_semantic_size(index);
+    if *amount_value >= width as i128 {
+        validator.push_diagnostic(Diagnostic::shift_amount_out_of_range(
+            *amount_value,
+            value_type.get_name(),
+            width,
+            amount.get_location(),
+        ));
+    }
+}
+
+/// generates the operand and the shift amount of a shift or rotation,
+/// the amount is returned both as given and converted to the type of the operand
+fn generate_shift_operands<'ink>(
+    generator: &ExpressionCodeGenerator<'ink, '_>,
+    params: &[&AstNode],
+    location: SourceLocation,
+) -> Result<(IntValue<'ink>, IntValue<'ink>, IntValue<'ink>), Diagnostic> {
+    let [value, amount] = params else {
+        return Err(Diagnostic::codegen_error("Bit shifts expect exactly two parameters", location));
+    };
+    let is_bool =
+        generator.annotations.get_type_or_void(value, generator.index).get_type_information().is_bool();
+    let value = generator.generate_expression(value)?.into_int_value();
+    // a BOOL is shifted as a single bit, not as the byte it is stored in
+    let value = if is_bool && value.get_type().get_bit_width() != 1 {
+        generator.llvm.builder.build_int_compare(IntPredicate::NE, value, value.get_type().const_zero(), "")
+    } else {
+        value
+    };
+    let amount = generator.generate_expression(amount)?.into_int_value();
+    let converted = generator.llvm.builder.build_int_cast_sign_flag(amount, value.get_type(), false, "");
+    Ok((value, amount, converted))
+}
+
+/// generates a `shl` or `lshr`, shifting by the width of the operand or more results in 0
+fn generate_shift<'ink>(
+    generator: &ExpressionCodeGenerator<'ink, '_>,
+    params: &[&AstNode],
+    is_left: bool,
+    location: SourceLocation,
+) -> Result<ExpressionValue<'ink>, Diagnostic> {
+    let (value, amount, converted) = generate_shift_operands(generator, params, location)?;
+    let builder = &generator.llvm.builder;
+    let value_type = value.get_type();
+    let shifted = if is_left {
+        builder.build_left_shift(value, converted, "")
+    } else {
+        builder.build_right_shift(value, converted, false, "")
+    };
+    // llvm shifts by the width of the operand or more are poison
+    let width = amount.get_type().const_int(value_type.get_bit_width() as u64, false);
+    let in_range = builder.build_int_compare(IntPredicate::ULT, amount, width, "");
+    Ok(ExpressionValue::RValue(builder.build_select(in_range, shifted, value_type.const_zero(), "")))
+}
+
+/// generates a rotation as a funnel shift of the operand with itself
+fn generate_rotation<'ink>(
+    generator: &ExpressionCodeGenerator<'ink, '_>,
+    params: &[&AstNode],
+    intrinsic: &str,
+    location: SourceLocation,
+) -> Result<ExpressionValue<'ink>, Diagnostic> {
+    let (value, _, converted) = generate_shift_operands(generator, params, location.clone())?;
+    let args = [value.as_basic_value_enum(), value.as_basic_value_enum(), converted.as_basic_value_enum()];
+    generate_intrinsic_call(generator, intrinsic, &args, location).map(ExpressionValue::RValue)
+}
+
 fn annotate_variable_length_array_bound_function(
     annotator: &mut TypeAnnotator,
     parameters: Option<&AstNode>,
@@ -497,7 +1069,8 @@ fn generate_variable_length_array_bound_function<'ink>(
     Ok(ExpressionValue::RValue(bound))
 }
 
-type AnnotationFunction = fn(&mut TypeAnnotator, &AstNode, Option<&AstNode>, VisitorContext);
+/// annotates a call of the builtin, the arguments are the call statement, its operator and its parameters
+type AnnotationFunction = fn(&mut TypeAnnotator, &AstNode, &AstNode, Option<&AstNode>, VisitorContext);
 type GenericNameResolver = fn(&str, &[GenericBinding], &HashMap<String, GenericType>) -> String;
 type CodegenFunction = for<'ink, 'b> fn(
     &'b ExpressionCodeGenerator<'ink, 'b>,
@@ -506,11 +1079,22 @@ type CodegenFunction = for<'ink, 'b> fn(
 ) -> Result<ExpressionValue<'ink>, Diagnostic>;
 type ValidationFunction = fn(&mut Validator, &AstNode, Option<&AstNode>, &dyn AnnotationMap, &Index);
 
+/// the llvm types an intrinsic used by a builtin is overloaded for
+#[derive(Clone, Copy)]
+enum Overload {
+    /// `REAL` and `LREAL`
+    Float,
+    /// the integer types backing `ANY_BIT`, including the single bit of a `BOOL`
+    Bit,
+}
+
 pub struct BuiltIn {
     decl: &'static str,
     annotation: Option<AnnotationFunction>,
     validation: Option<ValidationFunction>,
     generic_name_resolver: GenericNameResolver,
+    /// llvm intrinsics the generated code calls
+    intrinsics: &'static [(&'static str, Overload)],
     code: CodegenFunction,
 }
 
@@ -534,10 +1118,123 @@ impl BuiltIn {
     pub(crate) fn get_validation(&self) -> Option<ValidationFunction> {
         self.validation
     }
+
+    /// declares the intrinsics used by this builtin in the given module
+    pub(crate) fn generate_intrinsic_declarations<'ink>(
+        &self,
+        module: &Module<'ink>,
+    ) -> Result<Vec<FunctionValue<'ink>>, Diagnostic> {
+        let context = module.get_context();
+        let float_types = [context.f32_type().as_basic_type_enum(), context.f64_type().as_basic_type_enum()];
+        let bit_types = [
+            context.bool_type(),
+            context.i8_type(),
+            context.i16_type(),
+            context.i32_type(),
+            context.i64_type(),
+        ]
+        .map(|it| it.as_basic_type_enum());
+        self.intrinsics
+            .iter()
+            .flat_map(|(name, overload)| {
+                let types = match overload {
+                    Overload::Float => float_types.as_slice(),
+                    Overload::Bit => bit_types.as_slice(),
+                };
+                types.iter().map(move |it| (name, it))
+            })
+            .map(|(name, overload_type)| {
+                Intrinsic::find(name).and_then(|it| it.get_declaration(module, &[*overload_type])).ok_or_else(
+                    || {
+                        Diagnostic::codegen_error(
+                            &format!("Cannot declare intrinsic {name}"),
+                            SourceLocation::undefined(),
+                        )
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+/// calls the `REAL` or `LREAL` overload of the given intrinsic, depending on the type of `value`
+fn generate_float_intrinsic_call<'ink>(
+    generator: &ExpressionCodeGenerator<'ink, '_>,
+    intrinsic: &str,
+    value: FloatValue<'ink>,
+    location: SourceLocation,
+) -> Result<FloatValue<'ink>, Diagnostic> {
+    generate_intrinsic_call(generator, intrinsic, &[value.as_basic_value_enum()], location)
+        .map(BasicValueEnum::into_float_value)
+}
+
+/// calls the overload of the given intrinsic matching the type of the first argument
+fn generate_intrinsic_call<'ink>(
+    generator: &ExpressionCodeGenerator<'ink, '_>,
+    intrinsic: &str,
+    args: &[BasicValueEnum<'ink>],
+    location: SourceLocation,
+) -> Result<BasicValueEnum<'ink>, Diagnostic> {
+    let overload = match args.first().map(BasicValueEnum::get_type) {
+        Some(BasicTypeEnum::IntType(it)) => format!("i{}", it.get_bit_width()),
+        Some(it) if it == generator.llvm.context.f32_type().as_basic_type_enum() => "f32".to_string(),
+        _ => "f64".to_string(),
+    };
+    let function =
+        generator.llvm_index.find_associated_implementation(&format!("{intrinsic}.{overload}")).ok_or_else(
+            || Diagnostic::codegen_error(&format!("Cannot find intrinsic {intrinsic}"), location.clone()),
+        )?;
+    let args = args.iter().map(|it| (*it).into()).collect::<Vec<_>>();
+    generator.llvm.builder.build_call(function, &args, "").try_as_basic_value().left().ok_or_else(|| {
+        Diagnostic::codegen_error(&format!("Intrinsic {intrinsic} returned no value"), location)
+    })
+}
+
+/// The standard timer function blocks, their timing is implemented by the runtime
+const TIMERS: [&str; 3] = ["TP", "TON", "TOF"];
+
+/// Returns the declaration of the given timer, the members after its interface hold the runtime's state
+fn get_timer_declaration(name: &str) -> String {
+    format!(
+        "{{external}}
+        FUNCTION_BLOCK {name}
+        VAR_INPUT
+            IN : BOOL;
+            PT : TIME;
+        END_VAR
+        VAR_OUTPUT
+            Q : BOOL;
+            ET : TIME;
+        END_VAR
+        VAR
+            __signal__ : BOOL;
+            __BUFFER__ : ARRAY[1..24] OF BYTE;
+        END_VAR
+        END_FUNCTION_BLOCK"
+    )
 }
 
 pub fn parse_built_ins(id_provider: IdProvider) -> CompilationUnit {
-    let src = BUILTIN.iter().map(|(_, it)| it.decl).collect::<Vec<&str>>().join(" ");
+    parse_declarations(|_| true, id_provider)
+}
+
+/// Parses the builtins which are not shadowed by a POU of the given index, a POU declared by the
+/// user (e.g. its own `CONCAT` or `TON`) replaces the builtin of the same name
+pub fn parse_unshadowed_built_ins(id_provider: IdProvider, index: &Index) -> CompilationUnit {
+    parse_declarations(|name| index.find_pou(name).is_none(), id_provider)
+}
+
+/// Parses the declarations of the builtins and timers whose name matches the given filter
+fn parse_declarations(filter: impl Fn(&str) -> bool, id_provider: IdProvider) -> CompilationUnit {
+    //the builtins are sorted by name, so they get the same ids on every run
+    let mut builtins = BUILTIN.iter().filter(|(name, _)| filter(name)).collect::<Vec<_>>();
+    builtins.sort_unstable_by_key(|(name, _)| *name);
+    let src = builtins
+        .into_iter()
+        .map(|(_, it)| it.decl.to_string())
+        .chain(TIMERS.into_iter().filter(|name| filter(name)).map(get_timer_declaration))
+        .collect::<Vec<_>>()
+        .join(" ");
     let mut unit = parser::parse(
         lexer::lex_with_ids(&src, id_provider.clone(), SourceLocationFactory::internal(&src)),
         LinkageType::BuiltIn,
@@ -1,6 +1,7 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 use std::{
     cell::RefCell,
+    collections::{HashMap, HashSet},
     ops::Deref,
     path::{Path, PathBuf},
 };
@@ -13,6 +14,7 @@ use self::{
         llvm::{GlobalValueExt, Llvm},
         pou_generator::{self, PouGenerator},
         variable_generator::VariableGenerator,
+        ADDRESS_SPACE_GENERIC,
     },
     llvm_index::LlvmTypedIndex,
 };
@@ -29,23 +31,49 @@ use inkwell::{
     execution_engine::{ExecutionEngine, JitFunction},
     memory_buffer::MemoryBuffer,
     types::BasicType,
+    AddressSpace,
 };
 use inkwell::{
-    module::Module,
-    passes::PassBuilderOptions,
-    targets::{CodeModel, FileType, InitializationConfig, RelocMode},
+    module::{Linkage, Module},
+    passes::{PassBuilderOptions, PassManager, PassManagerBuilder},
+    support::LLVMString,
+    targets::{CodeModel, FileType, InitializationConfig, RelocMode, TargetMachine},
 };
+use itertools::Itertools;
 use plc_ast::ast::{CompilationUnit, LinkageType};
 use plc_diagnostics::diagnostics::Diagnostic;
 use plc_source::source_location::SourceLocation;
 
 mod debug;
 pub(crate) mod generators;
+pub mod layout;
 mod llvm_index;
 mod llvm_typesystem;
 #[cfg(test)]
 mod tests;
 
+/// the major version of the LLVM the compiler is built against
+const LLVM_MAJOR_VERSION: u32 = 14;
+
+/// Returns the `llc` on the path, which splits the debug information of generated objects and knows the
+/// cpus and features of the targets. Its bitcode reader only accepts modules of its own LLVM version, so
+/// other versions are rejected
+pub fn find_llc() -> Result<PathBuf, String> {
+    let llc = which::which("llc").map_err(|it| format!("{it} for llc"))?;
+    let output = std::process::Command::new(&llc).arg("--version").output().map_err(|it| it.to_string())?;
+    let output = String::from_utf8_lossy(&output.stdout);
+    // distributions may prefix the version, e.g. `Debian LLVM version 14.0.6`
+    let version = output
+        .lines()
+        .find_map(|it| it.find("LLVM version").map(|start| it[start..].trim()))
+        .unwrap_or("an unknown LLVM version");
+    if version.starts_with(&format!("LLVM version {LLVM_MAJOR_VERSION}.")) {
+        Ok(llc)
+    } else {
+        Err(format!("llc of LLVM {LLVM_MAJOR_VERSION} is required, found {version}"))
+    }
+}
+
 /// A wrapper around the LLVM context to allow passing it without exposing the inkwell dependencies
 pub struct CodegenContext(Context);
 
@@ -72,11 +100,148 @@ pub struct CodeGen<'ink> {
     pub debug: DebugBuilderEnum<'ink>,
 
     pub module_location: String,
+
+    /// whether assignments to sub-range variables call the matching `CheckRange*` function
+    range_checks: bool,
+
+    /// whether the debug information is emitted into a `.dwo` file next to the object
+    split_debug: bool,
 }
 
 pub struct GeneratedModule<'ink> {
     module: Module<'ink>,
     engine: RefCell<Option<ExecutionEngine<'ink>>>,
+    symbol_map: HashMap<String, SymbolLocation>,
+    /// overrides the inliner's threshold of the optimization level
+    inline_threshold: Option<u32>,
+    /// moves the debug information of generated objects into a `.dwo` file next to them
+    split_debug: bool,
+}
+
+/// The cpu and the features the generated machine code is tuned for,
+/// LLVM's generic cpu and default features are used if not specified
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TargetTuning {
+    /// the cpu name, e.g. `cortex-m4`
+    pub cpu: Option<String>,
+    /// a comma separated list of features, e.g. `+vfp4,-neon`
+    pub features: Option<String>,
+}
+
+impl TargetTuning {
+    /// reports the cpu and features LLVM ignores for the given target: features which are neither
+    /// enabled (`+`) nor disabled (`-`) and, if `llc` is available, names unknown to the target
+    pub fn validate(&self, target: &Target) -> Vec<Diagnostic> {
+        let features = self
+            .features
+            .iter()
+            .flat_map(|it| it.split(','))
+            .map(str::trim)
+            .filter(|it| !it.is_empty())
+            .collect::<Vec<_>>();
+        let mut diagnostics = features
+            .iter()
+            .filter(|it| !it.starts_with(['+', '-']))
+            .map(|it| Diagnostic::invalid_target_feature(it))
+            .collect::<Vec<_>>();
+
+        if self.cpu.is_none() && features.is_empty() {
+            return diagnostics;
+        }
+        let Some(tables) = TargetTables::read(target) else { return diagnostics };
+        let triple = target.get_target_triple();
+        let triple = triple.as_str().to_string_lossy();
+        if let Some(cpu) = self.cpu.as_deref().filter(|it| !tables.cpus.contains(*it)) {
+            diagnostics.push(Diagnostic::unknown_target_cpu(cpu, &triple));
+        }
+        diagnostics.extend(
+            features
+                .iter()
+                .filter_map(|it| it.strip_prefix(['+', '-']))
+                .filter(|it| !tables.features.contains(*it))
+                .map(|it| Diagnostic::unknown_target_feature(it, &triple)),
+        );
+        diagnostics
+    }
+}
+
+/// The names of the cpus and features LLVM knows for a target
+struct TargetTables {
+    cpus: HashSet<String>,
+    features: HashSet<String>,
+}
+
+impl TargetTables {
+    /// Reads the tables from the help of `llc` (see [`find_llc`]), LLVM's C API does not expose them.
+    /// Returns `None` if `llc` is not available
+    fn read(target: &Target) -> Option<TargetTables> {
+        let llc = find_llc().ok()?;
+        let output = std::process::Command::new(llc)
+            .arg(format!("-mtriple={}", target.get_target_triple().as_str().to_string_lossy()))
+            .args(["-mcpu=help", "-o", "-"])
+            .stdin(std::process::Stdio::null())
+            .output()
+            .ok()?;
+        // the help is printed to stderr, e.g. `  cortex-m4     - Select the cortex-m4 processor.`
+        let help = String::from_utf8_lossy(&output.stderr);
+        let mut tables = TargetTables { cpus: HashSet::new(), features: HashSet::new() };
+        let mut table = None;
+        for line in help.lines() {
+            if line.starts_with("Available CPUs") {
+                table = Some(&mut tables.cpus);
+            } else if line.starts_with("Available features") {
+                table = Some(&mut tables.features);
+            } else if let (Some(table), Some(name)) = (table.as_mut(), line.strip_prefix("  ")) {
+                table.extend(name.split_whitespace().next().map(str::to_string));
+            }
+        }
+        Some(tables)
+    }
+}
+
+/// Writes a file through `write` into a temporary file next to `output` and renames it into place once
+/// it is complete, so an interrupted compilation never leaves a truncated file a later build would use
+pub(crate) fn persist_atomically(
+    output: &Path,
+    write: impl FnOnce(&Path) -> Result<(), Diagnostic>,
+) -> Result<(), Diagnostic> {
+    let file_name = output.file_name().map(|it| it.to_string_lossy()).unwrap_or_default();
+    let temp = output.with_file_name(format!(".{file_name}.{}.tmp", std::process::id()));
+    let result = write(&temp).and_then(|_| {
+        std::fs::rename(&temp, output).map_err(|err| {
+            Diagnostic::io_write_error(output.to_str().unwrap_or_default(), err.to_string().as_str())
+        })
+    });
+    if result.is_err() {
+        // the temporary file may not exist if writing failed early
+        let _ = std::fs::remove_file(&temp);
+    }
+    result
+}
+
+/// Verifies that LLVM knows every given target, all unknown triples are reported in a single diagnostic
+pub fn validate_targets(targets: &[Target]) -> Result<(), Diagnostic> {
+    inkwell::targets::Target::initialize_all(&InitializationConfig::default());
+    let unknown = targets
+        .iter()
+        .filter(|it| inkwell::targets::Target::from_triple(&it.get_target_triple()).is_err())
+        .map(|it| it.try_get_name().unwrap_or_default())
+        .unique()
+        .collect::<Vec<_>>();
+    if unknown.is_empty() {
+        Ok(())
+    } else {
+        Err(Diagnostic::unknown_targets(&unknown))
+    }
+}
+
+/// The declaration a symbol of a generated module originates from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolLocation {
+    /// the name of the declaring POU or variable
+    pub name: String,
+    /// the location of the declaration
+    pub location: SourceLocation,
 }
 
 type MainFunction<T, U> = unsafe extern "C" fn(*mut T) -> U;
@@ -90,11 +255,31 @@ impl<'ink> CodeGen<'ink> {
         module_location: &str,
         optimization_level: OptimizationLevel,
         debug_level: DebugLevel,
+        split_debug: bool,
     ) -> CodeGen<'ink> {
         let module = context.create_module(module_location);
         module.set_source_file_name(module_location);
-        let debug = debug::DebugBuilderEnum::new(context, &module, root, optimization_level, debug_level);
-        CodeGen { module, debug, module_location: module_location.to_string() }
+        let debug = debug::DebugBuilderEnum::new(
+            context,
+            &module,
+            root,
+            optimization_level,
+            debug_level,
+            split_debug,
+        );
+        CodeGen {
+            module,
+            debug,
+            module_location: module_location.to_string(),
+            range_checks: true,
+            split_debug: split_debug && debug_level != DebugLevel::None,
+        }
+    }
+
+    /// lets assignments to a sub-range variable call the `CheckRange*` function matching its type with the
+    /// declared bounds, e.g. `x := CheckRangeSigned(y, 0, 100)`, if such a function is declared (default)
+    pub fn with_range_checks(self, range_checks: bool) -> Self {
+        CodeGen { range_checks, ..self }
     }
 
     pub fn generate_llvm_index(
@@ -197,7 +382,8 @@ impl<'ink> CodeGen<'ink> {
     ) -> Result<GeneratedModule<'ink>, Diagnostic> {
         //generate all pous
         let llvm = Llvm::new(context, context.create_builder());
-        let pou_generator = PouGenerator::new(llvm, global_index, annotations, llvm_index);
+        let pou_generator = PouGenerator::new(llvm, global_index, annotations, llvm_index)
+            .with_range_checks(self.range_checks);
 
         //Generate the POU stubs in the first go to make sure they can be referenced.
         for implementation in &unit.implementations {
@@ -224,14 +410,50 @@ impl<'ink> CodeGen<'ink> {
         }
 
         #[cfg(not(feature = "verify"))]
-        Ok(GeneratedModule { module: self.module, engine: RefCell::new(None) })
+        {
+            let symbol_map = self.collect_symbol_map(global_index);
+            Ok(GeneratedModule {
+                module: self.module,
+                engine: RefCell::new(None),
+                symbol_map,
+                inline_threshold: None,
+                split_debug: self.split_debug,
+            })
+        }
+    }
+
+    /// associates every function and global defined in the module with its declaration
+    fn collect_symbol_map(&self, index: &Index) -> HashMap<String, SymbolLocation> {
+        let functions = index.get_implementations().values().filter_map(|it| {
+            let function = self.module.get_function(it.get_symbol_name())?;
+            let location = index.find_pou(it.get_call_name()).map(|it| it.get_location());
+            (function.count_basic_blocks() > 0)
+                .then(|| (it.get_symbol_name(), it.get_call_name(), location.unwrap_or(it.get_location())))
+        });
+        let globals = index.get_globals().values().chain(index.get_program_instances()).filter_map(|it| {
+            let global = self.module.get_global(it.get_name())?;
+            (!global.is_declaration()).then(|| (it.get_name(), it.get_qualified_name(), &it.source_location))
+        });
+
+        functions
+            .chain(globals)
+            .map(|(symbol, name, location)| {
+                (symbol.to_string(), SymbolLocation { name: name.to_string(), location: location.clone() })
+            })
+            .collect()
     }
 }
 
 impl<'ink> GeneratedModule<'ink> {
     pub fn try_from_bitcode(context: &'ink CodegenContext, path: &Path) -> Result<Self, Diagnostic> {
         let module = Module::parse_bitcode_from_path(path, context.deref())?;
-        Ok(GeneratedModule { module, engine: RefCell::new(None) })
+        Ok(GeneratedModule {
+            module,
+            engine: RefCell::new(None),
+            symbol_map: HashMap::new(),
+            inline_threshold: None,
+            split_debug: false,
+        })
     }
 
     pub fn try_from_ir(context: &'ink CodegenContext, path: &Path) -> Result<Self, Diagnostic> {
@@ -240,16 +462,157 @@ impl<'ink> GeneratedModule<'ink> {
 
         log::debug!("{}", module.to_string());
 
-        Ok(GeneratedModule { module, engine: RefCell::new(None) })
+        Ok(GeneratedModule {
+            module,
+            engine: RefCell::new(None),
+            symbol_map: HashMap::new(),
+            inline_threshold: None,
+            split_debug: false,
+        })
     }
 
-    pub fn merge(self, other: GeneratedModule<'ink>) -> Result<Self, Diagnostic> {
+    pub fn merge(mut self, other: GeneratedModule<'ink>) -> Result<Self, Diagnostic> {
+        if let Some(symbol) = self.find_conflicting_definition(&other) {
+            let locations = [&self.symbol_map, &other.symbol_map]
+                .iter()
+                .filter_map(|it| it.get(&symbol))
+                .map(|it| it.location.clone())
+                .collect();
+            return Err(Diagnostic::duplicate_symbol(&symbol, locations));
+        }
         self.module.link_in_module(other.module)?;
         log::debug!("Merged: {}", self.module.to_string());
+        self.symbol_map.extend(other.symbol_map);
+
+        Ok(self)
+    }
+
+    /// Creates a module that only contains the C `main` function calling the given program,
+    /// see [`GeneratedModule::with_entry_point`]
+    pub fn entry_point(
+        context: &'ink CodegenContext,
+        program: &str,
+        instance: &str,
+    ) -> Result<Self, Diagnostic> {
+        GeneratedModule {
+            module: context.create_module("__entry_point"),
+            engine: RefCell::new(None),
+            symbol_map: HashMap::new(),
+            inline_threshold: None,
+            split_debug: false,
+        }
+        .with_entry_point(program, instance)
+    }
+
+    /// Adds a C `main` function which calls the given program with its global instance once and
+    /// returns 0, so an executable can start with a `PROGRAM`.
+    /// The program and its instance are declared if they are not defined in this module.
+    pub fn with_entry_point(self, program: &str, instance: &str) -> Result<Self, Diagnostic> {
+        let context = self.module.get_context();
+        let pointer_type = context.i8_type().ptr_type(AddressSpace::from(ADDRESS_SPACE_GENERIC));
+        let function = self.module.get_function(program).unwrap_or_else(|| {
+            let function_type = context.void_type().fn_type(&[pointer_type.into()], false);
+            self.module.add_function(program, function_type, Some(Linkage::External))
+        });
+        let instance = self
+            .module
+            .get_global(instance)
+            .unwrap_or_else(|| self.module.add_global(context.i8_type(), None, instance))
+            .as_pointer_value();
+        let instance_type =
+            function.get_type().get_param_types().first().map(|it| it.into_pointer_type()).ok_or_else(
+                || {
+                    Diagnostic::codegen_error(
+                        &format!("Cannot call '{program}' from main, it does not take an instance"),
+                        SourceLocation::undefined(),
+                    )
+                },
+            )?;
 
+        let main = self.module.add_function("main", context.i32_type().fn_type(&[], false), None);
+        let builder = context.create_builder();
+        builder.position_at_end(context.append_basic_block(main, "entry"));
+        let instance = builder.build_pointer_cast(instance, instance_type, "");
+        builder.build_call(function, &[instance.into()], "");
+        builder.build_return(Some(&context.i32_type().const_zero()));
         Ok(self)
     }
 
+    /// Counts the calls of every POU defined in this module in an exported 64 bit counter named
+    /// `__profile_<symbol>`, which is incremented when the POU's function is entered. A counter that is
+    /// already declared (e.g. by an `@EXTERNAL` global reading it) is defined instead.
+    pub fn with_profile_counters(self) -> Self {
+        let context = self.module.get_context();
+        let counter_type = context.i64_type();
+        let builder = context.create_builder();
+        // compiler-generated functions (e.g. initializers) are no POUs and not counted
+        let functions = self
+            .module
+            .get_functions()
+            .filter(|it| self.symbol_map.contains_key(it.get_name().to_string_lossy().as_ref()));
+        for function in functions {
+            let Some(entry) = function.get_first_basic_block() else { continue };
+            let name = format!("__profile_{}", function.get_name().to_string_lossy());
+            let counter = self
+                .module
+                .get_global(&name)
+                .unwrap_or_else(|| self.module.add_global(counter_type, None, &name));
+            counter.set_initializer(&counter_type.const_zero());
+            counter.set_linkage(Linkage::External);
+
+            match entry.get_first_instruction() {
+                Some(instruction) => builder.position_before(&instruction),
+                None => builder.position_at_end(entry),
+            }
+            let count = builder.build_load(counter.as_pointer_value(), "count").into_int_value();
+            let count = builder.build_int_add(count, counter_type.const_int(1, false), "count");
+            builder.build_store(counter.as_pointer_value(), count);
+        }
+        self
+    }
+
+    /// Overrides the threshold up to which the optimizer inlines a called function
+    pub fn with_inline_threshold(mut self, inline_threshold: Option<u32>) -> Self {
+        self.inline_threshold = inline_threshold;
+        self
+    }
+
+    /// Returns the name of the first function or global that is defined (not only declared) in both
+    /// modules. Symbols that are not visible outside of their module are no conflict.
+    fn find_conflicting_definition(&self, other: &GeneratedModule<'ink>) -> Option<String> {
+        let is_defined_function = |module: &Module<'ink>, name: &str| {
+            module
+                .get_function(name)
+                .filter(|it| it.get_linkage() == Linkage::External)
+                .map_or(false, |it| it.count_basic_blocks() > 0)
+        };
+        let is_defined_global = |module: &Module<'ink>, name: &str| {
+            module
+                .get_global(name)
+                .filter(|it| it.get_linkage() == Linkage::External)
+                .map_or(false, |it| !it.is_declaration())
+        };
+
+        let functions =
+            other.module.get_functions().map(|it| it.get_name().to_string_lossy().to_string()).find(|name| {
+                is_defined_function(&other.module, name) && is_defined_function(&self.module, name)
+            });
+        functions.or_else(|| {
+            other
+                .module
+                .get_globals()
+                .map(|it| it.get_name().to_string_lossy().to_string())
+                .find(|name| is_defined_global(&other.module, name) && is_defined_global(&self.module, name))
+        })
+    }
+
+    /// Returns the declaration every function and global defined in this module was generated from,
+    /// keyed by the symbol's name.
+    /// Modules read from bitcode or IR files carry no such information.
+    pub fn symbol_map(&self) -> HashMap<String, SymbolLocation> {
+        self.symbol_map.clone()
+    }
+
     /// Persists the module into the disk based on output and target requirments
     /// If an object file should be generated, all optimizations will be executed on the object
     pub fn persist(
@@ -259,6 +622,7 @@ impl<'ink> GeneratedModule<'ink> {
         format: FormatOption,
         target: &Target,
         optimization_level: OptimizationLevel,
+        tuning: &TargetTuning,
     ) -> Result<PathBuf, Diagnostic> {
         let output = Self::get_output_file(output_dir, output_name, target);
         //ensure output exists
@@ -267,14 +631,15 @@ impl<'ink> GeneratedModule<'ink> {
         }
         match format {
             FormatOption::Object | FormatOption::Relocatable => {
-                self.persist_as_static_obj(output, target, optimization_level)
+                self.persist_as_static_obj(output, target, optimization_level, tuning)
             }
             FormatOption::PIC | FormatOption::Shared | FormatOption::Static => {
-                self.persist_to_shared_pic_object(output, target, optimization_level)
+                self.persist_to_shared_pic_object(output, target, optimization_level, tuning)
             }
-            FormatOption::NoPIC => self.persist_to_shared_object(output, target, optimization_level),
+            FormatOption::NoPIC => self.persist_to_shared_object(output, target, optimization_level, tuning),
             FormatOption::Bitcode => self.persist_to_bitcode(output),
             FormatOption::IR => self.persist_to_ir(output),
+            FormatOption::Assembly => self.persist_to_assembly(output, target, optimization_level, tuning),
         }
     }
 
@@ -289,15 +654,157 @@ impl<'ink> GeneratedModule<'ink> {
     }
 
     ///
-    /// Compiles the given source into an object file and saves it in output
+    /// Compiles the given source into a file of the given type (object or assembly) and saves it in output
     ///
     fn persist_to_obj(
         &self,
         output: PathBuf,
         reloc: RelocMode,
+        file_type: FileType,
         target: &Target,
         optimization_level: OptimizationLevel,
+        tuning: &TargetTuning,
     ) -> Result<PathBuf, Diagnostic> {
+        let machine = Self::create_target_machine(target, reloc, optimization_level, tuning);
+
+        //Make sure all parents exist
+        if let Some(parent) = output.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        ////Run the passes
+        machine
+            .and_then(|it| {
+                self.set_target(&it);
+                self.optimize(&it, optimization_level)
+                    .map_err(|it| {
+                        Diagnostic::llvm_error(output.to_str().unwrap_or_default(), &it.to_string())
+                    })
+                    .and_then(|_| {
+                        persist_atomically(&output, |path| {
+                            if self.split_debug && matches!(file_type, FileType::Object) {
+                                return self.write_split_debug_object(
+                                    &it,
+                                    reloc,
+                                    optimization_level,
+                                    &output,
+                                    path,
+                                );
+                            }
+                            it.write_to_file(&self.module, file_type, path).map_err(|it| {
+                                Diagnostic::llvm_error(output.to_str().unwrap_or_default(), &it.to_string())
+                            })
+                        })
+                    })
+            })
+            .map(|_| output)
+    }
+
+    /// Writes the (already optimized) module into an object at `path` and its debug information into a
+    /// `.dwo` file next to the final `object`. LLVM's C API cannot configure a target machine to emit split
+    /// DWARF, so the object is generated by the `llc` of the LLVM version we build against (see [`find_llc`])
+    /// from the module's bitcode
+    fn write_split_debug_object(
+        &self,
+        machine: &TargetMachine,
+        reloc: RelocMode,
+        optimization_level: OptimizationLevel,
+        object: &Path,
+        path: &Path,
+    ) -> Result<(), Diagnostic> {
+        // the name has to match the one referenced by the debug information of the module
+        let dwo_name = debug::get_split_debug_file_name(&self.module);
+        let dwo = object.with_file_name(&dwo_name);
+        let error = |reason: &str| Diagnostic::llvm_error(object.to_str().unwrap_or_default(), reason);
+
+        let bitcode = path.with_extension("bc");
+        if !self.module.write_bitcode_to_path(&bitcode) {
+            return Err(error("Could not write bitcode to file"));
+        }
+        let mut args = vec![
+            "-filetype=obj".to_string(),
+            match optimization_level {
+                OptimizationLevel::None => "-O0",
+                OptimizationLevel::Less => "-O1",
+                OptimizationLevel::Default => "-O2",
+                OptimizationLevel::Aggressive => "-O3",
+            }
+            .to_string(),
+            format!("-mtriple={}", machine.get_triple().as_str().to_string_lossy()),
+            format!("-mcpu={}", machine.get_cpu()),
+            format!("-mattr={}", machine.get_feature_string().to_string_lossy()),
+            format!("-split-dwarf-file={dwo_name}"),
+            format!("-split-dwarf-output={}", dwo.to_string_lossy()),
+        ];
+        match reloc {
+            RelocMode::Static => args.push("-relocation-model=static".to_string()),
+            RelocMode::PIC => args.push("-relocation-model=pic".to_string()),
+            RelocMode::DynamicNoPic => args.push("-relocation-model=dynamic-no-pic".to_string()),
+            RelocMode::Default => {}
+        }
+        args.extend([
+            "-o".to_string(),
+            path.to_string_lossy().to_string(),
+            bitcode.to_string_lossy().to_string(),
+        ]);
+
+        log::debug!("Split debug command : llc {}", args.join(" "));
+        let status =
+            find_llc().map_err(|it| error(&format!("{it} to split the debug information"))).and_then(|llc| {
+                std::process::Command::new(llc).args(&args).status().map_err(|it| error(&it.to_string()))
+            });
+        let _ = std::fs::remove_file(&bitcode);
+        match status? {
+            status if status.success() => Ok(()),
+            status => Err(error(&format!("llc failed to split the debug information ({status})"))),
+        }
+    }
+
+    /// Sets the module's triple and data layout to the ones of the given machine. The optimizer
+    /// folds constants according to the module's data layout, e.g. in the target's byte order
+    pub(crate) fn set_target(&self, machine: &TargetMachine) {
+        self.module.set_triple(&machine.get_triple());
+        self.module.set_data_layout(&machine.get_target_data().get_data_layout());
+    }
+
+    /// Runs the optimization pipeline of the given level on the module. If an inline threshold is set,
+    /// the legacy pass manager is used since the new one does not expose the inliner's threshold
+    pub(crate) fn optimize(
+        &self,
+        machine: &TargetMachine,
+        optimization_level: OptimizationLevel,
+    ) -> Result<(), LLVMString> {
+        let Some(threshold) = self.inline_threshold else {
+            return self.module.run_passes(
+                optimization_level.opt_params(),
+                machine,
+                PassBuilderOptions::create(),
+            );
+        };
+        let builder = PassManagerBuilder::create();
+        builder.set_optimization_level(optimization_level.into());
+        builder.set_inliner_with_threshold(threshold);
+
+        let function_passes = PassManager::create(&self.module);
+        builder.populate_function_pass_manager(&function_passes);
+        function_passes.initialize();
+        for function in self.module.get_functions() {
+            function_passes.run_on(&function);
+        }
+        function_passes.finalize();
+
+        let module_passes = PassManager::create(());
+        builder.populate_module_pass_manager(&module_passes);
+        module_passes.run_on(&self.module);
+        Ok(())
+    }
+
+    /// Creates the machine generating code for the given target, tuned for the cpu and features in `tuning`
+    pub(crate) fn create_target_machine(
+        target: &Target,
+        reloc: RelocMode,
+        optimization_level: OptimizationLevel,
+        tuning: &TargetTuning,
+    ) -> Result<TargetMachine, Diagnostic> {
         let initialization_config = &InitializationConfig::default();
         inkwell::targets::Target::initialize_all(initialization_config);
 
@@ -309,39 +816,18 @@ impl<'ink> GeneratedModule<'ink> {
                 SourceLocation::undefined(),
             )
         })?;
-        let machine = target
+        target
             .create_target_machine(
                 &triple,
-                //TODO : Add cpu features as optionals
-                "generic", //TargetMachine::get_host_cpu_name().to_string().as_str(),
-                "",        //TargetMachine::get_host_cpu_features().to_string().as_str(),
+                tuning.cpu.as_deref().unwrap_or("generic"),
+                tuning.features.as_deref().unwrap_or_default(),
                 optimization_level.into(),
                 reloc,
                 CodeModel::Default,
             )
             .ok_or_else(|| {
                 Diagnostic::codegen_error("Cannot create target machine.", SourceLocation::undefined())
-            });
-
-        //Make sure all parents exist
-        if let Some(parent) = output.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-        ////Run the passes
-        machine
-            .and_then(|it| {
-                self.module
-                    .run_passes(optimization_level.opt_params(), &it, PassBuilderOptions::create())
-                    .map_err(|it| {
-                        Diagnostic::llvm_error(output.to_str().unwrap_or_default(), &it.to_string())
-                    })
-                    .and_then(|_| {
-                        it.write_to_file(&self.module, FileType::Object, output.as_path()).map_err(|it| {
-                            Diagnostic::llvm_error(output.to_str().unwrap_or_default(), &it.to_string())
-                        })
-                    })
             })
-            .map(|_| output)
     }
 
     /// Persists a given LLVM module to a static object and saves the output.
@@ -357,8 +843,9 @@ impl<'ink> GeneratedModule<'ink> {
         output: PathBuf,
         target: &Target,
         optimization_level: OptimizationLevel,
+        tuning: &TargetTuning,
     ) -> Result<PathBuf, Diagnostic> {
-        self.persist_to_obj(output, RelocMode::Default, target, optimization_level)
+        self.persist_to_obj(output, RelocMode::Default, FileType::Object, target, optimization_level, tuning)
     }
 
     /// Persists a given LLVM module to a shared postiion indepedent object and saves the output.
@@ -374,8 +861,9 @@ impl<'ink> GeneratedModule<'ink> {
         output: PathBuf,
         target: &Target,
         optimization_level: OptimizationLevel,
+        tuning: &TargetTuning,
     ) -> Result<PathBuf, Diagnostic> {
-        self.persist_to_obj(output, RelocMode::PIC, target, optimization_level)
+        self.persist_to_obj(output, RelocMode::PIC, FileType::Object, target, optimization_level, tuning)
     }
 
     /// Persists the given LLVM module to a dynamic non PIC object and saves the output.
@@ -390,8 +878,39 @@ impl<'ink> GeneratedModule<'ink> {
         output: PathBuf,
         target: &Target,
         optimization_level: OptimizationLevel,
+        tuning: &TargetTuning,
+    ) -> Result<PathBuf, Diagnostic> {
+        self.persist_to_obj(
+            output,
+            RelocMode::DynamicNoPic,
+            FileType::Object,
+            target,
+            optimization_level,
+            tuning,
+        )
+    }
+
+    /// Persists the given LLVM module as assembly for the given target and saves the output.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - the location on disk to save the output
+    /// * `target` - llvm target triple
+    pub fn persist_to_assembly(
+        &self,
+        output: PathBuf,
+        target: &Target,
+        optimization_level: OptimizationLevel,
+        tuning: &TargetTuning,
     ) -> Result<PathBuf, Diagnostic> {
-        self.persist_to_obj(output, RelocMode::DynamicNoPic, target, optimization_level)
+        self.persist_to_obj(
+            output,
+            RelocMode::Default,
+            FileType::Assembly,
+            target,
+            optimization_level,
+            tuning,
+        )
     }
 
     ///
@@ -402,11 +921,14 @@ impl<'ink> GeneratedModule<'ink> {
     /// * `codegen` - the genated LLVM module to persist
     /// * `output` - the location on disk to save the output
     pub fn persist_to_bitcode(&self, output: PathBuf) -> Result<PathBuf, Diagnostic> {
-        if self.module.write_bitcode_to_path(&output) {
-            Ok(output)
-        } else {
-            Err(Diagnostic::codegen_error("Could not write bitcode to file", SourceLocation::undefined()))
-        }
+        persist_atomically(&output, |path| {
+            if self.module.write_bitcode_to_path(path) {
+                Ok(())
+            } else {
+                Err(Diagnostic::codegen_error("Could not write bitcode to file", SourceLocation::undefined()))
+            }
+        })
+        .map(|_| output)
     }
 
     ///
@@ -420,12 +942,12 @@ impl<'ink> GeneratedModule<'ink> {
         log::debug!("Output location: {}", output.to_string_lossy());
         log::debug!("{}", self.persist_to_string());
 
-        self.module
-            .print_to_file(&output)
-            .map_err(|err| {
+        persist_atomically(&output, |path| {
+            self.module.print_to_file(path).map_err(|err| {
                 Diagnostic::io_write_error(output.to_str().unwrap_or_default(), err.to_string().as_str())
             })
-            .map(|_| output)
+        })
+        .map(|_| output)
     }
 
     ///
@@ -448,6 +970,8 @@ impl<'ink> GeneratedModule<'ink> {
     ///
     pub fn run<T, U>(&self, name: &str, params: &mut T) -> U {
         let engine = self.get_execution_engine();
+        #[cfg(debug_assertions)]
+        self.check_parameter_size::<T>(&engine, name);
 
         unsafe {
             let main: JitFunction<MainFunction<T, U>> = engine.get_function(name).unwrap();
@@ -457,6 +981,30 @@ impl<'ink> GeneratedModule<'ink> {
         }
     }
 
+    /// Panics if the struct passed to the function `name` is smaller than the one it expects, the
+    /// function would otherwise read and write past the end of the given struct
+    #[cfg(debug_assertions)]
+    fn check_parameter_size<T>(&self, engine: &ExecutionEngine<'ink>, name: &str) {
+        let Some(inkwell::values::BasicValueEnum::PointerValue(parameter)) =
+            self.module.get_function(name).and_then(|it| it.get_first_param())
+        else {
+            return;
+        };
+        let Ok(expected) = inkwell::types::BasicTypeEnum::try_from(parameter.get_type().get_element_type())
+        else {
+            return;
+        };
+        if !expected.is_sized() {
+            return;
+        }
+        let expected = engine.get_target_data().get_abi_size(&expected);
+        let actual = std::mem::size_of::<T>() as u64;
+        assert!(
+            actual >= expected,
+            "The parameter passed to '{name}' has {actual} bytes but {expected} bytes are expected"
+        );
+    }
+
     ///
     /// Runs the function given by `name` inside the compiled module.
     /// Returns the value returned by calling the function
@@ -18,7 +18,9 @@ use plc_source::source_location::SourceLocation;
 use crate::{
     datalayout::{Bytes, DataLayout, MemoryLocation},
     index::{ImplementationType, Index, PouIndexEntry, VariableIndexEntry},
-    typesystem::{DataType, DataTypeInformation, Dimension, StringEncoding, CHAR_TYPE, WCHAR_TYPE},
+    typesystem::{
+        DataType, DataTypeInformation, Dimension, StringEncoding, StructSource, CHAR_TYPE, WCHAR_TYPE,
+    },
     DebugLevel, OptimizationLevel,
 };
 
@@ -170,6 +172,16 @@ pub enum DebugBuilderEnum<'ink> {
     Full(DebugBuilder<'ink>),
 }
 
+/// returns the name of the `.dwo` file the debug information of the given module is moved into,
+/// it is named after the module's source
+pub(crate) fn get_split_debug_file_name(module: &Module) -> String {
+    Path::new(module.get_source_file_name().to_str().unwrap_or_default())
+        .with_extension("dwo")
+        .file_name()
+        .map(|it| it.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
 impl<'ink> DebugBuilderEnum<'ink> {
     pub fn new(
         context: &'ink Context,
@@ -177,6 +189,7 @@ impl<'ink> DebugBuilderEnum<'ink> {
         root: Option<&Path>,
         optimization: OptimizationLevel,
         debug_level: DebugLevel,
+        split_debug: bool,
     ) -> Self {
         let dwarf_version: BasicMetadataValueEnum<'ink> = context.i32_type().const_int(5, false).into();
         match debug_level {
@@ -190,6 +203,7 @@ impl<'ink> DebugBuilderEnum<'ink> {
                 let path = Path::new(module.get_source_file_name().to_str().unwrap_or(""));
                 let root = root.unwrap_or_else(|| Path::new(""));
                 let filename = path.strip_prefix(root).unwrap_or(path).to_str().unwrap_or_default();
+                let split_name = if split_debug { Some(get_split_debug_file_name(module)) } else { None };
                 let (debug_info, compile_unit) = module.create_debug_info_builder(
                     true,
                     inkwell::debug_info::DWARFSourceLanguage::C, //TODO: Own lang
@@ -199,7 +213,7 @@ impl<'ink> DebugBuilderEnum<'ink> {
                     optimization.is_optimized(),
                     "",
                     0,
-                    "",
+                    split_name.as_deref().unwrap_or_default(),
                     debug_level.into(),
                     0,
                     false,
@@ -268,15 +282,29 @@ impl<'ink> DebugBuilder<'ink> {
             .map(|it| self.get_or_create_debug_file(it))
             .unwrap_or_else(|| self.compile_unit.get_file());
 
+        let struct_dt = index.get_type_information_or_void(name);
+        //all members of a union are placed at offset zero
+        let is_union = matches!(struct_dt, DataTypeInformation::Struct { source: StructSource::Union, .. });
+        //the members of a bitfield occupy a single bit each
+        let is_bitfield =
+            matches!(struct_dt, DataTypeInformation::Struct { source: StructSource::Bitfield, .. });
+
         let mut types = vec![];
         let mut running_offset = MemoryLocation::new(0);
-        for (member_name, dt, location) in index_types.into_iter() {
+        for (bit, (member_name, dt, location)) in index_types.into_iter().enumerate() {
             let di_type = self.get_or_create_debug_type(dt, index)?;
             //Adjust the offset based on the field alignment
             let type_info = dt.get_type_information();
             let alignment = type_info.get_alignment(index);
             let size = type_info.get_size(index);
-            running_offset = running_offset.align_to(alignment);
+            if !is_union && !is_bitfield {
+                running_offset = running_offset.align_to(alignment);
+            }
+            let (size_in_bits, offset_in_bits) = if is_bitfield {
+                (1, bit as u64)
+            } else {
+                (size.bits().into(), running_offset.bits().into())
+            };
             types.push(
                 self.debug_info
                     .create_member_type(
@@ -284,18 +312,38 @@ impl<'ink> DebugBuilder<'ink> {
                         member_name,
                         file,
                         location.get_line_plus_one() as u32,
-                        size.bits().into(),
+                        size_in_bits,
                         alignment.bits(),
-                        running_offset.bits().into(),
+                        offset_in_bits,
                         DIFlags::PUBLIC,
                         di_type.into(),
                     )
                     .as_type(),
             );
-            running_offset += size;
+            if !is_union && !is_bitfield {
+                running_offset += size;
+            }
+        }
+        if is_bitfield {
+            running_offset = MemoryLocation::new(struct_dt.get_size(index).value());
         }
 
-        let struct_dt = index.get_type_information_or_void(name);
+        if is_union {
+            let union_type = self.debug_info.create_union_type(
+                file.as_debug_info_scope(),
+                name,
+                file,
+                location.get_line_plus_one() as u32,
+                struct_dt.get_size(index).bits().into(),
+                struct_dt.get_alignment(index).bits(),
+                DIFlags::PUBLIC,
+                types.as_slice(),
+                0,
+                name,
+            );
+            self.register_concrete_type(name, DebugType::Struct(union_type));
+            return Ok(());
+        }
 
         //Create a struct type
         let struct_type = self.debug_info.create_struct_type(
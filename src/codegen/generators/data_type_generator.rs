@@ -1,5 +1,6 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 use crate::codegen::debug::Debug;
+use crate::datalayout::{Bytes, MemoryLocation};
 use crate::index::{Index, VariableIndexEntry, VariableType};
 use crate::resolver::{AstAnnotations, Dependency};
 use crate::typesystem::{self, DataTypeInformation, Dimension, StringEncoding, StructSource};
@@ -173,26 +174,77 @@ impl<'ink, 'b> DataTypeGenerator<'ink, 'b> {
     /// generates the members of an opaque struct and associates its initial values
     fn expand_opaque_types(&mut self, data_type: &DataType) -> Result<(), Diagnostic> {
         let information = data_type.get_type_information();
-        if let DataTypeInformation::Struct { source, members, .. } = information {
-            let members = members
-                .iter()
-                .filter(|it| !it.is_temp() && !it.is_return())
-                .map(|m| self.types_index.get_associated_type(m.get_type_name()))
-                .collect::<Result<Vec<BasicTypeEnum>, Diagnostic>>()?;
+        if let DataTypeInformation::Struct { source: StructSource::Union, members, .. } = information {
+            //all members of a union share the same memory, the union is represented by its most aligned
+            //member padded to the size of the largest member, rounded up to the member's alignment
+            let union_type = self.types_index.get_associated_type(data_type.get_name())?.into_struct_type();
+            let Some(member) = members.iter().max_by_key(|it| {
+                self.get_natural_alignment(self.index.get_type_information_or_void(it.get_type_name()))
+            }) else {
+                union_type.set_body(&[], false);
+                return Ok(());
+            };
+            let member_type = self.index.get_type_information_or_void(member.get_type_name());
+            let size = MemoryLocation::new(information.get_size(self.index).value())
+                .align_to(Bytes::new(self.get_natural_alignment(member_type)))
+                .value();
+            let padding = size - member_type.get_size(self.index).value();
+            let mut body = vec![self.types_index.get_associated_type(member.get_type_name())?];
+            if padding > 0 {
+                body.push(self.llvm.context.i8_type().array_type(padding).into());
+            }
+            union_type.set_body(&body, false);
+        } else if let DataTypeInformation::Struct { source: StructSource::Bitfield, .. } = information {
+            //the members of a bitfield are packed into single bits, represented as a byte array
+            let size = information.get_size(self.index).value();
+            let bitfield_type =
+                self.types_index.get_associated_type(data_type.get_name())?.into_struct_type();
+            bitfield_type.set_body(&[self.llvm.context.i8_type().array_type(size).into()], false);
+        } else if let DataTypeInformation::Struct { source, members, .. } = information {
+            let mut fields = vec![];
+            for member in members.iter().filter(|it| !it.is_temp() && !it.is_return()) {
+                fields.extend(get_aligning_field(self.llvm, self.index, member.get_type_name()));
+                fields.push(self.types_index.get_associated_type(member.get_type_name())?);
+            }
 
             let struct_type = match source {
                 StructSource::Pou(..) => self.types_index.get_associated_pou_type(data_type.get_name()),
-                StructSource::OriginalDeclaration | StructSource::Internal(_) => {
-                    self.types_index.get_associated_type(data_type.get_name())
-                }
+                StructSource::OriginalDeclaration
+                | StructSource::Union
+                | StructSource::Bitfield
+                | StructSource::Internal(_) => self.types_index.get_associated_type(data_type.get_name()),
             }
             .map(BasicTypeEnum::into_struct_type)?;
 
-            struct_type.set_body(members.as_slice(), false);
+            struct_type.set_body(fields.as_slice(), false);
         }
         Ok(())
     }
 
+    /// returns the alignment of the largest scalar the given type consists of (e.g. 8 for an
+    /// `ARRAY OF LREAL`), assuming scalars are aligned to their size
+    fn get_natural_alignment(&self, information: &DataTypeInformation) -> u32 {
+        match information {
+            DataTypeInformation::Array { inner_type_name: referenced_type, .. }
+            | DataTypeInformation::Enum { referenced_type, .. }
+            | DataTypeInformation::SubRange { referenced_type, .. }
+            | DataTypeInformation::Alias { referenced_type, .. } => {
+                self.get_natural_alignment(self.index.get_type_information_or_void(referenced_type))
+            }
+            DataTypeInformation::String { encoding, .. } => encoding.get_bytes_per_char(),
+            DataTypeInformation::Struct { source: StructSource::Bitfield, .. } => 1,
+            DataTypeInformation::Struct { members, .. } => members
+                .iter()
+                .filter(|it| !it.is_temp() && !it.is_return())
+                .map(|it| {
+                    self.get_natural_alignment(self.index.get_type_information_or_void(it.get_type_name()))
+                })
+                .max()
+                .unwrap_or(1),
+            _ => information.get_size(self.index).value().max(1),
+        }
+    }
+
     /// Creates an llvm type to be associated with the given data type.
     /// Generates only an opaque type for structs.
     /// Eagerly generates but does not associate nested array and referenced aliased types
@@ -202,7 +254,7 @@ impl<'ink, 'b> DataTypeGenerator<'ink, 'b> {
         match information {
             DataTypeInformation::Struct { source, .. } => match source {
                 StructSource::Pou(..) => self.types_index.get_associated_pou_type(data_type.get_name()),
-                StructSource::OriginalDeclaration => {
+                StructSource::OriginalDeclaration | StructSource::Union | StructSource::Bitfield => {
                     self.types_index.get_associated_type(data_type.get_name())
                 }
                 StructSource::Internal(_) => self.types_index.get_associated_type(data_type.get_name()),
@@ -275,31 +327,39 @@ impl<'ink, 'b> DataTypeGenerator<'ink, 'b> {
     ) -> Result<Option<BasicValueEnum<'ink>>, Diagnostic> {
         let information = data_type.get_type_information();
         match information {
+            //unions are zero-initialized
+            DataTypeInformation::Struct { source: StructSource::Union, .. } => Ok(None),
+            DataTypeInformation::Struct { source: StructSource::Bitfield, members, .. } => {
+                self.generate_bitfield_initial_value(data_type, members)
+            }
             DataTypeInformation::Struct { source, members, .. } => {
-                let member_names_and_initializers = members
+                let members_and_initializers = members
                     .iter()
                     .filter(|it| it.get_variable_type() != VariableType::Temp)
                     .map(|it| {
                         self.generate_initial_value_for_variable(it).and_then(|v| match v {
-                            Some(v) => Ok((it.get_qualified_name(), v)),
+                            Some(v) => Ok((it, v)),
                             None => self
                                 .types_index
                                 .get_associated_type(it.get_type_name())
                                 .map(get_default_for)
-                                .map(|v| (it.get_qualified_name(), v)),
+                                .map(|v| (it, v)),
                         })
                     })
-                    .collect::<Result<Vec<(&str, BasicValueEnum)>, Diagnostic>>()?;
+                    .collect::<Result<Vec<(&VariableIndexEntry, BasicValueEnum)>, Diagnostic>>()?;
 
                 let mut member_values: Vec<BasicValueEnum> = Vec::new();
-                for (name, v) in &member_names_and_initializers {
-                    self.types_index.associate_initial_value(name, *v)?;
+                for (member, v) in &members_and_initializers {
+                    self.types_index.associate_initial_value(member.get_qualified_name(), *v)?;
+                    if let Some(field) = get_aligning_field(self.llvm, self.index, member.get_type_name()) {
+                        member_values.push(get_default_for(field));
+                    }
                     member_values.push(*v);
                 }
 
                 let struct_type = match source {
                     StructSource::Pou(..) => self.types_index.get_associated_pou_type(data_type.get_name()),
-                    StructSource::OriginalDeclaration => {
+                    StructSource::OriginalDeclaration | StructSource::Union | StructSource::Bitfield => {
                         self.types_index.get_associated_type(data_type.get_name())
                     }
                     StructSource::Internal(_) => self.types_index.get_associated_type(data_type.get_name()),
@@ -329,6 +389,34 @@ impl<'ink, 'b> DataTypeGenerator<'ink, 'b> {
         }
     }
 
+    /// packs the initial values of a bitfield's members into its bytes, a bitfield without any
+    /// initialized member is zero-initialized
+    fn generate_bitfield_initial_value(
+        &mut self,
+        data_type: &DataType,
+        members: &[VariableIndexEntry],
+    ) -> Result<Option<BasicValueEnum<'ink>>, Diagnostic> {
+        let size = data_type.get_type_information().get_size(self.index).value();
+        let mut bytes = vec![0_u64; size as usize];
+        let mut is_initialized = false;
+        for member in members {
+            let Some(value) = self.generate_initial_value_for_variable(member)? else { continue };
+            is_initialized = true;
+            if value.into_int_value().get_zero_extended_constant().unwrap_or_default() != 0 {
+                let bit = member.get_location_in_parent();
+                bytes[(bit / 8) as usize] |= 1 << (bit % 8);
+            }
+        }
+        if !is_initialized {
+            return Ok(None);
+        }
+
+        let byte_type = self.llvm.context.i8_type();
+        let bytes = bytes.into_iter().map(|it| byte_type.const_int(it, false)).collect::<Vec<_>>();
+        let bitfield_type = self.types_index.get_associated_type(data_type.get_name())?.into_struct_type();
+        Ok(Some(bitfield_type.const_named_struct(&[byte_type.const_array(&bytes).into()]).into()))
+    }
+
     /// generates and returns an optional inital value at the given declared variable
     /// if no initial value is defined, it returns the initial value of the variable's
     /// datatype or Ok(None) if the type also has no declared default value
@@ -463,3 +551,48 @@ pub fn get_default_for(basic_type: BasicTypeEnum) -> BasicValueEnum {
         BasicTypeEnum::VectorType(t) => t.const_zero().into(),
     }
 }
+
+/// returns the alignment of strings if the layout overrides it and the given type is a string
+pub fn get_string_alignment(index: &Index, type_name: &str) -> Option<u32> {
+    index
+        .get_type_layout()
+        .string
+        .filter(|_| index.get_effective_type_or_void_by_name(type_name).get_type_information().is_string())
+        .map(|it| it.value())
+}
+
+/// returns the zero-sized field which precedes a struct member of the given type to align it, e.g.
+/// `[0 x <4 x i8>]` for strings aligned to 4 bytes. LLVM places the members of a struct by their
+/// natural alignment, so an overridden alignment of strings needs such a field in front of them
+pub fn get_aligning_field<'ink>(
+    llvm: &Llvm<'ink>,
+    index: &Index,
+    type_name: &str,
+) -> Option<BasicTypeEnum<'ink>> {
+    get_string_alignment(index, type_name)
+        .map(|it| llvm.context.i8_type().vec_type(it).array_type(0).as_basic_type_enum())
+}
+
+/// returns the number of aligning fields in the struct of the given member's container up to and
+/// including the member's own (see [`get_aligning_field`])
+pub fn get_preceding_aligning_fields(index: &Index, member: &VariableIndexEntry) -> u32 {
+    if index.get_type_layout().string.is_none() {
+        return 0;
+    }
+    let container = member.get_qualified_name().rsplit_once('.').map(|(it, _)| it).unwrap_or_default();
+    let members = match index.get_pou_members(container) {
+        [] => index.get_container_members(container),
+        members => members,
+    };
+    members
+        .iter()
+        .filter(|it| !it.is_temp() && !it.is_return())
+        .filter(|it| it.get_location_in_parent() <= member.get_location_in_parent())
+        .filter(|it| get_string_alignment(index, it.get_type_name()).is_some())
+        .count() as u32
+}
+
+/// returns the index of the given member's field in the generated struct of its container
+pub fn get_field_index(index: &Index, member: &VariableIndexEntry) -> u32 {
+    member.get_location_in_parent() + get_preceding_aligning_fields(index, member)
+}
@@ -9,13 +9,14 @@ use crate::{
         const_expressions::ConstId, ArgumentType, ImplementationIndexEntry, Index, PouIndexEntry,
         VariableIndexEntry, VariableType,
     },
-    resolver::{AnnotationMap, AstAnnotations, StatementAnnotation},
+    resolver::{get_enable_parameter, AnnotationMap, AstAnnotations, EnableParameter, StatementAnnotation},
     typesystem::{
         is_same_type_class, DataType, DataTypeInformation, DataTypeInformationProvider, Dimension,
-        StringEncoding, VarArgs, DINT_TYPE, INT_SIZE, INT_TYPE, LINT_TYPE,
+        StringEncoding, StructSource, VarArgs, BOOL_TYPE, DINT_TYPE, INT_SIZE, INT_TYPE, LINT_TYPE,
     },
 };
 use inkwell::{
+    basic_block::BasicBlock,
     builder::Builder,
     types::{BasicType, BasicTypeEnum},
     values::{
@@ -26,17 +27,20 @@ use inkwell::{
 };
 use plc_ast::{
     ast::{
-        flatten_expression_list, AstFactory, AstNode, AstStatement, DirectAccessType, Operator,
-        ReferenceAccess, ReferenceExpr,
+        flatten_expression_list, Assignment, AstFactory, AstNode, AstStatement, DirectAccessType, Operator,
+        PointerKind, RangeStatement, ReferenceAccess, ReferenceExpr,
     },
-    literals::AstLiteral,
+    literals::{AstLiteral, StringValue},
 };
 use plc_diagnostics::diagnostics::{Diagnostic, INTERNAL_LLVM_ERROR};
 use plc_source::source_location::SourceLocation;
 use plc_util::convention::qualified_name;
 use std::{collections::HashSet, vec};
 
-use super::{llvm::Llvm, statement_generator::FunctionContext, ADDRESS_SPACE_CONST, ADDRESS_SPACE_GENERIC};
+use super::{
+    data_type_generator, llvm::Llvm, statement_generator::FunctionContext, ADDRESS_SPACE_CONST,
+    ADDRESS_SPACE_GENERIC,
+};
 /// the generator for expressions
 pub struct ExpressionCodeGenerator<'a, 'b> {
     pub llvm: &'b Llvm<'a>,
@@ -238,11 +242,22 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
                 Ok(ExpressionValue::RValue(self.llvm.i32_type().const_zero().into()))
             }
             AstStatement::ParenExpression(expr) => self.generate_expression_value(expr),
+            AstStatement::This => self.generate_this(expression).map(ExpressionValue::RValue),
             //fallback
             _ => self.generate_literal(expression),
         }
     }
 
+    /// generates the pointer to the current instance referenced by `THIS`. The instance is always
+    /// passed as the first parameter of function blocks, their actions and methods
+    fn generate_this(&self, expression: &AstNode) -> Result<BasicValueEnum<'ink>, Diagnostic> {
+        self.function_context
+            .filter(|_| self.annotations.get(expression).is_some())
+            .and_then(|context| context.function.get_first_param())
+            .filter(|it| it.is_pointer_value())
+            .ok_or_else(|| Diagnostic::this_outside_method(expression.get_location()))
+    }
+
     /// Propagate the constant value of the constant reference to  `qualified_name`.
     /// - `qualified _name` the qualified name of the referenced constant variable we want to propagate
     /// - `expression` the original expression
@@ -432,6 +447,15 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
             return builtin.codegen(self, parameters_list.as_slice(), operator.get_location());
         }
 
+        // the implicit EN/ENO parameters are not passed to the function block
+        let (enable_parameters, parameters_list): (Vec<_>, Vec<_>) =
+            parameters_list.into_iter().partition(|it| get_enable_parameter(pou, it, self.index).is_some());
+        let enable_parameters = enable_parameters
+            .into_iter()
+            .filter_map(|it| get_enable_parameter(pou, it, self.index))
+            .collect::<Vec<_>>();
+        let enable = self.generate_enable_input(&enable_parameters, operator)?;
+
         let mut arguments_list = self.generate_pou_call_arguments_list(
             pou,
             parameters_list.as_slice(),
@@ -459,7 +483,8 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
             if data_type.is_aggregate_type() {
                 // this is a function call with a return variable fed as an out-pointer
                 let llvm_type = self.llvm_index.get_associated_type(data_type.get_name())?;
-                let out_pointer = self.llvm.create_local_variable("", &llvm_type);
+                let alignment = data_type_generator::get_string_alignment(self.index, data_type.get_name());
+                let out_pointer = self.llvm.create_aligned_local_variable("", &llvm_type, alignment);
                 // add the out-ptr as its first parameter
                 arguments_list.insert(0, out_pointer.into());
                 Some(out_pointer)
@@ -501,9 +526,54 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
             self.assign_output_values(parameter_struct, implementation_name, parameters_list)?
         }
 
+        self.generate_enable_output(enable, &enable_parameters)?;
         value
     }
 
+    /// evaluates the `EN` input of a function block call and only continues with the call if it is `TRUE`
+    /// returns the evaluated `EN` and the block to continue with after the call, `None` if no `EN` was passed
+    fn generate_enable_input(
+        &self,
+        enable_parameters: &[(EnableParameter, &Assignment)],
+        operator: &AstNode,
+    ) -> Result<Option<(IntValue<'ink>, BasicBlock<'ink>)>, Diagnostic> {
+        let Some((_, enable)) = enable_parameters.iter().find(|(kind, _)| *kind == EnableParameter::Input)
+        else {
+            return Ok(None);
+        };
+
+        let builder = &self.llvm.builder;
+        let enable = to_i1(self.generate_expression(&enable.right)?.into_int_value(), builder);
+        let function = self.get_function_context(operator)?.function;
+        let call_block = self.llvm.context.append_basic_block(function, "enabled");
+        let continue_block = self.llvm.context.append_basic_block(function, "continue");
+        builder.build_conditional_branch(enable, call_block, continue_block);
+        builder.position_at_end(call_block);
+        Ok(Some((enable, continue_block)))
+    }
+
+    /// closes the call guarded by `EN` and assigns whether the call was executed to `ENO`
+    fn generate_enable_output(
+        &self,
+        enable: Option<(IntValue<'ink>, BasicBlock<'ink>)>,
+        enable_parameters: &[(EnableParameter, &Assignment)],
+    ) -> Result<(), Diagnostic> {
+        let builder = &self.llvm.builder;
+        let bool_type = self.llvm_index.get_associated_type(BOOL_TYPE)?.into_int_type();
+        let executed = if let Some((enable, continue_block)) = enable {
+            builder.build_unconditional_branch(continue_block);
+            builder.position_at_end(continue_block);
+            builder.build_int_z_extend(enable, bool_type, "")
+        } else {
+            bool_type.const_int(1, false)
+        };
+
+        for (_, data) in enable_parameters.iter().filter(|(kind, _)| *kind == EnableParameter::Output) {
+            builder.build_store(self.generate_lvalue(&data.right)?, executed);
+        }
+        Ok(())
+    }
+
     /// copies the output values to the assigned output variables
     /// - `parameter_struct` a pointer to a struct-instance that holds all function-parameters
     /// - `function_name` the name of the callable
@@ -554,7 +624,8 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
                     let assigned_output_type =
                         self.annotations.get_type_or_void(expression, self.index).get_type_information();
 
-                    let output = builder.build_struct_gep(parameter_struct, index, "").map_err(|_| {
+                    let field = data_type_generator::get_field_index(self.index, parameter);
+                    let output = builder.build_struct_gep(parameter_struct, field, "").map_err(|_| {
                         Diagnostic::codegen_error(
                             &format!("Cannot build generate parameter: {parameter:#?}"),
                             parameter.source_location.clone(),
@@ -682,8 +753,11 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
                 .get(i)
                 .map(|it| {
                     let name = it.get_type_name();
-                    if let Some(DataTypeInformation::Pointer { inner_type_name, auto_deref: true, .. }) =
-                        self.index.find_effective_type_info(name)
+                    if let Some(DataTypeInformation::Pointer {
+                        inner_type_name,
+                        kind: PointerKind::Reference,
+                        ..
+                    }) = self.index.find_effective_type_info(name)
                     {
                         // for auto_deref pointers (VAR_INPUT {ref}, VAR_IN_OUT) we call generate_argument_by_ref()
                         // we need the inner_type and not pointer to type otherwise we would generate a double pointer
@@ -759,6 +833,13 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
             Some(type_info) if type_info.information.is_string() => {
                 self.generate_string_argument(type_info, param_statement)?
             }
+            Some(type_info) if type_info.is_vla() => match get_array_slice(param_statement) {
+                Some((base, start, end)) => {
+                    let vla = self.generate_vla_for_array_slice(base, start, end, type_name)?;
+                    self.llvm.builder.build_load(vla, "")
+                }
+                None => self.generate_expression(param_statement)?,
+            },
             _ => self.generate_expression(param_statement)?,
         })
     }
@@ -775,7 +856,8 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
             .llvm_index
             .find_associated_type(type_info.get_name())
             .ok_or_else(|| Diagnostic::unknown_type(type_info.get_name(), argument.get_location()))?;
-        let temp_variable = self.llvm.builder.build_alloca(llvm_type, "");
+        let alignment = data_type_generator::get_string_alignment(self.index, type_info.get_name());
+        let temp_variable = self.llvm.create_aligned_local_variable("", &llvm_type, alignment);
         self.llvm
             .builder
             .build_memset(
@@ -807,7 +889,8 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
                 .find_associated_type(type_name)
                 .ok_or_else(|| Diagnostic::unknown_type(type_name, argument.get_location()))?;
 
-            let ptr_value = self.llvm.builder.build_alloca(v_type, "");
+            let alignment = data_type_generator::get_string_alignment(self.index, type_name);
+            let ptr_value = self.llvm.create_aligned_local_variable("", &v_type, alignment);
             if let Some(p) = declared_parameter {
                 if let Some(initial_value) =
                     self.get_initial_value(&p.initial_value, &self.get_parameter_type(p))
@@ -820,6 +903,11 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
             return Ok(ptr_value.into());
         }
 
+        // a slice passed to a VLA, e.g. `arr[2..5]`, is passed as a view into the sliced array
+        if let Some((base, start, end)) = get_array_slice(argument) {
+            return self.generate_vla_for_array_slice(base, start, end, type_name).map(Into::into);
+        }
+
         // Generate the element pointer, then...
         let value = {
             let value = self.generate_expression_value(argument)?;
@@ -1022,7 +1110,7 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
     }
 
     fn get_parameter_type(&self, parameter: &VariableIndexEntry) -> String {
-        if let Some(DataTypeInformation::Pointer { inner_type_name, auto_deref: true, .. }) =
+        if let Some(DataTypeInformation::Pointer { inner_type_name, kind: PointerKind::Reference, .. }) =
             self.index.find_effective_type_info(parameter.get_type_name())
         {
             inner_type_name.into()
@@ -1037,6 +1125,7 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
     ) -> Result<BasicValueEnum<'ink>, Diagnostic> {
         let parameter_type_name = self.get_parameter_type(parameter);
         let parameter_type = self.llvm_index.get_associated_type(&parameter_type_name)?;
+        let alignment = data_type_generator::get_string_alignment(self.index, &parameter_type_name);
         match parameter.get_declaration_type() {
             ArgumentType::ByVal(..) => {
                 if let Some(initial_value) =
@@ -1044,12 +1133,12 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
                 {
                     self.generate_expression(initial_value)
                 } else {
-                    let ptr_value = self.llvm.builder.build_alloca(parameter_type, "");
+                    let ptr_value = self.llvm.create_aligned_local_variable("", &parameter_type, alignment);
                     Ok(self.llvm.load_pointer(&ptr_value, ""))
                 }
             }
             _ => {
-                let ptr_value = self.llvm.builder.build_alloca(parameter_type, "");
+                let ptr_value = self.llvm.create_aligned_local_variable("", &parameter_type, alignment);
 
                 // if default value is given for an output
                 // we need to initialize the pointer value before returning
@@ -1116,7 +1205,8 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
                 return Ok(None);
             }
 
-            let pointer_to_param = builder.build_struct_gep(parameter_struct, index, "").map_err(|_| {
+            let field = data_type_generator::get_field_index(self.index, parameter);
+            let pointer_to_param = builder.build_struct_gep(parameter_struct, field, "").map_err(|_| {
                 Diagnostic::codegen_error(
                     &format!("Cannot build generate parameter: {expression:#?}"),
                     expression.get_location(),
@@ -1130,7 +1220,9 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
                 .map(|var| var.get_type_information())
                 .unwrap_or_else(|| self.index.get_void_type().get_type_information());
 
-            if let DataTypeInformation::Pointer { auto_deref: true, inner_type_name, .. } = parameter {
+            if let DataTypeInformation::Pointer { kind: PointerKind::Reference, inner_type_name, .. } =
+                parameter
+            {
                 //this is VAR_IN_OUT assignemt, so don't load the value, assign the pointer
 
                 //expression may be empty -> generate a local variable for it
@@ -1180,7 +1272,7 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
                     .find_effective_type_by_name(parameter.get_type_name())
                     .map(|var| var.get_type_information())
                     .unwrap_or_else(|| self.index.get_void_type().get_type_information()),
-                DataTypeInformation::Pointer { auto_deref: true, .. }
+                DataTypeInformation::Pointer { kind: PointerKind::Reference, .. }
             );
             if !right.is_empty_statement() || is_auto_deref {
                 self.generate_call_struct_argument_assignment(&CallParameterAssignment {
@@ -1229,11 +1321,20 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
                     }
                 }
                 Some(StatementAnnotation::Variable { qualified_name, .. }) => {
-                    let member_location = self
+                    let member = self
                         .index
                         .find_fully_qualified_variable(qualified_name)
-                        .map(VariableIndexEntry::get_location_in_parent)
                         .ok_or_else(|| Diagnostic::unresolved_reference(qualified_name, offset.clone()))?;
+                    if self.is_union_member(qualified_name) {
+                        //all members of a union start at offset zero
+                        let member_type = self.llvm_index.get_associated_type(member.get_type_name())?;
+                        return Ok(self.llvm.builder.build_pointer_cast(
+                            *qualifier,
+                            member_type.ptr_type(AddressSpace::from(ADDRESS_SPACE_GENERIC)),
+                            name,
+                        ));
+                    }
+                    let member_location = data_type_generator::get_field_index(self.index, member);
                     let gep: PointerValue<'_> = self.llvm.get_member_pointer_from_struct(
                         *qualifier,
                         member_location,
@@ -1265,12 +1366,75 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
         }
     }
 
+    /// returns true if the given qualified variable is a member of a `UNION`
+    fn is_union_member(&self, qualified_name: &str) -> bool {
+        qualified_name.rsplit_once('.').map_or(false, |(container, _)| {
+            matches!(
+                self.index.find_effective_type_info(container),
+                Some(DataTypeInformation::Struct { source: StructSource::Union, .. })
+            )
+        })
+    }
+
+    /// returns the member of a `{bitfield}` struct the given reference resolves to
+    pub fn find_bitfield_member(&self, reference: &AstNode) -> Option<&VariableIndexEntry> {
+        let Some(StatementAnnotation::Variable { qualified_name, .. }) = self.annotations.get(reference)
+        else {
+            return None;
+        };
+        let (container, _) = qualified_name.rsplit_once('.')?;
+        let is_bitfield = matches!(
+            self.index.find_effective_type_info(container),
+            Some(DataTypeInformation::Struct { source: StructSource::Bitfield, .. })
+        );
+        is_bitfield.then(|| self.index.find_fully_qualified_variable(qualified_name)).flatten()
+    }
+
+    /// returns a pointer to the byte of the `bitfield` holding the given member and the member's
+    /// bit-position within that byte
+    pub fn generate_bitfield_byte_pointer(
+        &self,
+        bitfield: PointerValue<'ink>,
+        member: &VariableIndexEntry,
+    ) -> (PointerValue<'ink>, u32) {
+        let bit = member.get_location_in_parent();
+        let byte_type = self.llvm.context.i8_type();
+        let bytes = self.llvm.builder.build_pointer_cast(
+            bitfield,
+            byte_type.ptr_type(AddressSpace::from(ADDRESS_SPACE_GENERIC)),
+            "",
+        );
+        let byte_index = self.llvm.i32_type().const_int((bit / 8) as u64, false);
+        let byte = unsafe { self.llvm.builder.build_in_bounds_gep(bytes, &[byte_index], "") };
+        (byte, bit % 8)
+    }
+
+    /// loads the bit of the given bitfield member, e.g. `status.ready`
+    fn generate_bitfield_member_read(
+        &self,
+        bitfield: &ExpressionValue<'ink>,
+        member: &VariableIndexEntry,
+    ) -> Result<ExpressionValue<'ink>, Diagnostic> {
+        let (byte, bit) =
+            self.generate_bitfield_byte_pointer(bitfield.get_basic_value_enum().into_pointer_value(), member);
+        let byte_type = self.llvm.context.i8_type();
+        let value = self.llvm.load_pointer(&byte, "").into_int_value();
+        let shift = self.llvm.builder.build_right_shift(
+            value,
+            byte_type.const_int(bit as u64, false),
+            false,
+            "shift",
+        );
+        let result = self.llvm.builder.build_and(shift, byte_type.const_int(1, false), member.get_name());
+        Ok(ExpressionValue::RValue(result.as_basic_value_enum()))
+    }
+
     fn deref(&self, accessor_ptr: PointerValue<'ink>) -> PointerValue<'ink> {
         self.llvm.load_pointer(&accessor_ptr, "deref").into_pointer_value()
     }
 
     pub fn ptr_as_value(&self, ptr: PointerValue<'ink>) -> BasicValueEnum<'ink> {
-        let int_type = self.llvm.context.i64_type();
+        let int_type = self.llvm.context.custom_width_int_type(self.index.get_type_layout().p64.bits());
         if ptr.is_const() {
             ptr.const_to_int(int_type)
         } else {
@@ -1461,6 +1625,91 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
             })
     }
 
+    /// generates the fat pointer of a VLA viewing the given slice of an array, e.g. `arr[2..5]`
+    /// the array pointer points to the slice's first element and the slice keeps the indices of the
+    /// sliced array, so `arr[2..5]` is passed as an `ARRAY[2..5]`
+    ///
+    /// - `reference` the reference-statement pointing to the sliced array
+    /// - `start` the first index of the slice
+    /// - `end` the last index of the slice
+    /// - `vla_type_name` the VLA type the slice is passed as
+    fn generate_vla_for_array_slice(
+        &self,
+        reference: &AstNode,
+        start: &AstNode,
+        end: &AstNode,
+        vla_type_name: &str,
+    ) -> Result<PointerValue<'ink>, Diagnostic> {
+        let builder = &self.llvm.builder;
+        let vla_type = self.llvm_index.get_associated_type(vla_type_name)?.into_struct_type();
+        let vla_struct = builder.build_alloca(vla_type, "vla_slice");
+
+        // the array pointer points to the first element of the slice
+        let first_element = self.generate_element_pointer_for_array(reference, start)?;
+        let vla_arr_gep =
+            builder.build_struct_gep(vla_struct, 0, "vla_array_gep").expect(INTERNAL_LLVM_ERROR);
+        let first_element = builder.build_pointer_cast(
+            first_element,
+            vla_type.get_field_type_at_index(0).expect(INTERNAL_LLVM_ERROR).into_pointer_type(),
+            "",
+        );
+        builder.build_store(vla_arr_gep, first_element);
+
+        // the dimensions are the start- and end-offset of the slice
+        let vla_dimensions_gep =
+            builder.build_struct_gep(vla_struct, 1, "vla_dimensions_gep").expect(INTERNAL_LLVM_ERROR);
+        for (idx, bound) in [start, end].into_iter().enumerate() {
+            let value = self.generate_expression(bound)?;
+            let value = cast_if_needed!(
+                self,
+                self.index.get_type(DINT_TYPE)?,
+                self.get_type_hint_for(bound)?,
+                value,
+                None
+            );
+            let accessor =
+                [self.llvm.i32_type().const_zero(), self.llvm.i32_type().const_int(idx as u64, false)];
+            let offset_ptr = unsafe { builder.build_in_bounds_gep(vla_dimensions_gep, &accessor, "") };
+            builder.build_store(offset_ptr, value);
+        }
+        Ok(vla_struct)
+    }
+
+    /// generates a gep statement for a single character of a string
+    /// the positions of the characters start at 1 (like the ones of the string functions), so `s[1]`
+    /// points to the first character of `s`
+    ///
+    /// - `reference` the reference-statement pointing to the string
+    /// - `access` the position of the character (the expression between the brackets: reference[access])
+    fn generate_element_pointer_for_string(
+        &self,
+        reference: &AstNode,
+        access: &AstNode,
+    ) -> Result<PointerValue<'ink>, Diagnostic> {
+        let lvalue = self.generate_expression_value(reference)?.get_basic_value_enum().into_pointer_value();
+        // positions only known at runtime may be checked by a range check function
+        let position = self.annotations.get_hidden_function_call(access).unwrap_or(access);
+        let position = self.generate_expression(position)?.into_int_value();
+        let offset = self.llvm.builder.build_int_sub(position, position.get_type().const_int(1, false), "");
+        //turn it into i32 immediately
+        let offset = cast_if_needed!(
+            self,
+            self.index.get_type(DINT_TYPE)?,
+            self.get_type_hint_for(access)?,
+            offset.as_basic_value_enum(),
+            None
+        )
+        .into_int_value();
+
+        let accessor_sequence = if lvalue.get_type().get_element_type().is_array_type() {
+            // e.g.: [81 x i8]* needs the additional first index (0) pointing to the array
+            vec![self.llvm.i32_type().const_zero(), offset]
+        } else {
+            vec![offset]
+        };
+        self.llvm.load_array_element(lvalue, &accessor_sequence, "tmpVar")
+    }
+
     /// generates the result of an pointer binary-expression
     ///
     /// - `operator` the binary operator
@@ -1855,7 +2104,7 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
                     }
                 }
             }
-            DataTypeInformation::Pointer { inner_type_name, auto_deref: true, .. } => {
+            DataTypeInformation::Pointer { inner_type_name, kind: PointerKind::Reference, .. } => {
                 let inner_type = self.index.get_type_information_or_void(inner_type_name);
                 self.generate_string_literal_for_type(inner_type, value, location)
             }
@@ -1910,7 +2159,7 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
                                 Diagnostic::unresolved_reference(qualified_name, data.left.get_location())
                             })?;
 
-                        let index_in_parent = member.get_location_in_parent();
+                        let index_in_parent = data_type_generator::get_field_index(self.index, member);
                         let value = self.generate_expression(data.right.as_ref())?;
 
                         uninitialized_members.remove(member);
@@ -1943,7 +2192,16 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
                         )
                     })?;
 
-                member_values.push((member.get_location_in_parent(), initial_value));
+                member_values.push((data_type_generator::get_field_index(self.index, member), initial_value));
+            }
+            //the fields aligning strings are zero-sized and hold no value
+            for member in members.iter().filter(|it| !it.is_temp() && !it.is_return()) {
+                if let Some(field) =
+                    data_type_generator::get_aligning_field(self.llvm, self.index, member.get_type_name())
+                {
+                    let index_in_parent = data_type_generator::get_field_index(self.index, member) - 1;
+                    member_values.push((index_in_parent, data_type_generator::get_default_for(field)));
+                }
             }
             let struct_type = self.llvm_index.get_associated_type(struct_name)?.into_struct_type();
             if member_values.len() == struct_type.count_fields() as usize {
@@ -2234,6 +2492,8 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
                 right_type,
                 right_statement.get_location(),
             )?;
+        } else if left_type.is_string() && right_type.is_character() {
+            self.generate_promoted_character(left, right_statement)?;
         } else if (left_type.is_struct() && right_type.is_struct())
             || (left_type.is_array() && right_type.is_array())
         {
@@ -2260,6 +2520,29 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
         Ok(())
     }
 
+    /// stores the given character as a string of length 1 at `target` (e.g. a `CHAR` promoted to a
+    /// `STRING`) and returns the pointer to the string's first character
+    pub fn generate_promoted_character(
+        &self,
+        target: PointerValue<'ink>,
+        character: &AstNode,
+    ) -> Result<PointerValue<'ink>, Diagnostic> {
+        // the character is loaded as it is, its type hint is the string it is promoted to
+        let value = self.generate_expression_value(character)?.as_r_value(self.llvm, None).into_int_value();
+        let builder = &self.llvm.builder;
+        let characters = builder.build_pointer_cast(
+            target,
+            value.get_type().ptr_type(AddressSpace::from(ADDRESS_SPACE_GENERIC)),
+            "",
+        );
+        builder.build_store(characters, value);
+        let terminator = unsafe {
+            builder.build_in_bounds_gep(characters, &[self.llvm.context.i32_type().const_int(1, false)], "")
+        };
+        builder.build_store(terminator, value.get_type().const_zero());
+        Ok(characters)
+    }
+
     pub fn generate_string_store(
         &self,
         left: inkwell::values::PointerValue<'ink>,
@@ -2418,6 +2701,10 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
                         return Err(Diagnostic::codegen_error("Cannot generate DirectAccess without base value.", original_expression.get_location()));
                     };
                     self.generate_direct_access_expression(base, &base_value, member, &data.access, &data.index)
+                } else if let (Some(bitfield_member), Some(base_value)) =
+                    (self.find_bitfield_member(original_expression), &base_value)
+                {
+                    self.generate_bitfield_member_read(base_value, bitfield_member)
                 } else {
                     let member_name = member.get_flat_reference_name().unwrap_or("unknown");
                     self.create_llvm_pointer_value_for_reference(
@@ -2440,6 +2727,9 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
                     )
                     .map_err(|_| unreachable!("invalid access statement"))
                     .map(ExpressionValue::LValue)
+                } else if self.get_type_hint_info_for(base)?.is_string() {
+                    // a single character of a string
+                    self.generate_element_pointer_for_string(base, array_idx).map(ExpressionValue::LValue)
                 } else {
                     // normal array expression
                     self.generate_element_pointer_for_array(base, array_idx).map(ExpressionValue::LValue)
@@ -2447,11 +2737,22 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
             }
 
             // INT#target (INT = base)
-            (ReferenceAccess::Cast(target), Some(_base)) => {
+            (ReferenceAccess::Cast(target), Some(base)) => {
+                let cast_type = self.annotations.get_type_or_void(base, self.index).get_type_information();
                 if target.as_ref().is_identifier() {
                     let mr =
                         AstFactory::create_member_reference(target.as_ref().clone(), None, target.get_id());
                     self.generate_expression_value(&mr)
+                } else if let (
+                    DataTypeInformation::String { encoding, .. },
+                    AstStatement::Literal(AstLiteral::String(StringValue { value, .. })),
+                    AstStatement::ReferenceExpr(ReferenceExpr { access: ReferenceAccess::Index(_), .. }),
+                ) = (cast_type, target.get_stmt(), base.get_stmt())
+                {
+                    // the literal is truncated to the length of a sized cast type, e.g. STRING#[3]'abcd'
+                    let capacity = self.get_string_size(cast_type, base.get_location())? - 1;
+                    let value = encoding.truncate(value, capacity.max(0) as usize);
+                    self.generate_string_literal(target, value, &target.get_location())
                 } else {
                     self.generate_expression_value(target.as_ref())
                 }
@@ -2460,6 +2761,10 @@ impl<'ink, 'b> ExpressionCodeGenerator<'ink, 'b> {
             // base^
             (ReferenceAccess::Deref, Some(base)) => {
                 let ptr = self.generate_expression_value(base)?;
+                // THIS already is the pointer to the instance, there is nothing to load
+                if let AstStatement::This = base.get_stmt() {
+                    return Ok(ExpressionValue::LValue(ptr.get_basic_value_enum().into_pointer_value()));
+                }
                 Ok(ExpressionValue::LValue(
                     self.llvm
                         .load_pointer(&ptr.get_basic_value_enum().into_pointer_value(), "deref")
@@ -2624,6 +2929,19 @@ fn get_dimension_lengths<'ink>(
         .collect::<Vec<_>>()
 }
 
+/// returns the sliced array, the start and the end of a slice like `arr[2..5]`
+fn get_array_slice(statement: &AstNode) -> Option<(&AstNode, &AstNode, &AstNode)> {
+    let AstStatement::ReferenceExpr(ReferenceExpr {
+        access: ReferenceAccess::Index(index),
+        base: Some(base),
+    }) = statement.get_stmt()
+    else {
+        return None;
+    };
+    let AstStatement::RangeStatement(RangeStatement { start, end }) = index.get_stmt() else { return None };
+    Some((base, start, end))
+}
+
 fn get_vla_accessor_factors<'ink>(llvm: &Llvm<'ink>, lengths: &[IntValue<'ink>]) -> Vec<IntValue<'ink>> {
     (0..lengths.len())
         .map(|idx| {
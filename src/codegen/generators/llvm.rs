@@ -87,6 +87,21 @@ impl<'a> Llvm<'a> {
         self.builder.build_alloca(*data_type, name)
     }
 
+    /// creates a local variable at the builder's location, aligned to the given number of bytes
+    /// instead of the datatype's alignment if one is given
+    pub fn create_aligned_local_variable(
+        &self,
+        name: &str,
+        data_type: &BasicTypeEnum<'a>,
+        alignment: Option<u32>,
+    ) -> PointerValue<'a> {
+        let variable = self.create_local_variable(name, data_type);
+        if let (Some(alignment), Some(instruction)) = (alignment, variable.as_instruction_value()) {
+            instruction.set_alignment(alignment).expect("alignments are powers of two");
+        }
+        variable
+    }
+
     /// sets a const-zero initializer for the given global_value according to the given type
     /// sets a const_zero initializer if the given variable_type is either an int_type or a struct_type
     ///
@@ -1,7 +1,7 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 
 use super::{
-    data_type_generator::get_default_for,
+    data_type_generator::{get_default_for, get_preceding_aligning_fields, get_string_alignment},
     expression_generator::ExpressionCodeGenerator,
     llvm::{GlobalValueExt, Llvm},
     statement_generator::{FunctionContext, StatementCodeGenerator},
@@ -28,6 +28,7 @@ use crate::index::{ImplementationIndexEntry, VariableIndexEntry};
 use crate::index::Index;
 use indexmap::{IndexMap, IndexSet};
 use inkwell::{
+    attributes::{Attribute, AttributeLoc},
     module::Module,
     types::{BasicMetadataTypeEnum, BasicTypeEnum, FunctionType},
     values::{BasicValue, BasicValueEnum, FunctionValue},
@@ -46,6 +47,8 @@ pub struct PouGenerator<'ink, 'cg> {
     index: &'cg Index,
     annotations: &'cg AstAnnotations,
     llvm_index: &'cg LlvmTypedIndex<'ink>,
+    /// whether assignments to sub-range variables call the matching `CheckRange*` function
+    range_checks: bool,
 }
 
 /// Creates opaque implementations for all callable items in the index
@@ -79,6 +82,18 @@ pub fn generate_implementation_stubs<'ink>(
         }
     }
 
+    // builtins lowered to llvm intrinsics need them declared in this module
+    let builtins = dependencies.iter().filter_map(|it| match it {
+        Dependency::Call(name) => index.get_builtin_function(name),
+        _ => None,
+    });
+    for builtin in builtins {
+        for intrinsic in builtin.generate_intrinsic_declarations(module)? {
+            llvm_index
+                .associate_implementation(intrinsic.get_name().to_str().unwrap_or_default(), intrinsic)?;
+        }
+    }
+
     Ok(llvm_index)
 }
 
@@ -147,7 +162,12 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
         annotations: &'cg AstAnnotations,
         llvm_index: &'cg LlvmTypedIndex<'ink>,
     ) -> PouGenerator<'ink, 'cg> {
-        PouGenerator { llvm, index, annotations, llvm_index }
+        PouGenerator { llvm, index, annotations, llvm_index, range_checks: true }
+    }
+
+    /// lets assignments to sub-range variables call the matching `CheckRange*` function
+    pub fn with_range_checks(self, range_checks: bool) -> Self {
+        PouGenerator { range_checks, ..self }
     }
 
     /// generates an empty llvm function for the given implementation, including all parameters and the return type
@@ -219,7 +239,11 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
 
         let function_declaration = self.create_llvm_function_type(parameters, variadic, return_type_llvm)?;
 
-        let curr_f = module.add_function(implementation.get_call_name(), function_declaration, None);
+        let curr_f = module.add_function(implementation.get_symbol_name(), function_declaration, None);
+        for attribute in implementation.get_function_attributes() {
+            let kind = Attribute::get_named_enum_kind_id(attribute);
+            curr_f.add_attribute(AttributeLoc::Function, self.llvm.context.create_enum_attribute(kind, 0));
+        }
 
         let pou_name = implementation.get_call_name();
         if let Some(pou) = self.index.find_pou(pou_name) {
@@ -386,6 +410,7 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
                 &local_index,
                 &function_context,
                 debug,
+                self.range_checks,
             );
             statement_gen.generate_body(&implementation.statements)?;
             statement_gen.generate_return_statement()?;
@@ -487,9 +512,11 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
                     .next()
                     .ok_or_else(|| Diagnostic::missing_function(m.source_location.clone()))?;
 
-                let ptr = self
-                    .llvm
-                    .create_local_variable(m.get_name(), &index.get_associated_type(m.get_type_name())?);
+                let ptr = self.llvm.create_aligned_local_variable(
+                    m.get_name(),
+                    &index.get_associated_type(m.get_type_name())?,
+                    get_string_alignment(self.index, m.get_type_name()),
+                );
 
                 if let Some(block) = self.llvm.builder.get_insert_block() {
                     debug.add_variable_declaration(
@@ -506,7 +533,8 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
                 (parameter_name, ptr)
             } else {
                 let temp_type = index.get_associated_type(m.get_type_name())?;
-                let value = self.llvm.create_local_variable(parameter_name, &temp_type);
+                let alignment = get_string_alignment(self.index, m.get_type_name());
+                let value = self.llvm.create_aligned_local_variable(parameter_name, &temp_type, alignment);
                 (parameter_name, value)
             };
 
@@ -552,12 +580,15 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
 
             let (name, variable) = if m.is_temp() || m.is_return() {
                 let temp_type = index.get_associated_type(m.get_type_name())?;
-                (parameter_name, self.llvm.create_local_variable(parameter_name, &temp_type))
+                let alignment = get_string_alignment(self.index, m.get_type_name());
+                let value = self.llvm.create_aligned_local_variable(parameter_name, &temp_type, alignment);
+                (parameter_name, value)
             } else {
+                let field = var_count + get_preceding_aligning_fields(self.index, m);
                 let ptr = self
                     .llvm
                     .builder
-                    .build_struct_gep(param_pointer, var_count as u32, parameter_name)
+                    .build_struct_gep(param_pointer, field, parameter_name)
                     .expect(INTERNAL_LLVM_ERROR);
 
                 var_count += 1;
@@ -8,8 +8,8 @@ use super::{
 use crate::{
     codegen::{debug::Debug, llvm_typesystem::cast_if_needed},
     codegen::{debug::DebugBuilderEnum, LlvmTypedIndex},
-    index::{ImplementationIndexEntry, Index},
-    resolver::{AnnotationMap, AstAnnotations, StatementAnnotation},
+    index::{ImplementationIndexEntry, Index, VariableIndexEntry},
+    resolver::{const_evaluator, AnnotationMap, AstAnnotations, StatementAnnotation},
     typesystem::{self, DataTypeInformation},
 };
 use inkwell::{
@@ -20,8 +20,8 @@ use inkwell::{
 };
 use plc_ast::{
     ast::{
-        flatten_expression_list, AstFactory, AstNode, AstStatement, JumpStatement, LabelStatement, Operator,
-        ReferenceAccess, ReferenceExpr,
+        contains_label, flatten_expression_list, AstFactory, AstNode, AstStatement, JumpStatement,
+        LabelStatement, Operator, ReferenceAccess, ReferenceExpr,
     },
     control_statements::{AstControlStatement, ConditionalBlock, ReturnStatement},
 };
@@ -55,6 +55,9 @@ pub struct StatementCodeGenerator<'a, 'b> {
     pub current_loop_continue: Option<BasicBlock<'a>>,
 
     pub debug: &'b DebugBuilderEnum<'a>,
+
+    /// whether assignments to sub-range variables call the matching `CheckRange*` function
+    range_checks: bool,
 }
 
 impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
@@ -66,6 +69,7 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
         llvm_index: &'b LlvmTypedIndex<'a>,
         linking_context: &'b FunctionContext<'a, 'b>,
         debug: &'b DebugBuilderEnum<'a>,
+        range_checks: bool,
     ) -> StatementCodeGenerator<'a, 'b> {
         StatementCodeGenerator {
             llvm,
@@ -78,6 +82,7 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
             current_loop_exit: None,
             current_loop_continue: None,
             debug,
+            range_checks,
         }
     }
 
@@ -252,6 +257,9 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
             return Ok(());
         }
         let exp_gen = self.create_expr_generator();
+        if let Some(member) = exp_gen.find_bitfield_member(left_statement) {
+            return self.generate_bitfield_member_assignment(left_statement, member, right_statement);
+        }
         let left: PointerValue = exp_gen.generate_expression_value(left_statement).and_then(|it| {
             it.get_basic_value_enum().try_into().map_err(|err| {
                 Diagnostic::codegen_error(format!("{err:?}").as_str(), left_statement.get_location())
@@ -261,12 +269,13 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
         let left_type = exp_gen.get_type_hint_info_for(left_statement)?;
         // if the lhs-type is a subrange type we may need to generate a check-call
         // e.g. x := y,  ==> x := CheckSignedInt(y);
-        let range_checked_right_side = if let DataTypeInformation::SubRange { .. } = left_type {
-            // there is a sub-range defined, so we need to wrap the right side into the check function if it exists
-            self.annotations.get_hidden_function_call(right_statement)
-        } else {
-            None
-        };
+        let range_checked_right_side =
+            if self.range_checks && matches!(left_type, DataTypeInformation::SubRange { .. }) {
+                // there is a sub-range defined, so we need to wrap the right side into the check function if it exists
+                self.annotations.get_hidden_function_call(right_statement)
+            } else {
+                None
+            };
 
         let right_statement = range_checked_right_side.unwrap_or(right_statement);
 
@@ -280,6 +289,48 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
         self.debug.set_debug_location(self.llvm, &self.function_context.function, line, column);
     }
 
+    /// generates an assignment to a member of a `{bitfield}` struct by masking its bit in the
+    /// containing byte, e.g. `status.ready := TRUE`
+    fn generate_bitfield_member_assignment(
+        &self,
+        left_statement: &AstNode,
+        member: &VariableIndexEntry,
+        right_statement: &AstNode,
+    ) -> Result<(), Diagnostic> {
+        let exp_gen = self.create_expr_generator();
+        let AstStatement::ReferenceExpr(ReferenceExpr { base: Some(base), .. }) = left_statement.get_stmt()
+        else {
+            return Err(Diagnostic::codegen_error(
+                &format!("Cannot access bitfield member '{}' without its bitfield", member.get_name()),
+                left_statement.get_location(),
+            ));
+        };
+        let bitfield = exp_gen.generate_lvalue(base)?;
+        let (byte, bit) = exp_gen.generate_bitfield_byte_pointer(bitfield, member);
+        let byte_type = self.llvm.context.i8_type();
+        let bit = byte_type.const_int(bit as u64, false);
+
+        //Erase the member's bit
+        let mask = self
+            .llvm
+            .builder
+            .build_not(self.llvm.builder.build_left_shift(byte_type.const_int(1, false), bit, ""), "invert");
+        let erased =
+            self.llvm.builder.build_and(self.llvm.load_pointer(&byte, "").into_int_value(), mask, "erase");
+
+        //Generate the right side as a single bit and move it to the member's position
+        let right = exp_gen.generate_expression(right_statement)?;
+        let right_type = exp_gen.get_type_hint_for(right_statement)?;
+        let left_type = self.index.get_type_or_panic(typesystem::BOOL_TYPE);
+        let right = cast_if_needed!(self, left_type, right_type, right, None).into_int_value();
+        let right = self.llvm.builder.build_and(right, byte_type.const_int(1, false), "");
+        let value = self.llvm.builder.build_left_shift(right, bit, "value");
+
+        let or_value = self.llvm.builder.build_or(erased, value, "or");
+        self.llvm.builder.build_store(byte, or_value);
+        Ok(())
+    }
+
     fn generate_direct_access_assignment(
         &self,
         left_statement: &AstNode,
@@ -721,6 +772,29 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
         conditional_blocks: &[ConditionalBlock],
         else_body: &[AstNode],
     ) -> Result<(), Diagnostic> {
+        // drop the branches whose condition is constant FALSE, a constant TRUE condition
+        // becomes the else-branch and makes all following branches unreachable. Branches
+        // containing a label are kept, since they can still be reached by a JMP
+        let scope = self.function_context.linking_context.get_type_name();
+        let is_constant = |block: &ConditionalBlock, value: bool| {
+            const_evaluator::evaluate_constant_condition(&block.condition, Some(scope), self.index)
+                == Some(value)
+        };
+        let mut else_body = else_body;
+        let mut conditional_blocks = conditional_blocks.iter().collect::<Vec<_>>();
+        if let Some(position) = conditional_blocks.iter().position(|it| is_constant(it, true)) {
+            let following_blocks = &conditional_blocks[position + 1..];
+            if !following_blocks.iter().any(|it| contains_label(&it.body)) && !contains_label(else_body) {
+                let constant_block: &ConditionalBlock = conditional_blocks[position];
+                else_body = &constant_block.body;
+                conditional_blocks.truncate(position);
+            }
+        }
+        conditional_blocks.retain(|it| !is_constant(it, false) || contains_label(&it.body));
+        if conditional_blocks.is_empty() {
+            return self.generate_body(else_body);
+        }
+
         let (builder, current_function, context) = self.get_llvm_deps();
         let mut blocks = vec![builder.get_insert_block().expect(INTERNAL_LLVM_ERROR)];
         for _ in 1..conditional_blocks.len() {
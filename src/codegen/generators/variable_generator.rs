@@ -3,19 +3,26 @@
 /// offers operations to generate global variables
 use crate::{
     codegen::{debug::Debug, llvm_index::LlvmTypedIndex, llvm_typesystem::cast_if_needed},
+    hardware_binding::{get_memory_region_name, get_memory_region_offset},
     index::{get_initializer_name, Index, PouIndexEntry, VariableIndexEntry},
     resolver::{AnnotationMap, AstAnnotations, Dependency},
 };
 use indexmap::IndexSet;
-use inkwell::{module::Module, values::GlobalValue};
+use inkwell::{
+    module::Module,
+    types::BasicType,
+    values::{GlobalValue, PointerValue},
+    AddressSpace,
+};
 use plc_ast::ast::LinkageType;
 use plc_diagnostics::{diagnostics::Diagnostic, errno::ErrNo};
 use plc_source::source_location::SourceLocation;
 
 use super::{
-    data_type_generator::get_default_for,
+    data_type_generator::{get_default_for, get_string_alignment},
     expression_generator::ExpressionCodeGenerator,
     llvm::{GlobalValueExt, Llvm},
+    ADDRESS_SPACE_GENERIC,
 };
 use crate::codegen::debug::DebugBuilderEnum;
 
@@ -76,6 +83,10 @@ impl<'ctx, 'b> VariableGenerator<'ctx, 'b> {
         });
 
         for (name, variable) in globals {
+            if let Some(address) = self.generate_hardware_address(variable)? {
+                index.associate_hardware_address(name, address);
+                continue;
+            }
             let linkage =
                 if !variable.is_in_unit(location) { LinkageType::External } else { variable.get_linkage() };
             let global_variable =
@@ -98,6 +109,30 @@ impl<'ctx, 'b> VariableGenerator<'ctx, 'b> {
         Ok(index)
     }
 
+    /// returns a pointer into the runtime's memory region for variables bound to a fixed
+    /// hardware address (e.g. `x AT %MW100 : WORD`), or `None` if the variable has no such binding
+    fn generate_hardware_address(
+        &self,
+        variable: &VariableIndexEntry,
+    ) -> Result<Option<PointerValue<'ctx>>, Diagnostic> {
+        let Some((binding, offset)) = variable.get_hardware_binding().and_then(|binding| {
+            get_memory_region_offset(binding, self.global_index).map(|offset| (binding, offset))
+        }) else {
+            return Ok(None);
+        };
+
+        let region_name = get_memory_region_name(&binding.direction);
+        let region = self.module.get_global(region_name).unwrap_or_else(|| {
+            self.llvm
+                .create_global_variable(self.module, region_name, self.llvm.context.i8_type().into())
+                .make_external()
+        });
+        let variable_type = self.types_index.get_associated_type(variable.get_type_name())?;
+        let offset = self.llvm.context.i64_type().const_int(offset, false);
+        let address = unsafe { region.as_pointer_value().const_gep(&[offset]) };
+        Ok(Some(address.const_cast(variable_type.ptr_type(AddressSpace::from(ADDRESS_SPACE_GENERIC)))))
+    }
+
     /// convenience function to generates a global variable for the given variable
     ///
     /// - `module` the module to generate the variable into
@@ -114,6 +149,10 @@ impl<'ctx, 'b> VariableGenerator<'ctx, 'b> {
 
         let mut global_ir_variable =
             self.llvm.create_global_variable(self.module, global_variable.get_name(), variable_type);
+        // strings are only aligned explicitly if the project overrides their alignment
+        if let Some(alignment) = get_string_alignment(self.global_index, type_name) {
+            global_ir_variable.set_alignment(alignment);
+        }
         if linkage == LinkageType::External {
             global_ir_variable = global_ir_variable.make_external();
         } else {
@@ -0,0 +1,100 @@
+//! The memory layout LLVM gives the types of an index on a target.
+//!
+//! Descriptions of a library's interface (e.g. its metadata or C header) take their sizes and
+//! offsets from here, so they match the generated code rather than the index's estimates.
+use indexmap::IndexSet;
+use inkwell::{
+    targets::{RelocMode, TargetData},
+    types::{BasicType, BasicTypeEnum},
+};
+use plc_diagnostics::diagnostics::Diagnostic;
+
+use super::{
+    debug::DebugBuilderEnum,
+    generators::{data_type_generator, llvm::Llvm},
+    llvm_index::LlvmTypedIndex,
+    CodegenContext, GeneratedModule, TargetTuning,
+};
+use crate::{
+    index::Index,
+    resolver::{AstAnnotations, Dependency},
+    typesystem::{DataType, DataTypeInformation, StructSource},
+    OptimizationLevel, Target,
+};
+
+/// The sizes, alignments and member offsets of the types of an index on a target
+pub struct TypeLayouts<'ink> {
+    types: LlvmTypedIndex<'ink>,
+    target_data: TargetData,
+}
+
+impl<'ink> TypeLayouts<'ink> {
+    /// Generates the LLVM types of all types and POUs of the given index, generic ones are skipped
+    pub fn new(
+        context: &'ink CodegenContext,
+        index: &Index,
+        annotations: &AstAnnotations,
+        target: &Target,
+    ) -> Result<Self, Diagnostic> {
+        let machine = GeneratedModule::create_target_machine(
+            target,
+            RelocMode::Default,
+            OptimizationLevel::None,
+            &TargetTuning::default(),
+        )?;
+        let dependencies = index
+            .get_types()
+            .values()
+            .chain(index.get_pou_types().values())
+            .map(|it| Dependency::Datatype(it.get_name().to_string()))
+            .collect::<IndexSet<_>>();
+        let types = data_type_generator::generate_data_types(
+            &Llvm::new(context, context.create_builder()),
+            &mut DebugBuilderEnum::None,
+            &dependencies,
+            index,
+            annotations,
+        )?;
+        Ok(TypeLayouts { types, target_data: machine.get_target_data() })
+    }
+
+    /// returns the size of the given type in bytes
+    pub fn get_size(&self, data_type: &DataType) -> Option<u64> {
+        self.find_type(data_type).map(|it| self.target_data.get_abi_size(&it))
+    }
+
+    /// returns the alignment of the given type in bytes
+    pub fn get_alignment(&self, data_type: &DataType) -> Option<u32> {
+        self.find_type(data_type).map(|it| self.target_data.get_abi_alignment(&it))
+    }
+
+    /// returns the byte-offset of the n-th member of the given struct. Temporaries and return
+    /// values are not part of the struct and do not count as members
+    pub fn get_member_offset(&self, index: &Index, data_type: &DataType, member: u32) -> Option<u64> {
+        let BasicTypeEnum::StructType(struct_type) = self.find_type(data_type)? else { return None };
+        // strings with an overridden alignment are preceded by a zero-sized field aligning them
+        let field = data_type
+            .get_members()
+            .iter()
+            .filter(|it| !it.is_temp() && !it.is_return())
+            .nth(member as usize)
+            .map(|it| member + data_type_generator::get_preceding_aligning_fields(index, it))?;
+        if field < struct_type.count_fields() {
+            self.target_data.offset_of_element(&struct_type, field)
+        } else {
+            None
+        }
+    }
+
+    fn find_type(&self, data_type: &DataType) -> Option<BasicTypeEnum<'ink>> {
+        let name = data_type.get_name();
+        // the instance struct of a POU may share its name with a type
+        match data_type.get_type_information() {
+            DataTypeInformation::Struct { source: StructSource::Pou(_), .. } => {
+                self.types.find_associated_pou_type(name)
+            }
+            _ => self.types.find_associated_type(name),
+        }
+        .filter(|it| it.is_sized())
+    }
+}
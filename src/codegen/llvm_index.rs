@@ -153,6 +153,11 @@ impl<'ink> LlvmTypedIndex<'ink> {
         Ok(())
     }
 
+    /// associates a variable bound to a fixed hardware address with its address
+    pub fn associate_hardware_address(&mut self, variable_name: &str, address: PointerValue<'ink>) {
+        self.initial_value_associations.insert(variable_name.to_lowercase(), address.into());
+    }
+
     pub fn associate_implementation(
         &mut self,
         callable_name: &str,
@@ -15,5 +15,6 @@ mod statement_codegen_test;
 mod string_tests;
 #[cfg(feature = "verify")]
 mod switch_case_tests;
+mod target_machine_tests;
 mod typesystem_test;
 mod vla_tests;
@@ -932,6 +932,28 @@ fn class_method_in_pou() {
     insta::assert_snapshot!(result)
 }
 
+#[test]
+fn this_in_fb_body_and_method_points_to_the_instance() {
+    let result = codegen(
+        "
+        FUNCTION_BLOCK MyFb
+            VAR
+                x, y : INT;
+            END_VAR
+
+            METHOD testMethod
+                VAR_INPUT myMethodArg : INT; END_VAR
+                THIS^.x := myMethodArg;
+            END_METHOD
+
+            THIS^.y := THIS^.x;
+        END_FUNCTION_BLOCK
+        ",
+    );
+
+    insta::assert_snapshot!(result)
+}
+
 #[test]
 fn fb_method_in_pou() {
     let result = codegen(
@@ -2065,6 +2087,26 @@ fn structs_are_generated() {
     insta::assert_snapshot!(result);
 }
 
+#[test]
+fn unions_are_generated_as_their_most_aligned_member_padded_to_the_largest_member() {
+    let result = codegen(
+        "
+        TYPE MyUnion: UNION
+          a: BYTE;
+          b: LREAL;
+          c: STRING[10];
+        END_UNION
+        END_TYPE
+
+        VAR_GLOBAL
+          u : MyUnion;
+        END_VAR
+        ",
+    );
+
+    insta::assert_snapshot!(result);
+}
+
 #[test]
 fn arrays_are_generated() {
     let result = codegen(
@@ -3314,3 +3356,23 @@ fn array_of_struct_as_member_of_another_struct_and_variable_declaration_is_initi
 
     insta::assert_snapshot!(res);
 }
+
+#[test]
+fn variables_bound_to_absolute_addresses_are_placed_in_memory_regions() {
+    let result = codegen(
+        "
+        VAR_GLOBAL
+            a AT %MW100 : WORD;
+            b AT %ID2 : DWORD;
+            c AT %IX1.2 : BOOL;
+        END_VAR
+
+        PROGRAM prg
+            a := 5;
+            b := a;
+            c := TRUE;
+        END_PROGRAM
+        ",
+    );
+    insta::assert_snapshot!(result);
+}
@@ -223,9 +223,9 @@ fn if_conditions_location_marked() {
     let result = codegen_with_debug(
         "
         FUNCTION myFunc : DINT
-            IF TRUE THEN
+            IF myFunc > 1 THEN
                 myFunc := 1;
-            ELSIF FALSE THEN
+            ELSIF myFunc > 2 THEN
                 myFunc := 1;
             ELSE 
                 myFunc := 1;
@@ -370,3 +370,47 @@ fn return_variable_in_nested_call() {
     // we want a call passing the return-variable as apointer (actually the adress as a LWORD)
     insta::assert_snapshot!(codegen(src));
 }
+
+#[test]
+fn extern_name_is_used_as_the_symbol_of_the_function() {
+    // GIVEN a function and an external function with an extern_name attribute
+    let src = "
+        {extern_name := 'my_c_func'}
+        FUNCTION foo : DINT
+            VAR_INPUT x : DINT; END_VAR
+            foo := x;
+        END_FUNCTION
+
+        {external}
+        {extern_name := 'c_abs'}
+        FUNCTION ext_abs : DINT
+            VAR_INPUT x : DINT; END_VAR
+        END_FUNCTION
+
+        PROGRAM main
+            VAR r : DINT; END_VAR
+            r := foo(1);
+            r := ext_abs(-1);
+        END_PROGRAM
+          ";
+
+    // we expect definitions, declarations and calls to use the extern name
+    insta::assert_snapshot!(codegen(src));
+}
+
+#[test]
+fn gnu_attr_is_added_as_attribute_of_the_function() {
+    // GIVEN a function requesting the noinline attribute
+    let src = "
+        {gnu_attr := 'noinline'}
+        FUNCTION foo : DINT
+            VAR_INPUT x : DINT; END_VAR
+            foo := x;
+        END_FUNCTION
+          ";
+
+    // we expect the generated function to carry the noinline attribute
+    let result = codegen(src);
+    assert!(result.contains("define i32 @foo(i32 %0) #0 {"), "{result}");
+    assert!(result.contains("attributes #0 = { noinline }"), "{result}");
+}
@@ -184,3 +184,55 @@ fn floating_point_type_casting() {
 
     insta::assert_snapshot!(result);
 }
+
+#[test]
+fn branches_with_constant_conditions_are_not_generated() {
+    let result = codegen(
+        "
+        FUNCTION main : DINT
+            IF FALSE THEN
+                main := 11;
+            ELSIF TRUE THEN
+                main := 22;
+            ELSE
+                main := 33;
+            END_IF
+        END_FUNCTION
+        ",
+    );
+
+    // the constant TRUE branch is generated without any branching, all other branches are dropped
+    assert!(result.contains("store i32 22"), "{result}");
+    assert!(!result.contains("store i32 11"), "{result}");
+    assert!(!result.contains("store i32 33"), "{result}");
+    assert!(!result.contains("condition_body"), "{result}");
+}
+
+#[test]
+fn branches_with_constant_false_conditions_are_dropped_from_if_chains() {
+    let result = codegen(
+        "
+        VAR_GLOBAL CONSTANT
+            debug : BOOL := FALSE;
+        END_VAR
+
+        FUNCTION main : DINT
+        VAR
+            x : DINT;
+        END_VAR
+            IF debug THEN
+                main := 11;
+            ELSIF x > 0 THEN
+                main := 22;
+            ELSE
+                main := 33;
+            END_IF
+        END_FUNCTION
+        ",
+    );
+
+    assert!(!result.contains("store i32 11"), "{result}");
+    assert!(result.contains("store i32 22"), "{result}");
+    assert!(result.contains("store i32 33"), "{result}");
+    assert!(result.contains("condition_body"), "{result}");
+}
@@ -0,0 +1,196 @@
+use std::io::Write;
+
+use inkwell::targets::RelocMode;
+use plc_diagnostics::diagnostics::Diagnostic;
+use plc_source::source_location::SourceLocation;
+
+use crate::{
+    codegen::{persist_atomically, validate_targets, GeneratedModule, TargetTuning},
+    test_utils::tests::{codegen_optimized, codegen_to_object},
+    OptimizationLevel, Target,
+};
+
+#[test]
+fn target_machine_uses_the_given_cpu_and_features() {
+    let target = Target::from("x86_64-unknown-linux-gnu");
+    let tuning = TargetTuning { cpu: Some("skylake".into()), features: Some("+avx2,-sse4a".into()) };
+
+    let machine =
+        GeneratedModule::create_target_machine(&target, RelocMode::Default, OptimizationLevel::None, &tuning)
+            .unwrap();
+
+    assert_eq!(machine.get_cpu().to_str().unwrap(), "skylake");
+    assert_eq!(machine.get_feature_string().to_str().unwrap(), "+avx2,-sse4a");
+}
+
+#[test]
+fn target_machine_defaults_to_the_generic_cpu() {
+    let target = Target::from("x86_64-unknown-linux-gnu");
+
+    let machine = GeneratedModule::create_target_machine(
+        &target,
+        RelocMode::Default,
+        OptimizationLevel::None,
+        &TargetTuning::default(),
+    )
+    .unwrap();
+
+    assert_eq!(machine.get_cpu().to_str().unwrap(), "generic");
+    assert_eq!(machine.get_feature_string().to_str().unwrap(), "");
+}
+
+#[test]
+fn features_without_a_sign_are_reported() {
+    let target = Target::from("thumbv7em-none-eabihf");
+    let tuning = TargetTuning { cpu: None, features: Some("+vfp4,neon, -d32,".into()) };
+
+    assert_eq!(tuning.validate(&target), vec![Diagnostic::invalid_target_feature("neon")]);
+}
+
+#[test]
+#[ignore = "looks the names up in the tables of llc, which is not installed everywhere"]
+fn cpus_and_features_unknown_to_the_target_are_reported() {
+    let target = Target::from("x86_64-unknown-linux-gnu");
+    let known = TargetTuning { cpu: Some("skylake".into()), features: Some("+avx2,-sse4a".into()) };
+    let unknown = TargetTuning { cpu: Some("cortex-m4".into()), features: Some("+avx2,+neon".into()) };
+
+    assert_eq!(known.validate(&target), vec![]);
+    assert_eq!(
+        unknown.validate(&target),
+        vec![
+            Diagnostic::unknown_target_cpu("cortex-m4", "x86_64-unknown-linux-gnu"),
+            Diagnostic::unknown_target_feature("neon", "x86_64-unknown-linux-gnu"),
+        ]
+    );
+}
+
+#[test]
+fn unknown_targets_are_reported() {
+    let targets = vec![Target::from("x86_64-unknown-linux-gnu"), Target::from("bogus-triple")];
+
+    assert_eq!(validate_targets(&targets), Err(Diagnostic::unknown_targets(&["bogus-triple"])));
+}
+
+#[test]
+fn known_targets_are_accepted() {
+    let targets = vec![Target::System, Target::from("x86_64-unknown-linux-gnu")];
+
+    assert_eq!(validate_targets(&targets), Ok(()));
+}
+
+#[test]
+fn inline_threshold_decides_whether_small_functions_are_inlined() {
+    let src = "
+        FUNCTION scale : DINT
+        VAR_INPUT
+            value : DINT;
+            factor : DINT;
+        END_VAR
+            scale := value * factor;
+            scale := scale / (factor + 3);
+            scale := scale * scale - value;
+            scale := scale MOD (value + 7);
+        END_FUNCTION
+
+        FUNCTION caller : DINT
+        VAR_INPUT
+            x : DINT;
+            y : DINT;
+        END_VAR
+            caller := scale(x, y);
+        END_FUNCTION
+    ";
+
+    let not_inlined = codegen_optimized(src, OptimizationLevel::Default, Some(0));
+    let inlined = codegen_optimized(src, OptimizationLevel::Default, Some(1000));
+
+    assert!(not_inlined.contains("call i32 @scale("));
+    assert!(!inlined.contains("call i32 @scale("));
+}
+
+#[test]
+fn constant_initializers_are_emitted_in_the_byte_order_of_the_target() {
+    let src = "
+        VAR_GLOBAL
+            value : DWORD := 16#11223344;
+            values : ARRAY[0..1] OF WORD := [16#5566, 16#7788];
+        END_VAR
+    ";
+    let contains = |object: &[u8], bytes: &[u8]| object.windows(bytes.len()).any(|it| it == bytes);
+
+    let (little_endian, ir) =
+        codegen_to_object(src, &Target::from("x86_64-unknown-linux-gnu"), OptimizationLevel::Default);
+    assert!(ir.contains("target datalayout = \"e-"));
+    assert!(contains(&little_endian, &[0x44, 0x33, 0x22, 0x11]));
+    assert!(contains(&little_endian, &[0x66, 0x55, 0x88, 0x77]));
+
+    let (big_endian, ir) =
+        codegen_to_object(src, &Target::from("mips-unknown-linux-gnu"), OptimizationLevel::Default);
+    assert!(ir.contains("target triple = \"mips-unknown-linux-gnu\""));
+    assert!(ir.contains("target datalayout = \"E-"));
+    assert!(contains(&big_endian, &[0x11, 0x22, 0x33, 0x44]));
+    assert!(contains(&big_endian, &[0x55, 0x66, 0x77, 0x88]));
+}
+
+#[test]
+fn persisted_modules_contain_the_ir_optimized_for_the_target() {
+    let src = "
+        FUNCTION main : DINT
+        VAR_INPUT
+            a : DINT;
+        END_VAR
+        VAR
+            unused : DINT;
+        END_VAR
+            unused := a * 42;
+            main := a;
+        END_FUNCTION
+    ";
+
+    //Without optimizations, the unused multiplication is kept
+    let (_, ir) = codegen_to_object(src, &Target::from("mips-unknown-linux-gnu"), OptimizationLevel::None);
+    assert!(ir.contains("mul i32"), "{ir}");
+
+    //The IR printed by `--print-ir-after-opt` is the one the object was generated from
+    let (_, ir) =
+        codegen_to_object(src, &Target::from("mips-unknown-linux-gnu"), OptimizationLevel::Aggressive);
+    assert!(ir.contains("target triple = \"mips-unknown-linux-gnu\""), "{ir}");
+    assert!(!ir.contains("mul i32"), "{ir}");
+    assert!(ir.contains("ret i32 %"), "{ir}");
+}
+
+#[test]
+fn persisted_files_are_renamed_into_place_once_complete() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("unit.o");
+
+    persist_atomically(&output, |path| {
+        // the output is not visible while it is written
+        assert!(!output.exists());
+        std::fs::write(path, "first part").unwrap();
+        std::fs::OpenOptions::new().append(true).open(path).unwrap().write_all(b", second part").unwrap();
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(std::fs::read_to_string(&output).unwrap(), "first part, second part");
+    let files = std::fs::read_dir(dir.path()).unwrap().map(|it| it.unwrap().path()).collect::<Vec<_>>();
+    assert_eq!(files, vec![output]);
+}
+
+#[test]
+fn failed_writes_keep_the_previous_output() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("unit.o");
+    std::fs::write(&output, "previous").unwrap();
+
+    let result = persist_atomically(&output, |path| {
+        std::fs::write(path, "trunc").unwrap();
+        Err(Diagnostic::codegen_error("interrupted", SourceLocation::undefined()))
+    });
+
+    assert!(result.is_err());
+    assert_eq!(std::fs::read_to_string(&output).unwrap(), "previous");
+    let files = std::fs::read_dir(dir.path()).unwrap().map(|it| it.unwrap().path()).collect::<Vec<_>>();
+    assert_eq!(files, vec![output]);
+}
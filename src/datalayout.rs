@@ -1,5 +1,7 @@
 use std::ops::{Add, AddAssign};
 
+use crate::Target;
+
 #[derive(Copy, Clone, Debug)]
 pub struct DataLayout {
     pub i1: Bytes,
@@ -9,10 +11,13 @@ pub struct DataLayout {
     pub i64: Bytes,
     pub f32: Bytes,
     pub f64: Bytes,
+    /// the size and alignment of pointers
     pub p64: Bytes,
     pub v64: Bytes,
     pub v128: Bytes,
     pub aggregate: Bytes,
+    /// the alignment of strings if it differs from the alignment of other arrays
+    pub string: Option<Bytes>,
 }
 
 impl Default for DataLayout {
@@ -30,6 +35,30 @@ impl Default for DataLayout {
             v64: Bytes::from_bits(64),
             v128: Bytes::from_bits(128),
             aggregate: Bytes::from_bits(64),
+            string: None,
+        }
+    }
+}
+
+impl DataLayout {
+    /// returns the type layout of the given target
+    /// 16-bit microcontrollers (e.g. `msp430`) use 16-bit pointers and align wider types to 16 bits
+    pub fn for_target(target: &Target) -> Self {
+        let triple = target.get_target_triple();
+        let triple = triple.as_str().to_string_lossy();
+        match triple.split('-').next() {
+            Some("msp430") => Self {
+                i32: Bytes::from_bits(16),
+                i64: Bytes::from_bits(16),
+                f32: Bytes::from_bits(16),
+                f64: Bytes::from_bits(16),
+                p64: Bytes::from_bits(16),
+                v64: Bytes::from_bits(16),
+                v128: Bytes::from_bits(16),
+                aggregate: Bytes::from_bits(16),
+                ..Self::default()
+            },
+            _ => Self::default(),
         }
     }
 }
@@ -167,6 +196,24 @@ mod tests {
         assert_eq!(i64_size.align_to(Bytes::new(8)), i64_size);
     }
 
+    #[test]
+    fn pointer_size_depends_on_the_target_layout() {
+        //Given a pointer type
+        let (_, mut index) = index("TYPE IntRef : REF_TO INT; END_TYPE");
+        let pointer_size = |index: &crate::index::Index| {
+            index.find_effective_type_info("IntRef").unwrap().get_size(index).bits()
+        };
+
+        //When compiling for a 64-bit target, pointers have 64 bits
+        index.set_type_layout(DataLayout::for_target(&Target::from("x86_64-unknown-linux-gnu")));
+        assert_eq!(pointer_size(&index), 64);
+
+        //When compiling for a 16-bit target, pointers have 16 bits
+        index.set_type_layout(DataLayout::for_target(&Target::from("msp430-none-elf")));
+        assert_eq!(pointer_size(&index), 16);
+        assert_eq!(index.find_effective_type_info("IntRef").unwrap().get_alignment(&index), Bytes::new(2));
+    }
+
     #[test]
     fn struct_with_default_alignment() {
         //Given the default data layout
@@ -5,7 +5,11 @@ use serde::{
     Serialize, Serializer,
 };
 
-use crate::{expression_path::ExpressionPath, index::Index, ConfigFormat};
+use crate::{
+    expression_path::ExpressionPath,
+    index::{self, Index},
+    ConfigFormat,
+};
 
 trait SerializeWithContext {
     fn serialize<S>(&self, ctx: &Index, serializer: S) -> Result<S::Ok, S::Error>
@@ -145,6 +149,29 @@ pub fn collect_hardware_configuration(index: &Index) -> Result<HardwareConfigura
         .map_err(|message| Diagnostic::GeneralError { err_no: ErrNo::general__io_err, message })
 }
 
+/// Returns the name of the memory region provided by the runtime in which variables
+/// bound to the given direction (e.g. `%M`) are placed
+pub fn get_memory_region_name(direction: &HardwareAccessType) -> &'static str {
+    match direction {
+        HardwareAccessType::Input => "__INPUT_AREA",
+        HardwareAccessType::Output => "__OUTPUT_AREA",
+        HardwareAccessType::Memory => "__MEMORY_AREA",
+        HardwareAccessType::Global => "__GLOBAL_AREA",
+    }
+}
+
+/// Returns the byte-offset of the given binding inside its memory region (e.g. `%MW100` is placed at byte 200).
+/// Only bindings with a single address addressing whole bytes have a fixed offset, bit-, template- and
+/// multi-segment addresses return `None`
+pub fn get_memory_region_offset(binding: &index::HardwareBinding, index: &Index) -> Option<u64> {
+    if matches!(binding.access, DirectAccessType::Bit | DirectAccessType::Template) {
+        return None;
+    }
+    let [address] = binding.entries.as_slice() else { return None };
+    let address = index.get_const_expressions().get_constant_int_statement_value(address).ok()?;
+    u64::try_from(address).ok().map(|it| it * binding.access.get_bit_width() / 8)
+}
+
 pub fn generate_hardware_configuration(
     config: &HardwareConfiguration,
     format: ConfigFormat,
@@ -0,0 +1,348 @@
+//! Generates a C header describing the interface of a compiled library.
+//!
+//! The header declares the exported functions, the instance structs and bodies of programs and
+//! function blocks, as well as the user-defined types they use. Every struct is followed by
+//! `_Static_assert`s on its size and the offsets of its members in the generated code, so a C
+//! compiler reports layouts it does not reproduce. Methods and actions are not declared, their
+//! symbol names are no valid C identifiers.
+use std::collections::HashSet;
+
+use plc_ast::ast::LinkageType;
+
+use crate::{
+    codegen::{generators::data_type_generator::get_string_alignment, layout::TypeLayouts},
+    index::{Index, PouIndexEntry},
+    typesystem::{DataType, DataTypeInformation, StringEncoding, StructSource, BOOL_TYPE, CHAR_TYPE},
+};
+
+/// A C type, split into the parts written before and after a declared name, e.g. `int16_t` and `[4]`
+struct CType {
+    base: String,
+    suffix: String,
+}
+
+impl CType {
+    fn new(base: impl Into<String>) -> CType {
+        CType { base: base.into(), suffix: String::new() }
+    }
+
+    fn declare(&self, name: &str) -> String {
+        format!("{} {name}{}", self.base, self.suffix)
+    }
+}
+
+struct HeaderGenerator<'idx, 'ink> {
+    index: &'idx Index,
+    layouts: &'idx TypeLayouts<'ink>,
+    /// the (lowercase) names of the types already declared
+    declared_types: HashSet<String>,
+    forward_declarations: Vec<String>,
+    type_definitions: Vec<String>,
+    declarations: Vec<String>,
+}
+
+/// Generates the C header of the POUs and types declared in the compiled sources, sizes and offsets
+/// are taken from the given layouts. Included (external), generic and compiler-generated declarations
+/// are skipped
+pub fn generate_header(index: &Index, layouts: &TypeLayouts) -> String {
+    let mut generator = HeaderGenerator {
+        index,
+        layouts,
+        declared_types: HashSet::new(),
+        forward_declarations: vec![],
+        type_definitions: vec![],
+        declarations: vec![],
+    };
+
+    index
+        .get_types()
+        .values()
+        .filter(|it| !it.is_internal() && !it.get_name().starts_with("__"))
+        .for_each(|it| generator.declare_type(it.get_name()));
+
+    index
+        .get_pous()
+        .values()
+        .filter(|it| *it.get_linkage() == LinkageType::Internal && !it.is_generic())
+        .filter(|it| !matches!(it, PouIndexEntry::Function { is_generated: true, .. }))
+        .for_each(|it| generator.declare_pou(it));
+
+    let mut sections = vec![
+        "#pragma once".to_string(),
+        "#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>".to_string(),
+    ];
+    if !generator.forward_declarations.is_empty() {
+        sections.push(generator.forward_declarations.join("\n"));
+    }
+    sections.extend(generator.type_definitions);
+    if !generator.declarations.is_empty() {
+        sections.push(generator.declarations.join("\n"));
+    }
+    sections.join("\n\n") + "\n"
+}
+
+impl<'idx, 'ink> HeaderGenerator<'idx, 'ink> {
+    /// declares the given type and every type it depends on, unless they were already declared
+    fn declare_type(&mut self, type_name: &str) {
+        let Some(data_type) = self.index.find_effective_type_by_name(type_name) else { return };
+        match data_type.get_type_information() {
+            DataTypeInformation::Array { inner_type_name, .. }
+            | DataTypeInformation::Pointer { inner_type_name, .. } => self.declare_type(inner_type_name),
+            DataTypeInformation::Struct { source: StructSource::Internal(_), .. } => {}
+            DataTypeInformation::Struct { name, members, source } => {
+                if !self.declared_types.insert(name.to_lowercase()) {
+                    return;
+                }
+                let tag = match source {
+                    StructSource::Union => format!("union {name}"),
+                    _ => format!("struct {name}"),
+                };
+                // user-defined types are declared as typedefs, the instance structs of POUs keep their
+                // tag since their name is already used by the POU's function
+                let c_name = if matches!(source, StructSource::Pou(_)) {
+                    tag.clone()
+                } else {
+                    self.forward_declarations.push(format!("typedef {tag} {name};"));
+                    name.to_string()
+                };
+                let members =
+                    members.iter().filter(|it| !it.is_temp() && !it.is_return()).collect::<Vec<_>>();
+                members.iter().for_each(|it| self.declare_type(it.get_type_name()));
+
+                let size = self.get_size(data_type);
+                let body = if matches!(source, StructSource::Bitfield) {
+                    // the members of a bitfield are single bits, which are not addressable in C
+                    format!("    uint8_t bits[{size}];\n")
+                } else {
+                    members
+                        .iter()
+                        .map(|it| {
+                            let declaration = self
+                                .get_c_type(it.get_type_name())
+                                .map(|c_type| c_type.declare(it.get_name()))
+                                .unwrap_or_else(|| {
+                                    // keep the layout of members without a C equivalent
+                                    let size = self
+                                        .index
+                                        .find_effective_type_by_name(it.get_type_name())
+                                        .map(|it| self.get_size(it))
+                                        .unwrap_or_default();
+                                    format!("uint8_t {}[{size}]", it.get_name())
+                                });
+                            // an overridden alignment of strings has to be repeated in C
+                            let alignment = get_string_alignment(self.index, it.get_type_name())
+                                .map(|it| format!("_Alignas({it}) "))
+                                .unwrap_or_default();
+                            format!("    {alignment}{declaration};\n")
+                        })
+                        .collect::<String>()
+                };
+                let mut assertions = vec![format!(
+                    "_Static_assert(sizeof({c_name}) == {size}, \"unexpected size of {name}\");"
+                )];
+                if matches!(source, StructSource::OriginalDeclaration | StructSource::Pou(_)) {
+                    assertions.extend(members.iter().enumerate().filter_map(|(position, it)| {
+                        let offset = self.layouts.get_member_offset(self.index, data_type, position as u32)?;
+                        let member = it.get_name();
+                        Some(format!(
+                            "_Static_assert(offsetof({c_name}, {member}) == {offset}, \"unexpected offset of {name}.{member}\");"
+                        ))
+                    }));
+                }
+                self.type_definitions.push(format!("{tag} {{\n{body}}};\n{}", assertions.join("\n")));
+            }
+            DataTypeInformation::Enum { name, referenced_type, .. } => {
+                if self.declared_types.insert(name.to_lowercase()) {
+                    if let Some(c_type) = self.get_c_type(referenced_type) {
+                        self.forward_declarations.push(format!("typedef {};", c_type.declare(name)));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn declare_pou(&mut self, pou: &PouIndexEntry) {
+        let name = pou.get_name();
+        let symbol_name = pou.find_implementation(self.index).map(|it| it.get_symbol_name()).unwrap_or(name);
+        match pou {
+            PouIndexEntry::Function { .. } => self.declare_function(pou, symbol_name),
+            PouIndexEntry::Program { .. }
+            | PouIndexEntry::FunctionBlock { .. }
+            | PouIndexEntry::Class { .. } => {
+                let Some(instance_type) = pou.find_instance_struct_type(self.index) else { return };
+                self.declare_type(instance_type.get_name());
+                let Some(c_type) = self.get_c_type(instance_type.get_name()) else { return };
+                if matches!(pou, PouIndexEntry::Program { .. }) {
+                    self.declarations
+                        .push(format!("extern {};", c_type.declare(&format!("{name}_instance"))));
+                }
+                self.declarations.push(format!("void {symbol_name}({}* instance);", c_type.base));
+            }
+            PouIndexEntry::Method { .. } | PouIndexEntry::Action { .. } => {}
+        }
+    }
+
+    fn declare_function(&mut self, pou: &PouIndexEntry, symbol_name: &str) {
+        let name = pou.get_name();
+        let mut parameters = vec![];
+
+        let return_type = self
+            .index
+            .find_return_type(name)
+            .filter(|it| !matches!(it.get_type_information(), DataTypeInformation::Void));
+        let return_type = match return_type {
+            // aggregates are returned through a pointer passed as the first parameter
+            Some(return_type) if return_type.is_aggregate_type() => {
+                let Some(c_type) = self.get_c_type(return_type.get_name()) else {
+                    return self.declare_unsupported(name, "its return type");
+                };
+                self.declare_type(return_type.get_name());
+                parameters.push(format!("{}* {name}", c_type.base));
+                CType::new("void")
+            }
+            Some(return_type) => {
+                let Some(c_type) = self.get_c_type(return_type.get_name()) else {
+                    return self.declare_unsupported(name, "its return type");
+                };
+                self.declare_type(return_type.get_name());
+                c_type
+            }
+            None => CType::new("void"),
+        };
+
+        for parameter in self.index.get_declared_parameters(name) {
+            // by-ref parameters are pointers, arrays and strings cannot be passed by value in C
+            let Some(c_type) = self.get_c_type(parameter.get_type_name()).filter(|it| it.suffix.is_empty())
+            else {
+                return self.declare_unsupported(name, &format!("parameter '{}'", parameter.get_name()));
+            };
+            self.declare_type(parameter.get_type_name());
+            parameters.push(c_type.declare(parameter.get_name()));
+        }
+        if pou.is_variadic() {
+            parameters.push("...".to_string());
+        }
+        if parameters.is_empty() {
+            parameters.push("void".to_string());
+        }
+        self.declarations
+            .push(format!("{};", return_type.declare(&format!("{symbol_name}({})", parameters.join(", ")))));
+    }
+
+    /// returns the size of the given type in the generated code, types without an LLVM equivalent
+    /// (e.g. `ARRAY[*]`) fall back to the size of the index
+    fn get_size(&self, data_type: &DataType) -> u64 {
+        self.layouts
+            .get_size(data_type)
+            .unwrap_or_else(|| data_type.get_type_information().get_size(self.index).value().into())
+    }
+
+    fn declare_unsupported(&mut self, name: &str, reason: &str) {
+        self.declarations.push(format!("/* {name} is not declared, {reason} has no C equivalent */"));
+    }
+
+    /// returns the C type of the given type or `None` if there is no C equivalent (e.g. for `ARRAY[*]`)
+    fn get_c_type(&self, type_name: &str) -> Option<CType> {
+        let data_type = self.index.find_effective_type_by_name(type_name)?;
+        let c_type = match data_type.get_type_information() {
+            DataTypeInformation::Integer { name, .. } if name == BOOL_TYPE => CType::new("bool"),
+            DataTypeInformation::Integer { name, .. } if name == CHAR_TYPE => CType::new("char"),
+            DataTypeInformation::Integer { signed: true, size, .. } => CType::new(format!("int{size}_t")),
+            DataTypeInformation::Integer { signed: false, size, .. } => CType::new(format!("uint{size}_t")),
+            DataTypeInformation::Float { size: 32, .. } => CType::new("float"),
+            DataTypeInformation::Float { .. } => CType::new("double"),
+            DataTypeInformation::String { size, encoding } => CType {
+                base: match encoding {
+                    StringEncoding::Utf8 => "char",
+                    StringEncoding::Utf16 => "uint16_t",
+                }
+                .to_string(),
+                suffix: format!("[{}]", size.as_int_value(self.index).ok()?),
+            },
+            information @ DataTypeInformation::Array { .. } => {
+                let length = information.get_array_length(self.index)?;
+                let mut inner_type = data_type;
+                while let DataTypeInformation::Array { inner_type_name, .. } =
+                    inner_type.get_type_information()
+                {
+                    inner_type = self.index.find_effective_type_by_name(inner_type_name)?;
+                }
+                let inner_type = self.get_c_type(inner_type.get_name())?;
+                CType { base: inner_type.base, suffix: format!("[{length}]{}", inner_type.suffix) }
+            }
+            // a pointer to an array points to the array's first element
+            DataTypeInformation::Pointer { inner_type_name, .. } => {
+                CType::new(format!("{}*", self.get_c_type(inner_type_name)?.base))
+            }
+            DataTypeInformation::Struct { source: StructSource::Internal(_), .. } => return None,
+            DataTypeInformation::Struct { name, source: StructSource::Pou(_), .. } => {
+                CType::new(format!("struct {name}"))
+            }
+            DataTypeInformation::Struct { name, .. } => CType::new(name),
+            DataTypeInformation::Enum { name, .. } => CType::new(name),
+            DataTypeInformation::SubRange { referenced_type, .. } => self.get_c_type(referenced_type)?,
+            DataTypeInformation::Void => CType::new("void"),
+            DataTypeInformation::Alias { .. } | DataTypeInformation::Generic { .. } => return None,
+        };
+        Some(c_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        codegen::{layout::TypeLayouts, CodegenContext},
+        header::generate_header,
+        test_utils::tests::index_and_annotate,
+        Target,
+    };
+
+    fn header(src: &str) -> String {
+        let (index, annotations) = index_and_annotate(src);
+        let context = CodegenContext::create();
+        let layouts =
+            TypeLayouts::new(&context, &index, &annotations, &Target::from("x86_64-unknown-linux-gnu"))
+                .unwrap();
+        generate_header(&index, &layouts)
+    }
+
+    #[test]
+    fn header_of_a_function_returning_a_struct() {
+        let header = header(
+            "
+        TYPE Point : STRUCT
+            x : INT;
+            y : LREAL;
+        END_STRUCT END_TYPE
+
+        FUNCTION make_point : Point
+        VAR_INPUT
+            x : INT;
+        END_VAR
+        END_FUNCTION
+        ",
+        );
+        insta::assert_snapshot!(header);
+    }
+
+    #[test]
+    fn header_of_structs_with_array_string_and_struct_members() {
+        let header = header(
+            "
+        TYPE Inner : STRUCT
+            flag : BOOL;
+            value : LREAL;
+        END_STRUCT END_TYPE
+
+        TYPE Outer : STRUCT
+            id : SINT;
+            name : STRING[10];
+            values : ARRAY[0..2] OF INT;
+            inner : Inner;
+        END_STRUCT END_TYPE
+        ",
+        );
+        insta::assert_snapshot!(header);
+    }
+}
@@ -13,6 +13,7 @@ use plc_ast::ast::{
 use plc_diagnostics::diagnostics::Diagnostic;
 use plc_source::source_location::SourceLocation;
 use plc_util::convention::qualified_name;
+use std::collections::HashMap;
 
 use self::{
     const_expressions::{ConstExpressions, ConstId},
@@ -363,12 +364,26 @@ pub struct ImplementationIndexEntry {
     pub(crate) implementation_type: ImplementationType,
     pub(crate) generic: bool,
     pub(crate) location: SourceLocation,
+    /// the symbol name requested using `{extern_name := '...'}`
+    pub(crate) extern_name: Option<String>,
+    /// the LLVM function attributes requested using `{gnu_attr := '...'}`
+    pub(crate) function_attributes: Vec<String>,
 }
 
 impl ImplementationIndexEntry {
     pub fn get_call_name(&self) -> &str {
         &self.call_name
     }
+    pub fn get_extern_name(&self) -> Option<&str> {
+        self.extern_name.as_deref()
+    }
+    pub fn get_function_attributes(&self) -> &[String] {
+        &self.function_attributes
+    }
+    /// returns the name of the generated symbol, i.e. the extern name if one was requested or the call name
+    pub fn get_symbol_name(&self) -> &str {
+        self.get_extern_name().unwrap_or(self.get_call_name())
+    }
     pub fn get_type_name(&self) -> &str {
         &self.type_name
     }
@@ -740,6 +755,12 @@ pub struct TypeIndex {
     types: SymbolMap<String, DataType>,
     pou_types: SymbolMap<String, DataType>,
 
+    /// the lowercase names of the effective types behind the resolved aliases, keyed by the
+    /// declared and the lowercase name of the alias. An imported type can change what an alias
+    /// resolves to (e.g. a type shadowing a POU of the same name), so all aliases are resolved
+    /// again on every import
+    effective_types: HashMap<String, String>,
+
     void_type: DataType,
 }
 
@@ -748,6 +769,7 @@ impl Default for TypeIndex {
         TypeIndex {
             types: SymbolMap::default(),
             pou_types: SymbolMap::default(),
+            effective_types: HashMap::default(),
             void_type: DataType {
                 name: VOID_TYPE.into(),
                 initial_value: None,
@@ -769,11 +791,33 @@ impl TypeIndex {
     }
 
     pub fn find_effective_type_by_name(&self, type_name: &str) -> Option<&DataType> {
+        if let Some(effective_type) = self.effective_types.get(type_name) {
+            return self.types.get(effective_type).or_else(|| self.pou_types.get(effective_type));
+        }
         self.find_type(type_name).and_then(|it| self.find_effective_type(it))
     }
 
     pub fn get_effective_type_by_name(&self, type_name: &str) -> &DataType {
-        self.find_type(type_name).and_then(|it| self.find_effective_type(it)).unwrap_or(&self.void_type)
+        self.find_effective_type_by_name(type_name).unwrap_or(&self.void_type)
+    }
+
+    /// invalidates the previously resolved aliases and resolves the effective types of all aliases
+    fn resolve_effective_types(&mut self) {
+        self.effective_types.clear();
+        let resolved = self
+            .types
+            .entries()
+            .filter_map(|(key, types)| types.get(0).map(|it| (key, it)))
+            .filter(|(_, it)| matches!(it.get_type_information(), DataTypeInformation::Alias { .. }))
+            .filter_map(|(key, it)| {
+                let effective_type = self.find_effective_type(it)?.get_name().to_lowercase();
+                Some((key.clone(), it.get_name().to_string(), effective_type))
+            })
+            .collect::<Vec<_>>();
+        for (key, name, effective_type) in resolved {
+            self.effective_types.insert(name, effective_type.clone());
+            self.effective_types.insert(key, effective_type);
+        }
     }
 
     pub fn get_type(&self, type_name: &str) -> Result<&DataType, Diagnostic> {
@@ -793,6 +837,27 @@ impl TypeIndex {
     }
 }
 
+/// A summary of the number of entries in an index.
+/// Useful to profile the compilation of large projects
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct IndexStatistics {
+    pub pous: usize,
+    pub types: usize,
+    pub globals: usize,
+    pub const_expressions: usize,
+    pub implementations: usize,
+}
+
+impl std::fmt::Display for IndexStatistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "POUs: {}", self.pous)?;
+        writeln!(f, "Types: {}", self.types)?;
+        writeln!(f, "Globals: {}", self.globals)?;
+        writeln!(f, "Constant expressions: {}", self.const_expressions)?;
+        write!(f, "Implementations: {}", self.implementations)
+    }
+}
+
 /// The global index of the rusty-compiler
 ///
 /// The index contains information about all referencable elements.
@@ -911,6 +976,12 @@ impl Index {
 
         //pou_types
         for (name, mut elements) in other.type_index.pou_types.drain(..) {
+            // every unit calling a generic function registers its own typed instance (e.g. foo__INT),
+            // skip the instances that were already imported from another unit
+            let is_generated = other.pous.get(&name).map_or(false, |it| it.is_auto_generated_function());
+            if is_generated && self.type_index.pou_types.contains_key(&name) {
+                continue;
+            }
             elements.iter_mut().for_each(|e| {
                 self.maybe_import_const_expr(&mut other.constant_expressions, &e.initial_value);
 
@@ -925,8 +996,19 @@ impl Index {
             self.type_index.pou_types.insert_many(name, elements)
         }
 
+        self.type_index.resolve_effective_types();
+
         //implementations
-        self.implementations.extend(other.implementations);
+        for (name, implementation) in other.implementations.drain(..) {
+            // keep the first implementation of a generated generic instance, identified by its call name
+            let is_generated = other
+                .pous
+                .get(&implementation.get_call_name().to_lowercase())
+                .map_or(false, |it| it.is_auto_generated_function());
+            if !is_generated || !self.implementations.contains_key(&name) {
+                self.implementations.insert(name, implementation);
+            }
+        }
 
         //pous
         for (name, elements) in other.pous.drain(..) {
@@ -945,6 +1027,48 @@ impl Index {
         // self.constant_expressions.import(other.constant_expressions)
     }
 
+    /// imports the given index like `import(...)`, but global variables and user-defined types that are
+    /// already defined in this index are not added a second time. Every such conflicting definition is
+    /// dropped and reported as a name-conflict instead. Internally generated types (e.g. pointer- or
+    /// string-types) are exempt since every file may generate them.
+    pub fn import_reporting_conflicts(&mut self, mut other: Index) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+
+        for (name, entries) in std::mem::take(&mut other.global_variables).drain(..) {
+            for entry in entries {
+                if let Some(existing) = self.global_variables.get(&name) {
+                    diagnostics.push(Diagnostic::global_name_conflict(
+                        entry.get_name(),
+                        entry.source_location.clone(),
+                        vec![existing.source_location.clone()],
+                    ));
+                } else {
+                    other.global_variables.insert(name.clone(), entry);
+                }
+            }
+        }
+
+        for (name, entries) in std::mem::take(&mut other.type_index.types).drain(..) {
+            for entry in entries {
+                let existing = self.type_index.types.get_all(&name).and_then(|existing| {
+                    existing.iter().find(|it| !it.is_internal()).filter(|_| !entry.is_internal())
+                });
+                if let Some(existing) = existing {
+                    diagnostics.push(Diagnostic::global_name_conflict(
+                        entry.get_name(),
+                        entry.location.clone(),
+                        vec![existing.location.clone()],
+                    ));
+                } else {
+                    other.type_index.types.insert(name.clone(), entry);
+                }
+            }
+        }
+
+        self.import(other);
+        diagnostics
+    }
+
     fn transfer_constants(
         &mut self,
         mut variable: VariableIndexEntry,
@@ -1325,6 +1449,25 @@ impl Index {
         &self.pous
     }
 
+    /// Returns all pous of the given type in declaration order, `PouType::Method` only returns the
+    /// methods of its `owner_class`
+    pub fn pous_of_type(&self, pou_type: PouType) -> Vec<&PouIndexEntry> {
+        self.pous
+            .values()
+            .filter(|it| match (&pou_type, it) {
+                (PouType::Program, PouIndexEntry::Program { .. })
+                | (PouType::Function, PouIndexEntry::Function { .. })
+                | (PouType::FunctionBlock, PouIndexEntry::FunctionBlock { .. })
+                | (PouType::Action, PouIndexEntry::Action { .. })
+                | (PouType::Class, PouIndexEntry::Class { .. }) => true,
+                (PouType::Method { owner_class }, PouIndexEntry::Method { parent_pou_name, .. }) => {
+                    owner_class.eq_ignore_ascii_case(parent_pou_name)
+                }
+                _ => false,
+            })
+            .collect()
+    }
+
     pub fn get_global_initializers(&self) -> &SymbolMap<String, VariableIndexEntry> {
         &self.global_initializers
     }
@@ -1355,10 +1498,26 @@ impl Index {
                 implementation_type: impl_type,
                 generic,
                 location,
+                extern_name: None,
+                function_attributes: vec![],
             },
         );
     }
 
+    /// uses the given `extern_name` as the symbol name of the implementation registered as `call_name`
+    pub fn register_extern_name(&mut self, call_name: &str, extern_name: &str) {
+        if let Some(implementation) = self.implementations.get_mut(&call_name.to_lowercase()) {
+            implementation.extern_name = Some(extern_name.into());
+        }
+    }
+
+    /// adds the given LLVM function attributes to the implementation registered as `call_name`
+    pub fn register_function_attributes(&mut self, call_name: &str, attributes: &[String]) {
+        if let Some(implementation) = self.implementations.get_mut(&call_name.to_lowercase()) {
+            implementation.function_attributes.extend(attributes.iter().cloned());
+        }
+    }
+
     pub fn find_pou(&self, pou_name: &str) -> Option<&PouIndexEntry> {
         self.pous.get(&pou_name.to_lowercase())
     }
@@ -1384,6 +1543,31 @@ impl Index {
         self.find_pou(pou_name).and_then(|it| it.find_implementation(self))
     }
 
+    /// returns the implementation behind the given symbol name, e.g. to map an address of a binary back
+    /// to its source. The symbol is either the extern name of an implementation, its call name or the name
+    /// of a monomorphized generic (e.g. `foo__INT`), in which case the generic implementation is returned
+    /// if the instance is not part of the index.
+    pub fn find_implementation_by_mangled_name(&self, name: &str) -> Option<&ImplementationIndexEntry> {
+        let implementation = self
+            .implementations
+            .values()
+            .find(|it| it.get_extern_name() == Some(name))
+            .or_else(|| self.find_implementation_by_name(name));
+        if implementation.is_some() {
+            return implementation;
+        }
+
+        // generic instances append the names of their resolved types to the generic's name
+        let mut generic_name = name;
+        while let Some((prefix, _)) = generic_name.rsplit_once("__") {
+            if self.find_pou(prefix).map(PouIndexEntry::is_generic).unwrap_or(false) {
+                return self.find_implementation_by_name(prefix);
+            }
+            generic_name = prefix;
+        }
+        None
+    }
+
     /// creates a member-variable of a container to be accessed in a qualified name.
     /// e.g. "POU.member", "StructName.member", etc.
     ///
@@ -1547,6 +1731,11 @@ impl Index {
         &self.data_layout
     }
 
+    /// sets the type layout used to calculate the sizes and alignments of types
+    pub fn set_type_layout(&mut self, data_layout: DataLayout) {
+        self.data_layout = data_layout;
+    }
+
     /// returns the implementation of the sub-range-check-function for a variable of the given dataType
     pub fn find_range_check_implementation_for(
         &self,
@@ -1588,6 +1777,17 @@ impl Index {
     pub fn get_labels(&self, pou_name: &str) -> Option<&SymbolMap<String, Label>> {
         self.labels.get(pou_name)
     }
+
+    /// returns the number of entries currently registered in the index
+    pub fn statistics(&self) -> IndexStatistics {
+        IndexStatistics {
+            pous: self.pous.values().count(),
+            types: self.type_index.types.values().count(),
+            globals: self.global_variables.values().count(),
+            const_expressions: self.constant_expressions.len(),
+            implementations: self.implementations.len(),
+        }
+    }
 }
 
 /// Returns a default initialization name for a variable or type
@@ -240,6 +240,15 @@ impl ConstExpressions {
     pub fn import(&mut self, other: ConstExpressions) {
         self.expressions.extend(other.expressions)
     }
+
+    /// returns the number of registered const-expressions
+    pub fn len(&self) -> usize {
+        self.expressions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.expressions.is_empty()
+    }
 }
 
 impl<'a> IntoIterator for &'a ConstExpressions {
@@ -1,7 +1,12 @@
 use plc_ast::ast::{GenericBinding, TypeNature};
 use pretty_assertions::assert_eq;
 
-use crate::{index::PouIndexEntry, test_utils::tests::index};
+use plc_ast::provider::IdProvider;
+
+use crate::{
+    index::PouIndexEntry,
+    test_utils::tests::{annotate_with_ids, index, index_with_ids},
+};
 
 #[test]
 fn generics_saved_in_index() {
@@ -20,3 +25,34 @@ fn generics_saved_in_index() {
         panic!("{foo_info:#?} not a generic function");
     }
 }
+
+#[test]
+fn monomorphized_generic_symbols_are_resolved_to_their_implementation() {
+    let src = "
+        FUNCTION foo<T: ANY_INT> : T
+        VAR_INPUT
+            x : T;
+        END_VAR
+        END_FUNCTION
+
+        PROGRAM main
+            foo(INT#1);
+        END_PROGRAM
+    ";
+
+    // without the generated instance, the symbol resolves to the generic implementation
+    let (_, index) = index(src);
+    let generic = index.find_implementation_by_name("foo").unwrap();
+    assert_eq!(index.find_implementation_by_mangled_name("foo__INT"), Some(generic));
+    assert_eq!(index.find_implementation_by_mangled_name("main").map(|it| it.get_call_name()), Some("main"));
+    assert_eq!(index.find_implementation_by_mangled_name("bar__INT"), None);
+
+    // the generated instance points to the generic's source
+    let id_provider = IdProvider::default();
+    let (unit, mut index) = index_with_ids(src, id_provider.clone());
+    annotate_with_ids(&unit, &mut index, id_provider);
+    let generic_location = index.find_implementation_by_name("foo").unwrap().get_location().clone();
+    let instance = index.find_implementation_by_mangled_name("foo__INT").unwrap();
+    assert_eq!(instance.get_call_name(), "foo__INT");
+    assert_eq!(instance.get_location(), &generic_location);
+}
@@ -1,13 +1,14 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 use insta::assert_debug_snapshot;
 use plc_ast::ast::{
-    pre_process, AstFactory, DataType, GenericBinding, LinkageType, Operator, TypeNature, UserTypeDeclaration,
+    pre_process, AstFactory, DataType, DirectAccessType, GenericBinding, HardwareAccessType, LinkageType,
+    Operator, PointerKind, PouType, TypeNature, UserTypeDeclaration,
 };
 use plc_ast::provider::IdProvider;
 use plc_source::source_location::{SourceLocation, SourceLocationFactory};
 use pretty_assertions::assert_eq;
 
-use crate::index::{ArgumentType, PouIndexEntry, VariableIndexEntry};
+use crate::index::{ArgumentType, IndexStatistics, PouIndexEntry, VariableIndexEntry};
 use crate::parser::tests::literal_int;
 use crate::test_utils::tests::{annotate_with_ids, index, index_with_ids, parse_and_preprocess};
 use crate::typesystem::{InternalType, StructSource, TypeSize, INT_TYPE, VOID_TYPE};
@@ -1227,6 +1228,39 @@ fn global_vars_for_structs() {
     assert!(global_var.is_some());
 }
 
+#[test]
+fn struct_members_can_reference_types_declared_below() {
+    // GIVEN a struct referencing a struct and an enum declared after it
+    // WHEN the unit is indexed
+    let (_, index) = index(
+        "
+        TYPE Outer : STRUCT
+            inner : Inner;
+            state : State;
+        END_STRUCT END_TYPE
+
+        TYPE Inner : STRUCT
+            a : DINT;
+            b : DINT;
+        END_STRUCT END_TYPE
+
+        TYPE State : (Idle, Running) END_TYPE
+        ",
+    );
+
+    // THEN the members resolve to the types declared below
+    let inner = index.find_member("Outer", "inner").expect("Outer.inner not found");
+    let inner_type = index.find_effective_type_info(inner.get_type_name()).unwrap();
+    assert!(matches!(inner_type, DataTypeInformation::Struct { name, .. } if name == "Inner"));
+    let state = index.find_member("Outer", "state").expect("Outer.state not found");
+    let state_type = index.find_effective_type_info(state.get_type_name()).unwrap();
+    assert!(matches!(state_type, DataTypeInformation::Enum { name, .. } if name == "State"));
+
+    // AND the size of the outer struct includes the forward-declared struct
+    let outer_type = index.find_effective_type_info("Outer").unwrap();
+    assert_eq!(outer_type.get_size(&index).value(), 12);
+}
+
 #[test]
 fn pointer_and_in_out_pointer_should_not_conflict() {
     // GIVEN an IN-OUT INT and a POINTER TO INT
@@ -1252,7 +1286,7 @@ fn pointer_and_in_out_pointer_should_not_conflict() {
         &DataTypeInformation::Pointer {
             name: "__main_x".to_string(),
             inner_type_name: "INT".to_string(),
-            auto_deref: false,
+            kind: PointerKind::Raw,
         }
     );
 
@@ -1263,7 +1297,7 @@ fn pointer_and_in_out_pointer_should_not_conflict() {
         &DataTypeInformation::Pointer {
             name: "__auto_pointer_to_INT".to_string(),
             inner_type_name: "INT".to_string(),
-            auto_deref: true,
+            kind: PointerKind::Reference,
         }
     );
 }
@@ -1302,7 +1336,7 @@ fn pointer_and_in_out_pointer_should_not_conflict_2() {
         &DataTypeInformation::Pointer {
             name: "__main_x".to_string(),
             inner_type_name: "INT".to_string(),
-            auto_deref: false,
+            kind: PointerKind::Raw,
         }
     );
 
@@ -1313,7 +1347,7 @@ fn pointer_and_in_out_pointer_should_not_conflict_2() {
         &DataTypeInformation::Pointer {
             name: "__auto_pointer_to_INT".to_string(),
             inner_type_name: "INT".to_string(),
-            auto_deref: true,
+            kind: PointerKind::Reference,
         }
     );
 }
@@ -1680,3 +1714,109 @@ fn string_type_alias_without_size_is_indexed() {
     let dt = index.find_effective_type_by_name(my_alias).unwrap();
     assert_eq!("WSTRING", dt.get_name());
 }
+
+#[test]
+fn index_statistics_count_entries() {
+    let (unit, _) = parse_and_preprocess(
+        r"
+        TYPE MyStruct : STRUCT
+            a : INT;
+            b : DINT;
+        END_STRUCT
+        END_TYPE
+
+        VAR_GLOBAL
+            g1 : INT := 5;
+            g2 : MyStruct;
+        END_VAR
+
+        FUNCTION foo : INT
+        END_FUNCTION
+
+        FUNCTION_BLOCK fb
+        END_FUNCTION_BLOCK
+
+        PROGRAM prg
+        VAR
+            x : INT := 3;
+        END_VAR
+        END_PROGRAM
+        ",
+    );
+    let index = crate::index::visitor::visit(&unit);
+
+    assert_eq!(
+        index.statistics(),
+        IndexStatistics { pous: 3, types: 1, globals: 2, const_expressions: 2, implementations: 3 }
+    );
+}
+
+#[test]
+fn absolute_address_binding_is_indexed() {
+    let (_, index) = index(
+        "
+        VAR_GLOBAL
+            x AT %MW100 : WORD;
+        END_VAR
+        ",
+    );
+
+    let binding = index.find_global_variable("x").and_then(|it| it.get_hardware_binding()).unwrap();
+    assert_eq!(binding.direction, HardwareAccessType::Memory);
+    assert_eq!(binding.access, DirectAccessType::Word);
+    let address = binding
+        .entries
+        .iter()
+        .map(|it| index.get_const_expressions().get_constant_int_statement_value(it).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(address, vec![100]);
+}
+
+#[test]
+fn effective_types_are_memoized_and_resolved_correctly_after_imports() {
+    // GIVEN an alias-chain and an alias to a type that is not known yet
+    let (_, mut index) = index(
+        "
+        TYPE Counter : MyDint; END_TYPE
+        TYPE MyDint : DINT; END_TYPE
+        TYPE MyInt : OtherInt; END_TYPE
+        ",
+    );
+    assert_eq!(index.get_effective_type_or_void_by_name("Counter").get_name(), "DINT");
+    assert!(index.find_effective_type_by_name("MyInt").is_none());
+
+    // THEN the resolved chain is memoized by its declared and its lowercase name
+    let effective_types = &index.type_index.effective_types;
+    assert_eq!(effective_types.get("Counter").map(String::as_str), Some("dint"));
+    assert_eq!(effective_types.get("counter").map(String::as_str), Some("dint"));
+    assert_eq!(effective_types.get("myint"), None);
+
+    // WHEN the missing type is imported
+    let (unit, _) = parse_and_preprocess("TYPE OtherInt : INT; END_TYPE");
+    index.import(crate::index::visitor::visit(&unit));
+
+    // THEN both aliases resolve correctly
+    assert_eq!(index.type_index.effective_types.get("myint").map(String::as_str), Some("int"));
+    assert_eq!(index.get_effective_type_or_void_by_name("MyInt").get_name(), "INT");
+    assert_eq!(index.get_effective_type_or_void_by_name("Counter").get_name(), "DINT");
+}
+
+#[test]
+fn effective_types_are_resolved_again_when_an_import_shadows_the_referenced_type() {
+    // GIVEN an alias to a function block
+    let (_, mut index) = index(
+        "
+        FUNCTION_BLOCK Motor END_FUNCTION_BLOCK
+        TYPE MyMotor : Motor; END_TYPE
+        ",
+    );
+    assert_eq!(index.get_effective_type_or_void_by_name("MyMotor").get_name(), "Motor");
+
+    // WHEN a type of the same name is imported, which is found before the function block
+    let (unit, _) = parse_and_preprocess("TYPE Motor : INT; END_TYPE");
+    index.import(crate::index::visitor::visit(&unit));
+
+    // THEN the alias resolves to the effective type of the imported type
+    assert_eq!(index.type_index.effective_types.get("mymotor").map(String::as_str), Some("int"));
+    assert_eq!(index.get_effective_type_or_void_by_name("MyMotor").get_name(), "INT");
+}
@@ -4,8 +4,8 @@ use crate::index::{ArgumentType, Index, MemberInfo};
 use crate::typesystem::{self, *};
 use plc_ast::ast::{
     self, ArgumentProperty, Assignment, AstFactory, AstNode, AstStatement, CompilationUnit, DataType,
-    DataTypeDeclaration, Implementation, Pou, PouType, RangeStatement, TypeNature, UserTypeDeclaration,
-    Variable, VariableBlock, VariableBlockType,
+    DataTypeDeclaration, Implementation, PointerKind, Pou, PouType, RangeStatement, TypeNature,
+    UserTypeDeclaration, Variable, VariableBlock, VariableBlockType,
 };
 use plc_ast::literals::AstLiteral;
 use plc_diagnostics::diagnostics::Diagnostic;
@@ -235,6 +235,12 @@ fn visit_implementation(index: &mut Index, implementation: &Implementation) {
         implementation.generic,
         start_location,
     );
+    if let Some(extern_name) = &implementation.extern_name {
+        index.register_extern_name(&implementation.name, extern_name);
+    }
+    if !implementation.function_attributes.is_empty() {
+        index.register_function_attributes(&implementation.name, &implementation.function_attributes);
+    }
     //if we are registing an action, also register a datatype for it
     if pou_type == &PouType::Action {
         let datatype = typesystem::DataType {
@@ -272,7 +278,7 @@ fn register_byref_pointer_type_for(index: &mut Index, inner_type_name: &str) ->
             information: DataTypeInformation::Pointer {
                 name: type_name.clone(),
                 inner_type_name: inner_type_name.to_string(),
-                auto_deref: true,
+                kind: PointerKind::Reference,
             },
             nature: TypeNature::Any,
             location: SourceLocation::internal(),
@@ -327,6 +333,14 @@ fn visit_data_type(index: &mut Index, type_declaration: &UserTypeDeclaration) {
             visit_struct(name, variables, index, scope, type_declaration, StructSource::OriginalDeclaration);
         }
 
+        DataType::UnionType { name: Some(name), variables } => {
+            visit_struct(name, variables, index, scope, type_declaration, StructSource::Union);
+        }
+
+        DataType::BitfieldType { name: Some(name), variables } => {
+            visit_struct(name, variables, index, scope, type_declaration, StructSource::Bitfield);
+        }
+
         DataType::EnumType { name: Some(name), elements, numeric_type, .. } => {
             let enum_name = name.as_str();
 
@@ -398,12 +412,12 @@ fn visit_data_type(index: &mut Index, type_declaration: &UserTypeDeclaration) {
         DataType::ArrayType { name: Some(name), bounds, referenced_type, .. } => {
             visit_array(bounds, index, scope, referenced_type, name, type_declaration);
         }
-        DataType::PointerType { name: Some(name), referenced_type, .. } => {
+        DataType::PointerType { name: Some(name), referenced_type, kind } => {
             let inner_type_name = referenced_type.get_name().expect("named datatype");
             let information = DataTypeInformation::Pointer {
                 name: name.clone(),
                 inner_type_name: inner_type_name.into(),
-                auto_deref: false,
+                kind: *kind,
             };
 
             let init = index.get_mut_const_expressions().maybe_add_constant_expression(
@@ -570,6 +584,7 @@ fn visit_variable_length_array(
                             referenced_type: dummy_array_name,
                             location: SourceLocation::undefined(),
                         }),
+                        kind: PointerKind::Raw,
                     },
                     location: SourceLocation::undefined(),
                     scope: None,
@@ -134,6 +134,7 @@ impl<'a> ParseSession<'a> {
             | Token::KeywordFunctionBlock
             | Token::KeywordEndFunctionBlock
             | Token::KeywordEndStruct
+            | Token::KeywordEndUnion
             | Token::KeywordEndAction
             | Token::KeywordEndActions
             | Token::KeywordEndIf
@@ -156,6 +157,16 @@ impl<'a> ParseSession<'a> {
         self.lexer.slice()
     }
 
+    /// returns the token following the current one without advancing
+    pub fn peek(&self) -> Token {
+        self.lexer.clone().next().unwrap_or(Token::End)
+    }
+
+    /// returns true if the current token is a keyword used as a name, i.e. it is followed by a `:` or `,`
+    pub fn is_keyword_used_as_name(&self) -> bool {
+        is_reserved_keyword(self.slice()) && matches!(self.peek(), Token::KeywordColon | Token::KeywordComma)
+    }
+
     pub fn location(&self) -> SourceLocation {
         self.source_range_factory.create_range(self.range())
     }
@@ -190,6 +201,12 @@ impl<'a> ParseSession<'a> {
         }
     }
 
+    /// closes the innermost region without expecting its closing token, e.g. if it
+    /// was omitted and the token closes an enclosing region instead
+    pub fn abandon_region(&mut self) {
+        self.closing_keywords.pop();
+    }
+
     /// returns the level (which corresponds to the position on the `closing_keywords` stack)
     /// returns `None` if this token does not close an open region
     fn get_close_region_level(&self, token: &Token) -> Option<usize> {
@@ -341,6 +358,18 @@ fn parse_hardware_access_type(lexer: &mut Lexer<Token>) -> Option<(HardwareAcces
     Some((hardware_type, access))
 }
 
+/// returns true if the given name is lexed as a keyword (including word-operators like `AND` and
+/// literals like `TRUE`) rather than an identifier
+pub fn is_reserved_keyword(name: &str) -> bool {
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return false;
+    }
+    let mut lexer = Token::lexer(name);
+    let token = lexer.next();
+    lexer.span() == (0..name.len())
+        && !matches!(token, None | Some(Token::Identifier | Token::Error) | Some(Token::LiteralInteger))
+}
+
 #[cfg(test)]
 pub fn lex(source: &str) -> ParseSession {
     ParseSession::new(Token::lexer(source), IdProvider::default(), SourceLocationFactory::internal(source))
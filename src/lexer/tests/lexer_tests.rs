@@ -150,6 +150,18 @@ fn action_tokens() {
     assert_eq!(lexer.token, KeywordEndActions);
 }
 
+#[test]
+fn configuration_tokens() {
+    let mut lexer = lex("CONFIGURATION RESOURCE END_RESOURCE END_CONFIGURATION");
+    assert_eq!(lexer.token, KeywordConfiguration);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordResource);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordEndResource);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordEndConfiguration);
+}
+
 #[test]
 fn var_tokens() {
     let mut lexer = lex("VAR VAR_INPUT VAR_OUTPUT VAR_GLOBAL VAR_IN_OUT END_VAR");
@@ -391,6 +403,24 @@ fn time_literals_test() {
     }
 }
 
+#[test]
+fn time_literals_with_fractions_and_signs_test() {
+    let mut lexer = lex(r#"
+    T#1.5h T#0.25s TIME#-1h30m T#-2.5d12h LT#-0.5ms
+    "#);
+    for expected in ["T#1.5h", "T#0.25s", "TIME#-1h30m", "T#-2.5d12h", "LT#-0.5ms"] {
+        assert_eq!(
+            lexer.token,
+            LiteralTime,
+            "{} at {:?} is no Time Literal",
+            lexer.slice(),
+            lexer.location()
+        );
+        assert_eq!(lexer.slice(), expected);
+        lexer.advance();
+    }
+}
+
 #[test]
 fn ltime_literals_test() {
     let mut lexer = lex(r#"
@@ -647,6 +677,7 @@ fn pointers_and_references_keyword() {
     POINTER TO x 
     REF_TO x
     REFTO x
+    REFERENCE TO x
     &x
     x^
     NULL
@@ -666,6 +697,14 @@ fn pointers_and_references_keyword() {
     lexer.advance();
     assert_eq!(lexer.slice(), "x");
     lexer.advance();
+    // REFERENCE is no keyword, the parser only treats it as one in front of TO
+    assert_eq!(lexer.token, Identifier);
+    assert_eq!(lexer.slice(), "REFERENCE");
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordTo);
+    lexer.advance();
+    assert_eq!(lexer.slice(), "x");
+    lexer.advance();
     assert_eq!(lexer.token, OperatorAmp);
     lexer.advance();
     assert_eq!(lexer.slice(), "x");
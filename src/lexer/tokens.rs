@@ -22,6 +22,18 @@ pub enum Token {
     #[token("{sized}")]
     PropertySized,
 
+    #[token("{bitfield}")]
+    PropertyBitfield,
+
+    #[regex(r"\{extern_name\s*:=\s*'[^']*'\s*\}")]
+    PropertyExternName,
+
+    #[regex(r"\{gnu_attr\s*:=\s*'[^']*'\s*\}")]
+    PropertyGnuAttr,
+
+    #[regex(r"\{include\s*:=\s*'[^']*'\s*\}")]
+    PropertyInclude,
+
     #[token("PROGRAM", ignore(case))]
     KeywordProgram,
 
@@ -35,6 +47,9 @@ pub enum Token {
     #[token("EXTENDS", ignore(case))]
     KeywordExtends,
 
+    #[token("THIS", ignore(case))]
+    KeywordThis,
+
     #[token("VAR_INPUT", ignore(case))]
     #[token("VARINPUT", ignore(case))]
     KeywordVarInput,
@@ -133,6 +148,13 @@ pub enum Token {
     #[token("ENDSTRUCT", ignore(case))]
     KeywordEndStruct,
 
+    #[token("UNION", ignore(case))]
+    KeywordUnion,
+
+    #[token("END_UNION", ignore(case))]
+    #[token("ENDUNION", ignore(case))]
+    KeywordEndUnion,
+
     #[token("ACTIONS", ignore(case))]
     KeywordActions,
 
@@ -147,6 +169,18 @@ pub enum Token {
     #[token("ENDACTIONS", ignore(case))]
     KeywordEndActions,
 
+    #[token("CONFIGURATION", ignore(case))]
+    KeywordConfiguration,
+
+    #[token("END_CONFIGURATION", ignore(case))]
+    KeywordEndConfiguration,
+
+    #[token("RESOURCE", ignore(case))]
+    KeywordResource,
+
+    #[token("END_RESOURCE", ignore(case))]
+    KeywordEndResource,
+
     #[token(":")]
     KeywordColon,
 
@@ -33,12 +33,14 @@ use validation::Validator;
 
 pub mod builtins;
 pub mod codegen;
-mod datalayout;
+pub mod datalayout;
 pub mod expression_path;
 pub mod hardware_binding;
+pub mod header;
 pub mod index;
 pub mod lexer;
 pub mod linker;
+pub mod metadata;
 pub mod output;
 pub mod parser;
 pub mod resolver;
@@ -149,6 +151,15 @@ pub enum ErrorFormat {
     None,
 }
 
+/// How the paths of source files are printed in diagnostics
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ArgEnum, Serialize, Deserialize, Default)]
+pub enum DiagnosticPaths {
+    #[default]
+    AsProvided,
+    Absolute,
+    Relative,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum Threads {
     Full,
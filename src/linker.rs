@@ -1,6 +1,7 @@
 // This file is based on code from the Mun Programming Language
 // https://github.com/mun-lang/mun
 
+use inkwell::targets::TargetMachine;
 use plc_diagnostics::diagnostics::Diagnostic;
 use which::which;
 
@@ -19,25 +20,9 @@ impl Linker {
     pub fn new(target: &str, linker: Option<&str>) -> Result<Linker, LinkerError> {
         Ok(Linker {
             errors: Vec::default(),
-            linker: match linker {
-                Some(linker) => Box::new(CcLinker::new(linker)),
-
-                // TODO: Linker for Windows is missing, see also:
-                // https://github.com/PLC-lang/rusty/pull/702/files#r1052446296
-                None => {
-                    let [platform, target_os] = target.split('-').collect::<Vec<&str>>()[1..=2] else {
-                        return Err(LinkerError::Target(target.into()));
-                    };
-                    match (platform, target_os) {
-                        (_, "win32") | (_, "windows") | ("win32", _) | ("windows", _) => {
-                            return Err(LinkerError::Target(target_os.into()))
-                        }
-
-                        (_, "darwin") => Box::new(CcLinker::new("clang")),
-
-                        _ => Box::new(LdLinker::new()),
-                    }
-                }
+            linker: match linker.or_else(|| get_default_linker(target, &get_host_triple())) {
+                Some(linker) => create_linker(linker),
+                None => Box::new(LdLinker::new()),
             },
         })
     }
@@ -60,12 +45,30 @@ impl Linker {
         self
     }
 
+    /// Add a static library of which all objects are linked, even if none of their symbols are used
+    pub fn add_whole_archive_lib<'a>(&'a mut self, path: &str) -> &'a mut Self {
+        self.linker.add_whole_archive_lib(path);
+        self
+    }
+
     /// Add path to system root
     pub fn add_sysroot<'a>(&'a mut self, path: &str) -> &'a mut Self {
         self.linker.add_sysroot(path);
         self
     }
 
+    /// Write a map of the linked symbols and their sizes to the given file
+    pub fn add_map_file<'a>(&'a mut self, path: &str) -> &'a mut Self {
+        self.linker.add_map_file(path);
+        self
+    }
+
+    /// Remove the symbol table and debug information from the linked output
+    pub fn strip_symbols(&mut self) -> &mut Self {
+        self.linker.strip_symbols();
+        self
+    }
+
     /// Set the output file and run the linker to generate a shared object
     pub fn build_shared_obj(&mut self, path: PathBuf) -> Result<PathBuf, LinkerError> {
         if let Some(file) = self.get_str_from_path(&path) {
@@ -103,6 +106,84 @@ impl Linker {
     }
 }
 
+/// returns the name of the linker used for the given `target` if none is configured, or `None` if the
+/// built-in ELF linker is used. Linux and bare-metal targets keep the built-in linker since, unlike `cc`,
+/// it needs no toolchain for the target and links executables without a `main`. `link.exe` is only
+/// available on a Windows `host`, other hosts link Windows targets with `lld-link`
+pub fn get_default_linker(target: &str, host: &str) -> Option<&'static str> {
+    let target_os = TargetOs::from_triple(target);
+    let is_host = get_arch(target) == get_arch(host) && target_os == TargetOs::from_triple(host);
+    match target_os {
+        TargetOs::Wasm => Some("wasm-ld"),
+        TargetOs::Darwin => Some("clang"),
+        TargetOs::Windows if is_host && target.to_lowercase().contains("msvc") => Some("link.exe"),
+        TargetOs::Windows => Some("lld-link"),
+        TargetOs::Other => None,
+    }
+}
+
+/// returns the normalized triple of the machine the compiler runs on
+fn get_host_triple() -> String {
+    TargetMachine::normalize_triple(&TargetMachine::get_default_triple()).as_str().to_string_lossy().into()
+}
+
+/// the operating system of a target triple, as far as it matters for the choice of the linker
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum TargetOs {
+    Darwin,
+    Windows,
+    Wasm,
+    Other,
+}
+
+impl TargetOs {
+    fn from_triple(triple: &str) -> TargetOs {
+        if get_arch(triple).starts_with("wasm") {
+            return TargetOs::Wasm;
+        }
+        triple
+            .to_lowercase()
+            .split('-')
+            .skip(1)
+            .find_map(|it| match it {
+                "windows" | "win32" => Some(TargetOs::Windows),
+                _ if it.starts_with("darwin") || it.starts_with("macos") => Some(TargetOs::Darwin),
+                _ => None,
+            })
+            .unwrap_or(TargetOs::Other)
+    }
+}
+
+fn get_arch(triple: &str) -> String {
+    triple.split('-').next().unwrap_or_default().to_lowercase()
+}
+
+/// creates the interface matching the arguments of the given linker, compiler drivers like `cc` or
+/// `clang` are used unless the linker is known to expect another syntax
+fn create_linker(linker: &str) -> Box<dyn LinkerInterface> {
+    let name = Path::new(linker).file_stem().map(|it| it.to_string_lossy().to_lowercase());
+    match name.as_deref() {
+        Some("wasm-ld") => Box::new(LdLinker::external(linker)),
+        Some("link" | "lld-link") => Box::new(MsvcLinker::new(linker)),
+        _ => Box::new(CcLinker::new(linker)),
+    }
+}
+
+/// runs the linker executable with the given arguments
+fn run_linker(linker: &str, args: &[String]) -> Result<(), LinkerError> {
+    let linker_location =
+        which(linker).map_err(|e| LinkerError::Link(format!("{e} for linker: {linker}")))?;
+
+    log::debug!("Linker command : {} {}", linker_location.to_string_lossy(), args.join(" "));
+
+    let status = Command::new(linker_location).args(args).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(LinkerError::Link("An error occured during linking".to_string()))
+    }
+}
+
 struct CcLinker {
     args: Vec<String>,
     linker: String,
@@ -119,28 +200,45 @@ impl LinkerInterface for CcLinker {
         &mut self.args
     }
 
-    fn finalize(&mut self) -> Result<(), LinkerError> {
-        let linker_location = which(&self.linker)
-            .map_err(|e| LinkerError::Link(format!("{e} for linker: {}", &self.linker)))?;
+    fn add_whole_archive_lib(&mut self, path: &str) {
+        // the compiler driver passes the flags on to the linker
+        self.args().push("-Wl,--whole-archive".into());
+        self.args().push(format!("-l{path}"));
+        self.args().push("-Wl,--no-whole-archive".into());
+    }
 
-        log::debug!("Linker command : {} {}", linker_location.to_string_lossy(), self.args.join(" "));
+    fn add_map_file(&mut self, path: &str) {
+        self.args().push(format!("-Wl,-Map={path}"));
+    }
 
-        let status = Command::new(linker_location).args(&self.args).status()?;
-        if status.success() {
-            Ok(())
-        } else {
-            Err(LinkerError::Link("An error occured during linking".to_string()))
-        }
+    fn build_relocatable(&mut self, path: &str) {
+        // a partial link must not pull in the startup files and libraries of an executable, the
+        // remaining symbols are resolved when the relocatable object is linked itself
+        self.args().push("-r".into());
+        self.args().push("-nostdlib".into());
+        self.args().push("-o".into());
+        self.args().push(path.into());
+    }
+
+    fn finalize(&mut self) -> Result<(), LinkerError> {
+        run_linker(&self.linker, &self.args)
     }
 }
 
+/// a linker taking the arguments of `ld`, either the built-in ELF linker or an external one like `wasm-ld`
 struct LdLinker {
     args: Vec<String>,
+    /// the external linker to run, the built-in linker is used if `None`
+    linker: Option<String>,
 }
 
 impl LdLinker {
     fn new() -> LdLinker {
-        LdLinker { args: Vec::default() }
+        LdLinker { args: Vec::default(), linker: None }
+    }
+
+    fn external(linker: &str) -> LdLinker {
+        LdLinker { args: Vec::default(), linker: Some(linker.to_string()) }
     }
 }
 
@@ -150,11 +248,79 @@ impl LinkerInterface for LdLinker {
     }
 
     fn finalize(&mut self) -> Result<(), LinkerError> {
+        if let Some(linker) = &self.linker {
+            return run_linker(linker, &self.args);
+        }
         log::debug!("Linker arguments : {}", self.args.join(" "));
         lld_rs::link(lld_rs::LldFlavor::Elf, &self.args).ok().map_err(LinkerError::Link)
     }
 }
 
+/// a linker taking the arguments of the MSVC linker `link.exe`, like `lld-link`
+struct MsvcLinker {
+    args: Vec<String>,
+    linker: String,
+    relocatable: bool,
+}
+
+impl MsvcLinker {
+    fn new(linker: &str) -> MsvcLinker {
+        MsvcLinker { args: Vec::default(), linker: linker.to_string(), relocatable: false }
+    }
+}
+
+impl LinkerInterface for MsvcLinker {
+    fn args(&mut self) -> &mut Vec<String> {
+        &mut self.args
+    }
+
+    fn finalize(&mut self) -> Result<(), LinkerError> {
+        if self.relocatable {
+            return Err(LinkerError::Link(format!("{} cannot link relocatable objects", self.linker)));
+        }
+        run_linker(&self.linker, &self.args)
+    }
+
+    fn add_lib_path(&mut self, path: &str) {
+        self.args().push(format!("/LIBPATH:{path}"));
+    }
+
+    fn add_lib(&mut self, path: &str) {
+        self.args().push(format!("{path}.lib"));
+    }
+
+    fn add_whole_archive_lib(&mut self, path: &str) {
+        self.args().push(format!("/WHOLEARCHIVE:{path}.lib"));
+    }
+
+    fn add_sysroot(&mut self, path: &str) {
+        // the libraries of a Windows sysroot are found through its library path
+        self.add_lib_path(path);
+    }
+
+    fn add_map_file(&mut self, path: &str) {
+        self.args().push(format!("/MAP:{path}"));
+    }
+
+    fn strip_symbols(&mut self) {
+        // no debug information is written unless /DEBUG is passed
+        self.args().push("/DEBUG:NONE".into());
+    }
+
+    fn build_shared_object(&mut self, path: &str) {
+        self.args().push("/DLL".into());
+        self.args().push(format!("/OUT:{path}"));
+    }
+
+    fn build_exectuable(&mut self, path: &str) {
+        self.args().push(format!("/OUT:{path}"));
+    }
+
+    fn build_relocatable(&mut self, _path: &str) {
+        self.relocatable = true;
+    }
+}
+
 trait LinkerInterface {
     fn args(&mut self) -> &mut Vec<String>;
     fn finalize(&mut self) -> Result<(), LinkerError>;
@@ -171,10 +337,24 @@ trait LinkerInterface {
         self.args().push(format!("-l{path}"));
     }
 
+    fn add_whole_archive_lib(&mut self, path: &str) {
+        self.args().push("--whole-archive".into());
+        self.args().push(format!("-l{path}"));
+        self.args().push("--no-whole-archive".into());
+    }
+
     fn add_sysroot(&mut self, path: &str) {
         self.args().push(format!("--sysroot={path}"));
     }
 
+    fn add_map_file(&mut self, path: &str) {
+        self.args().push(format!("-Map={path}"));
+    }
+
+    fn strip_symbols(&mut self) {
+        self.args().push("-s".into()); // equivalent to --strip-all
+    }
+
     fn build_shared_object(&mut self, path: &str) {
         self.args().push("--shared".into());
         self.args().push("-o".into());
@@ -226,7 +406,7 @@ impl<T: Error> From<T> for LinkerError {
 }
 
 #[test]
-fn windows_target_triple_should_result_in_error() {
+fn windows_target_triple_should_result_in_ok() {
     for target in &[
         "x86_64-pc-windows-gnu",
         "x86_64-pc-win32-gnu",
@@ -241,7 +421,7 @@ fn windows_target_triple_should_result_in_error() {
         "i686-windows-gnu",
         "i686-win32-gnu",
     ] {
-        assert!(Linker::new(target, None).is_err());
+        assert!(Linker::new(target, None).is_ok());
     }
 }
 
@@ -253,3 +433,84 @@ fn non_windows_target_triple_should_result_in_ok() {
         assert!(Linker::new(target, None).is_ok());
     }
 }
+
+#[test]
+fn whole_archive_libraries_are_bracketed_by_the_whole_archive_flags() {
+    let mut linker = Linker::new("x86_64-unknown-linux-gnu", Some("cc")).unwrap();
+    linker.add_lib("c").add_whole_archive_lib("plugins").add_lib("m");
+    assert_eq!(
+        linker.linker.args(),
+        &vec!["-lc", "-Wl,--whole-archive", "-lplugins", "-Wl,--no-whole-archive", "-lm"]
+    );
+
+    let mut linker = Linker::new("x86_64-unknown-linux-gnu", None).unwrap();
+    linker.add_whole_archive_lib("plugins");
+    assert_eq!(linker.linker.args(), &vec!["--whole-archive", "-lplugins", "--no-whole-archive"]);
+}
+
+#[test]
+fn map_file_is_passed_to_the_linker() {
+    let mut linker = Linker::new("x86_64-unknown-linux-gnu", Some("cc")).unwrap();
+    linker.add_map_file("build/out.map");
+    assert_eq!(linker.linker.args(), &vec!["-Wl,-Map=build/out.map"]);
+
+    let mut linker = Linker::new("x86_64-unknown-linux-gnu", None).unwrap();
+    linker.add_map_file("build/out.map");
+    assert_eq!(linker.linker.args(), &vec!["-Map=build/out.map"]);
+}
+
+#[test]
+fn strip_is_passed_to_the_linker() {
+    let mut linker = Linker::new("x86_64-unknown-linux-gnu", Some("cc")).unwrap();
+    linker.strip_symbols();
+    assert_eq!(linker.linker.args(), &vec!["-s"]);
+
+    let mut linker = Linker::new("x86_64-unknown-linux-gnu", None).unwrap();
+    linker.strip_symbols();
+    assert_eq!(linker.linker.args(), &vec!["-s"]);
+}
+
+#[test]
+fn default_linker_depends_on_the_target_os() {
+    let host = "x86_64-unknown-linux-gnu";
+    for (target, linker) in [
+        ("x86_64-unknown-linux-gnu", None),
+        ("aarch64-linux-gnu", None),
+        ("aarch64-apple-darwin", Some("clang")),
+        ("x86_64-apple-macosx10.15.0", Some("clang")),
+        ("x86_64-pc-windows-msvc", Some("lld-link")),
+        ("x86_64-pc-windows-gnu", Some("lld-link")),
+        ("wasm32-unknown-unknown", Some("wasm-ld")),
+        ("wasm32-wasi", Some("wasm-ld")),
+        ("thumbv7em-none-eabihf", None),
+    ] {
+        assert_eq!(get_default_linker(target, host), linker, "default linker of {target}");
+    }
+
+    // the MSVC linker is only used on a Windows host of the same architecture
+    assert_eq!(get_default_linker("x86_64-pc-windows-msvc", "x86_64-pc-windows-msvc"), Some("link.exe"));
+    assert_eq!(get_default_linker("aarch64-pc-windows-msvc", "x86_64-pc-windows-msvc"), Some("lld-link"));
+    assert_eq!(get_default_linker("x86_64-unknown-linux-gnu", "x86_64-pc-windows-msvc"), None);
+}
+
+#[test]
+fn relocatable_objects_are_linked_without_startup_files() {
+    let mut linker = Linker::new("x86_64-unknown-linux-gnu", Some("cc")).unwrap();
+    linker.linker.build_relocatable("out.o");
+    assert_eq!(linker.linker.args(), &vec!["-r", "-nostdlib", "-o", "out.o"]);
+
+    let mut linker = Linker::new("x86_64-unknown-linux-gnu", None).unwrap();
+    linker.linker.build_relocatable("out.o");
+    assert_eq!(linker.linker.args(), &vec!["-r", "-o", "out.o"]);
+}
+
+#[test]
+fn msvc_linkers_get_msvc_arguments() {
+    let mut linker = Linker::new("x86_64-pc-windows-msvc", Some("lld-link")).unwrap();
+    linker.add_lib_path("lib").add_lib("c").add_whole_archive_lib("plugins").add_map_file("out.map");
+    linker.linker.build_shared_object("out.dll");
+    assert_eq!(
+        linker.linker.args(),
+        &vec!["/LIBPATH:lib", "c.lib", "/WHOLEARCHIVE:plugins.lib", "/MAP:out.map", "/DLL", "/OUT:out.dll"]
+    );
+}
@@ -0,0 +1,254 @@
+//! Machine readable description of the interface of a compiled library.
+//!
+//! Unlike the index, the metadata is an external contract: its layout is versioned with
+//! [`METADATA_VERSION`] and documented in the book, so packaging tools can rely on it.
+use plc_ast::ast::LinkageType;
+use plc_diagnostics::{diagnostics::Diagnostic, errno::ErrNo};
+use serde::Serialize;
+
+use crate::{
+    codegen::layout::TypeLayouts,
+    index::{Index, PouIndexEntry, VariableIndexEntry, VariableType},
+    typesystem::{DataType, DataTypeInformation, StructSource},
+};
+
+/// The version of the metadata format, incremented on every incompatible change
+pub const METADATA_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize)]
+pub struct InterfaceMetadata {
+    metadata_version: u32,
+    compiler_version: &'static str,
+    pous: Vec<PouMetadata>,
+    types: Vec<TypeMetadata>,
+}
+
+#[derive(Debug, Serialize)]
+struct PouMetadata {
+    name: String,
+    kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    return_type: Option<String>,
+    parameters: Vec<ParameterMetadata>,
+    /// the layout of the instance struct for stateful POUs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instance: Option<TypeMetadata>,
+}
+
+#[derive(Debug, Serialize)]
+struct ParameterMetadata {
+    name: String,
+    direction: &'static str,
+    data_type: String,
+}
+
+#[derive(Debug, Serialize)]
+struct TypeMetadata {
+    name: String,
+    size: u32,
+    alignment: u32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    members: Vec<MemberMetadata>,
+}
+
+#[derive(Debug, Serialize)]
+struct MemberMetadata {
+    name: String,
+    data_type: String,
+    offset: u32,
+}
+
+/// Collects the exported POUs and types of the given index, sizes and offsets are taken from the
+/// given layouts. Only POUs and types declared in the compiled sources are exported, included
+/// (external) and compiler-generated declarations are skipped
+pub fn collect_metadata(index: &Index, layouts: &TypeLayouts) -> InterfaceMetadata {
+    let pous = index
+        .get_pous()
+        .values()
+        .filter(|it| *it.get_linkage() == LinkageType::Internal && !it.is_generic())
+        .filter(|it| !matches!(it, PouIndexEntry::Function { is_generated: true, .. }))
+        .map(|it| collect_pou(it, index, layouts))
+        .collect();
+    let types = index
+        .get_types()
+        .values()
+        .filter(|it| !it.is_internal() && !it.get_name().starts_with("__"))
+        .filter(|it| !matches!(it.get_type_information(), DataTypeInformation::Generic { .. }))
+        .map(|it| collect_type(it, index, layouts))
+        .collect();
+    InterfaceMetadata {
+        metadata_version: METADATA_VERSION,
+        compiler_version: env!("CARGO_PKG_VERSION"),
+        pous,
+        types,
+    }
+}
+
+fn collect_pou(pou: &PouIndexEntry, index: &Index, layouts: &TypeLayouts) -> PouMetadata {
+    let kind = match pou {
+        PouIndexEntry::Program { .. } => "program",
+        PouIndexEntry::FunctionBlock { .. } => "function_block",
+        PouIndexEntry::Function { .. } => "function",
+        PouIndexEntry::Class { .. } => "class",
+        PouIndexEntry::Method { .. } => "method",
+        PouIndexEntry::Action { .. } => "action",
+    };
+    let name = pou.get_name();
+    let return_type = match pou {
+        PouIndexEntry::Function { .. } | PouIndexEntry::Method { .. } => index
+            .find_return_type(name)
+            .filter(|it| !matches!(it.get_type_information(), DataTypeInformation::Void))
+            .map(|it| get_type_name(it.get_name(), index)),
+        _ => None,
+    };
+    let parameters = index
+        .get_declared_parameters(name)
+        .into_iter()
+        .map(|it| ParameterMetadata {
+            name: it.get_name().to_string(),
+            direction: match it.get_variable_type() {
+                VariableType::Output => "output",
+                VariableType::InOut => "inout",
+                _ => "input",
+            },
+            data_type: get_type_name(it.get_type_name(), index),
+        })
+        .collect();
+    let instance = pou
+        .find_instance_struct_type(index)
+        .filter(|_| !matches!(pou, PouIndexEntry::Method { .. } | PouIndexEntry::Action { .. }))
+        .map(|it| collect_type(it, index, layouts));
+    PouMetadata { name: name.to_string(), kind, return_type, parameters, instance }
+}
+
+fn collect_type(data_type: &DataType, index: &Index, layouts: &TypeLayouts) -> TypeMetadata {
+    let information = data_type.get_type_information();
+    let members = match information {
+        DataTypeInformation::Struct { members, source, .. } => {
+            get_member_layout(data_type, members, source, index, layouts)
+        }
+        _ => vec![],
+    };
+    TypeMetadata {
+        name: data_type.get_name().to_string(),
+        // types without an LLVM equivalent (e.g. `ARRAY[*]`) fall back to the sizes of the index
+        size: layouts.get_size(data_type).map_or_else(|| information.get_size(index).value(), |it| it as u32),
+        alignment: layouts
+            .get_alignment(data_type)
+            .unwrap_or_else(|| information.get_alignment(index).value()),
+        members,
+    }
+}
+
+/// Returns the byte-offsets of the given struct members as laid out by LLVM
+fn get_member_layout(
+    data_type: &DataType,
+    members: &[VariableIndexEntry],
+    source: &StructSource,
+    index: &Index,
+    layouts: &TypeLayouts,
+) -> Vec<MemberMetadata> {
+    members
+        .iter()
+        // temporaries and return values are not part of the struct
+        .filter(|it| !it.is_temp() && !it.is_return())
+        .enumerate()
+        .map(|(position, it)| {
+            let offset = match source {
+                // all members of a union share the same memory
+                StructSource::Union => 0,
+                // the offset of the byte holding the member's bit
+                StructSource::Bitfield => it.get_location_in_parent() / 8,
+                _ => layouts.get_member_offset(index, data_type, position as u32).unwrap_or_default() as u32,
+            };
+            MemberMetadata {
+                name: it.get_name().to_string(),
+                data_type: get_type_name(it.get_type_name(), index),
+                offset,
+            }
+        })
+        .collect()
+}
+
+/// Returns the declared name of a type, or a description of the type if it was declared inline
+/// (e.g. `ARRAY[0..2] OF INT`)
+fn get_type_name(type_name: &str, index: &Index) -> String {
+    if !type_name.starts_with("__") {
+        return type_name.to_string();
+    }
+    index.find_type(type_name).map(|it| it.get_display_name(index)).unwrap_or_else(|| type_name.to_string())
+}
+
+pub fn generate_metadata(metadata: &InterfaceMetadata) -> Result<String, Diagnostic> {
+    serde_json::to_string_pretty(metadata)
+        .map_err(|e| Diagnostic::GeneralError { message: e.to_string(), err_no: ErrNo::general__io_err })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        codegen::{layout::TypeLayouts, CodegenContext},
+        metadata::{collect_metadata, generate_metadata},
+        test_utils::tests::index_and_annotate,
+        Target,
+    };
+
+    #[test]
+    fn metadata_of_a_small_library() {
+        let (index, annotations) = index_and_annotate(
+            "
+        TYPE Point : STRUCT
+            x : BYTE;
+            y : DINT;
+            z : LREAL;
+        END_STRUCT END_TYPE
+
+        TYPE Color : (red, green, blue); END_TYPE
+
+        TYPE Named : STRUCT
+            tag : BYTE;
+            name : STRING[10];
+            value : LINT;
+        END_STRUCT END_TYPE
+
+        TYPE Overlay : UNION
+            dw : DWORD;
+            b : BYTE;
+        END_UNION END_TYPE
+
+        FUNCTION distance : LREAL
+        VAR_INPUT
+            a, b : Point;
+        END_VAR
+        END_FUNCTION
+
+        FUNCTION_BLOCK counter
+        VAR_INPUT
+            reset : BOOL;
+        END_VAR
+        VAR_OUTPUT
+            count : DINT;
+        END_VAR
+        VAR
+            history : ARRAY[0..3] OF INT;
+        END_VAR
+        END_FUNCTION_BLOCK
+
+        {external}
+        FUNCTION puts : DINT
+        VAR_INPUT
+            text : STRING;
+        END_VAR
+        END_FUNCTION
+        ",
+        );
+        let context = CodegenContext::create();
+        let layouts =
+            TypeLayouts::new(&context, &index, &annotations, &Target::from("x86_64-unknown-linux-gnu"))
+                .unwrap();
+        let mut metadata = collect_metadata(&index, &layouts);
+        metadata.compiler_version = "[compiler_version]";
+        let metadata = generate_metadata(&metadata).unwrap();
+        insta::assert_snapshot!(metadata);
+    }
+}
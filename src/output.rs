@@ -19,6 +19,8 @@ pub enum FormatOption {
     Bitcode,
     /// Indicates that the compile result will be LLVM IR
     IR,
+    /// Indicates that the compile result will be target assembly
+    Assembly,
 }
 
 impl FormatOption {
@@ -32,4 +34,15 @@ impl FormatOption {
                 | FormatOption::Relocatable
         )
     }
+
+    /// returns the extension of the file a single compilation unit is persisted to
+    /// formats that require linking persist their units as object files
+    pub fn get_extension(self) -> &'static str {
+        match self {
+            FormatOption::IR => "ll",
+            FormatOption::Bitcode => "bc",
+            FormatOption::Assembly => "s",
+            _ => "o",
+        }
+    }
 }
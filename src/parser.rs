@@ -5,8 +5,8 @@ use std::ops::Range;
 use plc_ast::{
     ast::{
         AccessModifier, ArgumentProperty, AstFactory, AstNode, AstStatement, CompilationUnit, DataType,
-        DataTypeDeclaration, DirectAccessType, GenericBinding, HardwareAccessType, Implementation,
-        LinkageType, PolymorphismMode, Pou, PouType, ReferenceAccess, ReferenceExpr, TypeNature,
+        DataTypeDeclaration, DirectAccessType, GenericBinding, HardwareAccessType, Implementation, Include,
+        LinkageType, PointerKind, PolymorphismMode, Pou, PouType, ReferenceAccess, ReferenceExpr, TypeNature,
         UserTypeDeclaration, Variable, VariableBlock, VariableBlockType,
     },
     provider::IdProvider,
@@ -59,6 +59,8 @@ pub fn parse(mut lexer: ParseSession, lnk: LinkageType, file_name: &str) -> Pars
     let mut unit = CompilationUnit::new(file_name);
 
     let mut linkage = lnk;
+    let mut extern_name = None;
+    let mut function_attributes = vec![];
     loop {
         match lexer.token {
             PropertyExternal => {
@@ -67,7 +69,19 @@ pub fn parse(mut lexer: ParseSession, lnk: LinkageType, file_name: &str) -> Pars
                 //Don't reset linkage
                 continue;
             }
+            PropertyExternName => {
+                extern_name = parse_extern_name(&mut lexer);
+                //Don't reset the extern name
+                continue;
+            }
+            PropertyGnuAttr => {
+                function_attributes.extend(parse_function_attribute(&mut lexer));
+                //Don't reset the function attributes
+                continue;
+            }
+            PropertyInclude => unit.includes.extend(parse_include(&mut lexer)),
             KeywordVarGlobal => unit.global_vars.push(parse_variable_block(&mut lexer, linkage)),
+            KeywordConfiguration => unit.global_vars.append(&mut parse_configuration(&mut lexer, linkage)),
             KeywordProgram | KeywordClass | KeywordFunction | KeywordFunctionBlock => {
                 let params = match lexer.token {
                     KeywordProgram => (PouType::Program, KeywordEndProgram),
@@ -77,6 +91,12 @@ pub fn parse(mut lexer: ParseSession, lnk: LinkageType, file_name: &str) -> Pars
                 };
 
                 let (mut pou, mut implementation) = parse_pou(&mut lexer, params.0, linkage, params.1);
+                // the POU's own implementation is the last one, the ones before belong to its methods
+                if let Some(implementation) = implementation.last_mut() {
+                    implementation.extern_name = extern_name.take().map(|(name, _)| name);
+                    implementation.function_attributes =
+                        function_attributes.drain(..).map(|(attribute, _)| attribute).collect();
+                }
 
                 unit.units.append(&mut pou);
                 unit.implementations.append(&mut implementation);
@@ -108,10 +128,102 @@ pub fn parse(mut lexer: ParseSession, lnk: LinkageType, file_name: &str) -> Pars
             }
         };
         linkage = lnk;
+        if let Some((name, location)) = extern_name.take() {
+            lexer.accept_diagnostic(Diagnostic::invalid_pragma_location(
+                &format!("extern_name := '{name}' can only be used on POUs"),
+                location,
+            ));
+        }
+        for (attribute, location) in function_attributes.drain(..) {
+            lexer.accept_diagnostic(Diagnostic::invalid_pragma_location(
+                &format!("gnu_attr := '{attribute}' can only be used on POUs"),
+                location,
+            ));
+        }
     }
     //the match in the loop will always return
 }
 
+/// parses the symbol name of an `{extern_name := 'name'}` attribute
+fn parse_extern_name(lexer: &mut ParseSession) -> Option<(String, SourceLocation)> {
+    let location = lexer.location();
+    let slice = lexer.slice_and_advance();
+    let name = slice.split('\'').nth(1).unwrap_or_default();
+    Some((name.to_string(), location)).filter(|(name, _)| !name.is_empty())
+}
+
+/// parses the file of an `{include := 'file'}` directive
+fn parse_include(lexer: &mut ParseSession) -> Option<Include> {
+    let location = lexer.location();
+    let slice = lexer.slice_and_advance();
+    let path = slice.split('\'').nth(1).unwrap_or_default();
+    Some(Include { path: path.to_string(), location }).filter(|it| !it.path.is_empty())
+}
+
+/// the LLVM function attributes which may be requested using `{gnu_attr := '...'}`
+const ALLOWED_FUNCTION_ATTRIBUTES: [&str; 4] = ["noinline", "cold", "hot", "naked"];
+
+/// parses the attribute of a `{gnu_attr := 'attribute'}` pragma, attributes which are not
+/// allowed are reported and ignored
+fn parse_function_attribute(lexer: &mut ParseSession) -> Option<(String, SourceLocation)> {
+    let location = lexer.location();
+    let slice = lexer.slice_and_advance();
+    let attribute = slice.split('\'').nth(1).unwrap_or_default().trim().to_lowercase();
+    if ALLOWED_FUNCTION_ATTRIBUTES.contains(&attribute.as_str()) {
+        Some((attribute, location))
+    } else {
+        lexer.accept_diagnostic(Diagnostic::unsupported_function_attribute(
+            &attribute,
+            &ALLOWED_FUNCTION_ATTRIBUTES,
+            location,
+        ));
+        None
+    }
+}
+
+/// parses a `CONFIGURATION name ... END_CONFIGURATION` and returns its `VAR_GLOBAL` blocks, which
+/// are visible project-wide. All other elements (e.g. `RESOURCE`s, `TASK`s or program instances)
+/// are reported and skipped
+fn parse_configuration(lexer: &mut ParseSession, linkage: LinkageType) -> Vec<VariableBlock> {
+    lexer.advance(); //Consume CONFIGURATION
+    parse_any_in_region(lexer, vec![KeywordEndConfiguration], |lexer| {
+        if !lexer.try_consume(&Identifier) {
+            lexer.accept_diagnostic(Diagnostic::missing_token("Identifier", lexer.location()));
+        }
+
+        let mut global_vars = vec![];
+        while lexer.token != KeywordEndConfiguration && !lexer.is_end_of_stream() {
+            if lexer.token == KeywordVarGlobal {
+                global_vars.push(parse_variable_block(lexer, linkage));
+                continue;
+            }
+
+            let start = lexer.range().start;
+            let element = lexer.slice().to_uppercase();
+            if lexer.token == KeywordResource {
+                //Skip the whole resource
+                while !matches!(lexer.token, KeywordEndResource | KeywordEndConfiguration)
+                    && !lexer.is_end_of_stream()
+                {
+                    lexer.advance();
+                }
+                lexer.try_consume(&KeywordEndResource);
+            } else {
+                //Skip until the next element we can continue with
+                lexer.advance();
+                while !matches!(lexer.token, KeywordVarGlobal | KeywordResource | KeywordEndConfiguration)
+                    && !lexer.is_end_of_stream()
+                {
+                    lexer.advance();
+                }
+            }
+            let location = lexer.source_range_factory.create_range(start..lexer.last_range.end);
+            lexer.accept_diagnostic(Diagnostic::unsupported_configuration_element(&element, location));
+        }
+        global_vars
+    })
+}
+
 fn parse_actions(
     lexer: &mut ParseSession,
     linkage: LinkageType,
@@ -353,7 +465,13 @@ fn parse_return_type(lexer: &mut ParseSession, pou_type: &PouType) -> Option<Dat
             }
 
             if let DataTypeDeclaration::DataTypeDefinition { data_type, .. } = &declaration {
-                if matches!(data_type, DataType::EnumType { .. } | DataType::StructType { .. }) {
+                if matches!(
+                    data_type,
+                    DataType::EnumType { .. }
+                        | DataType::StructType { .. }
+                        | DataType::UnionType { .. }
+                        | DataType::BitfieldType { .. }
+                ) {
                     lexer.accept_diagnostic(Diagnostic::function_unsupported_return_type(&declaration))
                 }
             }
@@ -491,6 +609,8 @@ fn parse_implementation(
         overriding: false,
         generic,
         access: None,
+        extern_name: None,
+        function_attributes: vec![],
     }
 }
 
@@ -574,7 +694,11 @@ fn parse_full_data_type_definition(
     lexer: &mut ParseSession,
     name: Option<String>,
 ) -> Option<DataTypeWithInitializer> {
-    let end_keyword = if lexer.token == KeywordStruct { KeywordEndStruct } else { KeywordSemicolon };
+    let end_keyword = match lexer.token {
+        KeywordStruct | PropertyBitfield => KeywordEndStruct,
+        KeywordUnion => KeywordEndUnion,
+        _ => KeywordSemicolon,
+    };
     parse_any_in_region(lexer, vec![end_keyword], |lexer| {
         let sized = lexer.try_consume(&PropertySized);
         if lexer.try_consume(&KeywordDotDotDot) {
@@ -622,6 +746,33 @@ fn parse_data_type_definition(
             },
             None,
         ))
+    } else if lexer.try_consume(&KeywordUnion) {
+        // Parse union
+        let variables = parse_variable_list(lexer);
+        Some((
+            DataTypeDeclaration::DataTypeDefinition {
+                data_type: DataType::UnionType { name, variables },
+                location: start.span(&lexer.location()),
+                scope: lexer.scope.clone(),
+            },
+            None,
+        ))
+    } else if lexer.try_consume(&PropertyBitfield) {
+        // Parse a struct whose members are packed into single bits
+        if let Err(diag) = lexer.expect(KeywordStruct) {
+            lexer.accept_diagnostic(diag);
+        } else {
+            lexer.advance();
+        }
+        let variables = parse_variable_list(lexer);
+        Some((
+            DataTypeDeclaration::DataTypeDefinition {
+                data_type: DataType::BitfieldType { name, variables },
+                location: start.span(&lexer.location()),
+                scope: lexer.scope.clone(),
+            },
+            None,
+        ))
     } else if lexer.try_consume(&KeywordArray) {
         parse_array_type_definition(lexer, name)
     } else if lexer.try_consume(&KeywordPointer) {
@@ -636,9 +787,15 @@ fn parse_data_type_definition(
         } else {
             lexer.advance();
         }
-        parse_pointer_definition(lexer, name, start_pos)
+        parse_pointer_definition(lexer, name, start_pos, PointerKind::Raw)
     } else if lexer.try_consume(&KeywordRef) {
-        parse_pointer_definition(lexer, name, lexer.last_range.start)
+        parse_pointer_definition(lexer, name, lexer.last_range.start, PointerKind::Raw)
+    } else if is_reference_to(lexer) {
+        // `REFERENCE` is no reserved keyword, it only starts a reference if it is followed by `TO`
+        let start_pos = lexer.range().start;
+        lexer.advance();
+        lexer.advance();
+        parse_pointer_definition(lexer, name, start_pos, PointerKind::Reference)
     } else if lexer.try_consume(&KeywordParensOpen) {
         //enum without datatype
         parse_enum_type_definition(lexer, name)
@@ -657,15 +814,21 @@ fn parse_data_type_definition(
     }
 }
 
+/// returns true if the lexer is positioned at a `REFERENCE TO`
+fn is_reference_to(lexer: &ParseSession) -> bool {
+    lexer.token == Identifier && lexer.slice().eq_ignore_ascii_case("REFERENCE") && lexer.peek() == KeywordTo
+}
+
 fn parse_pointer_definition(
     lexer: &mut ParseSession,
     name: Option<String>,
     start_pos: usize,
+    kind: PointerKind,
 ) -> Option<(DataTypeDeclaration, Option<AstNode>)> {
     parse_data_type_definition(lexer, None).map(|(decl, initializer)| {
         (
             DataTypeDeclaration::DataTypeDefinition {
-                data_type: DataType::PointerType { name, referenced_type: Box::new(decl) },
+                data_type: DataType::PointerType { name, referenced_type: Box::new(decl), kind },
                 location: lexer.source_range_factory.create_range(start_pos..lexer.last_range.end),
                 scope: lexer.scope.clone(),
             },
@@ -996,7 +1159,17 @@ fn parse_variable_block(lexer: &mut ParseSession, linkage: LinkageType) -> Varia
 
     let access = parse_access_modifier(lexer);
 
-    let mut variables = parse_any_in_region(lexer, vec![KeywordEndVar], parse_variable_list);
+    lexer.enter_region(vec![KeywordEndVar]);
+    let mut variables = parse_variable_list(lexer);
+    if lexer.token != KeywordEndVar && lexer.closes_open_region(&lexer.token) {
+        // the block ends where its container ends (e.g. at END_PROGRAM), report the missing
+        // END_VAR and close the block right there so the container can still be parsed
+        lexer.accept_diagnostic(Diagnostic::unterminated_var_block(location.clone()));
+        lexer.abandon_region();
+    } else {
+        lexer.recover_until_close();
+        lexer.close_region();
+    }
 
     if constant {
         // sneak in the DefaultValue-Statements if no initializers were defined
@@ -1010,7 +1183,8 @@ fn parse_variable_block(lexer: &mut ParseSession, linkage: LinkageType) -> Varia
 
 fn parse_variable_list(lexer: &mut ParseSession) -> Vec<Variable> {
     let mut variables = vec![];
-    while lexer.token == Identifier {
+    // keywords used as names are reported during validation
+    while lexer.token == Identifier || lexer.is_keyword_used_as_name() {
         let mut line_vars = parse_variable_line(lexer);
         variables.append(&mut line_vars);
     }
@@ -1020,7 +1194,7 @@ fn parse_variable_list(lexer: &mut ParseSession) -> Vec<Variable> {
 fn parse_variable_line(lexer: &mut ParseSession) -> Vec<Variable> {
     // read in a comma separated list of variable names
     let mut var_names: Vec<(String, Range<usize>)> = vec![];
-    while lexer.token == Identifier {
+    while lexer.token == Identifier || lexer.is_keyword_used_as_name() {
         let location = lexer.range();
         let identifier_end = location.end;
         var_names.push((lexer.slice_and_advance(), location));
@@ -265,6 +265,7 @@ fn parse_atomic_leaf_expression(lexer: &mut ParseSession<'_>) -> Result<AstNode,
             })
         }
         Identifier => Ok(parse_identifier(lexer)),
+        KeywordThis => parse_this(lexer),
         HardwareAccess((hw_type, access_type)) => parse_hardware_access(lexer, hw_type, access_type),
         LiteralInteger => parse_literal_number(lexer, false),
         LiteralIntegerBin => parse_literal_number_with_modifier(lexer, 2, false),
@@ -300,6 +301,14 @@ fn parse_identifier(lexer: &mut ParseSession<'_>) -> AstNode {
     AstFactory::create_identifier(&lexer.slice_and_advance(), &lexer.last_location(), lexer.next_id())
 }
 
+#[allow(clippy::unnecessary_wraps)]
+//Allowing the unnecessary wrap here because this method is used along other methods that need to return Results
+fn parse_this(lexer: &mut ParseSession) -> Result<AstNode, Diagnostic> {
+    let location = lexer.location();
+    lexer.advance();
+    Ok(AstFactory::create_this(location, lexer.next_id()))
+}
+
 fn parse_vla_range(lexer: &mut ParseSession) -> Result<AstNode, Diagnostic> {
     lexer.advance();
     Ok(AstFactory::create_vla_range_statement(lexer.last_location(), lexer.next_id()))
@@ -406,17 +415,31 @@ pub fn parse_qualified_reference(lexer: &mut ParseSession) -> Result<AstNode, Di
                 let location = lexer.location();
                 let mut type_name = lexer.slice_and_advance();
                 type_name.pop(); // get rid of the "#" at the end
-                let stmt = parse_atomic_leaf_expression(lexer)?;
-                let end = stmt.get_location();
                 let type_range = lexer
                     .source_range_factory
                     .create_range(location_start..(location_start + type_name.len()));
-                current = Some(AstFactory::create_cast_statement(
-                    AstFactory::create_member_reference(
-                        AstFactory::create_identifier(type_name.as_str(), &type_range, lexer.next_id()),
-                        None,
+                let mut type_reference = AstFactory::create_member_reference(
+                    AstFactory::create_identifier(type_name.as_str(), &type_range, lexer.next_id()),
+                    None,
+                    lexer.next_id(),
+                );
+                // a string cast may limit the length of the string: STRING#[20]'abc'
+                let is_string = ["STRING", "WSTRING"].iter().any(|it| it.eq_ignore_ascii_case(&type_name));
+                if is_string && lexer.token == KeywordSquareParensOpen {
+                    lexer.advance();
+                    let length = parse_any_in_region(lexer, vec![KeywordSquareParensClose], parse_expression);
+                    let location = type_range.span(&lexer.last_location());
+                    type_reference = AstFactory::create_index_reference(
+                        length,
+                        Some(type_reference),
                         lexer.next_id(),
-                    ),
+                        location,
+                    );
+                }
+                let stmt = parse_atomic_leaf_expression(lexer)?;
+                let end = stmt.get_location();
+                current = Some(AstFactory::create_cast_statement(
+                    type_reference,
                     stmt,
                     &location.span(&end),
                     lexer.next_id(),
@@ -472,6 +472,34 @@ fn literal_time_test() {
     assert_eq!(ast_string, expected_ast);
 }
 
+#[test]
+fn literal_time_with_fractions_and_signs_test() {
+    let src = "
+        PROGRAM exp
+            T#1.5h;
+            T#0.25s;
+            T#-1h30m;
+        END_PROGRAM
+        ";
+    let result = parse(src).0;
+    let times = result.implementations[0]
+        .statements
+        .iter()
+        .map(|it| match it.get_stmt() {
+            AstStatement::Literal(AstLiteral::Time(time)) => (time.hour, time.min, time.sec, time.negative),
+            _ => panic!("expected a time literal but found {it:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(times, vec![(1.5, 0.0, 0.0, false), (0.0, 0.0, 0.25, false), (1.0, 30.0, 0.0, true)]);
+
+    // the fractions are carried into the literal's value
+    let AstStatement::Literal(AstLiteral::Time(time)) = result.implementations[0].statements[0].get_stmt()
+    else {
+        unreachable!()
+    };
+    assert_eq!(time.value(), 90 * 60 * 1_000_000_000);
+}
+
 #[test]
 fn literal_long_time_test() {
     let src = "
@@ -520,3 +520,72 @@ fn var_input_by_ref_parsed() {
     }
     "###)
 }
+
+#[test]
+fn extern_name_attribute_is_parsed_on_pous() {
+    let src = "
+        {extern_name := 'my_c_func'}
+        FUNCTION foo : DINT END_FUNCTION
+
+        {external}
+        {extern_name := 'c_puts'}
+        FUNCTION bar : DINT END_FUNCTION
+
+        FUNCTION baz : DINT END_FUNCTION
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(result.implementations[0].extern_name.as_deref(), Some("my_c_func"));
+    assert_eq!(result.implementations[1].extern_name.as_deref(), Some("c_puts"));
+    assert_eq!(result.implementations[1].linkage, LinkageType::External);
+    assert_eq!(result.implementations[2].extern_name, None);
+}
+
+#[test]
+fn extern_name_attribute_on_non_pou_is_reported() {
+    let src = "
+        {extern_name := 'my_global'}
+        VAR_GLOBAL x : DINT; END_VAR
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert!(result.implementations.is_empty());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].get_message(),
+        "Invalid pragma location: extern_name := 'my_global' can only be used on POUs"
+    );
+}
+
+#[test]
+fn gnu_attr_attributes_are_parsed_on_pous() {
+    let src = "
+        {gnu_attr := 'noinline'}
+        {gnu_attr := 'cold'}
+        FUNCTION foo : DINT END_FUNCTION
+
+        FUNCTION bar : DINT END_FUNCTION
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(result.implementations[0].function_attributes, vec!["noinline", "cold"]);
+    assert!(result.implementations[1].function_attributes.is_empty());
+}
+
+#[test]
+fn disallowed_gnu_attr_attributes_are_reported_and_ignored() {
+    let src = "
+        {gnu_attr := 'alwaysinline'}
+        FUNCTION foo : DINT END_FUNCTION
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert!(result.implementations[0].function_attributes.is_empty());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].get_message(),
+        "Unsupported function attribute 'alwaysinline' will be ignored, allowed attributes are: noinline, cold, hot, naked"
+    );
+}
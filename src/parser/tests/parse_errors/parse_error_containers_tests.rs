@@ -161,6 +161,29 @@ fn unclosed_var_container() {
     assert_debug_snapshot!(var_block)
 }
 
+#[test]
+fn missing_end_var_is_reported_at_the_unterminated_var_block() {
+    let src = r"
+                PROGRAM foo
+                    VAR a : INT;
+                END_PROGRAM
+
+                PROGRAM bar
+                    VAR b : INT; END_VAR
+                    b := 1;
+                END_PROGRAM
+                ";
+
+    let (compilation_unit, diagnostics) = parse_buffered(src);
+    assert_snapshot!(diagnostics);
+
+    //check if a and the following POU were parsed successfully
+    assert_eq!(compilation_unit.units[0].variable_blocks[0].variables[0].name, "a");
+    assert_eq!(compilation_unit.units[1].name, "bar");
+    assert_eq!(compilation_unit.units[1].variable_blocks[0].variables[0].name, "b");
+    assert_eq!(compilation_unit.implementations[1].statements.len(), 1);
+}
+
 #[test]
 fn test_unexpected_type_declaration_error_message() {
     let src = "TYPE MyType:
@@ -2,8 +2,8 @@
 use crate::{parser::tests::ref_to, test_utils::tests::parse_buffered};
 use insta::{assert_debug_snapshot, assert_snapshot};
 use plc_ast::ast::{
-    AccessModifier, AstFactory, DataType, DataTypeDeclaration, LinkageType, UserTypeDeclaration, Variable,
-    VariableBlock, VariableBlockType,
+    AccessModifier, AstFactory, DataType, DataTypeDeclaration, LinkageType, PointerKind, UserTypeDeclaration,
+    Variable, VariableBlock, VariableBlockType,
 };
 use plc_source::source_location::SourceLocation;
 use pretty_assertions::*;
@@ -1128,6 +1128,7 @@ fn pointer_type_without_to_test() {
                 referenced_type: "INT".to_string(),
                 location: SourceLocation::undefined(),
             }),
+            kind: PointerKind::Raw,
         },
         location: SourceLocation::undefined(),
         initializer: None,
@@ -1154,6 +1155,7 @@ fn pointer_type_with_wrong_keyword_to_test() {
                 referenced_type: "tu".to_string(),
                 location: SourceLocation::undefined(),
             }),
+            kind: PointerKind::Raw,
         },
         location: SourceLocation::undefined(),
         initializer: None,
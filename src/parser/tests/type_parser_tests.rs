@@ -83,6 +83,44 @@ fn simple_struct_type_can_be_parsed() {
     assert_eq!(ast_string, expected_ast);
 }
 
+#[test]
+fn simple_union_type_can_be_parsed() {
+    let (result, diagnostics) = parse(
+        r#"
+        TYPE SampleUnion :
+            UNION
+                dw : DWORD;
+                b : BYTE;
+            END_UNION
+        END_TYPE
+        "#,
+    );
+
+    assert_eq!(diagnostics, vec![]);
+    assert_debug_snapshot!(result.user_types[0]);
+}
+
+#[test]
+fn bitfield_struct_type_can_be_parsed() {
+    let (result, diagnostics) = parse(
+        r#"
+        TYPE StatusWord :
+            {bitfield} STRUCT
+                ready : BOOL;
+                error : BOOL;
+            END_STRUCT
+        END_TYPE
+        "#,
+    );
+
+    assert_eq!(diagnostics, vec![]);
+    let DataType::BitfieldType { name, variables } = &result.user_types[0].data_type else {
+        panic!("expected a bitfield, found {:?}", result.user_types[0].data_type);
+    };
+    assert_eq!(name.as_deref(), Some("StatusWord"));
+    assert_eq!(variables.iter().map(|it| it.name.as_str()).collect::<Vec<_>>(), vec!["ready", "error"]);
+}
+
 #[test]
 fn simple_enum_type_can_be_parsed() {
     let (result, ..) = parse(
@@ -258,6 +296,19 @@ fn ref_type_test() {
     assert_eq!(diagnostics.len(), 0)
 }
 
+#[test]
+fn reference_to_type_test() {
+    let (result, diagnostics) = parse(
+        r#"
+        TYPE SampleReference :
+            REFERENCE TO INT;
+        END_TYPE
+        "#,
+    );
+    assert_debug_snapshot!(result.user_types[0]);
+    assert_eq!(diagnostics.len(), 0)
+}
+
 #[test]
 fn global_pointer_declaration() {
     let (result, diagnostics) = parse_buffered(
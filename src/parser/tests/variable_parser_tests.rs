@@ -247,3 +247,55 @@ fn date_and_time_constants_test() {
     assert_eq!(diag, vec![]);
     insta::assert_snapshot!(format!("{vars:#?}"));
 }
+
+#[test]
+fn global_vars_of_a_configuration_can_be_parsed() {
+    let src = "
+    CONFIGURATION config
+        VAR_GLOBAL
+            x : INT;
+        END_VAR
+        VAR_GLOBAL CONSTANT
+            y : BOOL := TRUE;
+        END_VAR
+    END_CONFIGURATION
+    ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    let names = result
+        .global_vars
+        .iter()
+        .flat_map(|it| it.variables.iter())
+        .map(|it| it.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["x", "y"]);
+    assert!(result.global_vars[1].constant);
+}
+
+#[test]
+fn unsupported_elements_of_a_configuration_are_reported_and_skipped() {
+    let src = "
+    CONFIGURATION config
+        RESOURCE res ON PLC
+            TASK fast(INTERVAL := T#10ms, PRIORITY := 1);
+            PROGRAM main WITH fast : prg;
+        END_RESOURCE
+        VAR_GLOBAL
+            x : INT;
+        END_VAR
+    END_CONFIGURATION
+
+    PROGRAM prg
+    END_PROGRAM
+    ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].get_message(),
+        "RESOURCE is not supported inside a CONFIGURATION and will be ignored, only VAR_GLOBAL blocks are supported"
+    );
+    assert_eq!(result.global_vars[0].variables[0].name, "x");
+    assert_eq!(result.units[0].name, "prg");
+}
@@ -15,8 +15,8 @@ use plc_ast::{
     ast::{
         self, flatten_expression_list, Assignment, AstFactory, AstId, AstNode, AstStatement,
         BinaryExpression, CastStatement, CompilationUnit, DataType, DataTypeDeclaration, DirectAccessType,
-        JumpStatement, Operator, Pou, ReferenceAccess, ReferenceExpr, TypeNature, UserTypeDeclaration,
-        Variable,
+        JumpStatement, Operator, PointerKind, Pou, ReferenceAccess, ReferenceExpr, TypeNature,
+        UserTypeDeclaration, Variable,
     },
     control_statements::{AstControlStatement, ReturnStatement},
     literals::{Array, AstLiteral, StringValue},
@@ -29,8 +29,8 @@ pub mod const_evaluator;
 pub mod generics;
 
 use crate::{
-    builtins::{self, BuiltIn},
-    index::{ArgumentType, Index, PouIndexEntry, VariableIndexEntry, VariableType},
+    builtins::BuiltIn,
+    index::{ArgumentType, ImplementationType, Index, PouIndexEntry, VariableIndexEntry, VariableType},
     typesystem::{
         self, get_bigger_type, DataTypeInformation, InternalType, StringEncoding, StructSource, BOOL_TYPE,
         BYTE_TYPE, DATE_AND_TIME_TYPE, DATE_TYPE, DINT_TYPE, DWORD_TYPE, LINT_TYPE, LREAL_TYPE, LWORD_TYPE,
@@ -163,6 +163,11 @@ impl<'s> VisitorContext<'s> {
         }
     }
 
+    /// the name of the POU whose declaration or body is visited
+    pub(crate) fn get_pou(&self) -> Option<&'s str> {
+        self.pou
+    }
+
     fn is_in_a_body(&self) -> bool {
         self.in_body
     }
@@ -249,7 +254,8 @@ impl TypeAnnotator<'_> {
     }
 
     /// tries to call one of the EQUAL_XXX, LESS_XXX, GREATER_XXX functions for the
-    /// given type (of left). The given operator has to be a comparison-operator
+    /// given type (of left). The given operator has to be a comparison-operator.
+    /// A character compared to a string is promoted to a string of length 1 (e.g. `STRING_EQUAL`)
     fn create_typed_compare_call_statement(
         &self,
         ctx: &mut VisitorContext,
@@ -262,10 +268,13 @@ impl TypeAnnotator<'_> {
             .annotation_map
             .get_type_hint(left, self.index)
             .unwrap_or_else(|| self.annotation_map.get_type_or_void(left, self.index));
-        let cmp_function_name = crate::typesystem::get_equals_function_name_for(
-            left_type.get_type_information().get_name(),
-            operator,
-        );
+        let left_type = left_type.get_type_information();
+        let right_type = self.annotation_map.get_type_or_void(right, self.index).get_type_information();
+        let type_name = match left_type.get_promoted_string_encoding() {
+            Some(encoding) if right_type.is_string() => encoding.get_string_type_name(),
+            _ => left_type.get_name(),
+        };
+        let cmp_function_name = crate::typesystem::get_equals_function_name_for(type_name, operator);
 
         cmp_function_name
             .map(|name| {
@@ -1079,7 +1088,9 @@ impl<'i> TypeAnnotator<'i> {
             self.dependencies.insert(Dependency::Datatype(name.to_string()));
         }
         match data_type {
-            DataType::StructType { name: Some(name), variables, .. } => {
+            DataType::StructType { name: Some(name), variables, .. }
+            | DataType::UnionType { name: Some(name), variables }
+            | DataType::BitfieldType { name: Some(name), variables } => {
                 let ctx = ctx.with_qualifier(name.clone());
                 variables.iter().for_each(|v| self.visit_variable(&ctx, v))
             }
@@ -1216,7 +1227,14 @@ impl<'i> TypeAnnotator<'i> {
                     let r_intrinsic_type =
                         self.index.get_intrinsic_type_by_name(right_type.get_name()).get_type_information();
 
-                    if l_intrinsic_type.is_numerical() && r_intrinsic_type.is_numerical() {
+                    if let Some(date_time_type) = typesystem::get_date_time_arithmetic_result_type(
+                        &data.operator,
+                        l_intrinsic_type,
+                        r_intrinsic_type,
+                    ) {
+                        // all date and time types share the same 64 bit representation
+                        Some(date_time_type.to_string())
+                    } else if l_intrinsic_type.is_numerical() && r_intrinsic_type.is_numerical() {
                         let bigger_type = if l_intrinsic_type.is_bool() && r_intrinsic_type.is_bool() {
                             left_type
                         } else {
@@ -1417,6 +1435,13 @@ impl<'i> TypeAnnotator<'i> {
                     jumps.push(statement.get_id());
                 }
             }
+            AstStatement::This => {
+                if let Some(instance_type) = ctx.pou.and_then(|pou| get_this_instance_type(pou, self.index)) {
+                    let ptr_type =
+                        add_pointer_type(&mut self.annotation_map.new_index, instance_type.to_string());
+                    self.annotate(statement, StatementAnnotation::value(ptr_type));
+                }
+            }
             _ => {
                 self.visit_statement_literals(ctx, statement);
             }
@@ -1430,8 +1455,10 @@ impl<'i> TypeAnnotator<'i> {
         stmt: &AstNode,
         ctx: &VisitorContext,
     ) {
-        // first resolve base
-        if let Some(base) = base {
+        // first resolve base, the type of a string cast may limit its length: STRING#[20]'abc'
+        let is_sized_string = matches!(access, ReferenceAccess::Cast(_))
+            && base.map_or(false, |base| self.visit_sized_string_type(ctx, base));
+        if let Some(base) = base.filter(|_| !is_sized_string) {
             self.visit_statement(ctx, base);
         };
 
@@ -1465,7 +1492,13 @@ impl<'i> TypeAnnotator<'i> {
                     // if base is an enum, we need to look for members of this specific enum
                     let optional_enum_qualifier = Some(qualifier.as_str()).filter(|_| base_type.is_enum());
                     if ctx.is_in_a_body() {
-                        accept_cast_string_literal(&mut self.string_literals, base_type, target);
+                        accept_cast_string_literal(
+                            &mut self.string_literals,
+                            base_type,
+                            target,
+                            is_sized_string,
+                            self.index,
+                        );
                     }
 
                     if let Some(annotation) = self.resolve_reference_expression(
@@ -1485,7 +1518,47 @@ impl<'i> TypeAnnotator<'i> {
             }
             (ReferenceAccess::Index(index), Some(base)) => {
                 self.visit_statement(ctx, index);
-                if let Some(inner_type) = self
+                if let Some(DataTypeInformation::String { encoding, size }) =
+                    self.index.find_effective_type_info(base.as_str())
+                {
+                    // indexing a string reads a single character, e.g. `s[1]`
+                    self.annotate(stmt, StatementAnnotation::value(encoding.get_character_type_name()));
+
+                    // positions only known at runtime are passed through the range check function of
+                    // their type if one is declared, e.g. `s[i]` reads `s[CheckRangeSigned(i, 1, 80)]`
+                    // (the size includes the string's terminator, which cannot be accessed)
+                    let length = size.as_int_value(self.index).ok().map(|it| it - 1);
+                    let check_function = self.annotation_map.get_type(index, self.index).and_then(|it| {
+                        self.index.find_range_check_implementation_for(it.get_type_information())
+                    });
+                    if let (false, Some(length), Some(check_function)) =
+                        (matches!(index.get_stmt(), AstStatement::Literal(..)), length, check_function)
+                    {
+                        let location = index.get_location();
+                        let bound = |value: i64| {
+                            AstFactory::create_literal(
+                                AstLiteral::new_integer(value as i128),
+                                location.clone(),
+                                ctx.id_provider.clone().next_id(),
+                            )
+                        };
+                        let statement = AstFactory::create_call_to_check_function_ast(
+                            check_function.get_call_name(),
+                            index.as_ref().clone(),
+                            bound(1)..bound(length),
+                            &location,
+                            ctx.id_provider.clone(),
+                        );
+                        self.visit_call_statement(&statement, ctx);
+                        self.annotation_map.annotate_hidden_function_call(index, statement);
+                    }
+                } else if let (AstStatement::RangeStatement(..), Some(array_type)) = (
+                    index.get_stmt(),
+                    self.index.find_effective_type_by_name(base.as_str()).filter(|it| it.is_array()),
+                ) {
+                    // a slice, e.g. `arr[2..5]`, is a view into the array and keeps its type
+                    self.annotate(stmt, StatementAnnotation::value(array_type.get_name()))
+                } else if let Some(inner_type) = self
                     .index
                     .find_effective_type_info(base.as_str())
                     .and_then(|t| t.get_inner_array_type_name())
@@ -1495,7 +1568,9 @@ impl<'i> TypeAnnotator<'i> {
                 }
             }
             (ReferenceAccess::Deref, _) => {
-                if let Some(DataTypeInformation::Pointer { inner_type_name, auto_deref: false, .. }) = base
+                if let Some(DataTypeInformation::Pointer {
+                    inner_type_name, kind: PointerKind::Raw, ..
+                }) = base
                     .map(|base| self.annotation_map.get_type_or_void(base, self.index))
                     .map(|it| it.get_type_information())
                 {
@@ -1520,6 +1595,37 @@ impl<'i> TypeAnnotator<'i> {
         }
     }
 
+    /// annotates the type of a length-limited string cast (`STRING[20]` in `STRING#[20]'abc'`) with a
+    /// string type of the given length, returns false if `base` is no such type
+    fn visit_sized_string_type(&mut self, ctx: &VisitorContext, base: &AstNode) -> bool {
+        let AstStatement::ReferenceExpr(ReferenceExpr {
+            access: ReferenceAccess::Index(length),
+            base: Some(string_type),
+        }) = base.get_stmt()
+        else {
+            return false;
+        };
+        let Some(DataTypeInformation::String { encoding, .. }) =
+            string_type.get_flat_reference_name().and_then(|it| self.index.find_effective_type_info(it))
+        else {
+            return false;
+        };
+        let is_wide = *encoding == StringEncoding::Utf16;
+
+        self.visit_statement(ctx, length);
+        // the length must be known at compile time, other lengths are reported by the validation
+        if let Ok(Some(AstNode { stmt: AstStatement::Literal(AstLiteral::Integer(length)), .. })) =
+            const_evaluator::evaluate(length, ctx.pou, self.index)
+        {
+            if length > 0 {
+                let type_name =
+                    register_string_type(&mut self.annotation_map.new_index, is_wide, length as usize);
+                self.annotate(base, StatementAnnotation::Type { type_name });
+            }
+        }
+        true
+    }
+
     fn is_const_reference(&self, stmt: &AstNode, ctx: &VisitorContext<'_>) -> bool {
         self.annotation_map
             .get(stmt)
@@ -1640,9 +1746,20 @@ impl<'i> TypeAnnotator<'i> {
         } else {
             vec![]
         };
-        if let Some(annotation) = builtins::get_builtin(&operator_qualifier).and_then(BuiltIn::get_annotation)
+        // EN and ENO are implicit BOOL parameters of every function block
+        if let Some(pou) = self.index.find_pou(&operator_qualifier) {
+            for (kind, data) in parameters.iter().filter_map(|it| get_enable_parameter(pou, it, self.index)) {
+                self.annotate(&data.left, StatementAnnotation::value(BOOL_TYPE));
+                if kind == EnableParameter::Input {
+                    self.annotation_map
+                        .annotate_type_hint(&data.right, StatementAnnotation::value(BOOL_TYPE));
+                }
+            }
+        }
+        if let Some(annotation) =
+            self.index.get_builtin_function(&operator_qualifier).and_then(BuiltIn::get_annotation)
         {
-            annotation(self, operator, parameters_stmt, ctx.to_owned())
+            annotation(self, statement, operator, parameters_stmt, ctx.to_owned())
         } else {
             //If builtin, skip this
             let mut generics_candidates: HashMap<String, Vec<String>> = HashMap::new();
@@ -1747,6 +1864,10 @@ impl<'i> TypeAnnotator<'i> {
                 ctx.to_owned(),
             );
         }
+        if matches!(self.annotation_map.get(statement), Some(StatementAnnotation::ReplacementAst { .. })) {
+            // the builtin already replaced the call (e.g. `__LINE()`)
+            return;
+        }
         if let Some(StatementAnnotation::Function { return_type, .. }) = self.annotation_map.get(operator) {
             if let Some(return_type) = self
                 .index
@@ -1758,6 +1879,19 @@ impl<'i> TypeAnnotator<'i> {
         }
     }
 
+    /// replaces the given statement with a literal known at compile time, e.g. the line of a `__LINE()` call
+    pub(crate) fn replace_with_literal(
+        &mut self,
+        ctx: &VisitorContext,
+        statement: &AstNode,
+        literal: AstLiteral,
+    ) {
+        let replacement =
+            AstFactory::create_literal(literal, statement.get_location(), ctx.id_provider.clone().next_id());
+        self.visit_statement(ctx, &replacement);
+        self.annotate(statement, StatementAnnotation::ReplacementAst { statement: replacement });
+    }
+
     fn get_call_name(&mut self, operator: &AstNode) -> String {
         let operator_qualifier = self
             .annotation_map
@@ -1886,9 +2020,8 @@ fn get_direct_access_type(access: &DirectAccessType) -> &'static str {
 }
 
 /// adds a string-type to the given index and returns it's name
-fn register_string_type(index: &mut Index, is_wide: bool, len: usize) -> String {
-    let prefix = if is_wide { "WSTRING_" } else { "STRING_" };
-    let new_type_name = internal_type_name(prefix, len.to_string().as_str());
+pub(crate) fn register_string_type(index: &mut Index, is_wide: bool, len: usize) -> String {
+    let new_type_name = get_string_type_name_for_length(is_wide, len);
 
     if index.find_effective_type_by_name(new_type_name.as_str()).is_none() {
         index.register_type(crate::typesystem::DataType {
@@ -1905,6 +2038,59 @@ fn register_string_type(index: &mut Index, is_wide: bool, len: usize) -> String
     new_type_name
 }
 
+/// returns the name of the internal string type of the given length (excluding the terminator)
+pub(crate) fn get_string_type_name_for_length(is_wide: bool, len: usize) -> String {
+    let prefix = if is_wide { "WSTRING_" } else { "STRING_" };
+    internal_type_name(prefix, len.to_string().as_str())
+}
+
+/// returns the name of the function block or class `THIS` refers to inside the given implementation,
+/// or `None` if `THIS` is not allowed there (e.g. in functions or programs)
+pub(crate) fn get_this_instance_type<'i>(implementation: &str, index: &'i Index) -> Option<&'i str> {
+    let implementation = index.find_implementation_by_name(implementation)?;
+    let instance_type = match implementation.get_implementation_type() {
+        ImplementationType::FunctionBlock | ImplementationType::Class | ImplementationType::Action => {
+            implementation.get_type_name()
+        }
+        ImplementationType::Method => implementation.get_associated_class_name()?.as_str(),
+        _ => return None,
+    };
+    index
+        .find_pou(instance_type)
+        .filter(|pou| pou.is_function_block() || pou.is_class())
+        .map(|pou| pou.get_name())
+}
+
+/// the implicit control signals of a function block call, e.g. `fb(EN := enable, ENO => executed)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EnableParameter {
+    /// `EN := <BOOL>`, the call is skipped if it evaluates to `FALSE`
+    Input,
+    /// `ENO => <BOOL>`, reports whether the call was executed
+    Output,
+}
+
+/// returns the implicit `EN` or `ENO` parameter the given call-parameter of `pou` assigns, or `None` if it is
+/// a regular parameter. function blocks declaring their own `EN` or `ENO` members use them as regular parameters
+pub(crate) fn get_enable_parameter<'a>(
+    pou: &PouIndexEntry,
+    parameter: &'a AstNode,
+    index: &Index,
+) -> Option<(EnableParameter, &'a Assignment)> {
+    if !pou.is_function_block() {
+        return None;
+    }
+
+    let (kind, expected_name, data) = match parameter.get_stmt() {
+        AstStatement::Assignment(data) => (EnableParameter::Input, "EN", data),
+        AstStatement::OutputAssignment(data) => (EnableParameter::Output, "ENO", data),
+        _ => return None,
+    };
+    let name = data.left.get_flat_reference_name()?;
+    (name.eq_ignore_ascii_case(expected_name) && index.find_member(pou.get_name(), name).is_none())
+        .then_some((kind, data))
+}
+
 /// adds a pointer to the given inner_type to the given index and return's its name
 pub(crate) fn add_pointer_type(index: &mut Index, inner_type_name: String) -> String {
     let new_type_name = internal_type_name("POINTER_TO_", inner_type_name.as_str());
@@ -1915,7 +2101,7 @@ pub(crate) fn add_pointer_type(index: &mut Index, inner_type_name: String) -> St
             initial_value: None,
             nature: TypeNature::Any,
             information: crate::typesystem::DataTypeInformation::Pointer {
-                auto_deref: false,
+                kind: PointerKind::Raw,
                 inner_type_name,
                 name: new_type_name.clone(),
             },
@@ -1964,7 +2150,7 @@ fn to_variable_annotation(
             // passed by-ref
             (v_type.get_name().to_string(), AUTO_DEREF)
         }
-        (DataTypeInformation::Pointer { inner_type_name, auto_deref: true, .. }, _) => {
+        (DataTypeInformation::Pointer { inner_type_name, kind: PointerKind::Reference, .. }, _) => {
             // real auto-deref pointer
             (inner_type_name.clone(), AUTO_DEREF)
         }
@@ -2115,28 +2301,26 @@ fn accept_cast_string_literal(
     literals: &mut StringLiterals,
     cast_type: &typesystem::DataType,
     literal: &AstNode,
+    is_sized: bool,
+    index: &Index,
 ) {
-    // check if we need to register an additional string-literal
-    match (cast_type.get_type_information(), literal.get_stmt()) {
-        (
-            DataTypeInformation::String { encoding: StringEncoding::Utf8, .. },
-            AstStatement::Literal(AstLiteral::String(StringValue { value, is_wide: is_wide @ true })),
-        )
-        | (
-            DataTypeInformation::String { encoding: StringEncoding::Utf16, .. },
-            AstStatement::Literal(AstLiteral::String(StringValue { value, is_wide: is_wide @ false })),
-        ) => {
-            // re-register the string-literal in the opposite encoding
-            if *is_wide {
-                literals.utf08.insert(value.to_string());
-            } else {
-                literals.utf16.insert(value.to_string());
-            }
-        }
-        _ => {
-            //ignore
-        }
-    }
+    let (
+        DataTypeInformation::String { encoding, size },
+        AstStatement::Literal(AstLiteral::String(StringValue { value, .. })),
+    ) = (cast_type.get_type_information(), literal.get_stmt())
+    else {
+        return;
+    };
+    // the literal is generated in the encoding of the cast type (e.g. `STRING#"abc"`), a cast with an
+    // explicit length truncates it (e.g. `STRING#[2]'abc'`), the last character is reserved for the terminator
+    let value = match size.as_int_value(index) {
+        Ok(size) if is_sized => encoding.truncate(value, (size - 1).max(0) as usize),
+        _ => value.as_str(),
+    };
+    match encoding {
+        StringEncoding::Utf8 => literals.utf08.insert(value.to_string()),
+        StringEncoding::Utf16 => literals.utf16.insert(value.to_string()),
+    };
 }
 
 #[cfg(test)]
@@ -306,6 +306,15 @@ pub fn evaluate(
     evaluate_with_target_hint(initial, scope, index, None)
 }
 
+/// evaluates the given condition (e.g. of an `IF` or `ELSIF`) to a constant boolean value
+/// returns `None` if the condition depends on non-constant values
+pub fn evaluate_constant_condition(condition: &AstNode, scope: Option<&str>, index: &Index) -> Option<bool> {
+    match evaluate(condition, scope, index) {
+        Ok(Some(AstNode { stmt: AstStatement::Literal(AstLiteral::Bool(value)), .. })) => Some(value),
+        _ => None,
+    }
+}
+
 /// evaluates the given Syntax-Tree `initial` to a `LiteralValue` if possible
 /// ## Arguments
 /// - `initial` the constant expression to resolve
@@ -384,6 +393,14 @@ fn evaluate_with_target_hint(
                 .get_flat_reference_name()
                 .and_then(|type_name| index.find_effective_type_info(type_name));
             match dti {
+                // the literal of a sized string cast (`STRING#[3]'abc'`) is truncated when it is generated
+                None if matches!(
+                    type_name.get_stmt(),
+                    AstStatement::ReferenceExpr(ReferenceExpr { access: ReferenceAccess::Index(_), .. })
+                ) =>
+                {
+                    return Ok(Some(initial.clone()))
+                }
                 Some(DataTypeInformation::Enum { name: enum_name, .. }) => {
                     if let AstStatement::Identifier(ref_name) = target.get_stmt() {
                         return index
@@ -584,6 +601,14 @@ fn get_cast_statement_literal(
     index: &Index,
 ) -> Result<AstNode, UnresolvableKind> {
     let dti = index.find_effective_type_info(type_name);
+    // boolean values (e.g. `BOOL#TRUE`) need no conversion, `BOOL#1` is cast like any other integer
+    if dti.map_or(false, DataTypeInformation::is_bool) {
+        if let Some(AstNode { stmt: stmt @ AstStatement::Literal(AstLiteral::Bool(_)), .. }) =
+            evaluate(cast_statement, scope, index)?
+        {
+            return Ok(AstNode::new(stmt, cast_statement.get_id(), cast_statement.get_location()));
+        }
+    }
     match dti {
         Some(&DataTypeInformation::Integer { .. }) => {
             let evaluated_initial = evaluate_with_target_hint(cast_statement, scope, index, Some(type_name))?
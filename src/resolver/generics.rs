@@ -1,10 +1,12 @@
 use std::collections::HashMap;
 
-use plc_ast::ast::{flatten_expression_list, AstNode, AstStatement, GenericBinding, LinkageType, TypeNature};
+use plc_ast::ast::{
+    flatten_expression_list, AstNode, AstStatement, GenericBinding, LinkageType, PointerKind, TypeNature,
+};
+use plc_diagnostics::diagnostics::Diagnostic;
 use plc_source::source_location::SourceLocation;
 
 use crate::{
-    builtins,
     codegen::generators::expression_generator::get_implicit_call_parameter,
     index::{Index, PouIndexEntry},
     resolver::AnnotationMap,
@@ -76,7 +78,9 @@ impl<'i> TypeAnnotator<'i> {
                     self.annotation_map.get(operator)
                 {
                     // Find the generic resolver
-                    let generic_name_resolver = builtins::get_builtin(qualified_name)
+                    let generic_name_resolver = self
+                        .index
+                        .get_builtin_function(qualified_name)
                         .map(|it| it.get_generic_name_resolver())
                         .unwrap_or_else(|| generic_name_resolver);
                     // get information about the generic function name and annotation
@@ -202,14 +206,17 @@ impl<'i> TypeAnnotator<'i> {
                     .unwrap_or_else(|| member_name)
                     .to_string()
             }
-            Some(DataTypeInformation::Pointer { name, inner_type_name, auto_deref: true }) => {
+            Some(DataTypeInformation::Pointer { name, inner_type_name, kind: PointerKind::Reference }) => {
                 // This is an auto deref pointer (VAR_IN_OUT or VAR_INPUT {ref}) that points to a
                 // generic. We first resolve the generic type, then create a new pointer type of
                 // the combination
                 let inner_type_name = self.find_or_create_datatype(inner_type_name, generics);
                 let name = format!("{name}__{inner_type_name}"); // TODO: Naming convention (see plc_util/src/convention.rs)
-                let new_type_info =
-                    DataTypeInformation::Pointer { name: name.clone(), inner_type_name, auto_deref: true };
+                let new_type_info = DataTypeInformation::Pointer {
+                    name: name.clone(),
+                    inner_type_name,
+                    kind: PointerKind::Reference,
+                };
 
                 // Registers a new pointer type to the index
                 self.annotation_map.new_index.register_type(DataType {
@@ -373,45 +380,30 @@ impl<'i> TypeAnnotator<'i> {
                             .find_effective_type_info(current)
                             // if type is not found, look for it in new index, because the type could have been created recently
                             .or_else(|| self.annotation_map.new_index.find_effective_type_info(current))
-                            .map(|it| {
-                                match it {
-                                    // generic strings are a special case and need to be handled differently
-                                    DataTypeInformation::String {
-                                        encoding: StringEncoding::Utf8, ..
-                                    } => self.index.find_effective_type_info(STRING_TYPE).unwrap_or(it),
-                                    DataTypeInformation::String {
-                                        encoding: StringEncoding::Utf16, ..
-                                    } => self.index.find_effective_type_info(WSTRING_TYPE).unwrap_or(it),
-                                    _ => self.index.find_intrinsic_type(it),
-                                }
-                            });
+                            .and_then(|it| get_generic_candidate_type(self.index, it, nature));
 
                         // Find bigger
                         if let Some(current) = current_type {
-                            // check if the current type derives from the generic nature
-                            if self
-                                .index
-                                .find_effective_type_by_name(current.get_name())
-                                .map(|t| {
-                                    t.has_nature(*nature, self.index)
-										// INT parameter for REAL is allowed
-                                            | (nature.is_real() & t.is_numerical())
-                                })
-                                .unwrap_or_default()
-                            {
-                                // if we got the right nature we can search for the bigger type
-                                if let Some(previous) = previous_type {
-                                    return Some(typesystem::get_bigger_type(current, previous, self.index));
-                                } else {
-                                    // if the previous type was None just return the current
-                                    // type should be ok because of the previouse nature check
-                                    return current_type;
-                                }
+                            // if we got the right nature we can search for the bigger type
+                            if let Some(previous) = previous_type {
+                                return Some(typesystem::get_bigger_type(current, previous, self.index));
+                            } else {
+                                // if the previous type was None just return the current
+                                // type should be ok because of the previouse nature check
+                                return current_type;
                             }
                         }
                         // if we didn't get the right nature return the last one
                         previous_type
                     })
+                    // there is no smallest user-defined type, resolve the call with the first argument's
+                    // type so the validation can report its nature
+                    .or_else(|| {
+                        candidates
+                            .first()
+                            .filter(|_| *nature == TypeNature::Derived)
+                            .and_then(|it| self.index.find_effective_type_info(it))
+                    })
                     .map(DataTypeInformation::get_name);
                 if let Some(winner) = winner {
                     generic_map.insert(
@@ -425,6 +417,92 @@ impl<'i> TypeAnnotator<'i> {
     }
 }
 
+/// returns the type an argument of the given type binds a generic of the given nature to, strings bind
+/// to `STRING` or `WSTRING`. Returns `None` if the argument does not derive from the nature
+fn get_generic_candidate_type<'idx>(
+    index: &'idx Index,
+    argument_type: &'idx DataTypeInformation,
+    nature: &TypeNature,
+) -> Option<&'idx DataTypeInformation> {
+    let candidate = match argument_type {
+        // generic strings are a special case and need to be handled differently
+        DataTypeInformation::String { encoding: StringEncoding::Utf8, .. } => {
+            index.find_effective_type_info(STRING_TYPE).unwrap_or(argument_type)
+        }
+        DataTypeInformation::String { encoding: StringEncoding::Utf16, .. } => {
+            index.find_effective_type_info(WSTRING_TYPE).unwrap_or(argument_type)
+        }
+        _ => index.find_intrinsic_type(argument_type),
+    };
+    // check if the candidate derives from the generic nature
+    index
+        .find_effective_type_by_name(candidate.get_name())
+        .map(|t| {
+            t.has_nature(*nature, index)
+                // INT parameter for REAL is allowed
+                | (nature.is_real() & t.is_numerical())
+        })
+        .unwrap_or_default()
+        .then_some(candidate)
+}
+
+/// Validates that the arguments bound to the same generic of the function `pou_name` can be converted
+/// into a common type, e.g. `MAX(1, 2.0)` binds `T` to `REAL` while `MAX('a', 1)` has no common type.
+/// Arguments which do not derive from the generic's nature are left to the nature validation
+pub fn validate_generic_arguments(
+    index: &Index,
+    pou_name: &str,
+    argument_types: &[&str],
+    location: &SourceLocation,
+) -> Result<(), Diagnostic> {
+    let parameters = index.get_declared_parameters(pou_name);
+    let variadic = index.get_variadic_member(pou_name);
+
+    let mut bound_types: HashMap<&str, &DataTypeInformation> = HashMap::new();
+    for (i, argument_type) in argument_types.iter().enumerate() {
+        let Some(parameter) = parameters.get(i).copied().or(variadic) else { break };
+        // the parameter may also be an array or a pointer of the generic (e.g. variadics)
+        let parameter_type = match index.get_type_information_or_void(parameter.get_type_name()) {
+            DataTypeInformation::Array { inner_type_name, .. }
+            | DataTypeInformation::Pointer { inner_type_name, .. } => {
+                index.get_type_information_or_void(inner_type_name)
+            }
+            it => it,
+        };
+        let DataTypeInformation::Generic { generic_symbol, nature, .. } = parameter_type else {
+            continue;
+        };
+        let Some(current) = index
+            .find_effective_type_info(argument_type)
+            .and_then(|it| get_generic_candidate_type(index, it, nature))
+        else {
+            continue;
+        };
+
+        let bound_type = match bound_types.get(generic_symbol.as_str()) {
+            Some(previous) => {
+                let is_convertible = typesystem::is_same_type_class(previous, current, index)
+                    || typesystem::is_same_type_class(current, previous, index)
+                    || (previous.is_numerical() && current.is_numerical())
+                    || (previous.is_string() && current.is_character())
+                    || (previous.is_character() && current.is_string());
+                if !is_convertible {
+                    return Err(Diagnostic::no_matching_overload(
+                        pou_name,
+                        previous.get_name(),
+                        current.get_name(),
+                        location.clone(),
+                    ));
+                }
+                typesystem::get_bigger_type(*previous, current, index)
+            }
+            None => current,
+        };
+        bound_types.insert(generic_symbol.as_str(), bound_type);
+    }
+    Ok(())
+}
+
 type GenericNameResolver = fn(&str, &[GenericBinding], &HashMap<String, GenericType>) -> String;
 
 /// Builds the correct generic name from the given information
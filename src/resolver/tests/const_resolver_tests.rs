@@ -1216,6 +1216,42 @@ fn contants_in_case_statements_resolved() {
     // AND the second case should be 62..70
 }
 
+#[test]
+fn boolean_and_enum_expressions_are_evaluated() {
+    // GIVEN some boolean constants using enum elements and boolean operators
+    let (_, index) = index(
+        "
+        TYPE Color : (Red, Green := 5, Blue); END_TYPE
+
+        VAR_GLOBAL
+            flag : BOOL;
+        END_VAR
+
+        VAR_GLOBAL CONSTANT
+            a : BOOL := BOOL#TRUE;
+            b : BOOL := NOT a OR (Color#Blue > Green);
+            c : BOOL := (a XOR b) AND Color#Red = 0;
+            d : DINT := Blue;
+            e : BOOL := a AND flag;
+        END_VAR
+        ",
+    );
+
+    // WHEN compile-time evaluation is applied
+    let (index, unresolvable) = evaluate_constants(index);
+
+    // THEN the constant expressions are folded
+    debug_assert_eq!(&create_bool_literal(true), find_constant_value(&index, "a").unwrap());
+    debug_assert_eq!(&create_bool_literal(true), find_constant_value(&index, "b").unwrap());
+    debug_assert_eq!(&create_bool_literal(false), find_constant_value(&index, "c").unwrap());
+    debug_assert_eq!(&create_int_literal(6), find_constant_value(&index, "d").unwrap());
+    // AND the non-constant operand is reported
+    debug_assert_eq!(
+        vec![UnresolvableConstant::new(global!(index, "e"), "'flag' is no const reference")],
+        unresolvable
+    );
+}
+
 #[test]
 fn default_values_are_transitive_for_range_types() {
     // GIVEN a range type that inherits the default value from its referenced type
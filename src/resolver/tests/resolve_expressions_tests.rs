@@ -4,8 +4,8 @@ use insta::{assert_debug_snapshot, assert_snapshot};
 use plc_ast::{
     ast::{
         flatten_expression_list, Assignment, AstNode, AstStatement, BinaryExpression, CallStatement,
-        DataType, DirectAccess, MultipliedStatement, Pou, RangeStatement, ReferenceAccess, ReferenceExpr,
-        UnaryExpression, UserTypeDeclaration,
+        DataType, DirectAccess, MultipliedStatement, PointerKind, Pou, RangeStatement, ReferenceAccess,
+        ReferenceExpr, UnaryExpression, UserTypeDeclaration,
     },
     control_statements::{AstControlStatement, CaseStatement},
     literals::{Array, AstLiteral},
@@ -59,6 +59,34 @@ fn binary_expressions_resolves_types() {
     assert_eq!(expected_types, types);
 }
 
+#[test]
+fn date_and_time_arithmetic_resolves_types() {
+    let id_provider = IdProvider::default();
+    let (unit, index) = index_with_ids(
+        "PROGRAM PRG
+            VAR d : DATE; dt : DT; tod : LTOD; t : TIME; END_VAR
+            dt - dt;
+            d - d;
+            tod - tod;
+            dt + t;
+            t + d;
+            tod - t;
+            d - t;
+            d > d;
+        END_PROGRAM",
+        id_provider.clone(),
+    );
+    let (annotations, ..) = TypeAnnotator::visit_unit(&index, &unit, id_provider);
+    let statements = &unit.implementations[0].statements;
+
+    let expected_types = vec!["TIME", "TIME", "TIME", "DATE_AND_TIME", "DATE", "TIME_OF_DAY", "DATE", "BOOL"];
+
+    let types: Vec<&str> =
+        statements.iter().map(|s| annotations.get_type_or_void(s, &index).get_name()).collect();
+
+    assert_eq!(expected_types, types);
+}
+
 #[test]
 fn cast_expressions_resolves_types() {
     let id_provider = IdProvider::default();
@@ -762,6 +790,37 @@ fn global_resolves_types() {
     assert_eq!(format!("{expected_types:?}"), format!("{type_names:?}"));
 }
 
+#[test]
+fn globals_of_a_configuration_are_visible_in_programs() {
+    let id_provider = IdProvider::default();
+    let (unit, mut index) = index_with_ids(
+        "
+        CONFIGURATION config
+            VAR_GLOBAL
+                speed : INT;
+            END_VAR
+        END_CONFIGURATION
+
+        PROGRAM PRG
+            speed;
+        END_PROGRAM",
+        id_provider.clone(),
+    );
+    let annotations = annotate_with_ids(&unit, &mut index, id_provider);
+    let statements = &unit.implementations[0].statements;
+
+    assert_eq!(
+        annotations.get(&statements[0]),
+        Some(&StatementAnnotation::Variable {
+            resulting_type: "INT".to_string(),
+            qualified_name: "speed".to_string(),
+            constant: false,
+            argument_type: ArgumentType::ByVal(VariableType::Global),
+            is_auto_deref: false
+        })
+    );
+}
+
 #[test]
 fn global_initializers_resolves_types() {
     let id_provider = IdProvider::default();
@@ -3385,7 +3444,7 @@ fn address_of_is_annotated_correctly() {
     if let Some(&StatementAnnotation::Value { resulting_type }) = annotations.get(s).as_ref() {
         assert_eq!(
             Some(&DataTypeInformation::Pointer {
-                auto_deref: false,
+                kind: PointerKind::Raw,
                 inner_type_name: "INT".to_string(),
                 name: "__POINTER_TO_INT".to_string(),
             }),
@@ -3665,6 +3724,33 @@ fn resolve_recursive_program_call() {
     // insta::assert_snapshot!(annotated_types);
 }
 
+#[test]
+fn builtin_timer_instance_members_are_resolved() {
+    let id_provider = IdProvider::default();
+    let (unit, index) = index_with_ids(
+        "PROGRAM PRG
+            VAR
+                timer : TON;
+            END_VAR
+            timer(IN := TRUE, PT := T#1s);
+            timer.Q;
+            timer.ET;
+        END_PROGRAM",
+        id_provider.clone(),
+    );
+    let (annotations, ..) = TypeAnnotator::visit_unit(&index, &unit, id_provider);
+    let statements = &unit.implementations[0].statements;
+
+    let types: Vec<&str> =
+        statements[1..].iter().map(|s| annotations.get_type_or_void(s, &index).get_name()).collect();
+
+    assert_eq!(vec!["BOOL", "TIME"], types);
+    assert!(matches!(
+        annotations.get(&statements[1]),
+        Some(StatementAnnotation::Variable { qualified_name, .. }) if qualified_name == "TON.Q"
+    ));
+}
+
 #[test]
 fn function_block_initialization_test() {
     let id_provider = IdProvider::default();
@@ -5313,3 +5399,89 @@ fn annotate_method_in_super() {
         );
     }
 }
+
+#[test]
+fn this_is_resolved_to_a_pointer_to_the_enclosing_function_block() {
+    //GIVEN a function block referencing THIS in its body, a method and an action
+    let id_provider = IdProvider::default();
+    let (unit, mut index) = index_with_ids(
+        "
+        FUNCTION_BLOCK fb
+            VAR x : INT; END_VAR
+            METHOD m
+                THIS^.x;
+            END_METHOD
+            THIS^.x;
+        END_FUNCTION_BLOCK
+
+        ACTIONS fb
+            ACTION a
+                THIS^.x;
+            END_ACTION
+        END_ACTIONS
+
+        FUNCTION foo : INT
+            THIS;
+        END_FUNCTION
+        ",
+        id_provider.clone(),
+    );
+
+    let annotations = annotate_with_ids(&unit, &mut index, id_provider);
+    let get_statement = |name: &str| {
+        &unit.implementations.iter().find(|it| it.name == name).expect("implementation exists").statements[0]
+    };
+
+    //THEN THIS^.x resolves to fb.x in the fb's body, its method and its action
+    for name in ["fb", "fb.m", "fb.a"] {
+        let AstStatement::ReferenceExpr(ReferenceExpr { base: Some(deref), .. }) =
+            get_statement(name).get_stmt()
+        else {
+            unreachable!()
+        };
+        let AstStatement::ReferenceExpr(ReferenceExpr { base: Some(this), .. }) = deref.get_stmt() else {
+            unreachable!()
+        };
+        assert_eq!(
+            Some(&DataTypeInformation::Pointer {
+                kind: PointerKind::Raw,
+                inner_type_name: "fb".to_string(),
+                name: "__POINTER_TO_fb".to_string(),
+            }),
+            annotations.get_type(this, &index).map(|it| it.get_type_information()),
+        );
+        assert_eq!(Some("fb"), annotations.get_type(deref, &index).map(|it| it.get_name()));
+        assert!(matches!(
+            annotations.get(get_statement(name)),
+            Some(StatementAnnotation::Variable { qualified_name, .. }) if qualified_name == "fb.x"
+        ));
+    }
+
+    //AND THIS is not resolved inside a function
+    assert_eq!(None, annotations.get(get_statement("foo")));
+}
+
+#[test]
+fn string_access_is_annotated_as_a_character() {
+    let id_provider = IdProvider::default();
+
+    let (unit, mut index) = index_with_ids(
+        r#"
+        PROGRAM prg
+        VAR
+            s : STRING;
+            ws : WSTRING;
+        END_VAR
+            s[1];
+            ws[1];
+        END_PROGRAM
+        "#,
+        id_provider.clone(),
+    );
+
+    let annotations = annotate_with_ids(&unit, &mut index, id_provider);
+    let statements = &unit.implementations[0].statements;
+
+    assert_type_and_hint!(&annotations, &index, &statements[0], "CHAR", None);
+    assert_type_and_hint!(&annotations, &index, &statements[1], "WCHAR", None);
+}
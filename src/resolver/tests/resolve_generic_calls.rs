@@ -1,5 +1,5 @@
 use plc_ast::{
-    ast::{flatten_expression_list, Assignment, AstNode, AstStatement, CallStatement},
+    ast::{flatten_expression_list, Assignment, AstNode, AstStatement, CallStatement, PointerKind},
     provider::IdProvider,
 };
 
@@ -8,7 +8,8 @@ use crate::{
     resolver::{AnnotationMap, StatementAnnotation, TypeAnnotator},
     test_utils::tests::{annotate_with_ids, index_with_ids},
     typesystem::{
-        DataTypeInformation, DINT_TYPE, INT_TYPE, LREAL_TYPE, LWORD_TYPE, REAL_TYPE, SINT_TYPE, STRING_TYPE,
+        DataTypeInformation, DINT_TYPE, INT_TYPE, LINT_TYPE, LREAL_TYPE, LWORD_TYPE, REAL_TYPE, SINT_TYPE,
+        STRING_TYPE,
     },
 };
 
@@ -685,6 +686,28 @@ fn mux_return_type_follows_params() {
     //Also test that the right side of the operator is dint
 }
 
+#[test]
+fn sel_and_mux_return_the_bigger_type_of_mixed_params() {
+    let id_provider = IdProvider::default();
+    let (unit, index) = index_with_ids(
+        "
+    FUNCTION main
+    VAR
+        a : SINT;
+        b : INT;
+        c : LINT;
+    END_VAR
+        SEL(TRUE, a, c);
+        MUX(2, a, b, c);
+    END_FUNCTION",
+        id_provider.clone(),
+    );
+
+    let (annotations, ..) = TypeAnnotator::visit_unit(&index, &unit, id_provider);
+    assert_type_and_hint!(&annotations, &index, &unit.implementations[0].statements[0], LINT_TYPE, None);
+    assert_type_and_hint!(&annotations, &index, &unit.implementations[0].statements[1], LINT_TYPE, None);
+}
+
 #[test]
 fn auto_pointer_of_generic_resolved() {
     let id_provider = IdProvider::default();
@@ -715,7 +738,7 @@ fn auto_pointer_of_generic_resolved() {
 
     let member = index.find_member("LEFT_EXT__DINT", "IN").unwrap();
     let dt = index.find_effective_type_info(&member.data_type_name).unwrap();
-    if let DataTypeInformation::Pointer { inner_type_name, auto_deref: true, .. } = dt {
+    if let DataTypeInformation::Pointer { inner_type_name, kind: PointerKind::Reference, .. } = dt {
         assert_eq!(inner_type_name, "DINT")
     } else {
         panic!("Expecting a pointer to dint, found {dt:?}")
@@ -766,7 +789,7 @@ fn string_ref_as_generic_resolved() {
 
     let member = index.find_member("LEFT_EXT__STRING", "IN").unwrap();
     let dt = index.find_effective_type_info(&member.data_type_name).unwrap();
-    if let DataTypeInformation::Pointer { inner_type_name, auto_deref: true, .. } = dt {
+    if let DataTypeInformation::Pointer { inner_type_name, kind: PointerKind::Reference, .. } = dt {
         assert_eq!(inner_type_name, STRING_TYPE)
     } else {
         panic!("Expecting auto deref pointer to string, found {dt:?}")
@@ -1141,3 +1164,31 @@ fn generic_external_function_having_same_name_as_local_variable() {
         &StatementAnnotation::Value { resulting_type: "INT".to_string() }
     );
 }
+
+#[test]
+fn generic_overload_is_selected_by_the_argument_types() {
+    let id_provider = IdProvider::default();
+    let (unit, index) = index_with_ids(
+        "
+        {external}
+        FUNCTION MAX<T : ANY_ELEMENTARY> : T
+            VAR_INPUT
+                args : {sized} T...;
+            END_VAR
+        END_FUNCTION
+
+        FUNCTION main : REAL
+            MAX(1, 2.0);
+        END_FUNCTION
+        ",
+        id_provider.clone(),
+    );
+
+    let (annotations, ..) = TypeAnnotator::visit_unit(&index, &unit, id_provider);
+    let call = &unit.implementations[1].statements[0];
+    assert_type_and_hint!(&annotations, &index, call, REAL_TYPE, None);
+    let AstNode { stmt: AstStatement::CallStatement(CallStatement { operator, .. }), .. } = call else {
+        unreachable!()
+    };
+    assert_eq!(Some("MAX__REAL"), annotations.get_call_name(operator));
+}
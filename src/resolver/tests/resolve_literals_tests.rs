@@ -1,6 +1,6 @@
 use plc_ast::literals::AstLiteral;
 use plc_ast::{
-    ast::{AstStatement, ReferenceAccess, ReferenceExpr, TypeNature},
+    ast::{Assignment, AstNode, AstStatement, ReferenceAccess, ReferenceExpr, TypeNature},
     provider::IdProvider,
 };
 use plc_source::source_location::SourceLocation;
@@ -562,3 +562,44 @@ fn struct_field_members_assignments_are_annotated_correctly_in_array_of_structs(
     let AstStatement::ParenExpression(z) = &elements[1].stmt else { panic!() };
     assert_eq!(&annotations.get_type_hint(&z, &index).unwrap().name, "STRUCT2");
 }
+
+#[test]
+fn pou_name_and_line_builtins_are_folded_into_literals() {
+    //GIVEN a program logging its name and the current line
+    let id_provider = IdProvider::default();
+    let (unit, mut index) = index_with_ids(
+        "PROGRAM prg
+        VAR
+            name : STRING;
+            line : DINT;
+        END_VAR
+            name := __POU_NAME();
+            line := __LINE();
+        END_PROGRAM",
+        id_provider.clone(),
+    );
+
+    //WHEN it is annotated
+    let annotations = annotate_with_ids(&unit, &mut index, id_provider);
+
+    //THEN the calls are replaced by the POU's name and the line of the call
+    let replacement = |statement: &AstNode| {
+        let AstStatement::Assignment(Assignment { right, .. }) = statement.get_stmt() else {
+            panic!("expected an assignment, found {statement:?}")
+        };
+        let Some(StatementAnnotation::ReplacementAst { statement }) = annotations.get(right) else {
+            panic!("expected {right:?} to be replaced")
+        };
+        let type_name = annotations.get_type_or_void(right, &index).get_name().to_string();
+        (statement.get_stmt().clone(), type_name)
+    };
+    let statements = &unit.implementations[0].statements;
+    assert_eq!(
+        replacement(&statements[0]),
+        (AstStatement::Literal(AstLiteral::new_string("prg".into(), false)), "__STRING_3".to_string())
+    );
+    assert_eq!(
+        replacement(&statements[1]),
+        (AstStatement::Literal(AstLiteral::new_integer(7)), DINT_TYPE.to_string())
+    );
+}
@@ -3,6 +3,7 @@ pub mod tests {
 
     use std::{path::PathBuf, str::FromStr};
 
+    use inkwell::targets::RelocMode;
     use plc_ast::{
         ast::{pre_process, CompilationUnit, LinkageType},
         provider::IdProvider,
@@ -14,12 +15,14 @@ pub mod tests {
 
     use crate::{
         builtins,
-        codegen::{CodegenContext, GeneratedModule},
+        codegen::{CodegenContext, GeneratedModule, TargetTuning},
+        datalayout::DataLayout,
         index::{self, Index},
         lexer, parser,
         resolver::{const_evaluator::evaluate_constants, AnnotationMapImpl, AstAnnotations, TypeAnnotator},
         typesystem::get_builtin_types,
-        DebugLevel, Validator,
+        validation::ValidationOptions,
+        DebugLevel, OptimizationLevel, Target, Validator,
     };
 
     pub fn parse(src: &str) -> (CompilationUnit, Vec<Diagnostic>) {
@@ -61,10 +64,6 @@ pub mod tests {
         let source_str = &source.source;
         let source_path = source.get_location_str();
         let mut index = Index::default();
-        //Import builtins
-        let builtins = builtins::parse_built_ins(id_provider.clone());
-
-        index.import(index::visitor::visit(&builtins));
         // import built-in types like INT, BOOL, etc.
         for data_type in get_builtin_types() {
             index.register_type(data_type);
@@ -77,8 +76,11 @@ pub mod tests {
             source_path,
         );
 
-        pre_process(&mut unit, id_provider);
+        pre_process(&mut unit, id_provider.clone());
         index.import(index::visitor::visit(&unit));
+        //Import builtins which are not shadowed by the unit's POUs
+        let builtins = builtins::parse_unshadowed_built_ins(id_provider, &index);
+        index.import(index::visitor::visit(&builtins));
         (unit, index, diagnostics)
     }
 
@@ -93,6 +95,25 @@ pub mod tests {
         (unit, index)
     }
 
+    /// indexes the given source as the file `file_name` without the built-in types and functions, so the
+    /// indexes of several files can be combined like the ones of a project's units
+    pub fn index_without_builtins(
+        src: &str,
+        id_provider: IdProvider,
+        file_name: &str,
+    ) -> (CompilationUnit, Index) {
+        let source = SourceCode::new(src, file_name);
+        let (mut unit, ..) = parser::parse(
+            lexer::lex_with_ids(src, id_provider.clone(), SourceLocationFactory::for_source(&source)),
+            LinkageType::Internal,
+            file_name,
+        );
+        pre_process(&mut unit, id_provider);
+        let mut index = Index::default();
+        index.import(index::visitor::visit(&unit));
+        (unit, index)
+    }
+
     pub fn annotate_with_ids(
         parse_result: &CompilationUnit,
         index: &mut Index,
@@ -103,8 +124,22 @@ pub mod tests {
         annotations
     }
 
+    /// indexes and annotates the given source, e.g. to generate the LLVM types of its index
+    pub fn index_and_annotate(src: &str) -> (Index, AstAnnotations) {
+        let mut id_provider = IdProvider::default();
+        let (unit, index, _) = do_index(src, id_provider.clone());
+        let (mut index, ..) = evaluate_constants(index);
+        let (mut annotations, ..) = TypeAnnotator::visit_unit(&index, &unit, id_provider.clone());
+        index.import(std::mem::take(&mut annotations.new_index));
+        (index, AstAnnotations::new(annotations, id_provider.next_id()))
+    }
+
     pub fn parse_and_validate_buffered(src: &str) -> String {
-        let diagnostics = parse_and_validate(src);
+        parse_and_validate_buffered_with_options(src, ValidationOptions::default())
+    }
+
+    pub fn parse_and_validate_buffered_with_options(src: &str, options: ValidationOptions) -> String {
+        let diagnostics = parse_and_validate_with_options(src, options);
         let mut reporter = Diagnostician::buffered();
 
         reporter.register_file("<internal>".to_string(), src.to_string());
@@ -116,16 +151,31 @@ pub mod tests {
     }
 
     pub fn parse_and_validate(src: &str) -> Vec<Diagnostic> {
+        parse_and_validate_with_options(src, ValidationOptions::default())
+    }
+
+    pub fn parse_and_validate_with_options(src: &str, options: ValidationOptions) -> Vec<Diagnostic> {
+        validate_with_layout(src, options, DataLayout::default())
+    }
+
+    /// validates the given source with the data layout of the given target, e.g. its pointer size
+    pub fn parse_and_validate_for_target(src: &str, target: &Target) -> Vec<Diagnostic> {
+        validate_with_layout(src, ValidationOptions::default(), DataLayout::for_target(target))
+    }
+
+    fn validate_with_layout(src: &str, options: ValidationOptions, layout: DataLayout) -> Vec<Diagnostic> {
         let id_provider = IdProvider::default();
-        let (unit, index, mut diagnostics) = do_index(src, id_provider.clone());
+        let (unit, mut index, mut diagnostics) = do_index(src, id_provider.clone());
+        index.set_type_layout(layout);
 
         let (mut index, ..) = evaluate_constants(index);
         let (mut annotations, ..) = TypeAnnotator::visit_unit(&index, &unit, id_provider);
         index.import(std::mem::take(&mut annotations.new_index));
 
-        let mut validator = Validator::new();
+        let mut validator = Validator::with_options(options);
         validator.perform_global_validation(&index);
         validator.visit_unit(&annotations, &index, &unit);
+        validator.perform_call_graph_validation();
         diagnostics.extend(validator.diagnostics());
         diagnostics
     }
@@ -158,6 +208,7 @@ pub mod tests {
             "main",
             crate::OptimizationLevel::None,
             debug_level,
+            false,
         );
         let annotations = AstAnnotations::new(annotations, id_provider.next_id());
         let llvm_index = code_generator
@@ -225,6 +276,7 @@ pub mod tests {
                     &unit.file_name,
                     crate::OptimizationLevel::None,
                     debug_level,
+                    false,
                 );
                 let llvm_index = code_generator.generate_llvm_index(
                     context,
@@ -251,6 +303,49 @@ pub mod tests {
             .collect()
     }
 
+    /// Generates the source into a single module and runs the optimization passes of the given level on it
+    pub fn codegen_optimized(
+        src: &str,
+        optimization_level: OptimizationLevel,
+        inline_threshold: Option<u32>,
+    ) -> String {
+        let context = CodegenContext::create();
+        let module = codegen_into_modules(&context, src, DebugLevel::None)
+            .unwrap()
+            .remove(0)
+            .with_inline_threshold(inline_threshold);
+        let machine = GeneratedModule::create_target_machine(
+            &Target::System,
+            RelocMode::Default,
+            optimization_level,
+            &TargetTuning::default(),
+        )
+        .unwrap();
+        module.optimize(&machine, optimization_level).unwrap();
+        module.persist_to_string()
+    }
+
+    /// Compiles the given source into an object file for the given target, returns the object's
+    /// content and the module's IR after it was persisted
+    pub fn codegen_to_object(
+        src: &str,
+        target: &Target,
+        optimization_level: OptimizationLevel,
+    ) -> (Vec<u8>, String) {
+        let context = CodegenContext::create();
+        let module = codegen_into_modules(&context, src, DebugLevel::None).unwrap().remove(0);
+        let dir = tempfile::tempdir().unwrap();
+        let object = module
+            .persist_as_static_obj(
+                dir.path().join("out.o"),
+                target,
+                optimization_level,
+                &TargetTuning::default(),
+            )
+            .unwrap();
+        (std::fs::read(object).unwrap(), module.persist_to_string())
+    }
+
     pub fn generate_with_empty_program(src: &str) -> String {
         let source = format!("{} {}", "PROGRAM main END_PROGRAM", src);
         codegen(source.as_str())
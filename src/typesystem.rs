@@ -5,8 +5,9 @@ use std::{
     ops::{Range, RangeInclusive},
 };
 
+use itertools::Itertools;
 use plc_ast::{
-    ast::{AstNode, Operator, PouType, TypeNature},
+    ast::{AstNode, AstStatement, Operator, PointerKind, PouType, TypeNature},
     literals::{AstLiteral, StringValue},
 };
 use plc_source::source_location::SourceLocation;
@@ -14,6 +15,7 @@ use plc_source::source_location::SourceLocation;
 use crate::{
     datalayout::{Bytes, MemoryLocation},
     index::{const_expressions::ConstId, Index, VariableIndexEntry},
+    resolver::const_evaluator,
 };
 
 pub const DEFAULT_STRING_LEN: u32 = 80;
@@ -47,7 +49,6 @@ pub const LINT_SIZE: u32 = NativeLintType::BITS;
 pub const REAL_SIZE: u32 = (size_of::<NativeRealType>() * 8) as u32;
 pub const LREAL_SIZE: u32 = (size_of::<NativeLrealType>() * 8) as u32;
 pub const DATE_TIME_SIZE: u32 = 64;
-pub const POINTER_SIZE: u32 = NativePointerType::BITS;
 
 pub const U1_TYPE: &str = "__U1";
 /// used internally for forced casts to u1
@@ -127,11 +128,25 @@ impl DataType {
         &self.information
     }
 
+    /// returns a human readable description of this type (see [`DataTypeInformation::get_display_name`])
+    pub fn get_display_name(&self, index: &Index) -> String {
+        self.information.get_display_name(index)
+    }
+
     pub fn clone_type_information(&self) -> DataTypeInformation {
         self.information.clone()
     }
 
     pub fn has_nature(&self, nature: TypeNature, index: &Index) -> bool {
+        // enums are user-defined types even though their values are integers
+        if nature == TypeNature::Derived
+            && matches!(
+                index.get_effective_type_or_void_by_name(self.get_name()).get_type_information(),
+                DataTypeInformation::Enum { .. }
+            )
+        {
+            return true;
+        }
         let type_nature = index.get_intrinsic_type_by_name(self.get_name()).nature;
         type_nature.derives_from(nature)
     }
@@ -278,6 +293,39 @@ impl StringEncoding {
             StringEncoding::Utf16 => 2,
         }
     }
+
+    /// Returns the name of the type of a single character of a string with this encoding
+    pub fn get_character_type_name(&self) -> &'static str {
+        match self {
+            StringEncoding::Utf8 => CHAR_TYPE,
+            StringEncoding::Utf16 => WCHAR_TYPE,
+        }
+    }
+
+    /// Returns the name of the string type of the default length with this encoding
+    pub fn get_string_type_name(&self) -> &'static str {
+        match self {
+            StringEncoding::Utf8 => STRING_TYPE,
+            StringEncoding::Utf16 => WSTRING_TYPE,
+        }
+    }
+
+    /// Returns the longest prefix of `value` that fits into a string of `length` characters with this
+    /// encoding, a character of a `STRING` is a single byte
+    pub fn truncate<'a>(&self, value: &'a str, length: usize) -> &'a str {
+        let mut used = 0;
+        let end = value
+            .char_indices()
+            .find_map(|(index, character)| {
+                used += match self {
+                    StringEncoding::Utf8 => character.len_utf8(),
+                    StringEncoding::Utf16 => character.len_utf16(),
+                };
+                (used > length).then_some(index)
+            })
+            .unwrap_or(value.len());
+        &value[..end]
+    }
 }
 
 /// Enum for ranges and aggregate type sizes.
@@ -304,7 +352,7 @@ impl TypeSize {
             TypeSize::ConstExpression(id) => {
                 index.get_const_expressions().get_constant_int_statement_value(id).map(|it| it as i64)
             }
-            TypeSize::Undetermined => Ok(POINTER_SIZE as i64),
+            TypeSize::Undetermined => Ok(index.get_type_layout().p64.bits() as i64),
         }
     }
 
@@ -323,6 +371,10 @@ impl TypeSize {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StructSource {
     OriginalDeclaration,
+    /// a `UNION` whose members all start at offset zero
+    Union,
+    /// a `{bitfield}` struct, every member occupies the bit at its position in the struct
+    Bitfield,
     Pou(PouType),
     Internal(InternalType),
 }
@@ -359,7 +411,7 @@ pub enum DataTypeInformation {
     Pointer {
         name: TypeId,
         inner_type_name: TypeId,
-        auto_deref: bool,
+        kind: PointerKind,
     },
     Integer {
         name: TypeId,
@@ -417,6 +469,54 @@ impl DataTypeInformation {
         }
     }
 
+    /// returns a human readable description of this type which, unlike [`DataTypeInformation::get_name`],
+    /// also describes the structure of anonymous or internal types, e.g.:
+    /// - `ARRAY[0..5] OF INT`
+    /// - `REF_TO INT`
+    /// - `STRING[20]`
+    /// - `(red, green, blue)` for enums
+    /// - `INT (0..100)` for sub-ranges
+    pub fn get_display_name(&self, index: &Index) -> String {
+        let inner_display_name = |name: &str| {
+            index
+                .find_effective_type_by_name(name)
+                .map(|it| it.get_display_name(index))
+                .unwrap_or(name.into())
+        };
+
+        match self {
+            DataTypeInformation::Array { inner_type_name, dimensions, .. } => {
+                let dimensions = dimensions
+                    .iter()
+                    .map(|it| match it.get_range_inclusive(index) {
+                        Ok(range) => format!("{}..{}", range.start(), range.end()),
+                        Err(_) => "*".into(),
+                    })
+                    .join(", ");
+                format!("ARRAY[{dimensions}] OF {}", inner_display_name(inner_type_name))
+            }
+            DataTypeInformation::Pointer { inner_type_name, .. } => {
+                format!("REF_TO {}", inner_display_name(inner_type_name))
+            }
+            DataTypeInformation::String { size, .. } => match size.as_int_value(index) {
+                // the size includes the terminating null character
+                Ok(size) => format!("{}[{}]", self.get_name(), size - 1),
+                Err(_) => self.get_name().into(),
+            },
+            DataTypeInformation::Enum { elements, .. } => format!("({})", elements.join(", ")),
+            DataTypeInformation::SubRange { referenced_type, sub_range, .. } => {
+                let bound = |node: &AstNode| match const_evaluator::evaluate(node, None, index) {
+                    Ok(Some(AstNode { stmt: AstStatement::Literal(AstLiteral::Integer(value)), .. })) => {
+                        value.to_string()
+                    }
+                    _ => "?".into(),
+                };
+                format!("{referenced_type} ({}..{})", bound(&sub_range.start), bound(&sub_range.end))
+            }
+            _ => self.get_name().into(),
+        }
+    }
+
     pub fn is_string(&self) -> bool {
         matches!(self, DataTypeInformation::String { .. })
     }
@@ -562,8 +662,18 @@ impl DataTypeInformation {
             DataTypeInformation::String { size, encoding } => size
                 .as_int_value(index)
                 .map(|size| encoding.get_bytes_per_char() * size as u32)
-                .map(Bytes::from_bits)
+                .map(Bytes::new)
                 .unwrap(),
+            DataTypeInformation::Struct { members, source: StructSource::Union, .. } => Bytes::new(
+                members
+                    .iter()
+                    .map(|it| index.get_type_information_or_void(it.get_type_name()).get_size(index).value())
+                    .max()
+                    .unwrap_or_default(),
+            ),
+            DataTypeInformation::Struct { members, source: StructSource::Bitfield, .. } => {
+                Bytes::new((members.len() as u32 + 7) / 8)
+            }
             DataTypeInformation::Struct { members, .. } => members
                 .iter()
                 .map(|it| it.get_type_name())
@@ -582,7 +692,7 @@ impl DataTypeInformation {
                     dimensions.iter().map(|dim| dim.get_length(index).unwrap()).product();
                 Bytes::from_bits(inner_size * element_count)
             }
-            DataTypeInformation::Pointer { .. } => Bytes::from_bits(POINTER_SIZE),
+            DataTypeInformation::Pointer { .. } => index.get_type_layout().p64,
             DataTypeInformation::Alias { referenced_type, .. }
             | DataTypeInformation::SubRange { referenced_type, .. } => {
                 let inner_type = index.get_type_information_or_void(referenced_type);
@@ -618,7 +728,8 @@ impl DataTypeInformation {
                 }
             }
             DataTypeInformation::Struct { .. } => type_layout.aggregate,
-            DataTypeInformation::String { .. } => type_layout.v64, //Strings are arrays
+            //Strings are arrays
+            DataTypeInformation::String { .. } => type_layout.string.unwrap_or(type_layout.v64),
             DataTypeInformation::Pointer { .. } => type_layout.p64,
             DataTypeInformation::Integer { size, semantic_size, .. } => {
                 if let Some(1) = semantic_size {
@@ -670,15 +781,24 @@ impl DataTypeInformation {
     }
 
     pub fn is_compatible_char_and_string(&self, other: &DataTypeInformation) -> bool {
-        match self.get_name() {
-            CHAR_TYPE => matches!(other, DataTypeInformation::String { encoding: StringEncoding::Utf8, .. }),
-            WCHAR_TYPE => {
-                matches!(other, DataTypeInformation::String { encoding: StringEncoding::Utf16, .. })
+        match (self.get_promoted_string_encoding(), other) {
+            (Some(encoding), DataTypeInformation::String { encoding: other_encoding, .. }) => {
+                encoding == *other_encoding
             }
             _ => false,
         }
     }
 
+    /// Returns the encoding of the string a character is promoted to when it is mixed with strings
+    /// (e.g. in `CONCAT` or comparisons), a `CHAR` becomes a `STRING` and a `WCHAR` a `WSTRING` of length 1
+    pub fn get_promoted_string_encoding(&self) -> Option<StringEncoding> {
+        match self.get_name() {
+            CHAR_TYPE => Some(StringEncoding::Utf8),
+            WCHAR_TYPE => Some(StringEncoding::Utf16),
+            _ => None,
+        }
+    }
+
     /// Returns the array length if [`DataTypeInformation`] is of variant [`DataTypeInformation::Array`] and
     /// None otherwise.
     ///
@@ -1217,8 +1337,8 @@ pub fn is_same_type_class(ltype: &DataTypeInformation, rtype: &DataTypeInformati
         // 2. foo := REF(bar)
         // 3. foo := &bar
         DataTypeInformation::Pointer { .. } => match rtype {
-            // Case 1: ADR(bar) returns a LWORD value, thus check if we're working with a LWORD
-            DataTypeInformation::Integer { size, .. } => *size == POINTER_SIZE,
+            // Case 1: ADR(bar) returns an integer as wide as a pointer of the target
+            DataTypeInformation::Integer { size, .. } => *size == index.get_type_layout().p64.bits(),
 
             // Case 2 & 3:
             // REF(bar) and &bar returns a pointer, thus deduce their inner types and check if they're equal
@@ -1259,7 +1379,12 @@ pub fn get_bigger_type<'t, T: DataTypeInformationProvider<'t> + std::convert::Fr
 
     // if left and right have the same type, check which ranks higher
     if is_same_type_class(lt, rt, index) {
-        if get_rank(lt, index) < get_rank(rt, index) {
+        let (left_rank, right_rank) = (get_rank(lt, index), get_rank(rt, index));
+        if left_rank < right_rank {
+            return right_type;
+        }
+        // a character mixed with an integer of the same size is treated as a plain number
+        if left_rank == right_rank && lt.is_character() && !rt.is_character() {
             return right_type;
         }
     } else if let (Ok(ldt), Ok(rdt)) = (ldt, rdt) {
@@ -1282,6 +1407,16 @@ pub fn get_bigger_type<'t, T: DataTypeInformationProvider<'t> + std::convert::Fr
     left_type
 }
 
+/// returns the name of the unsigned integer type as wide as a pointer of the index's layout,
+/// e.g. `LWORD` for 64-bit pointers
+pub fn get_pointer_sized_type_name(index: &Index) -> &'static str {
+    match index.get_type_layout().p64.bits() {
+        16 => WORD_TYPE,
+        32 => DWORD_TYPE,
+        _ => LWORD_TYPE,
+    }
+}
+
 /// returns the signed version of the given data_type if its a signed int-type
 /// returns the original type if it is no signed int-type
 pub fn get_signed_type<'t>(
@@ -1320,6 +1455,30 @@ pub fn get_equals_function_name_for(type_name: &str, operator: &Operator) -> Opt
     suffix.map(|suffix| format!("{type_name}_{suffix}")) // TODO: Naming convention (see plc_util/src/convention.rs)
 }
 
+/// returns the type of an addition or subtraction of date and time values, the difference of two
+/// points in time of the same kind is a `TIME`, shifting a point in time by a `TIME` keeps its type.
+/// Returns None if the operands are no such combination
+pub fn get_date_time_arithmetic_result_type(
+    operator: &Operator,
+    left: &DataTypeInformation,
+    right: &DataTypeInformation,
+) -> Option<&'static str> {
+    let as_point_in_time = |it: &DataTypeInformation| match it.get_name() {
+        DATE_TYPE => Some(DATE_TYPE),
+        DATE_AND_TIME_TYPE => Some(DATE_AND_TIME_TYPE),
+        TIME_OF_DAY_TYPE => Some(TIME_OF_DAY_TYPE),
+        _ => None,
+    };
+    let is_duration = |it: &DataTypeInformation| it.get_name() == TIME_TYPE;
+
+    match (operator, as_point_in_time(left), as_point_in_time(right)) {
+        (Operator::Minus, Some(left), Some(right)) if left == right => Some(TIME_TYPE),
+        (Operator::Plus | Operator::Minus, Some(left), None) if is_duration(right) => Some(left),
+        (Operator::Plus, None, Some(right)) if is_duration(left) => Some(right),
+        _ => None,
+    }
+}
+
 pub fn get_literal_actual_signed_type_name(lit: &AstLiteral, signed: bool) -> Option<&str> {
     // Returns a range with the min and max value of the given type
     macro_rules! is_covered_by {
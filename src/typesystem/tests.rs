@@ -45,6 +45,15 @@ pub fn signed_types_tests() {
     );
 }
 
+#[test]
+pub fn signed_types_of_characters_are_the_characters_themselves() {
+    // Given an initialized index
+    let index = get_builtin_index();
+    // characters are code-units without a signed interpretation
+    assert_signed_type!(CHAR_TYPE, CHAR_TYPE, index);
+    assert_signed_type!(WCHAR_TYPE, WCHAR_TYPE, index);
+}
+
 #[test]
 pub fn equal_method_function_names() {
     assert_eq!(Some("STRING_EQUAL".to_string()), get_equals_function_name_for("STRING", &Operator::Equal));
@@ -117,6 +126,29 @@ fn get_bigger_size_integers_mix_test() {
     assert_eq!(dint_type, typesystem::get_bigger_type(dint_type, udint_type, &index));
 }
 
+#[test]
+fn get_bigger_size_character_integer_mix_test() {
+    // Given an initialized index
+    let index = get_builtin_index();
+    let char_type = index.get_type_or_panic(CHAR_TYPE);
+    let wchar_type = index.get_type_or_panic(WCHAR_TYPE);
+    let usint_type = index.get_type_or_panic(USINT_TYPE);
+    let sint_type = index.get_type_or_panic(SINT_TYPE);
+    let int_type = index.get_type_or_panic(INT_TYPE);
+    let uint_type = index.get_type_or_panic(UINT_TYPE);
+
+    //A bigger integer wins over a character
+    assert_eq!(int_type, typesystem::get_bigger_type(char_type, int_type, &index));
+    assert_eq!(int_type, typesystem::get_bigger_type(int_type, char_type, &index));
+    assert_eq!(sint_type, typesystem::get_bigger_type(char_type, sint_type, &index));
+    //An integer of the same size as the character is treated as the number
+    assert_eq!(usint_type, typesystem::get_bigger_type(char_type, usint_type, &index));
+    assert_eq!(usint_type, typesystem::get_bigger_type(usint_type, char_type, &index));
+    assert_eq!(uint_type, typesystem::get_bigger_type(wchar_type, uint_type, &index));
+    //A bigger character wins over a smaller integer
+    assert_eq!(wchar_type, typesystem::get_bigger_type(wchar_type, usint_type, &index));
+}
+
 #[test]
 fn get_bigger_size_real_test() {
     // Given an initialized index
@@ -605,3 +637,80 @@ fn array_size_nested_tests() {
     //the size of the array is 20*size(int)
     assert_eq!(6400, nested_array.get_type_information().get_size_in_bits(&index));
 }
+
+#[test]
+fn union_size_is_the_size_of_its_biggest_member() {
+    let (_, index) = index(
+        "
+        TYPE MyUnion :
+            UNION
+                b : BYTE;
+                l : LINT;
+                i : INT;
+            END_UNION
+        END_TYPE
+        ",
+    );
+
+    let union_type = index.find_effective_type_info("MyUnion").unwrap();
+    assert_eq!(64, union_type.get_size_in_bits(&index));
+}
+
+#[test]
+fn string_size_is_the_number_of_bytes_of_its_characters_and_terminator() {
+    let (_, index) = index(
+        "
+        TYPE Name : STRING[20]; END_TYPE
+        TYPE WideName : WSTRING[20]; END_TYPE
+        ",
+    );
+
+    assert_eq!(21, index.find_effective_type_info("Name").unwrap().get_size(&index).value());
+    assert_eq!(42, index.find_effective_type_info("WideName").unwrap().get_size(&index).value());
+    assert_eq!(81 * 8, index.find_effective_type_info(STRING_TYPE).unwrap().get_size_in_bits(&index));
+}
+
+#[test]
+fn bitfield_members_are_packed_into_bits() {
+    let (_, index) = index(
+        "
+        TYPE Eight :
+            {bitfield} STRUCT
+                b0, b1, b2, b3, b4, b5, b6, b7 : BOOL;
+            END_STRUCT
+        END_TYPE
+
+        TYPE Nine :
+            {bitfield} STRUCT
+                b0, b1, b2, b3, b4, b5, b6, b7, b8 : BOOL;
+            END_STRUCT
+        END_TYPE
+        ",
+    );
+
+    assert_eq!(8, index.find_effective_type_info("Eight").unwrap().get_size_in_bits(&index));
+    assert_eq!(16, index.find_effective_type_info("Nine").unwrap().get_size_in_bits(&index));
+}
+
+#[test]
+fn display_names_describe_the_type() {
+    let (_, index) = index(
+        "
+        TYPE Color : (red, green, blue); END_TYPE
+        TYPE Percent : INT (0..100); END_TYPE
+        TYPE Temperature : DINT (-40..125); END_TYPE
+        TYPE Percentages : ARRAY[0..5, 1..2] OF Percent; END_TYPE
+        TYPE Name : STRING[20]; END_TYPE
+        TYPE PercentRef : REF_TO Percent; END_TYPE
+        ",
+    );
+
+    let display_name = |name: &str| index.get_type(name).unwrap().get_display_name(&index);
+    assert_eq!(display_name("Color"), "(red, green, blue)");
+    assert_eq!(display_name("Percent"), "INT (0..100)");
+    assert_eq!(display_name("Temperature"), "DINT (-40..125)");
+    assert_eq!(display_name("Percentages"), "ARRAY[0..5, 1..2] OF INT (0..100)");
+    assert_eq!(display_name("Name"), "STRING[20]");
+    assert_eq!(display_name("PercentRef"), "REF_TO INT (0..100)");
+    assert_eq!(display_name("DINT"), "DINT");
+}
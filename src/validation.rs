@@ -13,7 +13,7 @@ use crate::{
 use self::{
     global::GlobalValidator,
     pou::{visit_implementation, visit_pou},
-    recursive::RecursiveValidator,
+    recursive::{RecursiveCallValidator, RecursiveValidator},
     types::visit_user_type_declaration,
     variable::visit_variable_block,
 };
@@ -35,7 +35,11 @@ pub struct ValidationContext<'s, T: AnnotationMap> {
     index: &'s Index,
     /// the type_name of the context for a reference (e.g. `a.b` where `a`'s type is the context of `b`)
     qualifier: Option<&'s str>,
+    /// the name of the implementation whose statements are currently validated
+    implementation: Option<&'s str>,
     is_call: bool,
+    /// whether the statements are lexically inside a `FOR`, `WHILE` or `REPEAT` loop
+    is_in_loop: bool,
 }
 
 impl<'s, T: AnnotationMap> ValidationContext<'s, T> {
@@ -44,7 +48,20 @@ impl<'s, T: AnnotationMap> ValidationContext<'s, T> {
             annotations: self.annotations,
             index: self.index,
             qualifier: Some(qualifier),
+            implementation: self.implementation,
             is_call: self.is_call,
+            is_in_loop: self.is_in_loop,
+        }
+    }
+
+    fn with_implementation(&self, implementation: &'s str) -> Self {
+        ValidationContext {
+            annotations: self.annotations,
+            index: self.index,
+            qualifier: self.qualifier,
+            implementation: Some(implementation),
+            is_call: self.is_call,
+            is_in_loop: self.is_in_loop,
         }
     }
 
@@ -67,13 +84,30 @@ impl<'s, T: AnnotationMap> ValidationContext<'s, T> {
             annotations: self.annotations,
             index: self.index,
             qualifier: self.qualifier,
+            implementation: self.implementation,
             is_call: true,
+            is_in_loop: self.is_in_loop,
         }
     }
 
     fn is_call(&self) -> bool {
         self.is_call
     }
+
+    fn set_is_in_loop(&self) -> Self {
+        ValidationContext {
+            annotations: self.annotations,
+            index: self.index,
+            qualifier: self.qualifier,
+            implementation: self.implementation,
+            is_call: self.is_call,
+            is_in_loop: true,
+        }
+    }
+
+    fn is_in_loop(&self) -> bool {
+        self.is_in_loop
+    }
 }
 
 /// This trait should be implemented by any validator used by `validation::Validator`
@@ -83,12 +117,23 @@ pub trait Validators {
     fn take_diagnostics(&mut self) -> Vec<Diagnostic>;
 }
 
+/// Options to enable validations which are not performed by default
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationOptions {
+    /// Report direct or mutual recursion between `FUNCTION`s as an error
+    pub forbid_recursive_calls: bool,
+    /// Warn about comparisons between a signed and an unsigned integer
+    pub warn_mixed_signedness: bool,
+}
+
 #[derive(Validators)]
 pub struct Validator {
     //context: ValidationContext<'s>,
     diagnostics: Vec<Diagnostic>,
+    options: ValidationOptions,
     global_validator: GlobalValidator,
     recursive_validator: RecursiveValidator,
+    recursive_call_validator: RecursiveCallValidator,
 }
 
 impl Default for Validator {
@@ -99,10 +144,16 @@ impl Default for Validator {
 
 impl Validator {
     pub fn new() -> Validator {
+        Validator::with_options(ValidationOptions::default())
+    }
+
+    pub fn with_options(options: ValidationOptions) -> Validator {
         Validator {
             diagnostics: Vec::new(),
+            options,
             global_validator: GlobalValidator::new(),
             recursive_validator: RecursiveValidator::new(),
+            recursive_call_validator: RecursiveCallValidator::new(),
         }
     }
 
@@ -111,6 +162,7 @@ impl Validator {
         all_diagnostics.append(&mut self.take_diagnostics());
         all_diagnostics.append(&mut self.global_validator.take_diagnostics());
         all_diagnostics.append(&mut self.recursive_validator.take_diagnostics());
+        all_diagnostics.append(&mut self.recursive_call_validator.take_diagnostics());
         all_diagnostics
     }
 
@@ -134,8 +186,23 @@ impl Validator {
         }
     }
 
+    /// Performs the validations which need to know about calls across all compilation units.
+    /// Must be called after all units were visited using [`Validator::visit_unit`].
+    pub fn perform_call_graph_validation(&mut self) {
+        if self.options.forbid_recursive_calls {
+            self.recursive_call_validator.validate();
+        }
+    }
+
     pub fn visit_unit<T: AnnotationMap>(&mut self, annotations: &T, index: &Index, unit: &CompilationUnit) {
-        let context = ValidationContext { annotations, index, qualifier: None, is_call: false };
+        let context = ValidationContext {
+            annotations,
+            index,
+            qualifier: None,
+            implementation: None,
+            is_call: false,
+            is_in_loop: false,
+        };
         // validate POU and declared Variables
         for pou in &unit.units {
             visit_pou(self, pou, &context.with_qualifier(pou.name.as_str()));
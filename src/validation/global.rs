@@ -1,3 +1,4 @@
+use indexmap::IndexMap;
 use itertools::Itertools;
 use plc_ast::ast::PouType;
 use plc_diagnostics::diagnostics::Diagnostic;
@@ -76,6 +77,31 @@ impl GlobalValidator {
 
         // all POUs
         self.validate_unique_pous(index);
+
+        // POUs with an extern name
+        self.validate_unique_extern_names(index);
+    }
+
+    /// validates that the symbols requested using `{extern_name := '...'}` do not collide with
+    /// the symbol of another POU or global variable
+    fn validate_unique_extern_names(&mut self, index: &Index) {
+        let implementations = index.get_implementations().values().filter(|it| !it.is_generic()).map(|it| {
+            let location = index.find_pou(it.get_call_name()).map(|it| it.get_location());
+            (it.get_symbol_name(), location.unwrap_or(it.get_location()), it.get_extern_name().is_some())
+        });
+        let globals = index.get_globals().values().map(|it| (it.get_name(), &it.source_location, false));
+
+        let mut symbols: IndexMap<&str, Vec<(&SourceLocation, bool)>> = IndexMap::new();
+        for (name, location, is_extern_name) in implementations.chain(globals) {
+            symbols.entry(name).or_default().push((location, is_extern_name));
+        }
+
+        for (name, entries) in symbols {
+            if entries.len() > 1 && entries.iter().any(|(_, is_extern_name)| *is_extern_name) {
+                let locations = entries.into_iter().map(|(location, _)| location).collect::<Vec<_>>();
+                self.report_name_conflict(name, &locations, Some("Ambiguous extern name."));
+            }
+        }
     }
 
     /// validates following uniqueness-clusters:
@@ -2,7 +2,7 @@ use plc_ast::ast::{Implementation, LinkageType, Pou, PouType, VariableBlockType}
 use plc_diagnostics::diagnostics::Diagnostic;
 
 use super::{
-    statement::visit_statement, variable::visit_variable_block, ValidationContext, Validator, Validators,
+    statement::visit_statements, variable::visit_variable_block, ValidationContext, Validator, Validators,
 };
 use crate::resolver::AnnotationMap;
 
@@ -48,9 +48,9 @@ pub fn visit_implementation<T: AnnotationMap>(
                 }
             }
         }
-        implementation.statements.iter().for_each(|s| {
-            visit_statement(validator, s, &context.with_qualifier(implementation.name.as_str()))
-        });
+        let context =
+            context.with_qualifier(implementation.name.as_str()).with_implementation(&implementation.name);
+        visit_statements(validator, &implementation.statements, &context);
     }
 }
 
@@ -1,10 +1,11 @@
-use indexmap::IndexSet;
+use indexmap::{IndexMap, IndexSet};
 use itertools::Itertools;
 use plc_ast::ast::PouType;
 use plc_diagnostics::diagnostics::Diagnostic;
+use plc_source::source_location::SourceLocation;
 
 use crate::{
-    index::{Index, VariableIndexEntry},
+    index::{Index, PouIndexEntry, VariableIndexEntry},
     typesystem::{DataType, DataTypeInformation, DataTypeInformationProvider, StructSource},
 };
 
@@ -141,3 +142,104 @@ impl RecursiveValidator {
         }
     }
 }
+
+/// Validator to find and report all direct or mutual recursive calls between `FUNCTION`s using the same
+/// Depth-first search (DFS) approach as the [`RecursiveValidator`]. Many PLC runtimes do not offer a dynamic
+/// stack, hence the following code would be flagged if the validation is enabled:
+/// ```st
+/// FUNCTION A : DINT
+///     A := B();
+/// END_FUNCTION
+///
+/// FUNCTION B : DINT
+///     B := A();
+/// END_FUNCTION
+/// ```
+/// Function blocks, programs and methods are exempt from this validation.
+#[derive(Default, Validators)]
+pub struct RecursiveCallValidator {
+    pub diagnostics: Vec<Diagnostic>,
+    /// the call graph, mapping each calling function to its callees and the location of the (first) call
+    calls: IndexMap<String, IndexMap<String, SourceLocation>>,
+}
+
+impl RecursiveCallValidator {
+    pub fn new() -> RecursiveCallValidator {
+        RecursiveCallValidator { diagnostics: Vec::new(), calls: IndexMap::new() }
+    }
+
+    /// Registers a call from `caller` to `callee` in the call graph, calls not made from one function to
+    /// another are ignored.
+    pub fn register_call(
+        &mut self,
+        caller: &PouIndexEntry,
+        callee: &PouIndexEntry,
+        location: SourceLocation,
+    ) {
+        if !matches!(caller, PouIndexEntry::Function { .. })
+            || !matches!(callee, PouIndexEntry::Function { .. })
+        {
+            return;
+        }
+
+        self.calls
+            .entry(caller.get_name().to_string())
+            .or_default()
+            .entry(callee.get_name().to_string())
+            .or_insert(location);
+    }
+
+    /// Entry point of finding and reporting all recursive calls in the registered call graph.
+    pub fn validate(&mut self) {
+        let calls = std::mem::take(&mut self.calls);
+        let mut nodes_visited = IndexSet::new();
+        let mut path = IndexSet::new();
+
+        for node in calls.keys() {
+            if !nodes_visited.contains(node.as_str()) {
+                self.dfs(&calls, &mut path, node, &mut nodes_visited);
+            }
+        }
+    }
+
+    /// Visits all callees of `node_curr` in DFS manner, reporting a cycle whenever a callee is already part
+    /// of the current `path`.
+    fn dfs<'g>(
+        &mut self,
+        calls: &'g IndexMap<String, IndexMap<String, SourceLocation>>,
+        path: &mut IndexSet<&'g str>,
+        node_curr: &'g str,
+        nodes_visited: &mut IndexSet<&'g str>,
+    ) {
+        nodes_visited.insert(node_curr);
+        path.insert(node_curr);
+
+        for node in calls.get(node_curr).into_iter().flat_map(IndexMap::keys) {
+            if path.contains(node.as_str()) {
+                self.report(calls, node, path);
+            } else if !nodes_visited.contains(node.as_str()) {
+                self.dfs(calls, path, node, nodes_visited);
+            }
+        }
+        path.pop();
+    }
+
+    /// Reports the minimal cycle of `path` ending in `node`, pointing to every call taking part in it.
+    fn report(
+        &mut self,
+        calls: &IndexMap<String, IndexMap<String, SourceLocation>>,
+        node: &str,
+        path: &IndexSet<&str>,
+    ) {
+        let Some(idx) = path.get_index_of(node) else { unreachable!("Node has to be in the IndexSet") };
+        let mut slice = path.iter().skip(idx).copied().collect::<Vec<_>>();
+        slice.push(node); // Append to get `A -> B -> A` instead of `A -> B` in the report
+
+        let ranges = slice
+            .iter()
+            .tuple_windows()
+            .filter_map(|(caller, callee)| calls.get(*caller).and_then(|it| it.get(*callee)).cloned())
+            .collect();
+        self.diagnostics.push(Diagnostic::illegal_recursion(&slice.iter().join(" -> "), ranges));
+    }
+}
@@ -2,10 +2,11 @@ use std::{collections::HashSet, mem::discriminant};
 
 use plc_ast::{
     ast::{
-        flatten_expression_list, AstNode, AstStatement, DirectAccess, DirectAccessType, JumpStatement,
-        Operator, ReferenceAccess,
+        contains_label, flatten_expression_list, Assignment, AstNode, AstStatement, BinaryExpression,
+        CallStatement, DirectAccess, DirectAccessType, JumpStatement, Operator, PointerKind, RangeStatement,
+        ReferenceAccess, ReferenceExpr, TypeNature, UnaryExpression,
     },
-    control_statements::{AstControlStatement, ConditionalBlock},
+    control_statements::{AstControlStatement, ConditionalBlock, ReturnStatement},
     literals::{Array, AstLiteral, StringValue},
 };
 use plc_diagnostics::diagnostics::Diagnostic;
@@ -16,13 +17,13 @@ use super::{
     ValidationContext, Validator, Validators,
 };
 use crate::{
-    builtins::{self, BuiltIn},
+    builtins::BuiltIn,
     codegen::generators::expression_generator::get_implicit_call_parameter,
     index::{ArgumentType, Index, PouIndexEntry, VariableIndexEntry, VariableType},
-    resolver::{const_evaluator, AnnotationMap, StatementAnnotation},
+    resolver::{const_evaluator, generics, get_enable_parameter, AnnotationMap, StatementAnnotation},
     typesystem::{
         self, get_equals_function_name_for, get_literal_actual_signed_type_name, DataType,
-        DataTypeInformation, Dimension, StructSource, BOOL_TYPE, POINTER_SIZE,
+        DataTypeInformation, Dimension, StructSource, BOOL_TYPE,
     },
 };
 
@@ -72,7 +73,11 @@ pub fn visit_statement<T: AnnotationMap>(
             visit_statement(validator, &data.element, context);
         }
         AstStatement::ReferenceExpr(data) => {
-            if let Some(base) = &data.base {
+            // the length of a sized string cast is validated with the cast
+            let is_cast = matches!(data.access, ReferenceAccess::Cast(_));
+            if let Some(base) =
+                data.base.as_ref().filter(|it| !is_cast || get_sized_string_length(it).is_none())
+            {
                 visit_statement(validator, base, context);
             }
 
@@ -126,8 +131,22 @@ pub fn visit_statement<T: AnnotationMap>(
                 ))
             }
         }
-        // AstStatement::ExitStatement { location, id } => (),
-        // AstStatement::ContinueStatement { location, id } => (),
+        AstStatement::This => {
+            // the resolver only annotates `THIS` if it is used inside a function block
+            if context.annotations.get(statement).is_none() {
+                validator.push_diagnostic(Diagnostic::this_outside_method(statement.get_location()));
+            }
+        }
+        AstStatement::ExitStatement(_) | AstStatement::ContinueStatement(_) if !context.is_in_loop() => {
+            validator.push_diagnostic(Diagnostic::control_flow_outside_loop(
+                if matches!(statement.get_stmt(), AstStatement::ExitStatement(_)) {
+                    "EXIT"
+                } else {
+                    "CONTINUE"
+                },
+                statement.get_location(),
+            ));
+        }
         // AstStatement::ReturnStatement { location, id } => (),
         // AstStatement::LiteralNull { location, id } => (),
         AstStatement::ParenExpression(expr) => visit_statement(validator, expr, context),
@@ -161,7 +180,7 @@ fn validate_reference_expression<T: AnnotationMap>(
         }
         ReferenceAccess::Index(i) => {
             if let Some(base) = base {
-                visit_array_access(validator, base, i, context)
+                visit_array_access(validator, statement, base, i, context)
             } else {
                 validator.push_diagnostic(Diagnostic::invalid_operation(
                     "Index-Access requires an array-value.",
@@ -172,6 +191,16 @@ fn validate_reference_expression<T: AnnotationMap>(
         ReferenceAccess::Cast(c) => {
             visit_statement(validator, c.as_ref(), context);
 
+            // the length of a sized string cast is resolved to a string type if it is a positive constant
+            if let Some(length) = base.as_deref().and_then(get_sized_string_length) {
+                if base.as_deref().and_then(|it| context.annotations.get(it)).is_none() {
+                    validator.push_diagnostic(Diagnostic::invalid_operation(
+                        "The length of a string cast must be a positive constant integer",
+                        length.get_location(),
+                    ));
+                }
+            }
+
             // see if we try to cast a literal
             if let (AstStatement::Literal(literal), Some(StatementAnnotation::Type { type_name })) =
                 (c.get_stmt(), base.as_ref().and_then(|it| context.annotations.get(it)))
@@ -184,6 +213,16 @@ fn validate_reference_expression<T: AnnotationMap>(
                     &statement.get_location(),
                     context,
                 );
+                // only literals cast to an explicit length are truncated
+                if base.as_deref().and_then(get_sized_string_length).is_some() {
+                    validate_sized_string_cast(
+                        validator,
+                        literal,
+                        type_name.as_str(),
+                        &statement.get_location(),
+                        context,
+                    );
+                }
             }
         }
         ReferenceAccess::Deref => {
@@ -262,6 +301,131 @@ fn validate_direct_access<T: AnnotationMap>(
     }
 }
 
+/// visits the given block of statements and reports the statements following an unconditional
+/// `RETURN`, `EXIT` or `CONTINUE`
+pub fn visit_statements<T: AnnotationMap>(
+    validator: &mut Validator,
+    statements: &[AstNode],
+    context: &ValidationContext<T>,
+) {
+    statements.iter().for_each(|s| visit_statement(validator, s, context));
+    validate_unreachable_statements(validator, statements, context.is_in_loop);
+    validate_null_dereferences(validator, statements);
+}
+
+/// reports pointers dereferenced after they were assigned `NULL` in the same block of statements,
+/// e.g. `p := NULL; x := p^;`. Assigning the pointer again forgets its `NULL` value, all other
+/// statements (e.g. calls, control statements or labels) forget every `NULL` value since they might
+/// change the pointers as well
+fn validate_null_dereferences(validator: &mut Validator, statements: &[AstNode]) {
+    let mut null_pointers: Vec<String> = vec![];
+    for statement in statements {
+        let mut may_change_pointers = false;
+        visit_expressions(statement, &mut |it| match it.get_stmt() {
+            AstStatement::ReferenceExpr(ReferenceExpr {
+                access: ReferenceAccess::Deref,
+                base: Some(base),
+            }) => {
+                if let Some(pointer) = get_local_reference_name(base).filter(|it| null_pointers.contains(it))
+                {
+                    validator.push_diagnostic(Diagnostic::null_dereference(&pointer, it.get_location()));
+                }
+            }
+            AstStatement::ReferenceExpr(ReferenceExpr { access: ReferenceAccess::Address, .. })
+            | AstStatement::CallStatement(_) => may_change_pointers = true,
+            _ => {}
+        });
+
+        match statement.get_stmt() {
+            AstStatement::Assignment(Assignment { left, right }) if !may_change_pointers => {
+                if let Some(pointer) = get_local_reference_name(left) {
+                    null_pointers.retain(|it| *it != pointer);
+                    if matches!(right.get_stmt(), AstStatement::Literal(AstLiteral::Null)) {
+                        null_pointers.push(pointer);
+                    }
+                }
+            }
+            _ => null_pointers.clear(),
+        }
+    }
+}
+
+/// returns the (lowercase) name of a reference to a local variable, e.g. `p` but not `a.p`
+fn get_local_reference_name(reference: &AstNode) -> Option<String> {
+    match reference.get_stmt() {
+        AstStatement::ReferenceExpr(ReferenceExpr {
+            access: ReferenceAccess::Member(member),
+            base: None,
+        }) => member.get_flat_reference_name().map(str::to_lowercase),
+        _ => None,
+    }
+}
+
+/// calls `visit` for the given expression and all of its sub-expressions
+fn visit_expressions<'a>(expression: &'a AstNode, visit: &mut impl FnMut(&'a AstNode)) {
+    visit(expression);
+    match expression.get_stmt() {
+        AstStatement::ReferenceExpr(ReferenceExpr { access, base }) => {
+            match access {
+                ReferenceAccess::Index(it) | ReferenceAccess::Cast(it) => visit_expressions(it, visit),
+                _ => {}
+            }
+            if let Some(base) = base {
+                visit_expressions(base, visit);
+            }
+        }
+        AstStatement::Assignment(Assignment { left, right })
+        | AstStatement::OutputAssignment(Assignment { left, right })
+        | AstStatement::BinaryExpression(BinaryExpression { left, right, .. }) => {
+            visit_expressions(left, visit);
+            visit_expressions(right, visit);
+        }
+        AstStatement::RangeStatement(RangeStatement { start, end }) => {
+            visit_expressions(start, visit);
+            visit_expressions(end, visit);
+        }
+        AstStatement::UnaryExpression(UnaryExpression { value, .. }) => visit_expressions(value, visit),
+        AstStatement::ParenExpression(inner) => visit_expressions(inner, visit),
+        AstStatement::ExpressionList(expressions) => {
+            expressions.iter().for_each(|it| visit_expressions(it, visit))
+        }
+        AstStatement::CallStatement(CallStatement { operator, parameters }) => {
+            visit_expressions(operator, visit);
+            if let Some(parameters) = parameters {
+                visit_expressions(parameters, visit);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// reports the statements between an unconditional transfer of control and the next label,
+/// statements following a label can still be reached by a `JMP`.
+/// `EXIT` and `CONTINUE` outside of a loop are reported on their own and not considered a transfer
+fn validate_unreachable_statements(validator: &mut Validator, statements: &[AstNode], is_in_loop: bool) {
+    let Some((transfer, keyword)) = statements.iter().enumerate().find_map(|(i, it)| match it.get_stmt() {
+        AstStatement::ReturnStatement(ReturnStatement { condition: None }) => Some((i, "RETURN")),
+        AstStatement::ExitStatement(_) if is_in_loop => Some((i, "EXIT")),
+        AstStatement::ContinueStatement(_) if is_in_loop => Some((i, "CONTINUE")),
+        _ => None,
+    }) else {
+        return;
+    };
+
+    let remaining = &statements[transfer + 1..];
+    let label = remaining
+        .iter()
+        .position(|it| matches!(it.get_stmt(), AstStatement::LabelStatement(_)))
+        .unwrap_or(remaining.len());
+    if let (Some(first), Some(last)) = (remaining[..label].first(), remaining[..label].last()) {
+        validator.push_diagnostic(Diagnostic::unreachable_code(
+            &format!("it follows an unconditional {keyword}"),
+            first.get_location().span(&last.get_location()),
+        ));
+    }
+    validate_unreachable_statements(validator, &remaining[label..], is_in_loop);
+}
+
 fn validate_control_statement<T: AnnotationMap>(
     validator: &mut Validator,
     control_statement: &AstControlStatement,
@@ -271,20 +435,23 @@ fn validate_control_statement<T: AnnotationMap>(
         AstControlStatement::If(stmt) => {
             stmt.blocks.iter().for_each(|b| {
                 visit_statement(validator, b.condition.as_ref(), context);
-                b.body.iter().for_each(|s| visit_statement(validator, s, context));
+                visit_statements(validator, &b.body, context);
             });
-            stmt.else_block.iter().for_each(|e| visit_statement(validator, e, context));
+            visit_statements(validator, &stmt.else_block, context);
+            validate_unreachable_branches(validator, &stmt.blocks, &stmt.else_block, context);
         }
         AstControlStatement::ForLoop(stmt) => {
             visit_all_statements!(validator, context, &stmt.counter, &stmt.start, &stmt.end);
             if let Some(by_step) = &stmt.by_step {
                 visit_statement(validator, by_step, context);
             }
-            stmt.body.iter().for_each(|s| visit_statement(validator, s, context));
+            let body_context = context.set_is_in_loop();
+            visit_statements(validator, &stmt.body, &body_context);
         }
         AstControlStatement::WhileLoop(stmt) | AstControlStatement::RepeatLoop(stmt) => {
             visit_statement(validator, &stmt.condition, context);
-            stmt.body.iter().for_each(|s| visit_statement(validator, s, context));
+            let body_context = context.set_is_in_loop();
+            visit_statements(validator, &stmt.body, &body_context);
         }
         AstControlStatement::Case(stmt) => {
             validate_case_statement(validator, &stmt.selector, &stmt.case_blocks, &stmt.else_block, context);
@@ -292,6 +459,81 @@ fn validate_control_statement<T: AnnotationMap>(
     }
 }
 
+/// reports the branches of an `IF` statement that can never be executed because of a constant
+/// condition, i.e. the bodies of `FALSE` conditions and everything following a `TRUE` condition
+fn validate_unreachable_branches<T: AnnotationMap>(
+    validator: &mut Validator,
+    blocks: &[ConditionalBlock],
+    else_block: &[AstNode],
+    context: &ValidationContext<T>,
+) {
+    let mut dead_bodies = vec![];
+    for (i, block) in blocks.iter().enumerate() {
+        let condition =
+            const_evaluator::evaluate_constant_condition(&block.condition, context.qualifier, context.index);
+        match condition {
+            Some(false) => dead_bodies.push(block.body.as_slice()),
+            Some(true) => {
+                dead_bodies.extend(blocks[i + 1..].iter().map(|it| it.body.as_slice()));
+                dead_bodies.push(else_block);
+                break;
+            }
+            None => {}
+        }
+    }
+
+    // a branch containing a label can still be reached by a JMP
+    for body in dead_bodies.into_iter().filter(|it| !contains_label(it)) {
+        if let (Some(first), Some(last)) = (body.first(), body.last()) {
+            validator.push_diagnostic(Diagnostic::unreachable_code(
+                "the branch's condition is constant",
+                first.get_location().span(&last.get_location()),
+            ));
+        }
+    }
+}
+
+/// reports a literal exceeding the length of a sized string cast, e.g. `STRING#[2]'abc'`
+fn validate_sized_string_cast<T: AnnotationMap>(
+    validator: &mut Validator,
+    literal: &AstLiteral,
+    type_name: &str,
+    location: &SourceLocation,
+    context: &ValidationContext<T>,
+) {
+    let cast_type = context.index.get_effective_type_or_void_by_name(type_name).get_type_information();
+    let (DataTypeInformation::String { size, encoding }, AstLiteral::String(StringValue { value, .. })) =
+        (cast_type, literal)
+    else {
+        return;
+    };
+    // the last character is reserved for the terminator
+    let Ok(size) = size.as_int_value(context.index) else { return };
+    let capacity = (size - 1).max(0);
+    if encoding.truncate(value, capacity as usize).len() < value.len() {
+        let type_name = match encoding {
+            typesystem::StringEncoding::Utf8 => "STRING",
+            typesystem::StringEncoding::Utf16 => "WSTRING",
+        };
+        validator.push_diagnostic(Diagnostic::string_literal_truncated(
+            literal.get_literal_value().as_str(),
+            &format!("{type_name}[{capacity}]"),
+            location.clone(),
+        ));
+    }
+}
+
+/// returns the length of a sized string cast's type, e.g. `20` in `STRING#[20]'abc'`
+fn get_sized_string_length(cast_type: &AstNode) -> Option<&AstNode> {
+    match cast_type.get_stmt() {
+        AstStatement::ReferenceExpr(ReferenceExpr {
+            access: ReferenceAccess::Index(length),
+            base: Some(_),
+        }) => Some(length),
+        _ => None,
+    }
+}
+
 /// validates a literal statement with a dedicated type-prefix (e.g. INT#3)
 /// checks whether the type-prefix is valid and if the target is a literal
 fn validate_cast_literal<T: AnnotationMap>(
@@ -398,7 +640,28 @@ fn validate_reference<T: AnnotationMap>(
 ) {
     // unresolved reference
     if !context.annotations.has_type_annotation(statement) {
-        validator.push_diagnostic(Diagnostic::unresolved_reference(ref_name, location.clone()));
+        match base.map(|it| (it, context.annotations.get_type(it, context.index))) {
+            // the base could not be resolved either, only the first failing member of a chain is reported
+            Some((base, None)) if is_unresolved_member(base, context) => return,
+            Some((base, Some(container))) if is_raw_pointer(container.get_type_information()) => {
+                // members of `POINTER TO` and `REF_TO` are only accessible after an explicit `^`
+                validator.push_diagnostic(Diagnostic::explicit_dereference_required(
+                    base.get_flat_reference_name().unwrap_or_else(|| container.get_name()),
+                    ref_name,
+                    location.clone(),
+                ));
+                return;
+            }
+            Some((_, Some(container))) if container.get_type_information().is_struct() => {
+                validator.push_diagnostic(Diagnostic::no_such_member(
+                    ref_name,
+                    container.get_name(),
+                    location.clone(),
+                ));
+                return;
+            }
+            _ => validator.push_diagnostic(Diagnostic::unresolved_reference(ref_name, location.clone())),
+        }
 
         // was this meant as a direct access?
         // TODO: find a way to solve this without re-resolving this name
@@ -437,8 +700,22 @@ fn validate_reference<T: AnnotationMap>(
     }
 }
 
+/// returns true if the given statement is a member access which could not be resolved
+fn is_unresolved_member<T: AnnotationMap>(statement: &AstNode, context: &ValidationContext<T>) -> bool {
+    matches!(
+        statement.get_stmt(),
+        AstStatement::ReferenceExpr(ReferenceExpr { access: ReferenceAccess::Member(_), .. })
+    ) && !context.annotations.has_type_annotation(statement)
+}
+
+/// returns true if the given type is a pointer which has to be dereferenced explicitly
+fn is_raw_pointer(type_info: &DataTypeInformation) -> bool {
+    matches!(type_info, DataTypeInformation::Pointer { kind: PointerKind::Raw, .. })
+}
+
 fn visit_array_access<T: AnnotationMap>(
     validator: &mut Validator,
+    statement: &AstNode,
     reference: &AstNode,
     access: &AstNode,
     context: &ValidationContext<T>,
@@ -447,6 +724,13 @@ fn visit_array_access<T: AnnotationMap>(
 
     match target_type {
         DataTypeInformation::Array { dimensions, .. } => match access.get_stmt() {
+            AstStatement::RangeStatement(RangeStatement { start, end }) => {
+                validate_array_access_dimensions(dimensions.len(), 1, validator, access);
+                validate_array_access(validator, start, dimensions, 0, context);
+                validate_array_access(validator, end, dimensions, 0, context);
+                validate_array_slice(validator, statement, start, end, context);
+            }
+
             AstStatement::ExpressionList(expressions) => {
                 validate_array_access_dimensions(dimensions.len(), expressions.len(), validator, access);
 
@@ -467,12 +751,46 @@ fn visit_array_access<T: AnnotationMap>(
         } => {
             let dims = match access.get_stmt() {
                 AstStatement::ExpressionList(expressions) => expressions.len(),
+                AstStatement::RangeStatement(..) => {
+                    validator.push_diagnostic(Diagnostic::invalid_array_slice(
+                        "Slicing an ARRAY[*] is not supported",
+                        access.get_location(),
+                    ));
+                    return;
+                }
                 _ => 1,
             };
 
             validate_array_access_dimensions(*ndims, dims, validator, access);
         }
 
+        DataTypeInformation::String { size, .. } => match access.get_stmt() {
+            AstStatement::ExpressionList(expressions) => {
+                validate_array_access_dimensions(1, expressions.len(), validator, access)
+            }
+            AstStatement::Literal(AstLiteral::Integer(value)) => {
+                // the size includes the string's terminator, which cannot be accessed
+                if let Ok(length) = size.as_int_value(context.index).map(|it| it - 1) {
+                    if !(1..=length as i128).contains(value) {
+                        validator.push_diagnostic(Diagnostic::string_access_out_of_range(
+                            length,
+                            access.get_location(),
+                        ))
+                    }
+                }
+            }
+            _ => {
+                let type_info =
+                    context.annotations.get_type_or_void(access, context.index).get_type_information();
+                if !type_info.is_int() {
+                    validator.push_diagnostic(Diagnostic::incompatible_array_access_type(
+                        type_info.get_name(),
+                        access.get_location(),
+                    ))
+                }
+            }
+        },
+
         _ => validator.push_diagnostic(Diagnostic::incompatible_array_access_variable(
             target_type.get_name(),
             access.get_location(),
@@ -480,6 +798,40 @@ fn visit_array_access<T: AnnotationMap>(
     }
 }
 
+/// validates a slice like `arr[2..5]`, which is only allowed as an argument to an `ARRAY[*]` parameter
+fn validate_array_slice<T: AnnotationMap>(
+    validator: &mut Validator,
+    statement: &AstNode,
+    start: &AstNode,
+    end: &AstNode,
+    context: &ValidationContext<T>,
+) {
+    if let (
+        AstStatement::Literal(AstLiteral::Integer(start)),
+        AstStatement::Literal(AstLiteral::Integer(end)),
+    ) = (start.get_stmt(), end.get_stmt())
+    {
+        if start > end {
+            validator.push_diagnostic(Diagnostic::invalid_array_slice(
+                &format!("Array slice must not end before it starts, found {start}..{end}"),
+                statement.get_location(),
+            ));
+        }
+    }
+
+    let is_passed_as_vla = context
+        .annotations
+        .get_type_hint(statement, context.index)
+        .map(|hint| context.index.find_elementary_pointer_type(hint.get_type_information()).is_vla())
+        .unwrap_or(false);
+    if !is_passed_as_vla {
+        validator.push_diagnostic(Diagnostic::invalid_array_slice(
+            "Array slices can only be passed to ARRAY[*] parameters",
+            statement.get_location(),
+        ));
+    }
+}
+
 fn validate_array_access_dimensions(ndims: usize, dims: usize, validator: &mut Validator, access: &AstNode) {
     if ndims != dims {
         validator.push_diagnostic(Diagnostic::invalid_array_access(ndims, dims, access.get_location()))
@@ -523,6 +875,22 @@ fn visit_binary_expression<T: AnnotationMap>(
     right: &AstNode,
     context: &ValidationContext<T>,
 ) {
+    if validator.options.warn_mixed_signedness && operator.is_comparison_operator() {
+        validate_comparison_signedness(validator, statement, left, right, context);
+    }
+
+    if operator.is_comparison_operator() {
+        validate_string_comparison(validator, statement, left, right, context);
+    }
+
+    if matches!(operator, Operator::Division | Operator::Modulo) {
+        validate_divisor(validator, right, context);
+    }
+
+    if matches!(operator, Operator::Plus | Operator::Minus) {
+        validate_date_time_arithmetic(validator, statement, operator, left, right, context);
+    }
+
     match operator {
         Operator::NotEqual => {
             validate_binary_expression(validator, statement, &Operator::Equal, left, right, context)
@@ -543,6 +911,51 @@ fn visit_binary_expression<T: AnnotationMap>(
     }
 }
 
+/// warns about additions and subtractions of date and time values that do not result in a
+/// meaningful point in time or duration (e.g. `DATE + DATE`)
+fn validate_date_time_arithmetic<T: AnnotationMap>(
+    validator: &mut Validator,
+    statement: &AstNode,
+    operator: &Operator,
+    left: &AstNode,
+    right: &AstNode,
+    context: &ValidationContext<T>,
+) {
+    let left_type = context.annotations.get_type_or_void(left, context.index);
+    let right_type = context.annotations.get_type_or_void(right, context.index);
+    let left_info = context.index.find_intrinsic_type(left_type.get_type_information());
+    let right_info = context.index.find_intrinsic_type(right_type.get_type_information());
+
+    let is_point_in_time =
+        |it: &DataTypeInformation| it.is_date_or_time_type() && it.get_name() != typesystem::TIME_TYPE;
+    if (is_point_in_time(left_info) || is_point_in_time(right_info))
+        && left_info.is_date_or_time_type()
+        && right_info.is_date_or_time_type()
+        && typesystem::get_date_time_arithmetic_result_type(operator, left_info, right_info).is_none()
+    {
+        validator.push_diagnostic(Diagnostic::invalid_date_time_arithmetic(
+            &operator.to_string(),
+            left_type.get_name(),
+            right_type.get_name(),
+            statement.get_location(),
+        ));
+    }
+}
+
+/// reports divisions by an integer divisor that evaluates to zero at compile time
+fn validate_divisor<T: AnnotationMap>(
+    validator: &mut Validator,
+    divisor: &AstNode,
+    context: &ValidationContext<T>,
+) {
+    // divisors that cannot be evaluated at compile time are not checked
+    if let Ok(Some(AstNode { stmt: AstStatement::Literal(AstLiteral::Integer(0)), .. })) =
+        const_evaluator::evaluate(divisor, context.qualifier, context.index)
+    {
+        validator.push_diagnostic(Diagnostic::division_by_zero(divisor.get_location()));
+    }
+}
+
 fn validate_binary_expression<T: AnnotationMap>(
     validator: &mut Validator,
     statement: &AstNode,
@@ -575,6 +988,70 @@ fn validate_binary_expression<T: AnnotationMap>(
     }
 }
 
+/// warns about comparing a signed with an unsigned integer, literals are ignored since
+/// their type is derived from their value
+fn validate_comparison_signedness<T: AnnotationMap>(
+    validator: &mut Validator,
+    statement: &AstNode,
+    left: &AstNode,
+    right: &AstNode,
+    context: &ValidationContext<T>,
+) {
+    if left.is_literal() || right.is_literal() {
+        return;
+    }
+
+    let left_type = context.annotations.get_type_or_void(left, context.index);
+    let right_type = context.annotations.get_type_or_void(right, context.index);
+    let left_info = context.index.find_intrinsic_type(left_type.get_type_information());
+    let right_info = context.index.find_intrinsic_type(right_type.get_type_information());
+
+    let is_unsigned = |it: &DataTypeInformation| it.is_unsigned_int() && !it.is_bool();
+    if (left_info.is_signed_int() && is_unsigned(right_info))
+        || (is_unsigned(left_info) && right_info.is_signed_int())
+    {
+        validator.push_diagnostic(Diagnostic::mixed_signedness_comparison(
+            left_type.get_name(),
+            right_type.get_name(),
+            statement.get_location(),
+        ));
+    }
+}
+
+/// reports comparisons of a `STRING` with a `WSTRING`, the compare functions (e.g. `STRING_LESS`)
+/// expect two strings of the same encoding. Characters compared to a string are promoted to a
+/// string of their encoding, so comparing a `WCHAR` with a `STRING` is reported as well
+fn validate_string_comparison<T: AnnotationMap>(
+    validator: &mut Validator,
+    statement: &AstNode,
+    left: &AstNode,
+    right: &AstNode,
+    context: &ValidationContext<T>,
+) {
+    let left_type = context.annotations.get_type_or_void(left, context.index);
+    let right_type = context.annotations.get_type_or_void(right, context.index);
+    let left_info = context.index.find_intrinsic_type(left_type.get_type_information());
+    let right_info = context.index.find_intrinsic_type(right_type.get_type_information());
+
+    if !(left_info.is_string() || right_info.is_string()) {
+        return;
+    }
+
+    let get_encoding = |it: &DataTypeInformation| match it {
+        DataTypeInformation::String { encoding, .. } => Some(*encoding),
+        _ => it.get_promoted_string_encoding(),
+    };
+    if let (Some(left_encoding), Some(right_encoding)) = (get_encoding(left_info), get_encoding(right_info)) {
+        if left_encoding != right_encoding {
+            validator.push_diagnostic(Diagnostic::incompatible_string_comparison(
+                left_info.get_name(),
+                right_info.get_name(),
+                statement.get_location(),
+            ));
+        }
+    }
+}
+
 fn compare_function_exists<T: AnnotationMap>(
     type_name: &str,
     operator: &Operator,
@@ -650,6 +1127,111 @@ fn validate_call_by_ref(validator: &mut Validator, param: &VariableIndexEntry, a
     }
 }
 
+/// Validates that an argument passed to a [`VariableType::InOut`] parameter is of the parameter's type.
+/// In-out arguments are passed by reference, hence no implicit conversion can take place; aliases and
+/// subranges are compared by their effective type.
+/// Returns true if a mismatch was reported
+fn validate_inout_argument_type<T: AnnotationMap>(
+    validator: &mut Validator,
+    pou: &PouIndexEntry,
+    param: &VariableIndexEntry,
+    arg: &AstNode,
+    context: &ValidationContext<T>,
+) -> bool {
+    // non-references are reported by `validate_call_by_ref`, builtins validate their parameters themselves
+    if param.get_variable_type() != VariableType::InOut
+        || !arg.can_be_assigned_to()
+        || context.index.get_builtin_function(pou.get_name()).is_some()
+    {
+        return false;
+    }
+
+    // in-out parameters are declared as auto-deref pointers to the declared type
+    let parameter_type = match context.index.get_effective_type_or_void_by_name(param.get_type_name()) {
+        DataType {
+            information: DataTypeInformation::Pointer { inner_type_name, kind: PointerKind::Reference, .. },
+            ..
+        } => context.index.get_effective_type_or_void_by_name(inner_type_name),
+        parameter_type => parameter_type,
+    };
+    let Some(argument_type) = context.annotations.get_type(arg, context.index) else {
+        return false;
+    };
+
+    if parameter_type.get_type_information().is_generic(context.index)
+        || parameter_type.is_vla()
+        || matches!(argument_type.get_type_information(), DataTypeInformation::Void)
+        || is_same_effective_type(parameter_type, argument_type, context.index)
+    {
+        return false;
+    }
+
+    // anonymous types are described by their structure, e.g. `ARRAY[0..2] OF INT`
+    let type_name = |it: &DataType| {
+        if it.get_name().starts_with("__") {
+            it.get_display_name(context.index)
+        } else {
+            it.get_name().to_string()
+        }
+    };
+    validator.push_diagnostic(Diagnostic::inout_type_mismatch(
+        param.get_name(),
+        &type_name(parameter_type),
+        &type_name(argument_type),
+        arg.get_location(),
+    ));
+    true
+}
+
+/// Returns true if both types resolve to the same type, ignoring aliases and subranges.
+/// Arrays and strings may be declared inline, so they are compared by their structure rather than by name.
+fn is_same_effective_type(left: &DataType, right: &DataType, index: &Index) -> bool {
+    fn effective_type<'idx>(data_type: &'idx DataType, index: &'idx Index) -> &'idx DataType {
+        match data_type.get_type_information() {
+            DataTypeInformation::SubRange { referenced_type, .. } => {
+                index.get_effective_type_or_void_by_name(referenced_type)
+            }
+            _ => index.get_effective_type_or_void_by_name(data_type.get_name()),
+        }
+    }
+
+    let (left, right) = (effective_type(left, index), effective_type(right, index));
+    if left.get_name() == right.get_name() {
+        return true;
+    }
+
+    match (left.get_type_information(), right.get_type_information()) {
+        (
+            DataTypeInformation::Array { inner_type_name: left_inner, dimensions: left_dimensions, .. },
+            DataTypeInformation::Array { inner_type_name: right_inner, dimensions: right_dimensions, .. },
+        ) => {
+            left_dimensions.len() == right_dimensions.len()
+                && left_dimensions
+                    .iter()
+                    .zip(right_dimensions)
+                    .all(|(l, r)| l.get_range_inclusive(index).ok() == r.get_range_inclusive(index).ok())
+                && is_same_effective_type(
+                    index.get_effective_type_or_void_by_name(left_inner),
+                    index.get_effective_type_or_void_by_name(right_inner),
+                    index,
+                )
+        }
+        (
+            DataTypeInformation::String { encoding: left_encoding, .. },
+            DataTypeInformation::String { encoding: right_encoding, .. },
+        ) => left_encoding == right_encoding,
+        (
+            DataTypeInformation::Pointer { inner_type_name: left_inner, .. },
+            DataTypeInformation::Pointer { inner_type_name: right_inner, .. },
+        ) => is_same_effective_type(
+            index.get_effective_type_or_void_by_name(left_inner),
+            index.get_effective_type_or_void_by_name(right_inner),
+            index,
+        ),
+        _ => false,
+    }
+}
+
 fn validate_assignment<T: AnnotationMap>(
     validator: &mut Validator,
     right: &AstNode,
@@ -696,13 +1278,14 @@ fn validate_assignment<T: AnnotationMap>(
     if let (Some(right_type), Some(left_type)) = (right_type, left_type) {
         // implicit call parameter assignments are annotated to auto_deref pointers for ´ByRef` parameters
         // we need the inner type
-        let left_type = if let DataTypeInformation::Pointer { inner_type_name, auto_deref: true, .. } =
-            left_type.get_type_information()
-        {
-            context.index.get_effective_type_or_void_by_name(inner_type_name)
-        } else {
-            left_type
-        };
+        let left_type =
+            if let DataTypeInformation::Pointer { inner_type_name, kind: PointerKind::Reference, .. } =
+                left_type.get_type_information()
+            {
+                context.index.get_effective_type_or_void_by_name(inner_type_name)
+            } else {
+                left_type
+            };
 
         // VLA <- ARRAY assignments are valid when the array is passed to a function expecting a VLA, but
         // are no longer allowed inside a POU body
@@ -713,6 +1296,21 @@ fn validate_assignment<T: AnnotationMap>(
             return;
         }
 
+        if left_type.is_array() && right_type.is_array() && !left_type.is_vla() && !right_type.is_vla() {
+            if !is_compatible_array_assignment(
+                left_type.get_type_information(),
+                right_type.get_type_information(),
+                context.index,
+            ) {
+                validator.push_diagnostic(Diagnostic::incompatible_array_assignment(
+                    &right_type.get_display_name(context.index),
+                    &left_type.get_display_name(context.index),
+                    location.clone(),
+                ));
+            }
+            return;
+        }
+
         if !(left_type.is_compatible_with_type(right_type)
             && is_valid_assignment(left_type, right_type, right, context.index, location, validator))
         {
@@ -724,10 +1322,82 @@ fn validate_assignment<T: AnnotationMap>(
         } else if right.is_literal() {
             // TODO: See https://github.com/PLC-lang/rusty/issues/857
             // validate_assignment_type_sizes(validator, left_type, right_type, location, context)
+        } else {
+            validate_concat_truncation(validator, right, left_type, right_type, context);
         }
     }
 }
 
+/// warns if the result of `CONCAT`, which is long enough to hold all of its parameters, is assigned
+/// to a shorter string, the result is truncated to the length of the assigned string in that case
+fn validate_concat_truncation<T: AnnotationMap>(
+    validator: &mut Validator,
+    right: &AstNode,
+    left_type: &DataType,
+    right_type: &DataType,
+    context: &ValidationContext<T>,
+) {
+    let AstStatement::CallStatement(CallStatement { operator, .. }) = right.get_stmt() else { return };
+    if !matches!(
+        context.annotations.get(operator),
+        Some(StatementAnnotation::Function { qualified_name, .. }) if qualified_name == "CONCAT"
+    ) {
+        return;
+    }
+    let (
+        DataTypeInformation::String { size: target_size, encoding },
+        DataTypeInformation::String { size: result_size, .. },
+    ) = (left_type.get_type_information(), right_type.get_type_information())
+    else {
+        return;
+    };
+    let (Ok(target_size), Ok(result_size)) =
+        (target_size.as_int_value(context.index), result_size.as_int_value(context.index))
+    else {
+        return;
+    };
+    if result_size > target_size {
+        // the last character is reserved for the terminator
+        let capacity = target_size - 1;
+        validator.push_diagnostic(Diagnostic::concat_truncated(
+            &format!("{}[{capacity}]", encoding.get_string_type_name()),
+            capacity,
+            right.get_location(),
+        ));
+    }
+}
+
+/// arrays can be assigned to each other if all their dimensions have the same length
+/// and their elements are of the same type class and size
+fn is_compatible_array_assignment(
+    left: &DataTypeInformation,
+    right: &DataTypeInformation,
+    index: &Index,
+) -> bool {
+    let (
+        DataTypeInformation::Array { inner_type_name: left_inner, dimensions: left_dimensions, .. },
+        DataTypeInformation::Array { inner_type_name: right_inner, dimensions: right_dimensions, .. },
+    ) = (left, right)
+    else {
+        return false;
+    };
+
+    let lengths =
+        |dimensions: &[Dimension]| dimensions.iter().map(|it| it.get_length(index).ok()).collect::<Vec<_>>();
+    if lengths(left_dimensions) != lengths(right_dimensions) {
+        return false;
+    }
+
+    let left_inner = index.get_effective_type_or_void_by_name(left_inner).get_type_information();
+    let right_inner = index.get_effective_type_or_void_by_name(right_inner).get_type_information();
+    if left_inner.is_array() && right_inner.is_array() {
+        is_compatible_array_assignment(left_inner, right_inner, index)
+    } else {
+        typesystem::is_same_type_class(left_inner, right_inner, index)
+            && left_inner.get_size(index) == right_inner.get_size(index)
+    }
+}
+
 pub(crate) fn validate_enum_variant_assignment(
     validator: &mut Validator,
     left: &DataTypeInformation,
@@ -788,12 +1458,22 @@ fn is_valid_assignment(
         | is_invalid_pointer_assignment(left_type.get_type_information(), right_type.get_type_information(), index, location, validator)
         | is_aggregate_to_none_aggregate_assignment(left_type, right_type)
         | is_aggregate_type_missmatch(left_type, right_type, index)
+        | is_invalid_call_result_assignment(left_type, right_type, right)
     {
         return false;
     }
     true
 }
 
+/// the numerical result of a call cannot be converted to a BOOL, e.g. `flag := count()` with `count : INT`
+fn is_invalid_call_result_assignment(left_type: &DataType, right_type: &DataType, right: &AstNode) -> bool {
+    let (left_type, right_type) = (left_type.get_type_information(), right_type.get_type_information());
+    matches!(right.get_stmt(), AstStatement::CallStatement(_))
+        && left_type.is_bool()
+        && right_type.is_numerical()
+        && !right_type.is_bool()
+}
+
 /// strings with length 1 can be assigned to characters
 fn is_valid_string_to_char_assignment(
     left_type: &DataTypeInformation,
@@ -832,7 +1512,7 @@ fn is_invalid_pointer_assignment(
     //check if Datatype can hold a Pointer (u64)
     else if right_type.is_pointer()
         && !left_type.is_pointer()
-        && left_type.get_size_in_bits(index) < POINTER_SIZE
+        && left_type.get_size_in_bits(index) < index.get_type_layout().p64.bits()
     {
         validator.push_diagnostic(Diagnostic::incompatible_type_size(
             left_type.get_name(),
@@ -845,7 +1525,7 @@ fn is_invalid_pointer_assignment(
     //check if size allocated to Pointer is standart pointer size (u64)
     else if left_type.is_pointer()
         && !right_type.is_pointer()
-        && right_type.get_size_in_bits(index) < POINTER_SIZE
+        && right_type.get_size_in_bits(index) < index.get_type_layout().p64.bits()
     {
         validator.push_diagnostic(Diagnostic::incompatible_type_size(
             right_type.get_name(),
@@ -895,25 +1575,44 @@ fn validate_call<T: AnnotationMap>(
     visit_statement(validator, operator, context);
 
     if let Some(pou) = context.find_pou(operator) {
+        if let Some(caller) = context.implementation.and_then(|it| context.index.find_pou(it)) {
+            validator.recursive_call_validator.register_call(caller, pou, operator.get_location());
+        }
+
         // additional validation for builtin calls if necessary
-        if let Some(validation) = builtins::get_builtin(pou.get_name()).and_then(BuiltIn::get_validation) {
+        if let Some(validation) =
+            context.index.get_builtin_function(pou.get_name()).and_then(BuiltIn::get_validation)
+        {
             validation(validator, operator, parameters, context.annotations, context.index)
         }
 
         let declared_parameters = context.index.get_declared_parameters(pou.get_name());
-        let passed_parameters = parameters.map(flatten_expression_list).unwrap_or_default();
+        // the implicit EN/ENO parameters are neither declared nor positional
+        let (enable_parameters, passed_parameters): (Vec<_>, Vec<_>) = parameters
+            .map(flatten_expression_list)
+            .unwrap_or_default()
+            .into_iter()
+            .partition(|it| get_enable_parameter(pou, it, context.index).is_some());
+        enable_parameters.iter().for_each(|it| visit_statement(validator, it, context));
 
         let mut are_implicit_parameters = true;
         let mut variable_location_in_parent = vec![];
 
         // validate parameters
         for (i, p) in passed_parameters.iter().enumerate() {
+            // a mismatching VAR_IN_OUT argument is not reported again as an invalid assignment
+            let mut argument = *p;
+            let mut is_inout_mismatch = false;
             if let Ok((parameter_location_in_parent, right, is_implicit)) =
                 get_implicit_call_parameter(p, &declared_parameters, i)
             {
                 let left = declared_parameters.get(parameter_location_in_parent);
                 if let Some(left) = left {
                     validate_call_by_ref(validator, left, p);
+                    if validate_inout_argument_type(validator, pou, left, right, context) {
+                        is_inout_mismatch = true;
+                        argument = right;
+                    }
                     // 'parameter location in parent' and 'variable location in parent' are not the same (e.g VAR blocks are not counted as param).
                     // save actual location in parent for InOut validation
                     variable_location_in_parent.push(left.get_location_in_parent());
@@ -921,7 +1620,7 @@ fn validate_call<T: AnnotationMap>(
 
                 // explicit call parameter assignments will be handled by
                 // `visit_statement()` via `Assignment` and `OutputAssignment`
-                if is_implicit {
+                if is_implicit && !is_inout_mismatch {
                     validate_assignment(validator, right, None, &p.get_location(), context);
                 }
 
@@ -934,7 +1633,49 @@ fn validate_call<T: AnnotationMap>(
                 }
             }
 
-            visit_statement(validator, p, context);
+            visit_statement(validator, argument, context);
+        }
+
+        // the arguments of a generic call must share a common type
+        if let Some(StatementAnnotation::Function { qualified_name, .. }) = context.annotations.get(operator)
+        {
+            if are_implicit_parameters
+                && context.index.find_pou(qualified_name).map(PouIndexEntry::is_generic).unwrap_or_default()
+            {
+                let argument_types = passed_parameters
+                    .iter()
+                    .map(|it| context.annotations.get_type_or_void(it, context.index).get_name())
+                    .collect::<Vec<_>>();
+                if let Err(diagnostic) = generics::validate_generic_arguments(
+                    context.index,
+                    qualified_name,
+                    &argument_types,
+                    &operator.get_location(),
+                ) {
+                    validator.push_diagnostic(diagnostic);
+                }
+            }
+        }
+
+        // omitted function inputs are initialized with their default value, inputs without one must be passed
+        // builtins validate their parameters themselves
+        if matches!(pou, PouIndexEntry::Function { .. })
+            && context.index.get_builtin_function(pou.get_name()).is_none()
+        {
+            declared_parameters
+                .iter()
+                .filter(|p| p.get_declaration_type() == ArgumentType::ByVal(VariableType::Input))
+                .filter(|p| !variable_location_in_parent.contains(&p.get_location_in_parent()))
+                .filter(|p| {
+                    context.index.get_initial_value(&p.initial_value).is_none()
+                        && context.index.get_initial_value_for_type(p.get_type_name()).is_none()
+                })
+                .for_each(|p| {
+                    validator.push_diagnostic(Diagnostic::missing_input_parameter(
+                        p.get_name(),
+                        operator.get_location(),
+                    ))
+                });
         }
 
         // for PROGRAM/FB we need special inout validation
@@ -1006,10 +1747,10 @@ fn validate_case_statement<T: AnnotationMap>(
             .ok(); // no need to worry about the result
 
         visit_statement(validator, condition, context);
-        b.body.iter().for_each(|s| visit_statement(validator, s, context));
+        visit_statements(validator, &b.body, context);
     });
 
-    else_block.iter().for_each(|s| visit_statement(validator, s, context));
+    visit_statements(validator, else_block, context);
 }
 
 /// Validates that the assigned type and type hint are compatible with the nature for this
@@ -1042,11 +1783,15 @@ fn validate_type_nature<T: AnnotationMap>(
 				// INT parameter for REAL is allowed
                 | (type_hint.is_real() & actual_type.is_numerical()))
             {
-                validator.push_diagnostic(Diagnostic::invalid_type_nature(
-                    actual_type.get_name(),
-                    format!("{generic_nature:?}").as_str(),
-                    statement.get_location(),
-                ));
+                validator.push_diagnostic(if *generic_nature == TypeNature::Derived {
+                    Diagnostic::type_nature_mismatch(actual_type.get_name(), statement.get_location())
+                } else {
+                    Diagnostic::invalid_type_nature(
+                        actual_type.get_name(),
+                        format!("{generic_nature:?}").as_str(),
+                        statement.get_location(),
+                    )
+                });
             }
         }
     }
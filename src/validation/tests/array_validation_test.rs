@@ -1,6 +1,6 @@
 use insta::assert_snapshot;
 
-use crate::test_utils::tests::parse_and_validate_buffered;
+use crate::test_utils::tests::{parse_and_validate, parse_and_validate_buffered};
 
 #[test]
 fn array_access_validation() {
@@ -297,6 +297,23 @@ fn assignment_multiplied_statement() {
     assert_snapshot!(diagnostics);
 }
 
+#[test]
+fn multiple_repetitions_exceeding_the_array_length() {
+    let diagnostics = parse_and_validate_buffered(
+        "
+		PROGRAM main
+			VAR
+				valid : ARRAY[1..5] OF DINT := [3(1), 2(2)];
+				too_long : ARRAY[1..4] OF DINT := [3(1), 2(2)];
+				too_long_nested : ARRAY[1..2] OF ARRAY[1..2] OF DINT := [[2(1)], [3(2)]];
+			END_VAR
+		END_PROGRAM
+		",
+    );
+
+    assert_snapshot!(diagnostics);
+}
+
 #[test]
 fn parenthesized_struct_initializers() {
     let diagnostics = parse_and_validate_buffered(
@@ -320,3 +337,33 @@ fn parenthesized_struct_initializers() {
 
     assert_snapshot!(diagnostics);
 }
+
+#[test]
+fn string_access_out_of_range_is_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        PROGRAM prg
+        VAR
+            s : STRING[10];
+            ws : WSTRING;
+        END_VAR
+            s[1];
+            s[10];
+            ws[80];
+            s[0];
+            s[11];
+            ws[81];
+        END_PROGRAM
+       ",
+    );
+
+    let messages = diagnostics.iter().map(|it| it.get_message()).collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "String access must be in the range 1..10",
+            "String access must be in the range 1..10",
+            "String access must be in the range 1..80",
+        ]
+    );
+}
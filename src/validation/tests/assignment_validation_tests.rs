@@ -1,5 +1,6 @@
 use crate::assert_validation_snapshot;
-use crate::test_utils::tests::parse_and_validate;
+use crate::test_utils::tests::{parse_and_validate, parse_and_validate_for_target};
+use crate::Target;
 
 #[test]
 fn constant_assignment_validation() {
@@ -529,6 +530,26 @@ fn pointer_assignment_validation() {
     assert_validation_snapshot!(&diagnostics);
 }
 
+#[test]
+fn addresses_are_as_wide_as_the_pointers_of_the_target() {
+    let diagnostics = parse_and_validate_for_target(
+        "
+        FUNCTION main : DINT
+        VAR
+            v_int : INT;
+            v_word : WORD;
+            v_ptr_int : REF_TO INT;
+        END_VAR
+            v_ptr_int := ADR(v_int);
+            v_word := ADR(v_int);
+        END_FUNCTION
+        ",
+        &Target::from("msp430-none-elf"),
+    );
+
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+}
+
 #[test]
 fn array_assignment_validation() {
     let diagnostics = parse_and_validate(
@@ -944,3 +965,93 @@ fn string_type_alias_assignment_can_be_validated() {
 
     assert_validation_snapshot!(diagnostics)
 }
+
+#[test]
+fn array_assignment_with_mismatching_dimensions_is_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        PROGRAM main
+        VAR
+            arr_2x3 : ARRAY[0..1, 0..2] OF INT;
+            arr_3x2 : ARRAY[0..2, 0..1] OF INT;
+            arr_6 : ARRAY[0..5] OF INT;
+        END_VAR
+            arr_2x3 := arr_3x2;
+            arr_6 := arr_2x3;
+        END_PROGRAM
+        ",
+    );
+
+    assert_validation_snapshot!(diagnostics)
+}
+
+#[test]
+fn array_assignment_with_same_shape_and_compatible_elements_is_allowed() {
+    let diagnostics = parse_and_validate(
+        "
+        TYPE MyInt : INT; END_TYPE
+
+        PROGRAM main
+        VAR
+            arr_a : ARRAY[0..1, 0..2] OF INT;
+            arr_b : ARRAY[1..2, 10..12] OF MyInt;
+            arr_c : ARRAY[0..1] OF ARRAY[0..2] OF WORD;
+            arr_d : ARRAY[5..6] OF ARRAY[1..3] OF INT;
+        END_VAR
+            arr_a := arr_b;
+            arr_b := arr_a;
+            arr_c := arr_d;
+        END_PROGRAM
+        ",
+    );
+
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+}
+
+#[test]
+fn call_results_are_widened_to_the_assigned_type() {
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION int_result : INT
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            real_var : REAL;
+            lint_var : LINT;
+        END_VAR
+            real_var := int_result();
+            lint_var := int_result();
+        END_PROGRAM
+        ",
+    );
+
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+}
+
+#[test]
+fn numerical_call_results_cannot_be_assigned_to_bool() {
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION int_result : INT
+        END_FUNCTION
+
+        FUNCTION bool_result : BOOL
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            bool_var : BOOL;
+            int_var : INT;
+        END_VAR
+            bool_var := int_result();
+            bool_var := int_var;
+            bool_var := bool_result();
+            bool_var := 1;
+        END_PROGRAM
+        ",
+    );
+
+    //only the call's result is reported, variables and literals are converted as before
+    assert_validation_snapshot!(diagnostics)
+}
@@ -1,15 +1,10 @@
-use plc_ast::{
-    ast::{pre_process, CompilationUnit, LinkageType},
-    provider::IdProvider,
-};
-use plc_source::source_location::SourceLocationFactory;
+use plc_ast::provider::IdProvider;
 
 use crate::{
     assert_validation_snapshot,
-    index::{visitor, Index},
-    lexer, parser,
+    index::Index,
     resolver::TypeAnnotator,
-    test_utils::tests::parse_and_validate,
+    test_utils::tests::{index_without_builtins, parse_and_validate},
     typesystem,
     validation::Validator,
 };
@@ -339,23 +334,9 @@ fn automatically_generated_output_types_dont_cause_duplication_issues() {
 
 #[test]
 fn automatically_generated_output_types_in_different_files_dont_cause_duplication_issues() {
-    // a version of the test-util function that does not import the built-in and std-types
-    // (or they will cause a duplication issue)
-    fn do_index(src: &str, id_provider: IdProvider) -> Index {
-        let mut index = Index::default();
-        let (mut unit, ..) = parser::parse(
-            lexer::lex_with_ids(src, id_provider.clone(), SourceLocationFactory::internal(src)),
-            LinkageType::Internal,
-            "test.st",
-        );
-        pre_process(&mut unit, id_provider);
-        index.import(visitor::visit(&unit));
-        index
-    }
-
     // GIVEN some code that automatically generates a ptr-types
     let ids = IdProvider::default();
-    let index1 = do_index(
+    let (_, index1) = index_without_builtins(
         r#"
             FUNCTION foo : INT
                 VAR_OUTPUT
@@ -365,10 +346,11 @@ fn automatically_generated_output_types_in_different_files_dont_cause_duplicatio
             END_FUNCTION
         "#,
         ids.clone(),
+        "file1.st",
     );
 
     //AND another file with also OUTPUT-INTS
-    let index2 = do_index(
+    let (_, index2) = index_without_builtins(
         r#"
             FUNCTION foo2 : INT
                 VAR_OUTPUT
@@ -378,39 +360,35 @@ fn automatically_generated_output_types_in_different_files_dont_cause_duplicatio
             END_FUNCTION
         "#,
         ids,
+        "file2.st",
     );
 
-    // WHEN the index is combined
+    // WHEN the index is combined reporting conflicts
     let mut global_index = Index::default();
-    global_index.import(index1); //import file 1
-    global_index.import(index2); //import file 2
-
-    // THEN there should be no duplication diagnostics
-    let mut validator = Validator::new();
-    validator.perform_global_validation(&global_index);
-    let diagnostics = validator.diagnostics();
-    assert_eq!(diagnostics, vec![]);
+    assert_eq!(global_index.import_reporting_conflicts(index1), vec![]);
+    let diagnostics = global_index.import_reporting_conflicts(index2);
+
+    // THEN the conflicting global and type of the second file are reported, but not the generated
+    // ptr- and string-types
+    let reported = diagnostics
+        .iter()
+        .map(|it| (it.get_message(), it.get_location().get_file_name()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        reported,
+        vec![("g: Duplicate symbol.", Some("file2.st")), ("MyType: Duplicate symbol.", Some("file2.st"))]
+    );
+    // AND the first definitions are kept
+    assert_eq!(global_index.find_global_variable("g").unwrap().get_type_name(), "INT");
+    assert!(global_index.find_effective_type_by_name("MyType").unwrap().is_struct());
+    assert_eq!(global_index.get_globals().values().filter(|it| it.get_name() == "g").count(), 1);
 }
 
 #[test]
 fn duplicate_with_generic() {
-    // a version of the test-util function that does not import the built-in and std-types
-    // (or they will cause a duplication issue)
-    fn do_index(src: &str, id_provider: IdProvider, file_name: &str) -> (Index, CompilationUnit) {
-        let mut index = Index::default();
-        let (mut unit, ..) = parser::parse(
-            lexer::lex_with_ids(src, id_provider.clone(), SourceLocationFactory::internal(src)),
-            LinkageType::Internal,
-            file_name,
-        );
-        pre_process(&mut unit, id_provider);
-        index.import(visitor::visit(&unit));
-        (index, unit)
-    }
-
     // GIVEN a generic function defined in its own file
     let ids = IdProvider::default();
-    let (index1, unit1) = do_index(
+    let (unit1, index1) = index_without_builtins(
         r#"
             {external}
             FUNCTION foo <T: ANY_INT> : DATE
@@ -427,7 +405,7 @@ fn duplicate_with_generic() {
 
     // AND another file that calls that generic function and implicitely
     // create type-specific foo-implementations
-    let (index2, unit2) = do_index(
+    let (unit2, index2) = index_without_builtins(
         r#"
         PROGRAM prg1
             foo(INT#1, SINT#2, SINT#3);
@@ -442,7 +420,7 @@ fn duplicate_with_generic() {
 
     // AND another file that calls that generic function and implicitely
     // create type-specific foo-implementations
-    let (index3, unit3) = do_index(
+    let (unit3, index3) = index_without_builtins(
         r#"
         PROGRAM prg2
             foo(INT#1, SINT#2, SINT#3);
@@ -484,6 +462,107 @@ fn duplicate_with_generic() {
     assert_eq!(diagnostics, vec![]);
 }
 
+#[test]
+fn generic_instances_requested_by_several_files_are_imported_once() {
+    // GIVEN a generic function defined in its own file
+    let ids = IdProvider::default();
+    let generic = r#"
+        {external}
+        FUNCTION CONCAT_DATE <T: ANY_INT> : DATE
+        VAR_INPUT
+            year : T;
+            month : T;
+            day : T;
+        END_VAR
+        END_FUNCTION
+    "#;
+    // AND two files that both request the INT-instance
+    let caller = |name: &str| {
+        format!(
+            "
+        PROGRAM {name}
+            CONCAT_DATE(INT#2023, INT#1, INT#1);
+            CONCAT_DATE(INT#2024, INT#2, INT#2);
+        END_PROGRAM
+        "
+        )
+    };
+    let units = vec![
+        index_without_builtins(generic, ids.clone(), "file1.st"),
+        index_without_builtins(&caller("prg1"), ids.clone(), "file2.st"),
+        index_without_builtins(&caller("prg2"), ids.clone(), "file3.st"),
+    ];
+
+    // WHEN the indexes are combined
+    let mut global_index = Index::default();
+    for data_type in typesystem::get_builtin_types() {
+        global_index.register_type(data_type);
+    }
+    let units = units
+        .into_iter()
+        .map(|(unit, index)| {
+            global_index.import(index);
+            unit
+        })
+        .collect::<Vec<_>>();
+
+    // AND every file's resolver registers the instances it needs
+    let new_indexes = units
+        .iter()
+        .map(|unit| TypeAnnotator::visit_unit(&global_index, unit, ids.clone()).0.new_index)
+        .collect::<Vec<_>>();
+    for new_index in new_indexes {
+        global_index.import(new_index);
+    }
+
+    // THEN there is exactly one CONCAT_DATE__INT pou, implementation and pou-type
+    let count =
+        |names: Vec<&str>| names.into_iter().filter(|it| it.eq_ignore_ascii_case("CONCAT_DATE__INT")).count();
+    assert_eq!(count(global_index.get_pous().values().map(|it| it.get_name()).collect()), 1);
+    assert_eq!(count(global_index.get_implementations().values().map(|it| it.get_call_name()).collect()), 1);
+    assert_eq!(count(global_index.get_pou_types().values().map(|it| it.get_name()).collect()), 1);
+}
+
+#[test]
+fn extern_names_colliding_with_other_symbols_are_reported() {
+    // GIVEN a function renamed to the name of another function and one renamed to a global
+    // WHEN parse_and_validate is done
+    let diagnostics = parse_and_validate(
+        r#"
+        {extern_name := 'bar'}
+        FUNCTION foo : INT END_FUNCTION
+
+        FUNCTION bar : INT END_FUNCTION
+
+        {extern_name := 'g'}
+        FUNCTION baz : INT END_FUNCTION
+
+        VAR_GLOBAL
+            g : INT;
+        END_VAR
+    "#,
+    );
+    // THEN there should be a conflict reported for both extern names
+    assert_validation_snapshot!(&diagnostics);
+}
+
+#[test]
+fn unique_extern_names_are_no_issue() {
+    // GIVEN two functions with distinct extern names
+    // WHEN parse_and_validate is done
+    let diagnostics = parse_and_validate(
+        r#"
+        {extern_name := 'c_foo'}
+        FUNCTION foo : INT END_FUNCTION
+
+        {extern_name := 'c_bar'}
+        FUNCTION bar : INT END_FUNCTION
+    "#,
+    );
+    // THEN there should be no duplication diagnostics
+    assert_eq!(diagnostics, vec![]);
+}
+
 // #[test]
 // fn duplicate_with_generic_ir() {
 //     // GIVEN several files with calls to a generic function
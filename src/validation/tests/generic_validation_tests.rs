@@ -1,3 +1,5 @@
+use plc_diagnostics::{diagnostics::Diagnostic, errno::ErrNo};
+
 use crate::{assert_validation_snapshot, test_utils::tests::parse_and_validate};
 
 #[test]
@@ -1598,3 +1600,54 @@ fn any_date_multiple_parameters() {
     let diagnostics = parse_and_validate(src);
     assert_validation_snapshot!(&diagnostics);
 }
+
+#[test]
+fn generic_arguments_without_a_common_type_are_reported() {
+    let src = r"
+    FUNCTION MAX<T : ANY_ELEMENTARY> : T VAR_INPUT args : {sized} T...; END_VAR END_FUNCTION
+
+    FUNCTION foo : INT
+        MAX(1, 2.0);
+        MAX('a', 1);
+    END_FUNCTION
+    ";
+
+    let diagnostics = parse_and_validate(src);
+    let messages = diagnostics
+        .iter()
+        .filter(|it| it.get_type() == &ErrNo::call__no_matching_overload)
+        .map(Diagnostic::get_message)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec!["No matching overload of 'MAX', arguments of type STRING and DINT have no common type."]
+    );
+}
+
+// ##########    ANY_DERIVED    ##########
+
+#[test]
+fn any_derived_allows_user_defined_types() {
+    let src = r"
+        TYPE Point : STRUCT x, y : INT; END_STRUCT END_TYPE
+        TYPE Color : (red, green, blue); END_TYPE
+        FUNCTION test<T : ANY_DERIVED> : INT VAR_INPUT x : T; END_VAR END_FUNCTION
+        FUNCTION func1  : INT VAR x : Point; END_VAR test(x); END_FUNCTION
+        FUNCTION func2  : INT VAR x : Color; END_VAR test(x); END_FUNCTION
+    ";
+
+    let diagnostics = parse_and_validate(src);
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn any_derived_does_not_allow_elementary_types() {
+    let src = r"
+        FUNCTION test<T : ANY_DERIVED> : INT VAR_INPUT x : T; END_VAR END_FUNCTION
+        FUNCTION func1  : INT VAR x : INT; END_VAR test(x); END_FUNCTION
+        FUNCTION func2  : INT VAR x : STRING; END_VAR test(x); END_FUNCTION
+    ";
+
+    let diagnostics = parse_and_validate(src);
+    assert_validation_snapshot!(&diagnostics);
+}
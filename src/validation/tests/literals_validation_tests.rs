@@ -168,3 +168,49 @@ fn char_cast_validate() {
 
     assert_validation_snapshot!(&diagnostics);
 }
+
+#[test]
+fn sized_string_literal_casts_are_validated() {
+    let diagnostics = parse_and_validate(
+        r#"
+        PROGRAM prg
+        VAR CONSTANT
+            max_len : DINT := 4;
+        END_VAR
+        VAR
+            s : STRING;
+            w : WSTRING;
+            n : DINT;
+        END_VAR
+            s := STRING#[3]'abc';
+            s := STRING#[3]'abcd';
+            s := STRING#[max_len]'abcde';
+            w := WSTRING#[2]"ab";
+            w := WSTRING#[2]"abc";
+            STRING#[n]'abc';
+        END_PROGRAM
+       "#,
+    );
+
+    assert_validation_snapshot!(&diagnostics);
+}
+
+#[test]
+fn unsized_string_literal_casts_are_not_truncated() {
+    // a literal longer than the default length of 80 characters
+    let literal = "a".repeat(100);
+    let diagnostics = parse_and_validate(&format!(
+        r#"
+        PROGRAM prg
+        VAR
+            s : STRING[100];
+            w : WSTRING[100];
+        END_VAR
+            s := STRING#'{literal}';
+            w := WSTRING#"{literal}";
+        END_PROGRAM
+       "#
+    ));
+
+    assert_eq!(diagnostics, vec![]);
+}
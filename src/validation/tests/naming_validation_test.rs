@@ -49,3 +49,29 @@ assert_with_type_name!(WSTRING);
 assert_with_type_name!(CHAR);
 assert_with_type_name!(WCHAR);
 assert_with_type_name!(VOID);
+
+#[test]
+fn type_named_like_a_keyword_is_reported() {
+    let result = parse_and_validate(
+        "
+        TYPE BOOL : INT; END_TYPE
+        TYPE IF : INT; END_TYPE
+        ",
+    );
+    assert_validation_snapshot!(&result)
+}
+
+#[test]
+fn variable_named_like_a_keyword_is_reported() {
+    let result = parse_and_validate(
+        "
+        PROGRAM prg
+        VAR
+            END_IF : INT;
+            x, FUNCTION : DINT;
+        END_VAR
+        END_PROGRAM
+        ",
+    );
+    assert_validation_snapshot!(&result)
+}
@@ -696,3 +696,91 @@ mod mixed_structs_and_functionblocks {
         assert_validation_snapshot!(&diagnostics);
     }
 }
+
+mod functions {
+    use crate::{
+        assert_validation_snapshot,
+        test_utils::tests::{parse_and_validate, parse_and_validate_with_options},
+        validation::ValidationOptions,
+    };
+
+    const FORBID_RECURSION: ValidationOptions =
+        ValidationOptions { forbid_recursive_calls: true, warn_mixed_signedness: false };
+
+    #[test]
+    fn recursive_calls_are_allowed_by_default() {
+        let diagnostics = parse_and_validate(
+            "
+            FUNCTION A : DINT
+                A := A();
+            END_FUNCTION
+            ",
+        );
+
+        assert_eq!(diagnostics.len(), 0);
+    }
+
+    #[test]
+    fn self_recursion() {
+        let diagnostics = parse_and_validate_with_options(
+            "
+            FUNCTION A : DINT
+                A := A();
+            END_FUNCTION
+            ",
+            FORBID_RECURSION,
+        );
+
+        assert_validation_snapshot!(&diagnostics);
+    }
+
+    #[test]
+    fn mutual_recursion() {
+        let diagnostics = parse_and_validate_with_options(
+            "
+            FUNCTION A : DINT
+                A := B();
+            END_FUNCTION
+
+            FUNCTION B : DINT
+                B := C() + A();
+            END_FUNCTION
+
+            FUNCTION C : DINT
+                C := 1;
+            END_FUNCTION
+            ",
+            FORBID_RECURSION,
+        );
+
+        assert_validation_snapshot!(&diagnostics);
+    }
+
+    #[test]
+    fn recursion_in_function_blocks_and_programs_is_allowed() {
+        let diagnostics = parse_and_validate_with_options(
+            "
+            FUNCTION_BLOCK fb
+                VAR
+                    inst : fb2;
+                END_VAR
+                inst();
+            END_FUNCTION_BLOCK
+
+            FUNCTION_BLOCK fb2
+                VAR
+                    inst : REF_TO fb;
+                END_VAR
+                inst^();
+            END_FUNCTION_BLOCK
+
+            PROGRAM prg
+                prg();
+            END_PROGRAM
+            ",
+            FORBID_RECURSION,
+        );
+
+        assert_eq!(diagnostics.len(), 0);
+    }
+}
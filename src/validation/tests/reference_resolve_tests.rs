@@ -170,6 +170,46 @@ fn resolve_function_block_calls_in_structs_and_field_access() {
     assert_validation_snapshot!(&diagnostics);
 }
 
+/// tests that only the first unresolvable member of a nested function block chain is reported
+#[test]
+fn unknown_members_of_nested_function_blocks_are_reported_at_the_failing_level() {
+    let diagnostics = parse_and_validate(
+        "
+            FUNCTION_BLOCK inner
+                VAR_OUTPUT
+                    value : INT;
+                END_VAR
+            END_FUNCTION_BLOCK
+
+            FUNCTION_BLOCK middle
+                VAR_OUTPUT
+                    fb2 : inner;
+                END_VAR
+            END_FUNCTION_BLOCK
+
+            FUNCTION_BLOCK outer
+                VAR_OUTPUT
+                    fb1 : middle;
+                END_VAR
+            END_FUNCTION_BLOCK
+
+            PROGRAM prg
+                VAR
+                    o : outer;
+                    x : INT;
+                END_VAR
+
+                x := o.fb1.fb2.value;
+                x := o.fb1.fb3.value;
+                x := o.fb1.fb2.missing;
+                x := p.fb1.fb2.value;
+            END_PROGRAM
+       ",
+    );
+
+    assert_validation_snapshot!(&diagnostics);
+}
+
 /// tests wheter function's members cannot be access using the function's name as a qualifier
 #[test]
 fn resolve_function_members_via_qualifier() {
@@ -361,3 +401,108 @@ fn array_of_struct_as_member_of_another_struct_and_variable_declaration_is_initi
 
     assert!(diagnostics.is_empty());
 }
+
+#[test]
+fn members_of_raw_pointers_require_an_explicit_dereference() {
+    let diagnostics = parse_and_validate(
+        "
+        TYPE Point : STRUCT
+            x : INT;
+        END_STRUCT END_TYPE
+
+        PROGRAM prg
+        VAR
+            p : POINTER TO Point;
+            r : REF_TO Point;
+        END_VAR
+            p.x;
+            r.x;
+            p^.x;
+            r^.x;
+        END_PROGRAM
+       ",
+    );
+
+    let messages = diagnostics.iter().map(|it| it.get_message()).collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "'POINTER TO' is not a standard keyword, use REF_TO instead",
+            "`p` is a pointer and must be dereferenced explicitly, use `p^.x` instead",
+            "`r` is a pointer and must be dereferenced explicitly, use `r^.x` instead",
+        ]
+    );
+}
+
+#[test]
+fn members_of_references_are_dereferenced_implicitly() {
+    let diagnostics = parse_and_validate(
+        "
+        TYPE Point : STRUCT
+            x : INT;
+        END_STRUCT END_TYPE
+
+        FUNCTION move_point : INT
+        VAR_INPUT
+            point : REFERENCE TO Point;
+        END_VAR
+            point.x := point.x + 1;
+            move_point := point.x;
+        END_FUNCTION
+
+        PROGRAM prg
+        VAR
+            pt : Point;
+        END_VAR
+            move_point(pt);
+        END_PROGRAM
+       ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn members_of_local_references_are_dereferenced_implicitly() {
+    let diagnostics = parse_and_validate(
+        "
+        TYPE Point : STRUCT
+            x : INT;
+        END_STRUCT END_TYPE
+
+        PROGRAM prg
+        VAR
+            r : REFERENCE TO Point;
+            p : REF_TO Point;
+            x : INT;
+        END_VAR
+            x := r.x;
+            r.x := x;
+            x := p^.x;
+        END_PROGRAM
+       ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn reference_can_be_used_as_a_name() {
+    let diagnostics = parse_and_validate(
+        "
+        TYPE Point : STRUCT
+            reference : INT;
+        END_STRUCT END_TYPE
+
+        PROGRAM prg
+        VAR
+            reference : INT;
+            r : REFERENCE TO Point;
+        END_VAR
+            reference := r.reference;
+        END_PROGRAM
+       ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+}
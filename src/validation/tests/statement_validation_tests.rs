@@ -1,8 +1,18 @@
 use insta::assert_snapshot;
+use plc_ast::{
+    ast::{AstFactory, AstStatement},
+    control_statements::AstControlStatement,
+    provider::IdProvider,
+};
 use plc_diagnostics::diagnostics::Diagnostic;
+use plc_source::source_location::SourceLocation;
 
 use crate::assert_validation_snapshot;
-use crate::test_utils::tests::{parse_and_validate, parse_and_validate_buffered};
+use crate::resolver::TypeAnnotator;
+use crate::test_utils::tests::{
+    index_with_ids, parse_and_validate, parse_and_validate_buffered, parse_and_validate_with_options,
+};
+use crate::validation::{ValidationOptions, Validator};
 
 #[test]
 fn assign_pointer_to_too_small_type_result_in_an_error() {
@@ -289,6 +299,30 @@ fn wstring_compare_function_cause_no_error_if_functions_exist() {
     assert_eq!(diagnostics, vec![]);
 }
 
+#[test]
+fn comparing_strings_of_different_encodings_causes_error() {
+    // GIVEN a comparison of a STRING with a WSTRING
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        r#"
+        FUNCTION STRING_EQUAL : BOOL VAR_INPUT a,b : STRING; END_VAR END_FUNCTION
+        FUNCTION STRING_LESS : BOOL VAR_INPUT a,b : STRING; END_VAR END_FUNCTION
+
+        PROGRAM prg
+        VAR
+            s : STRING;
+            ws : WSTRING;
+        END_VAR
+            s < ws;
+            s = 'abc';
+        END_PROGRAM
+      "#,
+    );
+
+    // THEN the comparison is reported, the compare functions expect the same encoding
+    assert_validation_snapshot!(&diagnostics);
+}
+
 #[test]
 fn switch_case() {
     // GIVEN switch case statement
@@ -451,6 +485,80 @@ fn case_condition_used_outside_case_statement() {
     assert_validation_snapshot!(&diagnostics);
 }
 
+#[test]
+fn exit_and_continue_outside_a_loop_are_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        PROGRAM main
+        VAR
+            x : INT;
+        END_VAR
+            EXIT;
+            IF x > 0 THEN
+                CONTINUE;
+            END_IF
+        END_PROGRAM
+        ",
+    );
+
+    assert_validation_snapshot!(&diagnostics);
+}
+
+#[test]
+fn exit_and_continue_inside_a_loop_are_accepted() {
+    let diagnostics = parse_and_validate(
+        "
+        PROGRAM main
+        VAR
+            i, x : INT;
+        END_VAR
+            FOR i := 0 TO 10 DO
+                IF i = 5 THEN
+                    EXIT;
+                END_IF
+            END_FOR
+            WHILE x < 10 DO
+                x := x + 1;
+                CASE x OF
+                    3: CONTINUE;
+                END_CASE
+            END_WHILE
+            REPEAT
+                x := x - 1;
+                IF x = 3 THEN
+                    CONTINUE;
+                END_IF
+                EXIT;
+            UNTIL x < 0
+            END_REPEAT
+        END_PROGRAM
+        ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn exit_and_continue_after_a_loop_are_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        PROGRAM main
+        VAR
+            i : INT;
+        END_VAR
+            FOR i := 0 TO 10 DO
+                EXIT;
+            END_FOR
+            CONTINUE;
+        END_PROGRAM
+        ",
+    );
+
+    // only the CONTINUE behind the loop is reported
+    let messages = diagnostics.iter().map(|it| it.get_message()).collect::<Vec<_>>();
+    assert_eq!(messages, vec!["CONTINUE is only allowed inside a FOR, WHILE or REPEAT loop"]);
+}
+
 #[test]
 fn subrange_compare_function_causes_no_error() {
     // GIVEN comparison of subranges
@@ -534,6 +642,31 @@ fn aliased_int_compare_function_causes_no_error() {
     assert_eq!(diagnostics, vec![]);
 }
 
+#[test]
+fn function_inputs_without_default_values_must_be_passed() {
+    // GIVEN
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION foo : DINT
+        VAR_INPUT
+            required : DINT;
+            optional : DINT := 5;
+        END_VAR
+        END_FUNCTION
+
+        PROGRAM main
+            foo(1);
+            foo(1, 2);
+            foo(required := 1);
+            foo(optional := 1);
+            foo();
+        END_PROGRAM
+        ",
+    );
+    // THEN
+    assert_validation_snapshot!(&diagnostics);
+}
+
 #[test]
 fn program_missing_inout_assignment() {
     // GIVEN
@@ -982,6 +1115,9 @@ fn program_implicit_downcast() {
             out_var         : DINT;
         END_VAR
         END_PROGRAM
+
+        FUNCTION_BLOCK fb_t
+        END_FUNCTION_BLOCK
     "#,
     );
 
@@ -1338,3 +1474,570 @@ fn invalid_cast_statement_causes_error() {
 
     assert_validation_snapshot!(diagnostics);
 }
+
+#[test]
+fn this_outside_of_function_blocks_is_reported() {
+    // GIVEN THIS used in a function block, a function and a program
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION_BLOCK fb
+            VAR x : INT; END_VAR
+            THIS^.x := 1;
+        END_FUNCTION_BLOCK
+
+        FUNCTION foo : INT
+            THIS;
+        END_FUNCTION
+
+        PROGRAM prg
+            THIS;
+        END_PROGRAM
+        ",
+    );
+
+    // THEN only the usages in the function and the program are reported
+    assert_validation_snapshot!(&diagnostics);
+}
+
+#[test]
+fn abs_on_unsigned_types_is_reported_as_a_warning() {
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION main : DINT
+        VAR
+            a : UDINT;
+            b : DINT;
+            c : REAL;
+        END_VAR
+            ABS(a);
+            ABS(b);
+            ABS(c);
+        END_FUNCTION
+        ",
+    );
+
+    assert_validation_snapshot!(&diagnostics);
+}
+
+#[test]
+fn literal_shift_amounts_exceeding_the_operand_width_are_reported_as_a_warning() {
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION main : DINT
+        VAR
+            a : BYTE;
+            b : WORD;
+            n : UDINT;
+        END_VAR
+            SHL(a, 7);
+            SHR(a, 8);
+            ROL(b, 16);
+            ROR(b, 20);
+            SHL(b, n);
+        END_FUNCTION
+        ",
+    );
+
+    assert_validation_snapshot!(&diagnostics);
+}
+
+#[test]
+fn concat_results_exceeding_the_assigned_string_are_reported_as_a_warning() {
+    let long = "a".repeat(41);
+    let diagnostics = parse_and_validate(&format!(
+        "
+        FUNCTION main : DINT
+        VAR
+            a : STRING[3];
+            b : WSTRING[4];
+            x : STRING[100];
+            y : WSTRING[100];
+        END_VAR
+            x := CONCAT(a, '{long}', '{long}');
+            y := CONCAT(b, \"ä{long}\", \"{long}\");
+            a := CONCAT(a, 'a');
+            b := CONCAT(\"{long}\", b, \"ä\");
+        END_FUNCTION
+        "
+    ));
+
+    // the result of CONCAT is as long as all of its parameters together
+    let messages = diagnostics.iter().map(|it| it.get_message()).collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "The result of CONCAT exceeds the length of `STRING[3]` and will be truncated to 3 characters",
+            "The result of CONCAT exceeds the length of `WSTRING[4]` and will be truncated to 4 characters",
+        ]
+    );
+}
+
+#[test]
+fn characters_mixed_with_strings_of_another_encoding_are_reported() {
+    // GIVEN characters concatenated with and compared to strings
+    // WHEN it is validated
+    let diagnostics = parse_and_validate(
+        r#"
+        FUNCTION STRING_EQUAL : BOOL VAR_INPUT a,b : STRING; END_VAR END_FUNCTION
+        FUNCTION WSTRING_EQUAL : BOOL VAR_INPUT a,b : WSTRING; END_VAR END_FUNCTION
+
+        FUNCTION main : DINT
+        VAR
+            c : CHAR;
+            wc : WCHAR;
+            s : STRING;
+        END_VAR
+            s := CONCAT(c, 'bc');
+            s := CONCAT(wc, 'bc');
+            c = s;
+            wc = s;
+        END_FUNCTION
+      "#,
+    );
+
+    // THEN only the WCHAR is reported, it cannot be promoted to a STRING
+    assert_validation_snapshot!(&diagnostics);
+}
+
+#[test]
+fn type_name_of_a_generic_parameter_is_an_error() {
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION foo<T : ANY_NUM> : STRING
+        VAR_INPUT
+            x : T;
+        END_VAR
+            foo := TYPE_NAME(x);
+        END_FUNCTION
+
+        FUNCTION main : DINT
+        VAR
+            arr : ARRAY[0..4] OF INT;
+            s : STRING;
+        END_VAR
+            s := TYPE_NAME(arr);
+        END_FUNCTION
+        ",
+    );
+
+    assert_validation_snapshot!(&diagnostics);
+}
+
+#[test]
+fn invalid_date_and_time_arithmetic_is_reported_as_a_warning() {
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION main : DINT
+        VAR
+            d : DATE;
+            dt : DT;
+            tod : TOD;
+            t : TIME;
+        END_VAR
+            // valid
+            t := dt - dt;
+            d := d + t;
+            tod := t + tod;
+            t := t - t;
+            // invalid
+            d := d + d;
+            dt := dt - d;
+            t := t - tod;
+        END_FUNCTION
+        ",
+    );
+
+    assert_validation_snapshot!(&diagnostics);
+}
+
+#[test]
+fn comparison_of_signed_and_unsigned_is_reported_when_enabled() {
+    let src = "
+        FUNCTION main : BOOL
+        VAR
+            sint_var : SINT;
+            uint_var : UINT;
+        END_VAR
+            main := sint_var < uint_var;
+            main := uint_var >= sint_var;
+            main := sint_var < SINT#uint_var;
+            main := sint_var < 5;
+        END_FUNCTION
+        ";
+
+    // mixed signedness is only reported if enabled
+    assert!(parse_and_validate(src).is_empty());
+
+    // the explicit cast and the comparison with a literal are not reported
+    let options = ValidationOptions { warn_mixed_signedness: true, ..ValidationOptions::default() };
+    let diagnostics = parse_and_validate_with_options(src, options);
+    assert_validation_snapshot!(&diagnostics);
+}
+
+#[test]
+fn en_and_eno_are_implicit_parameters_of_function_blocks() {
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION_BLOCK fb
+        VAR_INPUT
+            x : INT;
+        END_VAR
+        END_FUNCTION_BLOCK
+
+        FUNCTION foo : INT
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            f : fb;
+            ok : BOOL;
+        END_VAR
+            f(EN := TRUE, x := 1, ENO => ok);
+            f(en := ok, eno => ok);
+            foo(EN := TRUE);
+        END_PROGRAM
+        ",
+    );
+
+    // THEN only EN on a function is unresolved
+    assert_validation_snapshot!(&diagnostics);
+}
+
+#[test]
+fn division_by_a_literal_zero_is_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        VAR_GLOBAL CONSTANT
+            ZERO : DINT := 0;
+        END_VAR
+
+        FUNCTION main : DINT
+        VAR
+            x, y : DINT;
+        END_VAR
+        VAR CONSTANT
+            local_zero : DINT := 0;
+        END_VAR
+            x := y / 0;
+            x := y MOD 0;
+            x := y / (2 - 2);
+            x := y / ZERO;
+            x := y MOD local_zero;
+        END_FUNCTION
+        ",
+    );
+
+    assert_validation_snapshot!(&diagnostics);
+}
+
+#[test]
+fn division_by_a_non_zero_or_variable_divisor_is_allowed() {
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION main : DINT
+        VAR
+            x, y : DINT;
+            r : REAL;
+        END_VAR
+            x := y / 2;
+            x := y MOD 3;
+            x := y / x;
+            r := r / 0.5;
+        END_FUNCTION
+        ",
+    );
+
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+}
+
+#[test]
+fn branches_with_constant_conditions_are_reported_as_unreachable() {
+    let diagnostics = parse_and_validate(
+        "
+        VAR_GLOBAL CONSTANT
+            debug : BOOL := FALSE;
+        END_VAR
+
+        FUNCTION main : DINT
+        VAR
+            x : DINT;
+        END_VAR
+            IF debug THEN
+                x := 1;
+            ELSIF x > 0 THEN
+                x := 2;
+            END_IF
+            IF TRUE THEN
+                x := 3;
+            ELSIF x > 0 THEN
+                x := 4;
+                x := 5;
+            ELSE
+                x := 6;
+            END_IF
+        END_FUNCTION
+        ",
+    );
+
+    assert_validation_snapshot!(&diagnostics);
+}
+
+#[test]
+fn branches_with_non_constant_conditions_are_not_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION main : DINT
+        VAR
+            x : DINT;
+            flag : BOOL := FALSE;
+        END_VAR
+            IF flag THEN
+                x := 1;
+            ELSIF x > 0 THEN
+                x := 2;
+            ELSE
+                x := 3;
+            END_IF
+        END_FUNCTION
+        ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn branches_with_constant_conditions_containing_a_label_are_not_reported() {
+    // GIVEN a constant FALSE branch containing a label (only CFC bodies can declare labels)
+    let mut ids = IdProvider::default();
+    let (mut unit, index) = index_with_ids(
+        "
+        FUNCTION main : DINT
+        VAR
+            x : DINT;
+        END_VAR
+            IF FALSE THEN
+                x := 1;
+            END_IF
+        END_FUNCTION
+        ",
+        ids.clone(),
+    );
+    let AstStatement::ControlStatement(AstControlStatement::If(if_statement)) =
+        &mut unit.implementations[0].statements[0].stmt
+    else {
+        unreachable!("expected an IF statement")
+    };
+    let label = AstFactory::create_label_statement("lbl".into(), SourceLocation::internal(), ids.next_id());
+    if_statement.blocks[0].body.insert(0, label);
+
+    // WHEN it is validated
+    let (annotations, ..) = TypeAnnotator::visit_unit(&index, &unit, ids);
+    let mut validator = Validator::new();
+    validator.visit_unit(&annotations, &index, &unit);
+
+    // THEN the branch is not reported, since it can still be reached by a JMP to the label
+    assert_eq!(validator.diagnostics(), vec![]);
+}
+
+#[test]
+fn statements_after_return_exit_and_continue_are_reported_as_unreachable() {
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION main : DINT
+        VAR
+            i, x : DINT;
+        END_VAR
+            FOR i := 0 TO 10 DO
+                EXIT;
+                x := 1;
+            END_FOR
+            WHILE x < 10 DO
+                CONTINUE;
+                x := 2;
+                x := 3;
+            END_WHILE
+            RETURN;
+            x := 4;
+            main := x;
+        END_FUNCTION
+        ",
+    );
+
+    assert_validation_snapshot!(&diagnostics);
+}
+
+#[test]
+fn statements_after_a_conditional_return_are_not_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION main : DINT
+        VAR
+            x : DINT;
+        END_VAR
+            IF x > 0 THEN
+                RETURN;
+            ELSE
+                x := 1;
+            END_IF
+            main := x;
+        END_FUNCTION
+        ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn inout_arguments_of_the_parameter_type_are_valid() {
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION foo : DINT
+        VAR_IN_OUT
+            x : DINT;
+            arr : ARRAY[0..2] OF INT;
+        END_VAR
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            x : DINT;
+            arr : ARRAY[0..2] OF INT;
+        END_VAR
+            foo(x, arr);
+            foo(x := x, arr := arr);
+        END_PROGRAM
+        ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn inout_arguments_of_an_alias_of_the_parameter_type_are_valid() {
+    let diagnostics = parse_and_validate(
+        "
+        TYPE MyDint : DINT; END_TYPE
+
+        FUNCTION foo : DINT
+        VAR_IN_OUT
+            x : MyDint;
+            y : DINT;
+        END_VAR
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            x : DINT;
+            y : MyDint;
+        END_VAR
+            foo(x, y);
+        END_PROGRAM
+        ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn inout_arguments_of_a_different_type_are_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION foo : DINT
+        VAR_IN_OUT
+            x : DINT;
+        END_VAR
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            x : INT;
+        END_VAR
+            foo(x);
+        END_PROGRAM
+        ",
+    );
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].get_message(),
+        "Invalid argument for VAR_IN_OUT parameter x: expected 'DINT' but got 'INT'"
+    );
+}
+
+#[test]
+fn inout_arguments_of_an_incompatible_type_are_reported_once() {
+    let diagnostics = parse_and_validate(
+        "
+        FUNCTION foo : DINT
+        VAR_IN_OUT
+            s : STRING;
+        END_VAR
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            ws : WSTRING;
+        END_VAR
+            foo(ws);
+            foo(s := ws);
+        END_PROGRAM
+        ",
+    );
+
+    let messages = diagnostics.iter().map(Diagnostic::get_message).collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "Invalid argument for VAR_IN_OUT parameter s: expected 'STRING' but got 'WSTRING'",
+            "Invalid argument for VAR_IN_OUT parameter s: expected 'STRING' but got 'WSTRING'",
+        ]
+    );
+}
+
+#[test]
+fn dereferencing_a_pointer_assigned_null_is_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        PROGRAM main
+        VAR
+            p : REF_TO DINT;
+            x : DINT;
+        END_VAR
+            p := NULL;
+            x := p^;
+            p^ := 3;
+        END_PROGRAM
+        ",
+    );
+
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].get_message(), "Dereferencing `p` which was assigned NULL");
+    assert_eq!(diagnostics[1].get_message(), "Dereferencing `p` which was assigned NULL");
+}
+
+#[test]
+fn dereferencing_a_pointer_reassigned_after_null_is_not_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        PROGRAM main
+        VAR
+            p : REF_TO DINT;
+            q : REF_TO DINT;
+            x : DINT;
+        END_VAR
+            p := NULL;
+            p := REF(x);
+            x := p^;
+
+            q := NULL;
+            IF x > 0 THEN
+                q := REF(x);
+            END_IF
+            x := q^;
+        END_PROGRAM
+        ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+}
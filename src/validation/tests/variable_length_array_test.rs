@@ -349,3 +349,94 @@ mod builtins {
         assert_validation_snapshot!(diagnostics);
     }
 }
+
+mod slicing {
+    use crate::test_utils::tests::parse_and_validate;
+
+    #[test]
+    fn slices_passed_to_a_vla_are_valid() {
+        let diagnostics = parse_and_validate(
+            "
+            FUNCTION fn : DINT
+                VAR_INPUT {ref}
+                    vla : ARRAY[*] OF DINT;
+                END_VAR
+            END_FUNCTION
+
+            FUNCTION main : DINT
+                VAR
+                    arr : ARRAY[0..10] OF DINT;
+                    i : DINT;
+                END_VAR
+
+                fn(arr[2..5]);
+                fn(arr[0..10]);
+                fn(arr[i..10]);
+            END_FUNCTION
+            ",
+        );
+
+        assert!(diagnostics.is_empty(), "{diagnostics:#?}");
+    }
+
+    #[test]
+    fn slices_exceeding_the_array_bounds_are_reported() {
+        let diagnostics = parse_and_validate(
+            "
+            FUNCTION fn : DINT
+                VAR_INPUT {ref}
+                    vla : ARRAY[*] OF DINT;
+                END_VAR
+            END_FUNCTION
+
+            FUNCTION main : DINT
+                VAR
+                    arr : ARRAY[1..10] OF DINT;
+                END_VAR
+
+                fn(arr[0..5]);
+                fn(arr[5..11]);
+                fn(arr[5..2]);
+            END_FUNCTION
+            ",
+        );
+
+        let messages = diagnostics.iter().map(|it| it.get_message()).collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "Array access must be in the range 1..10",
+                "Array access must be in the range 1..10",
+                "Array slice must not end before it starts, found 5..2",
+            ]
+        );
+    }
+
+    #[test]
+    fn slices_not_passed_to_a_vla_are_reported() {
+        let diagnostics = parse_and_validate(
+            "
+            FUNCTION main : DINT
+                VAR
+                    arr : ARRAY[0..10] OF DINT;
+                    other : ARRAY[0..10] OF DINT;
+                    matrix : ARRAY[0..1, 0..1] OF DINT;
+                END_VAR
+
+                other := arr[2..5];
+                matrix[0..1];
+            END_FUNCTION
+            ",
+        );
+
+        let messages = diagnostics.iter().map(|it| it.get_message()).collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "Array slices can only be passed to ARRAY[*] parameters",
+                "Expected array access with 2 dimensions, found 1",
+                "Array slices can only be passed to ARRAY[*] parameters",
+            ]
+        );
+    }
+}
@@ -360,3 +360,75 @@ mod overflows {
         assert_validation_snapshot!(diagnostics);
     }
 }
+
+#[test]
+fn bitfield_members_must_be_bool() {
+    let diagnostics = parse_and_validate(
+        "
+        TYPE Flags : BOOL; END_TYPE
+
+        TYPE StatusWord :
+            {bitfield} STRUCT
+                ready : BOOL;
+                alias : Flags;
+                count : INT;
+            END_STRUCT
+        END_TYPE
+        ",
+    );
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].get_message(), "Bitfield member 'count' must be of type BOOL but is 'INT'");
+}
+
+#[test]
+fn types_declared_below_their_use_are_resolved() {
+    let diagnostics = parse_and_validate(
+        "
+        TYPE Outer : STRUCT
+            inner : Inner;
+            items : ARRAY[0..1] OF Inner;
+        END_STRUCT END_TYPE
+
+        PROGRAM prg
+        VAR
+            outer : Outer;
+            fb : LaterFb;
+        END_VAR
+        END_PROGRAM
+
+        TYPE Inner : STRUCT
+            x : DINT;
+        END_STRUCT END_TYPE
+
+        FUNCTION_BLOCK LaterFb
+        END_FUNCTION_BLOCK
+        ",
+    );
+
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+}
+
+#[test]
+fn undefined_types_are_reported() {
+    let diagnostics = parse_and_validate(
+        "
+        TYPE Outer : STRUCT
+            inner : Missing;
+            items : ARRAY[0..1] OF AlsoMissing;
+        END_STRUCT END_TYPE
+
+        PROGRAM prg
+        VAR
+            x : NotDeclared;
+        END_VAR
+        END_PROGRAM
+        ",
+    );
+
+    let messages = diagnostics.iter().map(|it| it.get_message()).collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec!["Unknown type: NotDeclared", "Unknown type: Missing", "Unknown type: AlsoMissing"]
+    );
+}
@@ -1,11 +1,14 @@
-use plc_ast::ast::{AstNode, AstStatement, DataType, DataTypeDeclaration, PouType, UserTypeDeclaration};
+use plc_ast::ast::{
+    AstNode, AstStatement, DataType, DataTypeDeclaration, PouType, UserTypeDeclaration, Variable,
+};
 use plc_diagnostics::diagnostics::Diagnostic;
 use plc_source::source_location::SourceLocation;
 
 use crate::{
     index::Index,
+    lexer,
     resolver::AnnotationMap,
-    typesystem::{DataTypeInformation, StructSource},
+    typesystem::{self, DataTypeInformation, StructSource},
 };
 
 use super::{variable::visit_variable, ValidationContext, Validator, Validators};
@@ -15,8 +18,17 @@ pub fn visit_data_type_declaration<T: AnnotationMap>(
     declaration: &DataTypeDeclaration,
     context: &ValidationContext<T>,
 ) {
-    if let DataTypeDeclaration::DataTypeDefinition { data_type, location, .. } = declaration {
-        visit_data_type(validator, data_type, location, context);
+    match declaration {
+        DataTypeDeclaration::DataTypeDefinition { data_type, location, .. } => {
+            visit_data_type(validator, data_type, location, context);
+        }
+        DataTypeDeclaration::DataTypeReference { referenced_type, location } => {
+            // validation runs after every unit was indexed, so types declared further down the file or
+            // in another unit are known at this point
+            if context.index.find_effective_type_by_name(referenced_type).is_none() {
+                validator.push_diagnostic(Diagnostic::unknown_type(referenced_type, location.clone()));
+            }
+        }
     }
 }
 
@@ -27,9 +39,14 @@ pub fn visit_data_type<T: AnnotationMap>(
     context: &ValidationContext<T>,
 ) {
     validate_data_type(validator, data_type, location);
+    if let DataType::BitfieldType { variables, .. } = data_type {
+        validate_bitfield_members(validator, variables, context.index);
+    }
 
     match data_type {
-        DataType::StructType { variables, .. } => {
+        DataType::StructType { variables, .. }
+        | DataType::UnionType { variables, .. }
+        | DataType::BitfieldType { variables, .. } => {
             variables.iter().for_each(|v| visit_variable(validator, v, context))
         }
         DataType::ArrayType { referenced_type, .. } => {
@@ -44,7 +61,9 @@ pub fn visit_data_type<T: AnnotationMap>(
 
 fn validate_data_type(validator: &mut Validator, data_type: &DataType, location: &SourceLocation) {
     match data_type {
-        DataType::StructType { variables, .. } => {
+        DataType::StructType { variables, .. }
+        | DataType::UnionType { variables, .. }
+        | DataType::BitfieldType { variables, .. } => {
             if variables.is_empty() {
                 validator.push_diagnostic(Diagnostic::empty_variable_block(location.clone()));
             }
@@ -65,14 +84,42 @@ fn validate_data_type(validator: &mut Validator, data_type: &DataType, location:
     }
 }
 
+/// every member of a bitfield occupies a single bit, so only BOOL members can be packed
+fn validate_bitfield_members(validator: &mut Validator, variables: &[Variable], index: &Index) {
+    for variable in variables {
+        let type_name = variable.data_type_declaration.get_name().unwrap_or_default();
+        let is_bool = index
+            .find_effective_type_by_name(type_name)
+            .map_or(false, |it| it.get_name() == typesystem::BOOL_TYPE);
+        if !is_bool {
+            validator.push_diagnostic(Diagnostic::invalid_bitfield_member(
+                &variable.name,
+                type_name,
+                variable.location.clone(),
+            ));
+        }
+    }
+}
+
 pub fn visit_user_type_declaration<T: AnnotationMap>(
     validator: &mut Validator,
     user_type: &UserTypeDeclaration,
     context: &ValidationContext<T>,
 ) {
+    validate_type_name(validator, user_type);
     visit_data_type(validator, &user_type.data_type, &user_type.location, context);
 }
 
+/// user types must not be named like a keyword, builtin type names are reported by the `GlobalValidator`
+fn validate_type_name(validator: &mut Validator, user_type: &UserTypeDeclaration) {
+    let Some(name) = user_type.data_type.get_name() else { return };
+    if lexer::is_reserved_keyword(name)
+        && !typesystem::get_builtin_types().iter().any(|it| it.get_name().eq_ignore_ascii_case(name))
+    {
+        validator.push_diagnostic(Diagnostic::reserved_keyword(name, user_type.location.clone()));
+    }
+}
+
 pub fn data_type_is_fb_or_class_instance(type_name: &str, index: &Index) -> bool {
     let data_type_info = index.find_effective_type_by_name(type_name).map_or_else(
         || index.get_void_type().get_type_information(),
@@ -1,7 +1,14 @@
-use plc_ast::ast::{ArgumentProperty, Pou, PouType, Variable, VariableBlock, VariableBlockType};
+use plc_ast::ast::{
+    ArgumentProperty, DirectAccessType, Pou, PouType, Variable, VariableBlock, VariableBlockType,
+};
 use plc_diagnostics::diagnostics::Diagnostic;
 
-use crate::{index::const_expressions::ConstExpression, resolver::AnnotationMap};
+use crate::{
+    hardware_binding::{get_memory_region_name, get_memory_region_offset},
+    index::{const_expressions::ConstExpression, VariableIndexEntry, VariableType},
+    lexer,
+    resolver::AnnotationMap,
+};
 
 use super::{
     array::{validate_array_assignment, Wrapper},
@@ -96,6 +103,10 @@ fn validate_variable<T: AnnotationMap>(
     variable: &Variable,
     context: &ValidationContext<T>,
 ) {
+    if lexer::is_reserved_keyword(&variable.name) {
+        validator.push_diagnostic(Diagnostic::reserved_keyword(&variable.name, variable.location.clone()));
+    }
+
     if let Some(v_entry) = context
         .qualifier
         .and_then(|qualifier| context.index.find_member(qualifier, variable.name.as_str()))
@@ -150,6 +161,8 @@ fn validate_variable<T: AnnotationMap>(
             }
         }
 
+        validate_hardware_binding(validator, v_entry, context);
+
         // check if we declared a constant fb-instance or class-instance
         if v_entry.is_constant() && data_type_is_fb_or_class_instance(v_entry.get_type_name(), context.index)
         {
@@ -159,6 +172,41 @@ fn validate_variable<T: AnnotationMap>(
     }
 }
 
+/// Validates that the size of a hardware binding (e.g. `%MW100`) matches the size of the bound variable
+/// and warns about global bindings which cannot be placed in the runtime's memory region (e.g. `%IX1.2`)
+fn validate_hardware_binding<T: AnnotationMap>(
+    validator: &mut Validator,
+    variable: &VariableIndexEntry,
+    context: &ValidationContext<T>,
+) {
+    let Some(binding) = variable.get_hardware_binding() else { return };
+    if binding.access == DirectAccessType::Template {
+        return;
+    }
+
+    let data_type = context.index.get_effective_type_or_void_by_name(variable.get_type_name());
+    let type_size = data_type.get_type_information().get_semantic_size(context.index) as u64;
+    let access_size = binding.access.get_bit_width();
+    if type_size != access_size {
+        validator.push_diagnostic(Diagnostic::incompatible_hardware_binding(
+            &format!("{:?}", binding.access),
+            access_size,
+            data_type.get_name(),
+            type_size,
+            binding.location.clone(),
+        ));
+    }
+
+    if variable.get_variable_type() == VariableType::Global
+        && get_memory_region_offset(binding, context.index).is_none()
+    {
+        validator.push_diagnostic(Diagnostic::hardware_binding_not_placed(
+            get_memory_region_name(&binding.direction),
+            binding.location.clone(),
+        ));
+    }
+}
+
 #[cfg(test)]
 mod variable_validator_tests {
     use insta::assert_snapshot;
@@ -198,6 +246,23 @@ mod variable_validator_tests {
         assert_snapshot!(diagnostics);
     }
 
+    #[test]
+    fn validate_hardware_binding_size() {
+        let diagnostics = parse_and_validate_buffered(
+            "
+        VAR_GLOBAL
+            a AT %MW100 : WORD;
+            b AT %MW102 : DINT; // error
+            c AT %IX1.2 : BOOL;
+            d AT %QB4 : INT; // error
+            e AT %I* : DWORD;
+            f AT %QW1.2 : WORD;
+        END_VAR
+        ",
+        );
+        assert_snapshot!(diagnostics);
+    }
+
     #[test]
     fn validate_enum_variant_initializer() {
         let diagnostics = parse_and_validate_buffered(
@@ -680,3 +680,40 @@ fn struct_initialization_with_array_initializer_using_multiplied_statement() {
     assert_eq!(maintype.arr, [111; 64]);
     assert_eq!(maintype.idx, 222);
 }
+
+#[test]
+fn array_initialization_with_repetitions() {
+    #[repr(C)]
+    struct MainType {
+        zeros: [i16; 10],
+        mixed: [i16; 5],
+        partial: [i16; 5],
+    }
+
+    let source = "
+        PROGRAM target
+            VAR
+                zeros : ARRAY[1..10] OF INT := [10(0)];
+                mixed : ARRAY[1..5] OF INT := [3(1), 2(2)];
+                partial : ARRAY[1..5] OF INT := [2(3), 4];
+            END_VAR
+        END_PROGRAM
+        PROGRAM main
+            VAR
+                zeros : ARRAY[1..10] OF INT;
+                mixed : ARRAY[1..5] OF INT;
+                partial : ARRAY[1..5] OF INT;
+            END_VAR
+            zeros := target.zeros;
+            mixed := target.mixed;
+            partial := target.partial;
+        END_PROGRAM
+        "
+    .to_string();
+
+    let mut maintype = MainType { zeros: [9; 10], mixed: [9; 5], partial: [9; 5] };
+    let _: i32 = compile_and_run(source, &mut maintype);
+    assert_eq!(maintype.zeros, [0; 10]);
+    assert_eq!(maintype.mixed, [1, 1, 1, 2, 2]);
+    assert_eq!(maintype.partial, [3, 3, 4, 0, 0]);
+}
@@ -0,0 +1,223 @@
+use crate::*;
+
+#[derive(Default, Debug)]
+#[repr(C)]
+struct MainType {
+    byte: u8,
+    word: u16,
+    dword: u32,
+    lword: u64,
+}
+
+#[test]
+fn shift_left_test() {
+    let src = "
+        PROGRAM main
+        VAR
+           b : BYTE;
+           w : WORD;
+           d : DWORD;
+           l : LWORD;
+        END_VAR
+        b := SHL(BYTE#2#0001_1001,3);
+        w := SHL(WORD#2#0001_1001,11);
+        d := SHL(DWORD#2#0001_1001,27);
+        l := SHL(LWORD#2#0001_1001,59);
+        END_PROGRAM
+        ";
+    let mut maintype = MainType::default();
+    let _res: u32 = compile_and_run(src.to_string(), &mut maintype);
+    assert_eq!(maintype.byte, 0b1100_1000);
+    assert_eq!(maintype.word, 0b1100_1000_0000_0000);
+    assert_eq!(maintype.dword, 0b1100_1000_0000_0000_0000_0000_0000_0000);
+    assert_eq!(
+        maintype.lword,
+        0b1100_1000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000
+    );
+}
+
+#[test]
+fn shift_right_test() {
+    let src = "
+        PROGRAM main
+        VAR
+           b : BYTE;
+           w : WORD;
+           d : DWORD;
+           l : LWORD;
+        END_VAR
+        b := SHR(BYTE#16#11,3);
+        w := SHR(WORD#16#101,3);
+        d := SHR(DWORD#16#1_0001,3);
+        l := SHR(LWORD#16#1_0000_0000_0001,3);
+        END_PROGRAM
+        ";
+    let mut maintype = MainType::default();
+    let _res: u32 = compile_and_run(src.to_string(), &mut maintype);
+    assert_eq!(maintype.byte, 0x2);
+    assert_eq!(maintype.word, 0x20);
+    assert_eq!(maintype.dword, 0x2000);
+    assert_eq!(maintype.lword, 0x2000_0000_0000);
+}
+
+#[test]
+fn rotate_left_test() {
+    let src = "
+        PROGRAM main
+        VAR
+           b : BYTE;
+           w : WORD;
+           d : DWORD;
+           l : LWORD;
+        END_VAR
+        b := ROL(BYTE#16#81,3);
+        w := ROL(WORD#16#8001,3);
+        d := ROL(DWORD#16#8000_0001,3);
+        l := ROL(LWORD#16#8000_0000_0000_0001,3);
+        END_PROGRAM
+        ";
+    let mut maintype = MainType::default();
+    let _res: u32 = compile_and_run(src.to_string(), &mut maintype);
+    assert_eq!(maintype.byte, 0xC);
+    assert_eq!(maintype.word, 0xC);
+    assert_eq!(maintype.dword, 0xC);
+    assert_eq!(maintype.lword, 0xC);
+}
+
+#[test]
+fn rotate_right_test() {
+    let src = "
+        PROGRAM main
+        VAR
+           b : BYTE;
+           w : WORD;
+           d : DWORD;
+           l : LWORD;
+        END_VAR
+        b := ROR(BYTE#16#81,3);
+        w := ROR(WORD#16#8001,3);
+        d := ROR(DWORD#16#8000_0001,3);
+        l := ROR(LWORD#16#8000_0000_0000_0001,3);
+        END_PROGRAM
+        ";
+    let mut maintype = MainType::default();
+    let _res: u32 = compile_and_run(src.to_string(), &mut maintype);
+    assert_eq!(maintype.byte, 0x30);
+    assert_eq!(maintype.word, 0x3000);
+    assert_eq!(maintype.dword, 0x3000_0000);
+    assert_eq!(maintype.lword, 0x3000_0000_0000_0000);
+}
+
+#[test]
+fn shift_left_a_byte() {
+    let src = "
+        PROGRAM main
+        VAR
+           b : BYTE;
+        END_VAR
+        b := SHL(BYTE#1, 3);
+        END_PROGRAM
+        ";
+    let mut maintype = MainType::default();
+    let _res: u32 = compile_and_run(src.to_string(), &mut maintype);
+    assert_eq!(maintype.byte, 8);
+}
+
+#[test]
+fn rotate_a_byte_left_by_one() {
+    let src = "
+        PROGRAM main
+        VAR
+           b : BYTE;
+        END_VAR
+        b := ROL(BYTE#16#81, 1);
+        END_PROGRAM
+        ";
+    let mut maintype = MainType::default();
+    let _res: u32 = compile_and_run(src.to_string(), &mut maintype);
+    assert_eq!(maintype.byte, 0x03);
+}
+
+#[test]
+fn shifting_by_the_width_or_more_results_in_zero() {
+    let src = "
+        PROGRAM main
+        VAR
+           b : BYTE;
+           w : WORD;
+           d : DWORD;
+           l : LWORD;
+        END_VAR
+        VAR_TEMP
+           n : UDINT := 8;
+        END_VAR
+        b := SHL(BYTE#16#FF, n);
+        w := SHR(WORD#16#FFFF, n + 8);
+        d := SHL(DWORD#16#FFFF_FFFF, n * 10);
+        l := SHR(LWORD#16#FFFF_FFFF_FFFF_FFFF, n * 8);
+        END_PROGRAM
+        ";
+    let mut maintype = MainType { byte: 1, word: 1, dword: 1, lword: 1 };
+    let _res: u32 = compile_and_run(src.to_string(), &mut maintype);
+    assert_eq!(maintype.byte, 0);
+    assert_eq!(maintype.word, 0);
+    assert_eq!(maintype.dword, 0);
+    assert_eq!(maintype.lword, 0);
+}
+
+#[test]
+fn rotating_by_more_than_the_width_wraps_around() {
+    let src = "
+        PROGRAM main
+        VAR
+           b : BYTE;
+           w : WORD;
+        END_VAR
+        VAR_TEMP
+           n : UDINT := 9;
+        END_VAR
+        b := ROL(BYTE#16#81, n);
+        w := ROR(WORD#16#8001, n + 8);
+        END_PROGRAM
+        ";
+    let mut maintype = MainType::default();
+    let _res: u32 = compile_and_run(src.to_string(), &mut maintype);
+    assert_eq!(maintype.byte, 0x03);
+    assert_eq!(maintype.word, 0xC000);
+}
+
+#[derive(Default, Debug)]
+#[repr(C)]
+struct BoolType {
+    rol: bool,
+    ror: bool,
+    shl: bool,
+    shr: bool,
+}
+
+#[test]
+fn bools_are_shifted_and_rotated_as_a_single_bit() {
+    let src = "
+        PROGRAM main
+        VAR
+           rol_result : BOOL;
+           ror_result : BOOL;
+           shl_result : BOOL;
+           shr_result : BOOL;
+        END_VAR
+        VAR_TEMP
+           flag : BOOL := TRUE;
+        END_VAR
+        rol_result := ROL(flag, 3);
+        ror_result := ROR(flag, 5);
+        shl_result := SHL(flag, 1);
+        shr_result := SHR(flag, 0);
+        END_PROGRAM
+        ";
+    let mut main_type = BoolType::default();
+    let _res: u32 = compile_and_run(src.to_string(), &mut main_type);
+    assert!(main_type.rol);
+    assert!(main_type.ror);
+    assert!(!main_type.shl);
+    assert!(main_type.shr);
+}
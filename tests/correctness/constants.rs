@@ -159,6 +159,87 @@ fn constant_expressions_used_in_case_statement() {
     assert_eq!((r1, r2, r3, r4, r5), (7, 401, 301, 201, 101));
 }
 
+#[test]
+fn folded_boolean_constants_used_in_conditions() {
+    #[allow(dead_code)]
+    #[derive(PartialEq, Debug, Default)]
+    #[repr(C)]
+    struct MainType {
+        i: i32,
+        b: bool,
+    }
+    // GIVEN some boolean constants that are folded from other constants
+    let src = r#"
+    VAR_GLOBAL CONSTANT
+        DEBUG : BOOL := FALSE;
+        ENABLED : BOOL := BOOL#TRUE;
+        LIMIT : DINT := 10;
+        CHECKED : BOOL := ENABLED AND NOT DEBUG AND (LIMIT > 5 OR DEBUG);
+    END_VAR
+
+    PROGRAM main
+        VAR
+            i : DINT;
+            b : BOOL;
+        END_VAR
+
+        IF DEBUG THEN
+            i := 1;
+        ELSIF CHECKED THEN
+            i := 2;
+        ELSE
+            i := 3;
+        END_IF
+        b := CHECKED XOR ENABLED;
+    END_PROGRAM"#;
+
+    let mut main = MainType { i: 0, b: true };
+
+    // WHEN the code gets executed
+    let _: i32 = compile_and_run(src, &mut main);
+
+    //THEN only the branch of the folded TRUE condition was executed
+    assert_eq!(main, MainType { i: 2, b: false });
+}
+
+#[test]
+fn enum_elements_used_in_case_statement() {
+    #[allow(dead_code)]
+    #[derive(PartialEq, Debug, Default)]
+    #[repr(C)]
+    struct MainType {
+        mode: i32,
+        i: i32,
+    }
+    // GIVEN a case statement whose labels are enum elements
+    let src = r#"
+    TYPE Mode : (Idle, Run := 5, Stop); END_TYPE
+
+    PROGRAM main
+        VAR
+            mode : Mode;
+            i : DINT;
+        END_VAR
+
+        CASE mode OF
+            Mode#Idle:  i := 1;
+            Run:        i := 2;
+            Mode#Stop:  i := 3;
+            ELSE        i := 4;
+        END_CASE
+    END_PROGRAM"#;
+
+    // WHEN the code gets executed for every element and an unknown value
+    let results = [0, 5, 6, 7].map(|mode| {
+        let mut main = MainType { mode, i: 0 };
+        let _: i32 = compile_and_run(src, &mut main);
+        main.i
+    });
+
+    //THEN the matching case was executed
+    assert_eq!(results, [1, 2, 3, 4]);
+}
+
 #[test]
 fn constant_expressions_used_in_array_declaration() {
     #[allow(dead_code)]
@@ -660,6 +660,117 @@ fn repeat_loop_reference() {
     assert_eq!(res, 11111);
 }
 
+#[test]
+fn repeat_loop_with_empty_body_terminates() {
+    let function = r#"
+    FUNCTION main : DINT
+    main := 7;
+    REPEAT
+    UNTIL TRUE
+    END_REPEAT
+    main := main + 1;
+    END_FUNCTION
+    "#;
+
+    let res: i32 = compile_and_run(function.to_string(), &mut crate::MainType::default());
+
+    assert_eq!(res, 8);
+}
+
+#[test]
+fn repeat_loop_runs_once_if_condition_is_true_from_the_start() {
+    let function = r#"
+    FUNCTION main : DINT
+    VAR
+        i : DINT;
+    END_VAR
+    REPEAT
+        i := i + 1;
+    UNTIL TRUE
+    END_REPEAT
+    main := i;
+    END_FUNCTION
+    "#;
+
+    let res: i32 = compile_and_run(function.to_string(), &mut crate::MainType::default());
+
+    assert_eq!(res, 1);
+}
+
+#[test]
+fn repeat_loop_continue_evaluates_the_condition() {
+    let function = r#"
+    FUNCTION main : DINT
+    VAR
+        i : DINT;
+    END_VAR
+    REPEAT
+        i := i + 1;
+        IF i MOD 2 = 0 THEN
+            CONTINUE;
+        END_IF
+        main := main + i;
+    UNTIL i >= 10
+    END_REPEAT
+    END_FUNCTION
+    "#;
+
+    let res: i32 = compile_and_run(function.to_string(), &mut crate::MainType::default());
+
+    //1 + 3 + 5 + 7 + 9
+    assert_eq!(res, 25);
+}
+
+#[test]
+fn repeat_loop_exit_leaves_the_loop() {
+    let function = r#"
+    FUNCTION main : DINT
+    VAR
+        i : DINT;
+    END_VAR
+    REPEAT
+        i := i + 1;
+        IF i = 5 THEN
+            EXIT;
+        END_IF
+    UNTIL FALSE
+    END_REPEAT
+    main := i * 10;
+    END_FUNCTION
+    "#;
+
+    let res: i32 = compile_and_run(function.to_string(), &mut crate::MainType::default());
+
+    assert_eq!(res, 50);
+}
+
+#[test]
+fn repeat_loop_condition_can_be_a_function_call() {
+    let function = r#"
+    FUNCTION is_done : BOOL
+    VAR_INPUT
+        i : DINT;
+    END_VAR
+        is_done := i >= 3;
+    END_FUNCTION
+
+    FUNCTION main : DINT
+    VAR
+        i : DINT;
+    END_VAR
+    REPEAT
+        i := i + 1;
+    UNTIL is_done(i)
+    END_REPEAT
+    main := i;
+    END_FUNCTION
+    "#;
+
+    let res: i32 = compile_and_run(function.to_string(), &mut crate::MainType::default());
+
+    assert_eq!(res, 3);
+}
+
 #[test]
 fn case_statement() {
     #[allow(dead_code)]
@@ -43,6 +43,104 @@ fn using_structs() {
     assert_eq!(10, main_data.my_s.field3);
 }
 
+#[test]
+fn union_members_overlay_each_other() {
+    #[repr(C)]
+    #[derive(Debug, Default)]
+    struct MainType {
+        dw_in: u32,
+        b0: u8,
+        b1: u8,
+        b2: u8,
+        b3: u8,
+        dw_out: u32,
+    }
+
+    let testcode = r#"
+    TYPE FourBytes :
+        STRUCT
+            b0, b1, b2, b3 : BYTE;
+        END_STRUCT
+    END_TYPE
+
+    TYPE Overlay :
+        UNION
+            dw : DWORD;
+            bytes : FourBytes;
+        END_UNION
+    END_TYPE
+
+    PROGRAM main
+    VAR_INPUT
+        dw_in : DWORD;
+    END_VAR
+    VAR_OUTPUT
+        b0, b1, b2, b3 : BYTE;
+        dw_out : DWORD;
+    END_VAR
+    VAR
+        overlay : Overlay;
+    END_VAR
+        overlay.dw := dw_in;
+        b0 := overlay.bytes.b0;
+        b1 := overlay.bytes.b1;
+        b2 := overlay.bytes.b2;
+        b3 := overlay.bytes.b3;
+
+        overlay.bytes.b3 := 16#FF;
+        dw_out := overlay.dw;
+    END_PROGRAM
+    "#;
+
+    let mut main_data = MainType { dw_in: 0x04030201, ..MainType::default() };
+    let _: i32 = compile_and_run(testcode, &mut main_data);
+
+    //the bytes are stored in little endian order
+    assert_eq!([1, 2, 3, 4], [main_data.b0, main_data.b1, main_data.b2, main_data.b3]);
+    assert_eq!(0xFF030201, main_data.dw_out);
+}
+
+#[test]
+fn bitfield_members_are_packed_into_single_bits() {
+    #[repr(C)]
+    #[derive(Debug, Default)]
+    struct MainType {
+        status: u8,
+        bit3: bool,
+        bit4: bool,
+    }
+
+    let testcode = r#"
+    TYPE StatusWord :
+        {bitfield} STRUCT
+            b0, b1, b2, b3, b4, b5, b6, b7 : BOOL;
+        END_STRUCT
+    END_TYPE
+
+    PROGRAM main
+    VAR_OUTPUT
+        status : StatusWord;
+        bit3 : BOOL;
+        bit4 : BOOL;
+    END_VAR
+        status.b0 := TRUE;
+        status.b3 := TRUE;
+        status.b7 := TRUE;
+        status.b0 := FALSE;
+        bit3 := status.b3;
+        bit4 := status.b4;
+    END_PROGRAM
+    "#;
+
+    let mut main_data = MainType::default();
+    let _: i32 = compile_and_run(testcode, &mut main_data);
+
+    //all eight members share a single byte
+    assert_eq!(0b1000_1000, main_data.status);
+    assert!(main_data.bit3);
+    assert!(!main_data.bit4);
+}
+
 #[test]
 fn using_nested_structs() {
     #[repr(C)]
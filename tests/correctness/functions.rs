@@ -690,6 +690,75 @@ fn direct_call_on_function_block_array_access() {
     assert_eq!(interface.y, 20);
 }
 
+#[test]
+fn calls_on_function_block_array_elements_use_the_element_instance() {
+    #[allow(dead_code)]
+    #[derive(Default, Debug, PartialEq)]
+    #[repr(C)]
+    struct CounterType {
+        step: i32,
+        count: i32,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Default)]
+    #[repr(C)]
+    struct MainType {
+        counters: [CounterType; 4],
+        x: i32,
+        y: i32,
+    }
+
+    let function = r#"
+    FUNCTION_BLOCK counter
+    VAR_INPUT
+        step : DINT;
+    END_VAR
+    VAR_OUTPUT
+        count : DINT;
+    END_VAR
+
+        METHOD add : DINT
+        VAR_INPUT
+            value : DINT;
+        END_VAR
+            count := count + value;
+            add := count;
+        END_METHOD
+
+        count := count + step;
+    END_FUNCTION_BLOCK
+
+    PROGRAM main
+    VAR
+        counters : ARRAY[1..4] OF counter;
+        x : DINT;
+        y : DINT;
+    END_VAR
+        counters[2](step := 5);
+        counters[2](step := 5, count => x);
+        counters[3](step := 1);
+        y := counters[2].add(100);
+        counters[4].add(7);
+    END_PROGRAM
+    "#;
+
+    let mut interface = MainType::default();
+    let _: i32 = compile_and_run(function.to_string(), &mut interface);
+    assert_eq!(interface.x, 10);
+    assert_eq!(interface.y, 110);
+    // every call changed the state of its own element only
+    assert_eq!(
+        interface.counters,
+        [
+            CounterType { step: 0, count: 0 },
+            CounterType { step: 5, count: 110 },
+            CounterType { step: 1, count: 1 },
+            CounterType { step: 0, count: 7 },
+        ]
+    );
+}
+
 #[test]
 fn nested_calls_in_call_statement() {
     #[repr(C)]
@@ -816,6 +885,39 @@ fn mux_test() {
     assert_eq!(res, 6)
 }
 
+#[test]
+fn mux_test_literals() {
+    let function = r#"
+        FUNCTION main : DINT
+            main := MUX(2, 10, 20, 30); //Result is 30
+        END_FUNCTION
+        "#;
+
+    let context = CodegenContext::create();
+    let module = compile(&context, function);
+    let res: i32 = module.run_no_param("main");
+    assert_eq!(res, 30)
+}
+
+#[test]
+fn mux_test_mixed_types_results_in_bigger_type() {
+    let function = r#"
+        FUNCTION main : LINT
+        VAR
+            a : SINT := 10;
+            b : INT := 20;
+            c : LINT := 5000000000;
+        END_VAR
+            main := MUX(2, a, b, c); //Result is c as LINT
+        END_FUNCTION
+        "#;
+
+    let context = CodegenContext::create();
+    let module = compile(&context, function);
+    let res: i64 = module.run_no_param("main");
+    assert_eq!(res, 5_000_000_000)
+}
+
 #[test]
 fn mux_test_variables() {
     let function = r#"
@@ -987,6 +1089,38 @@ fn sel_test_true() {
     assert_eq!(res, 5)
 }
 
+#[test]
+fn sel_test_literals() {
+    let function = r#"
+        FUNCTION main : DINT
+            main := SEL(TRUE, 1, 2); //Result is 2
+        END_FUNCTION
+        "#;
+
+    let context = CodegenContext::create();
+    let module = compile(&context, function);
+    let res: i32 = module.run_no_param("main");
+    assert_eq!(res, 2);
+}
+
+#[test]
+fn sel_test_mixed_types_results_in_bigger_type() {
+    let function = r#"
+        FUNCTION main : LINT
+        VAR
+            a : INT := 4;
+            b : LINT := 5000000000;
+        END_VAR
+            main := SEL(TRUE, a, b); //Result is b as LINT
+        END_FUNCTION
+        "#;
+
+    let context = CodegenContext::create();
+    let module = compile(&context, function);
+    let res: i64 = module.run_no_param("main");
+    assert_eq!(res, 5_000_000_000);
+}
+
 #[test]
 fn sel_test_true_vars() {
     let function = r#"
@@ -1243,3 +1377,320 @@ fn sizeof_len() {
 
     assert_eq!(13, res);
 }
+
+#[test]
+fn abs_test() {
+    #[derive(Debug, Default, PartialEq)]
+    #[repr(C)]
+    struct MainType {
+        a: i16,
+        b: i32,
+        c: i64,
+        d: f32,
+        e: f64,
+        f: u8,
+    }
+    let function = r#"
+        PROGRAM main
+        VAR
+            a : INT;
+            b : DINT;
+            c : LINT;
+            d : REAL;
+            e : LREAL;
+            f : USINT;
+        END_VAR
+        VAR_TEMP
+            i : INT := -3;
+        END_VAR
+            a := ABS(i);
+            b := ABS(DINT#7);
+            c := ABS(LINT#-9000000000);
+            d := ABS(REAL#-1.5);
+            e := ABS(LREAL#-2.25);
+            f := ABS(USINT#200);
+        END_PROGRAM
+        "#;
+
+    let mut maintype = MainType::default();
+    let _: i32 = compile_and_run(function.to_string(), &mut maintype);
+
+    let expected = MainType { a: 3, b: 7, c: 9_000_000_000, d: 1.5, e: 2.25, f: 200 };
+    assert_eq!(expected, maintype);
+}
+
+#[test]
+fn round_test() {
+    #[derive(Debug, Default, PartialEq)]
+    #[repr(C)]
+    struct MainType {
+        a: f32,
+        b: f32,
+        c: f64,
+        d: f64,
+    }
+    let function = r#"
+        PROGRAM main
+        VAR
+            a, b : REAL;
+            c, d : LREAL;
+        END_VAR
+            a := ROUND(REAL#2.5);
+            b := ROUND(REAL#-2.5);
+            c := ROUND(LREAL#1.49);
+            d := ROUND(LREAL#-1.51);
+        END_PROGRAM
+        "#;
+
+    let mut maintype = MainType::default();
+    let _: i32 = compile_and_run(function.to_string(), &mut maintype);
+
+    let expected = MainType { a: 3.0, b: -3.0, c: 1.0, d: -2.0 };
+    assert_eq!(expected, maintype);
+}
+
+#[test]
+fn trunc_test() {
+    #[derive(Debug, Default, PartialEq)]
+    #[repr(C)]
+    struct MainType {
+        a: i64,
+        b: i64,
+        c: i64,
+        d: i64,
+    }
+    let function = r#"
+        PROGRAM main
+        VAR
+            a, b, c, d : LINT;
+        END_VAR
+            a := TRUNC(REAL#2.9);
+            b := TRUNC(REAL#-2.9);
+            c := TRUNC(LREAL#12345678901.7);
+            d := TRUNC(LREAL#-0.5);
+        END_PROGRAM
+        "#;
+
+    let mut maintype = MainType::default();
+    let _: i32 = compile_and_run(function.to_string(), &mut maintype);
+
+    let expected = MainType { a: 2, b: -2, c: 12_345_678_901, d: 0 };
+    assert_eq!(expected, maintype);
+}
+
+#[test]
+fn function_block_call_is_skipped_if_en_is_false() {
+    #[derive(Debug, Default, PartialEq)]
+    #[repr(C)]
+    struct MainType {
+        calls: i32,
+        disabled_count: i32,
+        enabled_count: i32,
+        disabled_eno: bool,
+        enabled_eno: bool,
+        default_eno: bool,
+    }
+    let function = r#"
+    FUNCTION_BLOCK counter
+    VAR_OUTPUT
+        count : DINT;
+    END_VAR
+        count := count + 1;
+    END_FUNCTION_BLOCK
+
+    PROGRAM main
+    VAR
+        calls, disabled_count, enabled_count : DINT;
+        disabled_eno, enabled_eno, default_eno : BOOL;
+    END_VAR
+    VAR_TEMP
+        c : counter;
+    END_VAR
+        c(EN := FALSE, ENO => disabled_eno, count => disabled_count);
+        c(EN := TRUE, ENO => enabled_eno, count => enabled_count);
+        c(ENO => default_eno);
+        calls := c.count;
+    END_PROGRAM
+    "#;
+
+    let mut main = MainType { disabled_eno: true, disabled_count: -1, ..MainType::default() };
+    let _: i32 = compile_and_run(function.to_string(), &mut main);
+
+    let expected = MainType {
+        calls: 2,
+        disabled_count: -1,
+        enabled_count: 1,
+        disabled_eno: false,
+        enabled_eno: true,
+        default_eno: true,
+    };
+    assert_eq!(expected, main);
+}
+
+#[test]
+fn omitted_function_inputs_use_their_default_values() {
+    #[derive(Default, Debug, PartialEq)]
+    #[repr(C)]
+    struct MainType {
+        implicit: i16,
+        explicit: i16,
+        passed: i16,
+    }
+    let function = r#"
+    FUNCTION scale : INT
+    VAR_INPUT
+        value : INT;
+        factor : INT := 5;
+        offset : INT := 2;
+    END_VAR
+        scale := value * factor + offset;
+    END_FUNCTION
+
+    PROGRAM main
+    VAR
+        implicit, explicit, passed : INT;
+    END_VAR
+        implicit := scale(3);
+        explicit := scale(offset := 0, value := 3);
+        passed := scale(3, 2, 1);
+    END_PROGRAM
+    "#;
+
+    let mut main = MainType::default();
+    let _: i32 = compile_and_run(function.to_string(), &mut main);
+
+    assert_eq!(MainType { implicit: 17, explicit: 15, passed: 7 }, main);
+}
+
+#[test]
+fn nested_function_block_members_can_be_read_and_written() {
+    #[derive(Default, Debug, PartialEq)]
+    #[repr(C)]
+    struct MainType {
+        value: i16,
+        doubled: i16,
+    }
+    let function = r#"
+    FUNCTION_BLOCK inner
+    VAR_INPUT
+        value : INT;
+    END_VAR
+    VAR_OUTPUT
+        doubled : INT;
+    END_VAR
+        doubled := value * 2;
+    END_FUNCTION_BLOCK
+
+    FUNCTION_BLOCK middle
+    VAR_OUTPUT
+        fb2 : inner;
+    END_VAR
+    END_FUNCTION_BLOCK
+
+    FUNCTION_BLOCK outer
+    VAR_OUTPUT
+        fb1 : middle;
+    END_VAR
+    END_FUNCTION_BLOCK
+
+    PROGRAM main
+    VAR
+        value, doubled : INT;
+    END_VAR
+    VAR_TEMP
+        o : outer;
+    END_VAR
+        o.fb1.fb2.value := 21;
+        o.fb1.fb2();
+        value := o.fb1.fb2.value;
+        doubled := o.fb1.fb2.doubled;
+    END_PROGRAM
+    "#;
+
+    let mut main = MainType::default();
+    let _: i32 = compile_and_run(function.to_string(), &mut main);
+
+    assert_eq!(MainType { value: 21, doubled: 42 }, main);
+}
+
+#[test]
+fn struct_inputs_are_passed_by_value() {
+    #[derive(Default, Debug, PartialEq)]
+    #[repr(C)]
+    struct MainType {
+        x: i16,
+        y: i16,
+        returned: i16,
+    }
+    let function = r#"
+    TYPE Point : STRUCT
+        x, y : INT;
+    END_STRUCT
+    END_TYPE
+
+    FUNCTION move_point : INT
+    VAR_INPUT
+        p : Point;
+    END_VAR
+        p.x := p.x + 100;
+        p.y := 0;
+        move_point := p.x;
+    END_FUNCTION
+
+    PROGRAM main
+    VAR
+        x, y, returned : INT;
+    END_VAR
+    VAR_TEMP
+        p : Point;
+    END_VAR
+        p.x := 1;
+        p.y := 2;
+        returned := move_point(p);
+        x := p.x;
+        y := p.y;
+    END_PROGRAM
+    "#;
+
+    let mut main = MainType::default();
+    let _: i32 = compile_and_run(function.to_string(), &mut main);
+
+    // the function changed its own copy, the caller's struct is unchanged
+    assert_eq!(MainType { x: 1, y: 2, returned: 101 }, main);
+}
+
+#[test]
+fn profile_counters_count_the_calls_of_a_function() {
+    #[derive(Default, Debug, PartialEq)]
+    #[repr(C)]
+    struct MainType {
+        calls: u64,
+    }
+    let function = r#"
+    @EXTERNAL VAR_GLOBAL
+        __profile_foo : ULINT;
+    END_VAR
+
+    FUNCTION foo : INT
+    END_FUNCTION
+
+    PROGRAM main
+    VAR
+        calls : ULINT;
+    END_VAR
+        foo();
+        foo();
+        calls := __profile_foo;
+    END_PROGRAM
+    "#;
+
+    let context = CodegenContext::create();
+    let compile_options = driver::CompileOptions { profile: true, ..Default::default() };
+    let module = driver::runner::compile_with_options(&context, function, compile_options);
+    let mut main = MainType::default();
+
+    let _: i32 = module.run("main", &mut main);
+    assert_eq!(MainType { calls: 2 }, main);
+    let _: i32 = module.run("main", &mut main);
+    assert_eq!(MainType { calls: 4 }, main);
+}
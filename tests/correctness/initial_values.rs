@@ -1402,6 +1402,44 @@ fn initial_value_in_array_of_struct() {
     );
 }
 
+#[test]
+fn nested_initial_value_in_array_of_struct_fills_missing_members() {
+    let function = "
+	TYPE myStruct : STRUCT
+			var1 : DINT;
+			var2 : ARRAY[1..2] OF DINT;
+		END_STRUCT
+	END_TYPE
+
+	PROGRAM main
+	VAR_TEMP
+		arr : ARRAY[1..2] OF myStruct := [(var1 := 1), (var1 := 2, var2 := [3, 4])];
+	END_VAR
+	VAR
+		a, b, c, d, e : DINT;
+	END_VAR
+		a := arr[1].var1;
+		b := arr[1].var2[1];
+		c := arr[1].var2[2];
+		d := arr[2].var2[1];
+		e := arr[2].var2[2];
+	END_PROGRAM
+	";
+
+    #[derive(Default)]
+    struct MainType {
+        a: i32,
+        b: i32,
+        c: i32,
+        d: i32,
+        e: i32,
+    }
+    let mut maintype = MainType::default();
+
+    let _: i32 = compile_and_run(function.to_string(), &mut maintype);
+    assert_eq!([1, 0, 0, 3, 4], [maintype.a, maintype.b, maintype.c, maintype.d, maintype.e]);
+}
+
 #[test]
 fn array_of_struct_as_member_of_another_struct_and_variable_declaration_is_initialized() {
     let function = "
@@ -237,6 +237,57 @@ fn substract_date_basic() {
     assert_eq!(res, date_temp - date_var);
 }
 
+#[test]
+fn substracting_date_and_times_yields_a_duration() {
+    let prog = "
+    FUNCTION main : TIME
+    VAR
+        dt1 : DT := DT#2021-01-01-12:00:00;
+        dt2 : DT := DT#2021-01-02-13:30:15;
+    END_VAR
+        main := dt2 - dt1;
+    END_FUNCTION
+    ";
+
+    let res: i64 = compile_and_run(prog.to_string(), &mut MainType::default());
+    assert_eq!(res, chrono::Duration::seconds(25 * 3600 + 30 * 60 + 15).num_nanoseconds().unwrap());
+}
+
+#[test]
+fn adding_a_duration_to_a_date_and_comparing_dates() {
+    #[allow(dead_code)]
+    #[derive(Default)]
+    #[repr(C)]
+    struct MainType {
+        later: i64,
+        is_later: bool,
+        is_equal: bool,
+    }
+
+    let prog = "
+    PROGRAM main
+    VAR
+        later : DATE;
+        is_later : BOOL;
+        is_equal : BOOL;
+    END_VAR
+    VAR_TEMP
+        start : DATE := D#2021-01-01;
+    END_VAR
+        later := start + T#2d;
+        is_later := later > start;
+        is_equal := later - T#2d = start;
+    END_PROGRAM
+    ";
+
+    let mut main = MainType::default();
+    let _: i32 = compile_and_run(prog.to_string(), &mut main);
+    let expected = chrono::Utc.with_ymd_and_hms(2021, 1, 3, 0, 0, 0).unwrap().timestamp_nanos();
+    assert_eq!(main.later, expected);
+    assert!(main.is_later);
+    assert!(main.is_equal);
+}
+
 #[test]
 fn substract_array_basic() {
     let prog = "
@@ -1222,3 +1222,296 @@ fn assigning_global_strings_in_program_by_passing_literals() {
     assert_eq!("in literal\0".as_bytes(), &main_type.str_in[0..11]);
     assert_eq!("in ref literal\0".as_bytes(), &main_type.str_in_ref[0..15]);
 }
+
+#[test]
+fn concat_joins_two_literals() {
+    let src = r#"
+        PROGRAM main
+            VAR
+                x : STRING;
+            END_VAR
+            x := CONCAT('hello ', 'world');
+        END_PROGRAM
+    "#;
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        x: [u8; 81],
+    }
+    let mut main_type = MainType { x: [0; 81] };
+
+    let _: i32 = compile_and_run(src, &mut main_type);
+    assert_eq!("hello world", CStr::from_bytes_until_nul(&main_type.x).unwrap().to_str().unwrap());
+}
+
+#[test]
+fn concat_joins_string_variables_and_literals() {
+    let src = r#"
+        PROGRAM main
+            VAR
+                a : STRING[10];
+                b : STRING[3];
+                x : STRING;
+            END_VAR
+            a := 'abc';
+            b := 'de';
+            x := CONCAT(a, '-', b, '', a);
+        END_PROGRAM
+    "#;
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        a: [u8; 11],
+        b: [u8; 4],
+        x: [u8; 81],
+    }
+    let mut main_type = MainType { a: [0; 11], b: [0; 4], x: [0; 81] };
+
+    let _: i32 = compile_and_run(src, &mut main_type);
+    assert_eq!("abc-deabc", CStr::from_bytes_until_nul(&main_type.x).unwrap().to_str().unwrap());
+}
+
+#[test]
+fn concat_of_wide_strings() {
+    let src = r#"
+        PROGRAM main
+            VAR
+                a : WSTRING;
+                x : WSTRING;
+            END_VAR
+            a := "hellö ";
+            x := CONCAT(a, "wörld");
+        END_PROGRAM
+    "#;
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        a: [u16; 81],
+        x: [u16; 81],
+    }
+    let mut main_type = MainType { a: [0; 81], x: [0; 81] };
+
+    let _: i32 = compile_and_run(src, &mut main_type);
+    let end = main_type.x.iter().position(|it| *it == 0).unwrap();
+    assert_eq!("hellö wörld", String::from_utf16_lossy(&main_type.x[..end]));
+}
+
+#[test]
+fn concat_results_hold_all_of_their_parameters() {
+    let src = r#"
+        PROGRAM main
+            VAR
+                a : STRING[50];
+                x : STRING[100];
+            END_VAR
+            a := '01234567890123456789012345678901234567890123456789';
+            x := CONCAT(a, a);
+        END_PROGRAM
+    "#;
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        a: [u8; 51],
+        x: [u8; 101],
+    }
+    let mut main_type = MainType { a: [0; 51], x: [0; 101] };
+
+    let _: i32 = compile_and_run(src, &mut main_type);
+    // the result of CONCAT is as long as all of its parameters together
+    let expected = "0123456789".repeat(10);
+    assert_eq!(expected, CStr::from_bytes_until_nul(&main_type.x).unwrap().to_str().unwrap());
+}
+
+#[test]
+fn concat_promotes_characters_to_strings() {
+    let src = r#"
+        PROGRAM main
+            VAR
+                c : CHAR;
+                x : STRING;
+                y : STRING;
+            END_VAR
+            c := 'a';
+            x := CONCAT(c, 'bc');
+            y := CONCAT(c, c);
+        END_PROGRAM
+    "#;
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        c: u8,
+        x: [u8; 81],
+        y: [u8; 81],
+    }
+    let mut main_type = MainType { c: 0, x: [0; 81], y: [0; 81] };
+
+    let _: i32 = compile_and_run(src, &mut main_type);
+    assert_eq!("abc", CStr::from_bytes_until_nul(&main_type.x).unwrap().to_str().unwrap());
+    assert_eq!("aa", CStr::from_bytes_until_nul(&main_type.y).unwrap().to_str().unwrap());
+}
+
+#[test]
+fn type_name_returns_the_display_name_of_a_type() {
+    let src = r#"
+        PROGRAM main
+            VAR
+                myArr : ARRAY[0..4] OF INT;
+                arr_name : STRING;
+                int_name : STRING;
+            END_VAR
+            arr_name := TYPE_NAME(myArr);
+            int_name := TYPE_NAME(myArr[1]);
+        END_PROGRAM
+    "#;
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        my_arr: [i16; 5],
+        arr_name: [u8; 81],
+        int_name: [u8; 81],
+    }
+    let mut main_type = MainType { my_arr: [0; 5], arr_name: [0; 81], int_name: [0; 81] };
+
+    let _: i32 = compile_and_run(src, &mut main_type);
+    assert_eq!(
+        "ARRAY[0..4] OF INT",
+        CStr::from_bytes_until_nul(&main_type.arr_name).unwrap().to_str().unwrap()
+    );
+    assert_eq!("INT", CStr::from_bytes_until_nul(&main_type.int_name).unwrap().to_str().unwrap());
+}
+
+#[test]
+fn indexing_a_string_reads_a_single_character() {
+    let src = r#"
+        PROGRAM main
+            VAR
+                s : STRING := 'abc';
+                ws : WSTRING := "xyz";
+                c : CHAR;
+                wc : WCHAR;
+            END_VAR
+            c := s[2];
+            wc := ws[2];
+        END_PROGRAM
+    "#;
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        s: [u8; 81],
+        ws: [u16; 81],
+        c: u8,
+        wc: u16,
+    }
+    let mut main_type = MainType { s: [0; 81], ws: [0; 81], c: 0, wc: 0 };
+
+    let _: i32 = compile_and_run(src, &mut main_type);
+    assert_eq!(b'b', main_type.c);
+    assert_eq!('y' as u16, main_type.wc);
+}
+
+#[test]
+fn sized_string_casts_truncate_the_literal() {
+    let src = r#"
+        PROGRAM main
+            VAR
+                x : STRING[10];
+                y : WSTRING[10];
+                z : STRING[10];
+            END_VAR
+            x := STRING#[3]'hello';
+            y := WSTRING#[3]"hello";
+            z := STRING#[5]'hello';
+        END_PROGRAM
+    "#;
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        x: [u8; 11],
+        y: [u16; 11],
+        z: [u8; 11],
+    }
+    let mut main_type = MainType { x: [0; 11], y: [0; 11], z: [0; 11] };
+
+    let _: i32 = compile_and_run(src, &mut main_type);
+    assert_eq!("hel\0\0\0\0\0\0\0\0".as_bytes(), &main_type.x);
+    assert_eq!("hel\0", String::from_utf16_lossy(&main_type.y[..4]));
+    assert_eq!("hello\0\0\0\0\0\0".as_bytes(), &main_type.z);
+}
+
+#[test]
+fn indexing_a_string_at_runtime_positions_is_range_checked() {
+    let src = r#"
+        FUNCTION CheckRangeSigned : DINT
+            VAR_INPUT v: DINT; low: DINT; up: DINT; END_VAR
+            IF v < low THEN
+                CheckRangeSigned := low;
+            ELSIF v > up THEN
+                CheckRangeSigned := up;
+            ELSE
+                CheckRangeSigned := v;
+            END_IF
+        END_FUNCTION
+
+        PROGRAM main
+            VAR
+                s : STRING[3] := 'abc';
+                low : CHAR;
+                high : CHAR;
+            END_VAR
+            VAR_TEMP
+                i : DINT;
+            END_VAR
+            i := 0;
+            low := s[i];
+            i := 10;
+            high := s[i];
+        END_PROGRAM
+    "#;
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        s: [u8; 4],
+        low: u8,
+        high: u8,
+    }
+    let mut main_type = MainType { s: [0; 4], low: 0, high: 0 };
+
+    let _: i32 = compile_and_run(src, &mut main_type);
+    assert_eq!(b'a', main_type.low);
+    assert_eq!(b'c', main_type.high);
+}
+
+#[test]
+fn unsized_string_casts_keep_the_whole_literal() {
+    let literal = "a".repeat(90);
+    let src = format!(
+        r#"
+        PROGRAM main
+            VAR
+                x : STRING[100];
+            END_VAR
+            x := STRING#'{literal}';
+        END_PROGRAM
+    "#
+    );
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        x: [u8; 101],
+    }
+    let mut main_type = MainType { x: [0; 101] };
+
+    let _: i32 = compile_and_run(src, &mut main_type);
+    assert_eq!(literal.as_bytes(), &main_type.x[..90]);
+    assert_eq!(0, main_type.x[90]);
+}
@@ -1,5 +1,7 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 
+use rusty::codegen::CodegenContext;
+
 use crate::compile_and_run;
 
 #[allow(dead_code)]
@@ -87,3 +89,55 @@ fn sub_range_chooses_right_implementation() {
     };
     assert_eq!(expected, maintype);
 }
+
+#[test]
+fn out_of_range_assignments_call_the_check_function_unless_range_checks_are_disabled() {
+    #[derive(Default, Debug, PartialEq)]
+    #[repr(C)]
+    struct MainType {
+        value: i16,
+        count: i32,
+    }
+    let function = r"
+        VAR_GLOBAL
+            violations : DINT;
+        END_VAR
+
+        FUNCTION CheckRangeSigned : DINT
+            VAR_INPUT v: DINT; low: DINT; up: DINT; END_VAR
+            IF v < low THEN
+                violations := violations + 1;
+                CheckRangeSigned := low;
+            ELSIF v > up THEN
+                violations := violations + 1;
+                CheckRangeSigned := up;
+            ELSE
+                CheckRangeSigned := v;
+            END_IF
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            value : INT(0 .. 100);
+            count : DINT;
+        END_VAR
+            value := 50;
+            value := 150;
+            count := violations;
+        END_PROGRAM
+        ";
+
+    let context = CodegenContext::create();
+    let module = driver::runner::compile_with_options(&context, function, Default::default());
+    let mut main = MainType::default();
+    let _: i32 = module.run("main", &mut main);
+    assert_eq!(MainType { value: 100, count: 1 }, main);
+
+    // without range checks the value is assigned as is
+    let context = CodegenContext::create();
+    let compile_options = driver::CompileOptions { range_checks: false, ..Default::default() };
+    let module = driver::runner::compile_with_options(&context, function, compile_options);
+    let mut main = MainType::default();
+    let _: i32 = module.run("main", &mut main);
+    assert_eq!(MainType { value: 150, count: 0 }, main);
+}
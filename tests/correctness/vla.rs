@@ -991,3 +991,65 @@ mod builtins {
         assert_eq!(main_type.b, 5);
     }
 }
+
+#[test]
+fn array_slice_passed_to_variable_length_array() {
+    #[derive(Default)]
+    struct MainType {
+        sum: i32,
+        lower: i32,
+        upper: i32,
+        dynamic_sum: i32,
+    }
+
+    let src = r#"
+    PROGRAM main
+        VAR
+            sum, lower, upper, dynamic_sum : DINT;
+        END_VAR
+        VAR_TEMP
+            arr : ARRAY[1..10] OF DINT := [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+            start : DINT := 8;
+        END_VAR
+
+        sum := total(arr[2..5]);
+        lower := lower_of(arr[2..5]);
+        upper := upper_of(arr[2..5]);
+        dynamic_sum := total(arr[start..10]);
+    END_PROGRAM
+
+    FUNCTION total : DINT
+        VAR_INPUT {ref}
+            vla : ARRAY[*] OF DINT;
+        END_VAR
+        VAR
+            i : DINT;
+        END_VAR
+
+        FOR i := LOWER_BOUND(vla, 1) TO UPPER_BOUND(vla, 1) DO
+            total := total + vla[i];
+        END_FOR
+    END_FUNCTION
+
+    FUNCTION lower_of : DINT
+        VAR_INPUT {ref}
+            vla : ARRAY[*] OF DINT;
+        END_VAR
+        lower_of := LOWER_BOUND(vla, 1);
+    END_FUNCTION
+
+    FUNCTION upper_of : DINT
+        VAR_INPUT {ref}
+            vla : ARRAY[*] OF DINT;
+        END_VAR
+        upper_of := UPPER_BOUND(vla, 1);
+    END_FUNCTION
+    "#;
+
+    let mut main_type = MainType::default();
+    let _: i32 = compile_and_run(src.to_string(), &mut main_type);
+    assert_eq!(14, main_type.sum);
+    assert_eq!(2, main_type.lower);
+    assert_eq!(5, main_type.upper);
+    assert_eq!(27, main_type.dynamic_sum);
+}
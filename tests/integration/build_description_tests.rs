@@ -1,5 +1,5 @@
 use crate::get_test_file;
-use driver::compile;
+use driver::{compile, dry_run};
 
 #[test]
 #[serial]
@@ -54,6 +54,72 @@ fn exports_env_variable() {
     assert_eq!(std::env::var("ARCH").unwrap(), "aarch64-unknown-linux-gnu");
 }
 
+#[test]
+#[serial]
+fn dry_run_lists_resolved_files_without_building() {
+    let dir = tempfile::tempdir().unwrap();
+    let build_location = dir.path().to_str().unwrap();
+    let parameters = &[
+        "plc",
+        "build",
+        &get_test_file("json/build_to_temp.json"),
+        "--target",
+        "x86_64-linux-gnu",
+        "--build-location",
+        build_location,
+        "--dry-run",
+    ];
+    let description = dry_run(parameters).unwrap();
+
+    let data = get_test_file("json");
+    assert_eq!(
+        description,
+        format!(
+            "Sources:
+    {data}/simple_program.st
+Includes:
+Objects:
+    {data}/libs/libcopy.so
+    {data}/libs/libnocopy.so
+Libraries:
+    copy ({data}/libs/)
+    nocopy ({data}/libs/)
+Targets:
+    x86_64-linux-gnu
+Output:
+    {build_location}/x86_64-linux-gnu/proj.so
+"
+        )
+    );
+
+    // nothing was built
+    compile(parameters).unwrap();
+    assert!(!dir.path().join("x86_64-linux-gnu").exists());
+}
+
+#[test]
+#[serial]
+fn dry_run_lists_the_output_name_given_on_the_command_line() {
+    let dir = tempfile::tempdir().unwrap();
+    let build_location = dir.path().to_str().unwrap();
+    let parameters = &[
+        "plc",
+        "build",
+        &get_test_file("json/build_with_output_name.json"),
+        "--target",
+        "x86_64-linux-gnu",
+        "--build-location",
+        build_location,
+        "-o",
+        "from_cli.so",
+        "--dry-run",
+    ];
+    let description = dry_run(parameters).unwrap();
+
+    let output = description.split("Output:\n").nth(1).unwrap();
+    assert_eq!(output, format!("    {build_location}/x86_64-linux-gnu/from_cli.so\n"));
+}
+
 #[test]
 #[serial]
 fn build_with_separate_lib_folder() {
@@ -120,6 +186,81 @@ fn build_for_multiple_targets_and_sysroots() {
     assert!(dir.path().join("x86_64-linux-gnu").join("proj.so").is_file());
 }
 
+#[test]
+#[serial]
+fn build_for_multiple_targets_from_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let parameters = &[
+        "plc",
+        "build",
+        &get_test_file("json/multi_target_from_config.json"),
+        "--build-location",
+        dir.path().to_str().unwrap(),
+    ];
+    compile(parameters).unwrap();
+
+    assert!(dir.path().join("aarch64-linux-gnu").join("proj.so").is_file());
+    assert!(dir.path().join("x86_64-linux-gnu").join("proj.so").is_file());
+}
+
+#[test]
+#[serial]
+fn command_line_targets_override_config_targets() {
+    let dir = tempfile::tempdir().unwrap();
+    let parameters = &[
+        "plc",
+        "build",
+        &get_test_file("json/multi_target_from_config.json"),
+        "--target",
+        "x86_64-linux-gnu",
+        "--build-location",
+        dir.path().to_str().unwrap(),
+    ];
+    compile(parameters).unwrap();
+
+    assert!(!dir.path().join("aarch64-linux-gnu").exists());
+    assert!(dir.path().join("x86_64-linux-gnu").join("proj.so").is_file());
+}
+
+#[test]
+#[serial]
+fn output_name_of_the_config_is_used_unless_given_on_the_command_line() {
+    let dir = tempfile::tempdir().unwrap();
+    let build_location = dir.path().to_str().unwrap();
+    let parameters = &[
+        "plc",
+        "build",
+        &get_test_file("json/build_with_output_name.json"),
+        "--target",
+        "x86_64-linux-gnu",
+        "--sysroot",
+        "sysroot",
+        "--build-location",
+        build_location,
+    ];
+    compile(parameters).unwrap();
+
+    assert!(dir.path().join("x86_64-linux-gnu").join("renamed.so").is_file());
+    assert!(!dir.path().join("x86_64-linux-gnu").join("proj.so").exists());
+
+    let parameters = &[
+        "plc",
+        "build",
+        &get_test_file("json/build_with_output_name.json"),
+        "--target",
+        "x86_64-linux-gnu",
+        "--sysroot",
+        "sysroot",
+        "--build-location",
+        build_location,
+        "-o",
+        "from_cli.so",
+    ];
+    compile(parameters).unwrap();
+
+    assert!(dir.path().join("x86_64-linux-gnu").join("from_cli.so").is_file());
+}
+
 #[test]
 #[serial]
 #[cfg_attr(target_os = "windows", ignore = "linker not available for Windows")]
@@ -175,3 +316,70 @@ fn build_with_clang_linker_windows() {
 
     assert!(dir.path().join("clang_proj.so").is_file());
 }
+
+#[test]
+fn missing_build_config_reports_the_searched_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let searched = dir.path().join("missing");
+    let config = searched.join("plc.json");
+    let Err(err) = compile(&["plc", "build", config.to_str().unwrap()]) else {
+        panic!("Expected the missing build description to be reported")
+    };
+    let current_dir = std::env::current_dir().unwrap();
+    assert_eq!(
+        err.into_diagnostic().unwrap().get_message(),
+        format!(
+            "Could not find 'plc.json' in '{}' (current directory: '{}')",
+            searched.to_string_lossy(),
+            current_dir.to_string_lossy()
+        )
+    );
+}
+
+#[test]
+#[serial]
+fn bundle_manifest_groups_the_objects_of_every_unit_by_target() {
+    let dir = tempfile::tempdir().unwrap();
+    let manifest = dir.path().join("bundle.json");
+    let parameters = &[
+        "plc",
+        "build",
+        &get_test_file("json/multi_target_from_config.json"),
+        "--build-location",
+        dir.path().to_str().unwrap(),
+        "--emit-bundle",
+        manifest.to_str().unwrap(),
+    ];
+    compile(parameters).unwrap();
+
+    let manifest: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(manifest).unwrap()).unwrap();
+    let units = manifest["units"].as_array().unwrap();
+    assert_eq!(units.len(), 1);
+    assert_eq!(units[0]["name"], "simple_program.o");
+    let objects = units[0]["objects"].as_array().unwrap();
+    let targets = objects.iter().map(|it| it["target"].as_str().unwrap()).collect::<Vec<_>>();
+    assert_eq!(targets, vec!["aarch64-unknown-linux-gnu", "x86_64-unknown-linux-gnu"]);
+    for (object, directory) in objects.iter().zip(["aarch64-linux-gnu", "x86_64-linux-gnu"]) {
+        let path = std::path::PathBuf::from(object["path"].as_str().unwrap());
+        assert_eq!(path, dir.path().join(directory).join("simple_program.o"));
+        assert!(path.is_file());
+    }
+}
+
+#[test]
+#[serial]
+fn unknown_library_architectures_are_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    let parameters = &[
+        "plc",
+        "build",
+        &get_test_file("json/library_with_unknown_architecture.json"),
+        "--target",
+        "x86_64-linux-gnu",
+        "--build-location",
+        dir.path().to_str().unwrap(),
+    ];
+    let Err(err) = compile(parameters) else { panic!("Expected the unknown architecture to be reported") };
+    assert_eq!(err.into_diagnostic().unwrap().get_message(), "Unknown target triples: 'bogus-arch'");
+}
@@ -26,6 +26,22 @@ fn ir_generation_full_pass() {
     assert_snapshot!(content);
 }
 
+#[test]
+fn assembly_generation_full_pass() {
+    let file = get_test_file("command_line.st");
+
+    let mut temp_file = tempfile::NamedTempFile::new().unwrap();
+    let path = temp_file.path().to_string_lossy();
+    compile(&["plc", file.as_str(), "-o", &path, "--asm"]).unwrap();
+
+    //Verify file content
+    let mut content = String::new();
+    temp_file.as_file_mut().read_to_string(&mut content).unwrap();
+
+    //The generated assembly depends on the host target, only check for the function's label
+    assert!(content.contains("myFunc:"));
+}
+
 #[test]
 fn hardware_conf_full_pass_json() {
     let file = get_test_file("io.st");
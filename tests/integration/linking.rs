@@ -229,3 +229,42 @@ fn link_files_with_same_name() {
     //Delete it
     fs::remove_file(&out1).unwrap();
 }
+
+#[test]
+#[cfg_attr(target_os = "windows", ignore = "linker is not available for windows")]
+fn link_executable_with_a_program_as_entry_point() {
+    let file = get_test_file("linking/entry_point.st");
+    let dir = tempfile::tempdir().unwrap();
+    let executable = dir.path().join("startup");
+
+    compile(&[
+        "plc",
+        file.as_str(),
+        "-o",
+        executable.to_str().unwrap(),
+        "--linker",
+        "cc",
+        "--entry-point",
+        "startup",
+    ])
+    .unwrap();
+
+    //The generated main calls the program, which exits with the value of its counter
+    let status = std::process::Command::new(&executable).status().unwrap();
+    assert_eq!(status.code(), Some(42));
+}
+
+#[test]
+fn entry_point_must_be_a_program() {
+    let file = get_test_file("linking/entry_point.st");
+
+    //Neither an undeclared POU nor a FUNCTION can be called with a program instance
+    for entry_point in ["missing", "exit"] {
+        let res = compile(&["plc", file.as_str(), "--check", "--entry-point", entry_point]);
+        let Err(err) = res else { panic!("Expected '{entry_point}' to be rejected") };
+        assert_eq!(
+            err.into_diagnostic().unwrap().get_message(),
+            format!("Cannot use '{entry_point}' as entry point, there is no PROGRAM with that name")
+        );
+    }
+}
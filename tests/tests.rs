@@ -10,6 +10,7 @@ pub use plc_source::*;
 
 mod correctness {
     mod arrays;
+    mod bit_shift_functions;
     mod bitaccess;
     mod classes;
     mod constants;